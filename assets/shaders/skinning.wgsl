@@ -15,27 +15,60 @@ struct BonePalette {
     matrices : array<mat4x4<f32>, 128>,
 };
 
+// Displacement of one vertex by one morph target (`w` unused).
+struct MorphDelta {
+    position : vec4<f32>,
+    normal   : vec4<f32>,
+};
+
+// Up to 64 morph weights, packed four per vec4.
+struct MorphParams {
+    vertex_count : u32,
+    target_count : u32,
+    _pad0        : u32,
+    _pad1        : u32,
+    weights      : array<vec4<f32>, 16>,
+};
+
 @group(0) @binding(0)
 var<storage, read> in_vertices: array<Vertex>;
 
 @group(0) @binding(1)
 var<storage, read> influences: array<Influence>;
 
+// Target-major: the delta of vertex `v` for target `t` is at
+// `t * vertex_count + v`.
+@group(0) @binding(2)
+var<storage, read> morph_deltas: array<MorphDelta>;
+
 @group(1) @binding(0)
 var<uniform> palette: BonePalette;
 
+@group(1) @binding(1)
+var<uniform> morph: MorphParams;
+
 @group(2) @binding(0)
 var<storage, read_write> out_vertices: array<Vertex>;
 
 @compute @workgroup_size(64)
 fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let id = global_id.x;
-    
-    // Safety check (we should pass the vertex count in a uniform)
-    // For now we assume the dispatch is correct.
-    
-    let v = in_vertices[id];
+    if (id >= morph.vertex_count) {
+        return;
+    }
+
+    var v = in_vertices[id];
     let infl = influences[id];
+
+    // Morph targets are applied in bind space, before skinning.
+    for (var t = 0u; t < morph.target_count; t++) {
+        let w = morph.weights[t / 4u][t % 4u];
+        if (w != 0.0) {
+            let d = morph_deltas[t * morph.vertex_count + id];
+            v.pos += d.position.xyz * w;
+            v.normal += d.normal.xyz * w;
+        }
+    }
     
     var skinned_pos = vec4<f32>(0.0);
     var skinned_normal = vec3<f32>(0.0);
@@ -1,13 +1,36 @@
 use crate::Vec3;
 use anyhow::Result;
-use ferrous_assets::{AssetHandle, AssetState, AssetServer, GltfModel};
+use ferrous_assets::{
+    AssetAnimation, AssetChannel, AssetChannelValues, AssetHandle, AssetInterpolation,
+    AssetLightKind, AssetMesh, AssetModel, AssetProjection, AssetServer, AssetSkin, AssetState,
+    GltfModel, MorphedVertices,
+};
+use ferrous_core::glam::{Mat4, Quat};
+use ferrous_core::scene::skinning::{Bone, Transform as BoneTransform};
+use ferrous_core::scene::world::{MaterialComponent, MeshGeometry};
+use ferrous_core::scene::{
+    Animatable, AnimationClip, AnimationPlayer, BoneInfluence, BoneInfluences, Camera3D, Keyframe, MorphWeights,
+    NodeCamera, NodeLight, NodeMesh, PointLightComponent, Prefab, PrefabLibrary, SceneGraph,
+    SceneGraphNode, Skeleton, SkinnedMesh, Track,
+};
+use ferrous_core::{Color, Transform};
+use ferrous_ecs::prelude::{Changed, Component, Query};
+use ferrous_renderer::geometry::Vertex;
+use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Arc;
 
 /// Helper that loads a GLTF/GLB file via `ferrous_assets`, registers the
 /// resulting textures, materials and meshes with the renderer, and spawns an
 /// entity for each mesh in the provided world.  The returned vector contains
 /// the handles of the newly-created entities, in the same order as the
 /// meshes stored inside the file.
+///
+/// Skinned meshes also get their [`BoneInfluences`] and a [`SkinnedMesh`]
+/// pointing at an ECS entity holding the skin's [`Skeleton`] and an
+/// [`AnimationPlayer`] with every animation of the file (see
+/// [`spawn_skeletons`]).  Meshes with morph targets get their own player
+/// with the file's weight tracks (see [`spawn_morph_targets`]).
 pub fn spawn_gltf(
     world: &mut ferrous_core::scene::World,
     renderer: &mut ferrous_renderer::Renderer,
@@ -69,7 +92,7 @@ pub fn spawn_gltf(
 
     // convert raw materials into engine descriptors and register them.
     let mut mat_handles = Vec::with_capacity(model.materials.len());
-    for raw in &model.materials {
+    for (mat_idx, raw) in model.materials.iter().enumerate() {
         let mut desc = ferrous_core::scene::MaterialDescriptor::default();
         desc.base_color = raw.base_color;
         desc.emissive = raw.emissive;
@@ -129,11 +152,15 @@ pub fn spawn_gltf(
         // every frame with the descriptor stored in the world, which would
         // overwrite the material uniform (clearing the texture flags) if we
         // only stored the default descriptor there.
+        // prefabs refer to materials by asset key, never by GPU handle
+        let material = MaterialComponent { handle: mh, descriptor: desc.clone() };
+        world.register_material(format!("{}#material{}", path.display(), mat_idx), material);
         mat_handles.push((mh, desc));
     }
 
+    let mesh_nodes = mesh_nodes(&model);
     let mut out_handles = Vec::new();
-    for (i, mesh) in model.meshes.into_iter().enumerate() {
+    for (i, mesh) in model.meshes.iter().enumerate() {
         eprintln!(
             " mesh {}: {} vertices {} indices mat_idx={:?}",
             i,
@@ -146,17 +173,7 @@ pub fn spawn_gltf(
         let key = format!("{}#{}", path.display(), i);
 
         // convert to renderer vertices
-        let n = mesh.positions.len();
-        let mut verts = Vec::with_capacity(n);
-        for j in 0..n {
-            verts.push(ferrous_renderer::geometry::Vertex {
-                position: mesh.positions[j],
-                normal: *mesh.normals.get(j).unwrap_or(&[0.0, 1.0, 0.0]),
-                tangent: *mesh.tangents.get(j).unwrap_or(&[1.0, 0.0, 0.0, 1.0]),
-                color: *mesh.colors.get(j).unwrap_or(&[1.0, 1.0, 1.0, 1.0]),
-                uv: *mesh.uvs.get(j).unwrap_or(&[0.0, 0.0]),
-            });
-        }
+        let verts = vertices(mesh, &mesh.positions, &mesh.normals, &mesh.tangents);
 
         // we always use 32‑bit indices for simplicity; GLTF already gives us
         // u32 so no conversion is required.
@@ -164,8 +181,10 @@ pub fn spawn_gltf(
 
         let gpu_mesh = renderer.create_mesh("gltf_submesh", verts, mesh.indices.clone());
 
-        // register mesh with renderer so world_sync can find it later
+        // register mesh with renderer so world_sync can find it later, and
+        // its triangles with the world for `World::raycast`
         renderer.register_mesh(&key, gpu_mesh.clone());
+        world.register_mesh_geometry(key.clone(), mesh_geometry(mesh));
 
         // spawn an entity referencing the mesh and material.
         // Crucially we also store the full descriptor (including texture
@@ -178,9 +197,15 @@ pub fn spawn_gltf(
                 world.set_material_descriptor(handle, mat_desc.clone());
             }
         }
+        if !mesh.morph_targets.is_empty() {
+            let morphs = vec![(key, Arc::new(mesh.clone()))];
+            spawn_morph_targets(world, handle, morphs, &model.animations, &mesh_nodes[i]);
+        }
         out_handles.push(handle);
     }
 
+    let skinned: Vec<(&AssetMesh, Option<usize>)> = model.meshes.iter().map(|m| (m, m.skin)).collect();
+    spawn_skeletons(world, &model.skins, &model.animations, &skinned, &out_handles);
     Ok(out_handles)
 }
 
@@ -264,6 +289,458 @@ pub fn spawn_gltf_async(server: &mut AssetServer, path: &str) -> GltfSpawnTask {
     GltfSpawnTask { handle }
 }
 
+// ── Skins & animations ───────────────────────────────────────────────────────
+
+/// Spawn one ECS entity per skin used by the meshes, holding its
+/// [`Skeleton`] and an [`AnimationPlayer`] with every clip of `animations`
+/// (nothing playing yet), and attach a [`SkinnedMesh`] and the mesh's
+/// [`BoneInfluences`] to each skinned mesh entity in `handles`
+/// (`meshes[i]` is the primitive drawn by `handles[i]` and its skin).
+pub fn spawn_skeletons(
+    world: &mut ferrous_core::scene::World,
+    skins: &[AssetSkin],
+    animations: &[AssetAnimation],
+    meshes: &[(&AssetMesh, Option<usize>)],
+    handles: &[ferrous_core::scene::Handle],
+) {
+    let mut skeleton_entities = vec![None; skins.len()];
+    for (handle, &(mesh, skin)) in handles.iter().zip(meshes) {
+        let Some(skin) = skin.filter(|&s| s < skins.len()) else { continue };
+        let skeleton = *skeleton_entities[skin].get_or_insert_with(|| {
+            let player = animations.iter().fold(AnimationPlayer::default(), |player, anim| {
+                player.with_clip(anim.name.clone(), clip_from_animation(anim))
+            });
+            world.ecs.spawn((skeleton_from_skin(&skins[skin]), player))
+        });
+        if let Some(&entity) = world.ecs_mapping.get(&handle.0) {
+            world.ecs.insert(entity, SkinnedMesh { skeleton_entity: Some(skeleton) });
+            world.ecs.insert(entity, BoneInfluences(bone_influences(mesh, &skins[skin])));
+        }
+    }
+}
+
+/// Joints of `skin` in bone order: parents before children, otherwise in
+/// skin order.
+fn bone_order(skin: &AssetSkin) -> Vec<usize> {
+    let depth = |mut joint: usize| {
+        let mut depth = 0;
+        while let Some(parent) = skin.joints[joint].parent {
+            joint = parent;
+            depth += 1;
+            if depth > skin.joints.len() {
+                break; // cyclic hierarchy; keep going rather than hang
+            }
+        }
+        depth
+    };
+    let mut order: Vec<usize> = (0..skin.joints.len()).collect();
+    order.sort_by_key(|&j| depth(j));
+    order
+}
+
+/// Bone index of every joint of `skin` (the inverse of [`bone_order`]).
+fn joint_to_bone(skin: &AssetSkin) -> Vec<usize> {
+    let mut to_bone = vec![0; skin.joints.len()];
+    for (bone, joint) in bone_order(skin).into_iter().enumerate() {
+        to_bone[joint] = bone;
+    }
+    to_bone
+}
+
+/// Convert a glTF skin into a [`Skeleton`] in its rest pose.
+///
+/// glTF lists joints in any order while `Skeleton` needs parents first, so
+/// bones may be reordered; use [`bone_influences`] to remap the mesh's
+/// joint indices accordingly.
+pub fn skeleton_from_skin(skin: &AssetSkin) -> Skeleton {
+    let to_bone = joint_to_bone(skin);
+    let bones = bone_order(skin)
+        .into_iter()
+        .map(|j| {
+            let joint = &skin.joints[j];
+            Bone {
+                name: joint.name.clone(),
+                parent_index: joint.parent.map(|p| to_bone[p]),
+                inverse_bind_matrix: Mat4::from_cols_array_2d(&joint.inverse_bind_matrix),
+                local_transform: BoneTransform {
+                    position: joint.translation.into(),
+                    rotation: Quat::from_array(joint.rotation),
+                    scale: joint.scale.into(),
+                },
+            }
+        })
+        .collect();
+    let mut skeleton = Skeleton::new(bones);
+    skeleton.update_matrices();
+    skeleton
+}
+
+/// Per-vertex [`BoneInfluence`]s of `mesh`, with joint indices remapped to
+/// the bones of [`skeleton_from_skin`]`(skin)`.
+pub fn bone_influences(mesh: &AssetMesh, skin: &AssetSkin) -> Vec<BoneInfluence> {
+    let to_bone = joint_to_bone(skin);
+    mesh.joints
+        .iter()
+        .zip(&mesh.joint_weights)
+        .map(|(joints, weights)| BoneInfluence {
+            indices: joints.map(|j| to_bone.get(j as usize).copied().unwrap_or(0) as u32),
+            weights: *weights,
+        })
+        .collect()
+}
+
+/// Convert a glTF animation into an [`AnimationClip`].
+///
+/// Transform channels become bone tracks keyed by node name, which matches
+/// the bone names of [`skeleton_from_skin`].  Morph-weight channels drive
+/// meshes rather than bones; see [`morph_clip_from_animation`].
+pub fn clip_from_animation(animation: &AssetAnimation) -> AnimationClip {
+    let mut clip = AnimationClip::new(animation.duration());
+    for channel in &animation.channels {
+        if matches!(channel.values, AssetChannelValues::MorphWeights(_)) {
+            continue;
+        }
+        let tracks = clip.bones.entry(channel.node_name.clone()).or_default();
+        match &channel.values {
+            AssetChannelValues::Translation(v) => tracks.translation = Some(track(channel, v, |&t| t.into())),
+            AssetChannelValues::Rotation(v) => tracks.rotation = Some(track(channel, v, |&r| Quat::from_array(r))),
+            AssetChannelValues::Scale(v) => tracks.scale = Some(track(channel, v, |&s| s.into())),
+            AssetChannelValues::MorphWeights(_) => {}
+        }
+    }
+    clip
+}
+
+/// Convert the morph-weight channel of `animation` targeting any of `nodes`
+/// into a clip with only a weights track, or `None` when no such channel
+/// exists.
+pub fn morph_clip_from_animation(animation: &AssetAnimation, nodes: &[usize]) -> Option<AnimationClip> {
+    animation.channels.iter().filter(|c| nodes.contains(&c.node)).find_map(|channel| {
+        let AssetChannelValues::MorphWeights(values) = &channel.values else { return None };
+        let per_key = if channel.interpolation == AssetInterpolation::CubicSpline { 3 } else { 1 };
+        let targets = values.len() / (channel.times.len() * per_key).max(1);
+        if targets == 0 {
+            return None;
+        }
+        let keys: Vec<Vec<f32>> = values.chunks_exact(targets).map(<[f32]>::to_vec).collect();
+        Some(AnimationClip::new(animation.duration()).with_weights(track(channel, &keys, Vec::clone)))
+    })
+}
+
+/// Build a [`Track`] from a channel's times and (converted) output values.
+fn track<V, T: Animatable>(channel: &AssetChannel, values: &[V], convert: impl Fn(&V) -> T) -> Track<T> {
+    match channel.interpolation {
+        AssetInterpolation::CubicSpline => {
+            let triples = values.chunks_exact(3);
+            let keys = channel
+                .times
+                .iter()
+                .zip(triples.clone())
+                .map(|(&time, v)| Keyframe::new(time, convert(&v[1])))
+                .collect();
+            let tangents = triples.map(|v| (convert(&v[0]), convert(&v[2]))).collect();
+            Track::cubic(keys, tangents)
+        }
+        interpolation => {
+            let keys = channel
+                .times
+                .iter()
+                .zip(values)
+                .map(|(&time, v)| Keyframe::new(time, convert(v)))
+                .collect();
+            match interpolation {
+                AssetInterpolation::Step => Track::step(keys),
+                _ => Track::linear(keys),
+            }
+        }
+    }
+}
+
+// ── Morph targets ───────────────────────────────────────────────────────────
+
+/// Morph-target primitives drawn by an entity, each paired with the key it
+/// is registered under in the renderer.  All of them follow the entity's
+/// [`MorphWeights`], as the primitives of a glTF mesh share its weights.
+///
+/// The runner re-blends them with `AssetMesh::blend_morph_targets` and
+/// re-registers the result under the same key whenever the weights change,
+/// so entities sharing a key also share the latest blend.
+#[derive(Clone)]
+pub struct MorphMeshes(pub Vec<(String, Arc<AssetMesh>)>);
+
+impl Component for MorphMeshes {}
+
+/// Give `handle` the morph targets of `meshes` (renderer key and primitive,
+/// instanced by the glTF `nodes`): a [`MorphMeshes`], the initial
+/// [`MorphWeights`] and, when any of `animations` drives those nodes'
+/// weights, an [`AnimationPlayer`] with their weight clips (nothing playing
+/// yet; see [`morph_clip_from_animation`]).  Primitives without morph
+/// targets are dropped; nothing is added when none are left.
+pub fn spawn_morph_targets(
+    world: &mut ferrous_core::scene::World,
+    handle: ferrous_core::scene::Handle,
+    mut meshes: Vec<(String, Arc<AssetMesh>)>,
+    animations: &[AssetAnimation],
+    nodes: &[usize],
+) {
+    meshes.retain(|(_, mesh)| !mesh.morph_targets.is_empty());
+    let Some(&entity) = world.ecs_mapping.get(&handle.0) else { return };
+    let Some(weights) = meshes.first().map(|(_, mesh)| mesh.morph_weights.clone()) else { return };
+    let player = animations.iter().fold(AnimationPlayer::default(), |player, anim| {
+        match morph_clip_from_animation(anim, nodes) {
+            Some(clip) => player.with_clip(anim.name.clone(), clip),
+            None => player,
+        }
+    });
+    if !player.clips.is_empty() {
+        world.ecs.insert(entity, player);
+    }
+    world.ecs.insert(entity, MorphWeights(weights));
+    world.ecs.insert(entity, MorphMeshes(meshes));
+}
+
+/// Blend every [`MorphMeshes`] whose [`MorphWeights`] changed after tick
+/// `tick` and re-register the result with the renderer, then advance `tick`
+/// to the world's current one.  Called by the runner before `sync_world`.
+pub(crate) fn blend_morph_meshes(
+    world: &ferrous_core::scene::World,
+    renderer: &mut ferrous_renderer::Renderer,
+    tick: &mut u64,
+) {
+    // A replaced world restarts its tick counter; reblend everything.
+    if world.ecs.change_tick() < *tick {
+        *tick = 0;
+    }
+    for (key, mesh, verts) in changed_morph_meshes(&world.ecs, *tick) {
+        let gpu_mesh = renderer.create_mesh("gltf_morph", verts, mesh.indices.clone());
+        renderer.register_mesh(&key, gpu_mesh);
+    }
+    *tick = world.ecs.change_tick();
+}
+
+/// Key, primitive and blended vertices of every [`MorphMeshes`] entry whose
+/// entity's [`MorphWeights`] changed after tick `since`.
+fn changed_morph_meshes(world: &ferrous_ecs::world::World, since: u64) -> Vec<(String, Arc<AssetMesh>, Vec<Vertex>)> {
+    Query::<(&MorphMeshes, &MorphWeights), Changed<MorphWeights>>::new_since(world, since)
+        .iter()
+        .flat_map(|(_, (meshes, weights))| {
+            meshes.0.iter().map(|(key, mesh)| {
+                let MorphedVertices { positions, normals, tangents } = mesh.blend_morph_targets(&weights.0);
+                (key.clone(), mesh.clone(), vertices(mesh, &positions, &normals, &tangents))
+            })
+        })
+        .collect()
+}
+
+// ── Scenes ──────────────────────────────────────────────────────────────────
+
+/// Load a GLTF/GLB file and instantiate its default scene: one entity per
+/// node, linked with `ChildOf`, carrying the node's local transform, mesh,
+/// camera (`Camera3D`) and light (`PointLightComponent` /
+/// `DirectionalLight`).  Skinned nodes get a [`SkinnedMesh`] and morphing
+/// nodes their morph targets as in [`spawn_gltf`].
+///
+/// Returns the handle of every spawned node, in depth-first order from the
+/// scene roots.  Files without nodes spawn nothing; use [`spawn_gltf`] for
+/// those.
+pub fn spawn_gltf_scene(
+    world: &mut ferrous_core::scene::World,
+    renderer: &mut ferrous_renderer::Renderer,
+    path: &str,
+) -> Result<Vec<ferrous_core::scene::Handle>> {
+    let model = ferrous_assets::load_gltf(Path::new(path))?;
+    let meshes = register_model(world, renderer, path, &model);
+    let handles = world.spawn_graph(&scene_graph_from_model(&model, &meshes));
+
+    let mut skinned = Vec::new();
+    let mut skins = Vec::new();
+    for (&node_index, &handle) in scene_nodes(&model).iter().zip(&handles) {
+        let node = &model.nodes[node_index];
+        if let (Some(skin), Some(mesh)) = (node.skin, node.meshes.first().and_then(|&m| model.meshes.get(m))) {
+            skinned.push(handle);
+            skins.push((mesh, Some(skin)));
+        }
+        let morphs = node
+            .meshes
+            .iter()
+            .filter_map(|&m| {
+                let mesh = model.meshes.get(m).filter(|mesh| !mesh.morph_targets.is_empty())?;
+                Some((meshes.get(m)?.asset_key.clone(), Arc::new(mesh.clone())))
+            })
+            .collect();
+        spawn_morph_targets(world, handle, morphs, &model.animations, &[node_index]);
+    }
+    spawn_skeletons(world, &model.skins, &model.animations, &skins, &skinned);
+    Ok(handles)
+}
+
+/// Nodes of the default scene of `model`, depth-first from its roots.
+fn scene_nodes(model: &AssetModel) -> Vec<usize> {
+    let mut order = Vec::new();
+    let mut seen = vec![false; model.nodes.len()];
+    let mut stack: Vec<usize> = model.scene_roots.iter().rev().copied().collect();
+    while let Some(n) = stack.pop() {
+        if n >= seen.len() || std::mem::replace(&mut seen[n], true) {
+            continue;
+        }
+        order.push(n);
+        stack.extend(model.nodes[n].children.iter().rev());
+    }
+    order
+}
+
+/// Convert the default scene of `model` into a [`SceneGraph`] for
+/// `World::spawn_graph`, in depth-first order from the scene roots.
+/// `meshes[i]` is what to draw for `model.meshes[i]`.
+///
+/// Perspective cameras keep their field of view; orthographic cameras,
+/// which `Camera3D` cannot express, get its default one.  Spot lights are
+/// imported as point lights.  Light intensities are copied unchanged.
+pub fn scene_graph_from_model(model: &AssetModel, meshes: &[NodeMesh]) -> SceneGraph {
+    let order = scene_nodes(model);
+    let mut index = vec![None; model.nodes.len()];
+    for (i, &n) in order.iter().enumerate() {
+        index[n] = Some(i);
+    }
+    let default_camera = Camera3D::default();
+    let nodes = order
+        .iter()
+        .map(|&n| {
+            let node = &model.nodes[n];
+            SceneGraphNode {
+                name: node.name.clone(),
+                transform: Transform {
+                    position: node.translation.into(),
+                    rotation: Quat::from_array(node.rotation),
+                    scale: node.scale.into(),
+                },
+                children: node.children.iter().filter_map(|&c| index[c]).collect(),
+                meshes: node.meshes.iter().filter_map(|&m| meshes.get(m).cloned()).collect(),
+                camera: node.camera.and_then(|c| model.cameras.get(c)).map(|camera| match camera.projection {
+                    AssetProjection::Perspective { yfov, znear, zfar, .. } => NodeCamera {
+                        fov_deg: yfov.to_degrees(),
+                        near: znear,
+                        far: zfar.unwrap_or(default_camera.far),
+                    },
+                    AssetProjection::Orthographic { znear, zfar, .. } => NodeCamera {
+                        fov_deg: default_camera.fov_deg,
+                        near: znear,
+                        far: zfar,
+                    },
+                }),
+                light: node.light.and_then(|l| model.lights.get(l)).map(|light| match light.kind {
+                    AssetLightKind::Directional => NodeLight::Directional {
+                        color: Color::rgb(light.color[0], light.color[1], light.color[2]),
+                        intensity: light.intensity,
+                    },
+                    AssetLightKind::Point | AssetLightKind::Spot { .. } => NodeLight::Point(PointLightComponent {
+                        color: light.color,
+                        intensity: light.intensity,
+                        radius: light.range.unwrap_or(PointLightComponent::default().radius),
+                    }),
+                }),
+            }
+        })
+        .collect();
+    SceneGraph { nodes }
+}
+
+// ── Prefabs ─────────────────────────────────────────────────────────────────
+
+/// Loads [`Prefab`]s through the [`AssetServer`] into a [`PrefabLibrary`]
+/// and keeps their live instances in sync with the files on disk.
+///
+/// Prefabs are keyed by the path they were requested with; keys of nested
+/// prefabs are loaded as paths too.  Call [`PrefabAssets::update`] once per
+/// frame, then instantiate from [`PrefabAssets::library`]:
+///
+/// ```rust,ignore
+/// // In setup():
+/// self.prefabs.load(&mut ctx.asset_server, "assets/tower.prefab");
+///
+/// // In update():
+/// self.prefabs.update(&mut ctx.asset_server, &mut ctx.world);
+/// if self.tower.is_none() && self.prefabs.library.contains("assets/tower.prefab") {
+///     self.tower = ctx.world
+///         .instantiate_prefab(&self.prefabs.library, "assets/tower.prefab", Transform::default())
+///         .ok();
+/// }
+/// ```
+#[derive(Default)]
+pub struct PrefabAssets {
+    pub library: PrefabLibrary,
+    handles: HashMap<String, PrefabSlot>,
+}
+
+struct PrefabSlot {
+    handle: AssetHandle<Prefab>,
+    /// Set once the current load has been moved into the library or
+    /// reported as failed.
+    settled: bool,
+}
+
+impl PrefabAssets {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Begin loading the prefab at `path` (and, on desktop, watching it for
+    /// changes).  Loading the same path again does nothing.
+    pub fn load(&mut self, server: &mut AssetServer, path: &str) {
+        if self.handles.contains_key(path) {
+            return;
+        }
+        let handle = server.load::<Prefab>(path);
+        #[cfg(not(target_arch = "wasm32"))]
+        server.watch(handle);
+        self.handles.insert(path.to_string(), PrefabSlot { handle, settled: false });
+    }
+
+    /// Returns `true` while any requested prefab is still loading.
+    pub fn is_loading(&self, server: &mut AssetServer) -> bool {
+        self.handles.values().any(|slot| matches!(server.get(slot.handle), AssetState::Loading))
+    }
+
+    /// Move finished loads into the library, request the prefabs they nest
+    /// and refresh every instance depending on a (re)loaded prefab.
+    ///
+    /// Returns the keys that were loaded or reloaded this call.
+    pub fn update(&mut self, server: &mut AssetServer, world: &mut ferrous_core::scene::World) -> Vec<String> {
+        let mut changed = Vec::new();
+        for (key, slot) in &mut self.handles {
+            match server.get(slot.handle) {
+                AssetState::Loading => {}
+                // A hot-reload bumps the slot generation; pick up the new handle.
+                AssetState::NotFound => {
+                    slot.handle = server.load::<Prefab>(key.as_str());
+                    slot.settled = false;
+                }
+                AssetState::Failed(msg) => {
+                    if !std::mem::replace(&mut slot.settled, true) {
+                        eprintln!("[PrefabAssets] failed to load '{key}': {msg}");
+                    }
+                }
+                AssetState::Ready(prefab) => {
+                    if !std::mem::replace(&mut slot.settled, true) {
+                        self.library.insert(key.clone(), prefab);
+                        changed.push(key.clone());
+                    }
+                }
+            }
+        }
+
+        for key in self.library.missing() {
+            self.load(server, &key);
+        }
+        for key in &changed {
+            if let Err(e) = world.refresh_prefab_instances(&self.library, key) {
+                eprintln!("[PrefabAssets] could not refresh instances of '{key}': {e}");
+            }
+        }
+        changed
+    }
+}
+
 // ── Internal helpers ─────────────────────────────────────────────────────────
 
 /// Perform GPU registration and entity spawning from an already-loaded
@@ -275,8 +752,6 @@ fn spawn_gltf_from_model(
     path: &str,
     model: &ferrous_assets::AssetModel,
 ) -> Result<Vec<ferrous_core::scene::Handle>> {
-    let path_obj = Path::new(path);
-
     eprintln!(
         "spawning gltf '{}' -> {} meshes, {} materials, {} images",
         path,
@@ -285,6 +760,38 @@ fn spawn_gltf_from_model(
         model.images.len()
     );
 
+    let meshes = register_model(world, renderer, path, model);
+    let mesh_nodes = mesh_nodes(model);
+    let mut out_handles = Vec::new();
+    for (i, mesh) in meshes.iter().enumerate() {
+        let handle = world.spawn_mesh(mesh.asset_key.clone(), mesh.asset_key.clone(), Vec3::ZERO);
+        if let Some(material) = &mesh.material {
+            world.set_material_handle(handle, material.handle);
+            world.set_material_descriptor(handle, material.descriptor.clone());
+        }
+        if !model.meshes[i].morph_targets.is_empty() {
+            let morphs = vec![(mesh.asset_key.clone(), Arc::new(model.meshes[i].clone()))];
+            spawn_morph_targets(world, handle, morphs, &model.animations, &mesh_nodes[i]);
+        }
+        out_handles.push(handle);
+    }
+
+    let skinned: Vec<(&AssetMesh, Option<usize>)> = model.meshes.iter().map(|m| (m, m.skin)).collect();
+    spawn_skeletons(world, &model.skins, &model.animations, &skinned, &out_handles);
+    Ok(out_handles)
+}
+
+/// Register the textures, materials and meshes of `model` with the renderer,
+/// and the mesh geometry with `world` for ray casting.  Returns the mesh key
+/// and material of every entry of `model.meshes`.
+fn register_model(
+    world: &mut ferrous_core::scene::World,
+    renderer: &mut ferrous_renderer::Renderer,
+    path: &str,
+    model: &ferrous_assets::AssetModel,
+) -> Vec<NodeMesh> {
+    let path_obj = Path::new(path);
+
     let n_images = model.images.len();
     let mut linear_flag = vec![false; n_images];
     for raw in &model.materials {
@@ -310,7 +817,7 @@ fn spawn_gltf_from_model(
     }
 
     let mut mat_handles = Vec::with_capacity(model.materials.len());
-    for raw in &model.materials {
+    for (mat_idx, raw) in model.materials.iter().enumerate() {
         let mut desc = ferrous_core::scene::MaterialDescriptor::default();
         desc.base_color = raw.base_color;
         desc.emissive = raw.emissive;
@@ -344,35 +851,301 @@ fn spawn_gltf_from_model(
             if let Some(tex) = tex_handles.get(idx) { desc.ao_tex = Some(tex.0); }
         }
         let mh = renderer.create_material(&desc);
+        let material = MaterialComponent { handle: mh, descriptor: desc.clone() };
+        world.register_material(format!("{}#material{}", path_obj.display(), mat_idx), material);
         mat_handles.push((mh, desc));
     }
 
-    let mut out_handles = Vec::new();
+    let mut out_meshes = Vec::with_capacity(model.meshes.len());
     for (i, mesh) in model.meshes.iter().enumerate() {
         let key = format!("{}#{}", path_obj.display(), i);
-        let n = mesh.positions.len();
-        let mut verts = Vec::with_capacity(n);
-        for j in 0..n {
-            verts.push(ferrous_renderer::geometry::Vertex {
-                position: mesh.positions[j],
-                normal: *mesh.normals.get(j).unwrap_or(&[0.0, 1.0, 0.0]),
-                tangent: *mesh.tangents.get(j).unwrap_or(&[1.0, 0.0, 0.0, 1.0]),
-                color: *mesh.colors.get(j).unwrap_or(&[1.0, 1.0, 1.0, 1.0]),
-                uv: *mesh.uvs.get(j).unwrap_or(&[0.0, 0.0]),
-            });
-        }
+        let verts = vertices(mesh, &mesh.positions, &mesh.normals, &mesh.tangents);
         let gpu_mesh = renderer.create_mesh("gltf_submesh", verts, mesh.indices.clone());
         renderer.register_mesh(&key, gpu_mesh.clone());
+        world.register_mesh_geometry(key.clone(), mesh_geometry(mesh));
 
-        let handle = world.spawn_mesh(key.clone(), key.clone(), Vec3::ZERO);
-        if let Some(mat_idx) = mesh.material_idx {
-            if let Some((mat_h, mat_desc)) = mat_handles.get(mat_idx) {
-                world.set_material_handle(handle, *mat_h);
-                world.set_material_descriptor(handle, mat_desc.clone());
+        let material = mesh
+            .material_idx
+            .and_then(|idx| mat_handles.get(idx))
+            .map(|(handle, descriptor)| MaterialComponent { handle: *handle, descriptor: descriptor.clone() });
+        out_meshes.push(NodeMesh { asset_key: key, material });
+    }
+    out_meshes
+}
+
+/// Renderer vertices of `mesh` with the given (possibly morphed) positions,
+/// normals and tangents.
+fn vertices(mesh: &AssetMesh, positions: &[[f32; 3]], normals: &[[f32; 3]], tangents: &[[f32; 4]]) -> Vec<Vertex> {
+    positions
+        .iter()
+        .enumerate()
+        .map(|(j, &position)| Vertex {
+            position,
+            normal: *normals.get(j).unwrap_or(&[0.0, 1.0, 0.0]),
+            tangent: *tangents.get(j).unwrap_or(&[1.0, 0.0, 0.0, 1.0]),
+            color: *mesh.colors.get(j).unwrap_or(&[1.0, 1.0, 1.0, 1.0]),
+            uv: *mesh.uvs.get(j).unwrap_or(&[0.0, 0.0]),
+        })
+        .collect()
+}
+
+/// Nodes instancing each entry of `model.meshes`.
+fn mesh_nodes(model: &AssetModel) -> Vec<Vec<usize>> {
+    let mut nodes = vec![Vec::new(); model.meshes.len()];
+    for (n, node) in model.nodes.iter().enumerate() {
+        for &m in &node.meshes {
+            if let Some(list) = nodes.get_mut(m) {
+                list.push(n);
             }
         }
-        out_handles.push(handle);
     }
+    nodes
+}
 
-    Ok(out_handles)
+/// CPU copy of a mesh's positions, indices and UVs for [`World::raycast`].
+///
+/// [`World::raycast`]: ferrous_core::scene::World::raycast
+fn mesh_geometry(mesh: &AssetMesh) -> MeshGeometry {
+    MeshGeometry::new(mesh.positions.iter().map(|&p| Vec3::from(p)).collect(), mesh.indices.clone())
+        .with_uvs(mesh.uvs.iter().map(|&uv| uv.into()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ferrous_assets::AssetJoint;
+    use ferrous_core::glam::Vec3;
+
+    /// A joint `y` above its parent and `bind_y` above the origin in bind pose.
+    fn joint(name: &str, node: usize, parent: Option<usize>, y: f32, bind_y: f32) -> AssetJoint {
+        AssetJoint {
+            name: name.into(),
+            node,
+            parent,
+            inverse_bind_matrix: Mat4::from_translation(Vec3::new(0.0, -bind_y, 0.0)).to_cols_array_2d(),
+            translation: [0.0, y, 0.0],
+            rotation: [0.0, 0.0, 0.0, 1.0],
+            scale: [1.0; 3],
+        }
+    }
+
+    #[test]
+    fn skins_and_animations_convert_to_core_components() {
+        // `child` is listed before its parent, as glTF allows.
+        let skin = AssetSkin {
+            name: "rig".into(),
+            joints: vec![joint("child", 2, Some(1), 1.0, 1.5), joint("root", 1, None, 0.5, 0.5)],
+        };
+        let skeleton = skeleton_from_skin(&skin);
+        let names: Vec<&str> = skeleton.bones.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, ["root", "child"]);
+        assert_eq!(skeleton.bones[1].parent_index, Some(0));
+        // Rest pose matches the bind pose: every joint matrix is identity.
+        for m in &skeleton.joint_matrices {
+            assert!(m.abs_diff_eq(Mat4::IDENTITY, 1e-6));
+        }
+
+        let mesh = AssetMesh {
+            joints: vec![[0, 0, 0, 0], [1, 0, 0, 0]],
+            joint_weights: vec![[1.0, 0.0, 0.0, 0.0]; 2],
+            skin: Some(0),
+            ..Default::default()
+        };
+        let influences = bone_influences(&mesh, &skin);
+        assert_eq!(influences[0].indices, [1, 1, 1, 1]);
+        assert_eq!(influences[1].indices, [0, 1, 1, 1]);
+
+        let animation = AssetAnimation {
+            name: "lift".into(),
+            channels: vec![
+                AssetChannel {
+                    node: 1,
+                    node_name: "root".into(),
+                    interpolation: AssetInterpolation::CubicSpline,
+                    times: vec![0.0, 1.0],
+                    values: AssetChannelValues::Translation(vec![
+                        [0.0; 3], [0.0, 1.0, 0.0], [0.0; 3],
+                        [0.0; 3], [0.0, 3.0, 0.0], [0.0; 3],
+                    ]),
+                },
+                AssetChannel {
+                    node: 0,
+                    node_name: "body".into(),
+                    interpolation: AssetInterpolation::Step,
+                    times: vec![0.0, 0.5],
+                    values: AssetChannelValues::MorphWeights(vec![0.0, 1.0, 1.0, 0.0]),
+                },
+            ],
+        };
+        let clip = clip_from_animation(&animation);
+        assert_eq!(clip.duration, 1.0);
+        assert!(!clip.bones.contains_key("body"));
+        let root = clip.bones["root"].sample(0.5);
+        assert!(root.translation.unwrap().abs_diff_eq(Vec3::new(0.0, 2.0, 0.0), 1e-6));
+        assert!(clip.weights.is_none());
+        assert!(morph_clip_from_animation(&animation, &[1]).is_none());
+        let morph = morph_clip_from_animation(&animation, &[0]).unwrap();
+        assert_eq!(morph.weights.as_ref().unwrap().sample(0.75), Some(vec![1.0, 0.0]));
+    }
+
+    #[test]
+    fn spawned_skinned_meshes_keep_their_bone_influences() {
+        let skin = AssetSkin {
+            name: "rig".into(),
+            joints: vec![joint("child", 2, Some(1), 1.0, 1.5), joint("root", 1, None, 0.5, 0.5)],
+        };
+        let mesh = AssetMesh {
+            joints: vec![[0, 1, 0, 0]],
+            joint_weights: vec![[0.75, 0.25, 0.0, 0.0]],
+            skin: Some(0),
+            ..Default::default()
+        };
+
+        let mut world = ferrous_core::scene::World::new();
+        let handle = world.spawn_mesh("body", "rig.glb#0", Vec3::ZERO);
+        spawn_skeletons(&mut world, &[skin], &[], &[(&mesh, mesh.skin)], &[handle]);
+
+        let entity = world.ecs_mapping[&handle.0];
+        let skeleton = world.ecs.get::<SkinnedMesh>(entity).unwrap().skeleton_entity.unwrap();
+        assert!(world.ecs.get::<Skeleton>(skeleton).is_some());
+        let influences = &world.ecs.get::<BoneInfluences>(entity).unwrap().0;
+        assert_eq!(influences.len(), 1);
+        assert_eq!(influences[0].indices, [1, 0, 1, 1]);
+        assert_eq!(influences[0].weights, [0.75, 0.25, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn morph_weights_drive_their_mesh_entity() {
+        use ferrous_core::AnimationSystem;
+        use ferrous_ecs::prelude::{ResourceMap, System};
+
+        let mesh = AssetMesh {
+            positions: vec![[0.0; 3], [1.0, 0.0, 0.0]],
+            indices: vec![0, 1, 0],
+            morph_targets: vec![ferrous_assets::MorphTarget {
+                positions: vec![[0.0, 1.0, 0.0], [0.0, 2.0, 0.0]],
+                ..Default::default()
+            }],
+            morph_weights: vec![0.0],
+            ..Default::default()
+        };
+        let animation = AssetAnimation {
+            name: "smile".into(),
+            channels: vec![AssetChannel {
+                node: 3,
+                node_name: "face".into(),
+                interpolation: AssetInterpolation::Linear,
+                times: vec![0.0, 1.0],
+                values: AssetChannelValues::MorphWeights(vec![0.0, 1.0]),
+            }],
+        };
+
+        let mut world = ferrous_core::scene::World::new();
+        let handle = world.spawn_mesh("face", "face.glb#0", Vec3::ZERO);
+        let entity = world.ecs_mapping[&handle.0];
+        spawn_morph_targets(&mut world, handle, vec![("face.glb#0".into(), Arc::new(mesh))], &[animation], &[3]);
+        assert!(world.ecs.get::<AnimationPlayer>(entity).unwrap().clips.contains_key("smile"));
+
+        // The initial weights count as a change: the mesh is blended once.
+        let blended = changed_morph_meshes(&world.ecs, 0);
+        assert_eq!(blended.len(), 1);
+        assert_eq!(blended[0].0, "face.glb#0");
+        assert_eq!(blended[0].2[1].position, [1.0, 0.0, 0.0]);
+        let tick = world.ecs.change_tick();
+        assert!(changed_morph_meshes(&world.ecs, tick).is_empty());
+        world.ecs.increment_change_tick();
+
+        world.ecs.get_mut::<AnimationPlayer>(entity).unwrap().play("smile");
+        let mut resources = ResourceMap::new();
+        resources.insert(ferrous_core::time::Time { delta: 0.5, ..Default::default() });
+        AnimationSystem.run(&mut world.ecs, &mut resources);
+        assert_eq!(world.ecs.get::<MorphWeights>(entity).unwrap().0, vec![0.5]);
+        let blended = changed_morph_meshes(&world.ecs, tick);
+        assert_eq!(blended[0].2[1].position, [1.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn default_scene_converts_to_scene_graph() {
+        let node = |name: &str, children: Vec<usize>| ferrous_assets::AssetNode {
+            name: name.into(),
+            translation: [0.0, 1.0, 0.0],
+            rotation: [0.0, 0.0, 0.0, 1.0],
+            scale: [1.0; 3],
+            children,
+            meshes: Vec::new(),
+            skin: None,
+            camera: None,
+            light: None,
+        };
+        let mut model = ferrous_assets::AssetModel {
+            nodes: vec![node("level", vec![2]), node("other_scene", vec![]), node("lamp", vec![])],
+            scene_roots: vec![0],
+            cameras: vec![ferrous_assets::AssetCamera {
+                name: "cam".into(),
+                projection: AssetProjection::Perspective {
+                    yfov: std::f32::consts::FRAC_PI_2,
+                    aspect_ratio: None,
+                    znear: 0.5,
+                    zfar: None,
+                },
+            }],
+            lights: vec![ferrous_assets::AssetLight {
+                name: "bulb".into(),
+                kind: AssetLightKind::Spot { inner_cone_angle: 0.0, outer_cone_angle: 0.5 },
+                color: [1.0, 0.5, 0.0],
+                intensity: 20.0,
+                range: Some(4.0),
+            }],
+            ..Default::default()
+        };
+        model.nodes[0].camera = Some(0);
+        model.nodes[0].meshes = vec![0];
+        model.nodes[2].light = Some(0);
+        let meshes = [NodeMesh { asset_key: "level.glb#0".into(), material: None }];
+
+        let graph = scene_graph_from_model(&model, &meshes);
+        let names: Vec<&str> = graph.nodes.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, ["level", "lamp"]);
+        assert_eq!(graph.nodes[0].children, vec![1]);
+        assert_eq!(graph.nodes[0].transform.position, Vec3::Y);
+        assert_eq!(graph.nodes[0].meshes[0].asset_key, "level.glb#0");
+        let camera = graph.nodes[0].camera.unwrap();
+        assert!((camera.fov_deg - 90.0).abs() < 1e-4);
+        assert_eq!((camera.near, camera.far), (0.5, Camera3D::default().far));
+        assert_eq!(
+            graph.nodes[1].light,
+            Some(NodeLight::Point(PointLightComponent { color: [1.0, 0.5, 0.0], intensity: 20.0, radius: 4.0 }))
+        );
+    }
+
+    #[test]
+    fn prefab_assets_load_nested_prefabs_from_disk() {
+        use ferrous_core::scene::PrefabNode;
+
+        let dir = std::env::temp_dir().join(format!("ferrous_prefab_assets_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let barrel_path = dir.join("barrel.prefab").to_string_lossy().into_owned();
+        let tower_path = dir.join("tower.prefab").to_string_lossy().into_owned();
+        let mut barrel = Prefab::new("barrel");
+        barrel.add(PrefabNode::new("barrel"));
+        let mut tower = Prefab::new("tower");
+        tower.add(PrefabNode::new("base").with_prefab(barrel_path.clone()));
+        std::fs::write(&barrel_path, barrel.to_json().unwrap()).unwrap();
+        std::fs::write(&tower_path, tower.to_json().unwrap()).unwrap();
+
+        let mut server = AssetServer::new();
+        let mut world = ferrous_core::scene::World::new();
+        let mut prefabs = PrefabAssets::new();
+        prefabs.load(&mut server, &tower_path);
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(2);
+        while !(prefabs.library.contains(&tower_path) && prefabs.library.contains(&barrel_path)) {
+            assert!(std::time::Instant::now() < deadline, "prefabs did not load");
+            prefabs.update(&mut server, &mut world);
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        let root = world.instantiate_prefab(&prefabs.library, &tower_path, Transform::default()).unwrap();
+        assert!(world.prefab_instance(root).unwrap().node("base/barrel").is_some());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
@@ -6,9 +6,10 @@
 //!
 //! | Etapa | Sistemas registrados |
 //! |-------|---------------------|
-//! | `PreUpdate`  | `TimeSystem` — actualiza el reloj de frame |
-//! | `Update`     | `VelocitySystem`, `AnimationSystem`, `BehaviorSystem` |
-//! | `PostUpdate` | `TransformSystem` — propaga `GlobalTransform` por la jerarquía |
+//! | `PreUpdate`   | `TimeSystem` — actualiza el reloj de frame |
+//! | `FixedUpdate` | `TransformSnapshotSystem`, `VelocitySystem` — paso fijo (por defecto 60 Hz); `PhysicsSystem` y `CharacterControllerSystem` con [`PhysicsPlugin`] |
+//! | `Update`      | `FixedTimeSystem`, `AnimationControllerSystem`, `AnimationSystem`, `BehaviorSystem`, `FollowPathSystem` |
+//! | `PostUpdate` | `TransformSystem` — propaga `GlobalTransform` por la jerarquía; `SpatialIndexSystem` — mantiene el `SpatialIndex` (árbol AABB) |
 //!
 //! Para añadir sistemas propios usa `AppContext::scheduler` (si está expuesto)
 //! o implementa un [`FerrousApp::setup`] que inserte componentes ECS.
@@ -55,8 +56,8 @@ pub use builder::{App, AppConfig, AppMode};
 pub use config::{load_config, ConfigError, EngineConfig};
 pub use context::{AppContext, WindowResizeDirection};
 pub use plugin::{
-    AppBuilder, AssetPlugin, CorePlugin, DefaultPlugins, GuiPlugin, InputPlugin, Plugin,
-    RendererPlugin, TimePlugin, WindowPlugin,
+    AppBuilder, AssetPlugin, CorePlugin, DefaultPlugins, GuiPlugin, InputPlugin, PhysicsPlugin,
+    Plugin, RendererPlugin, TimePlugin, WindowPlugin,
 };
 pub use render_context::RenderContext;
 pub use traits::{DrawContext, FerrousApp};
@@ -89,17 +90,30 @@ pub use ferrous_core::{RenderStats, Viewport};
 pub use ferrous_renderer::scene::GizmoDraw;
 
 // ECS stage / system types — game code can register custom systems
-pub use ferrous_core::{AnimationClip, AnimationPlayer, Keyframe};
+pub use ferrous_core::{
+    AnimationClip, AnimationController, AnimationPlayer, AnimationStateMachine, Interpolation,
+    Keyframe, Track,
+};
 pub use ferrous_core::{Behavior, BehaviorComponent, Stage, Velocity};
 
 // ── Phase 4.5: High-level component API ────────────────────────────────────
 // New ECS components for ergonomic scene construction.
 pub use ferrous_core::scene::{Camera3D, Camera3DBuilder, DirectionalLight, OrbitCamera};
 pub use ferrous_core::scene::{Material, MaterialBuilder};
-pub use ferrous_core::{Children, GlobalTransform, Parent};
+#[allow(deprecated)]
+pub use ferrous_core::{ChildOf, Children, GlobalTransform, Parent};
 pub use ferrous_ecs::prelude::{Entity, StagedScheduler};
 // Plain-function system conversion — users need this to call add_system_fn
 pub use ferrous_ecs::fn_system::IntoSystem;
 
+// Rigid-body physics, joints and character controllers (registered by `PhysicsPlugin`)
+pub use ferrous_physics::{
+    CharacterController, Collider, CollisionEvent, Joint, JointBroken, Motor, PhysicsWorld, RigidBody,
+};
+
 // helpers
-pub use crate::asset_bridge::{spawn_gltf, spawn_gltf_async, GltfSpawnTask};
+pub use crate::asset_bridge::{
+    bone_influences, clip_from_animation, morph_clip_from_animation, scene_graph_from_model,
+    skeleton_from_skin, spawn_gltf, spawn_gltf_async, spawn_gltf_scene, spawn_morph_targets,
+    spawn_skeletons, GltfSpawnTask, MorphMeshes, PrefabAssets,
+};
@@ -15,7 +15,14 @@
 //!     fn name(&self) -> &'static str { "MyPlugin" }
 //!
 //!     fn build(&self, app: &mut AppBuilder) {
-//!         app.add_system(ferrous_ecs::prelude::Stage::Update, my_system);
+//!         // Land after the built-in velocity integration, whatever order
+//!         // the plugins were added in.
+//!         app.add_system_boxed(
+//!             ferrous_ecs::prelude::Stage::Update,
+//!             my_system
+//!                 .into_system()
+//!                 .after(ferrous_core::scene::systems::labels::VELOCITY),
+//!         );
 //!     }
 //! }
 //!
@@ -27,7 +34,9 @@
 //! }
 //! ```
 
-use ferrous_ecs::prelude::Stage;
+use ferrous_core::scene::systems::labels;
+use ferrous_ecs::prelude::{EventUpdateSystem, IntoSystemConfig, Stage, SystemConfig, SystemSet};
+use ferrous_ecs::snapshot::ComponentRegistry;
 use ferrous_ecs::system::System;
 
 use crate::builder::AppConfig;
@@ -70,8 +79,14 @@ pub struct AppBuilder {
     /// Accumulated configuration — plugins can mutate this.
     pub config: AppConfig,
 
-    /// Staged systems to add.  Each entry is `(stage, boxed_system)`.
-    pub(crate) staged_systems: Vec<(Stage, Box<dyn System>)>,
+    /// Staged systems to add.  Each entry is `(stage, configured_system)`.
+    pub(crate) staged_systems: Vec<(Stage, SystemConfig)>,
+
+    /// System sets configured by plugins.
+    pub(crate) system_sets: Vec<SystemSet>,
+
+    /// Components persisted by world snapshots; inserted as a resource.
+    pub(crate) components: ComponentRegistry,
 
     /// Names of registered plugins (for duplicate detection / debug).
     registered_names: Vec<&'static str>,
@@ -83,6 +98,8 @@ impl AppBuilder {
         Self {
             config: AppConfig::default(),
             staged_systems: Vec::new(),
+            system_sets: Vec::new(),
+            components: ComponentRegistry::new(),
             registered_names: Vec::new(),
         }
     }
@@ -152,15 +169,23 @@ impl AppBuilder {
     ///
     /// The system is stored as a boxed `FnMut` closure and registered into
     /// the [`StagedScheduler`][ferrous_ecs::prelude::StagedScheduler] before
-    /// the first frame.
+    /// the first frame.  Labels, `before`/`after` constraints and run
+    /// conditions attached via [`IntoSystemConfig`] are kept.
     ///
     /// ```rust,ignore
     /// app.add_system(Stage::Update, |world: &mut ferrous_ecs::World, res: &mut ResourceMap| {
     ///     // ...
     /// });
     /// ```
-    pub fn add_system<S: System>(mut self, stage: Stage, system: S) -> Self {
-        self.staged_systems.push((stage, Box::new(system)));
+    pub fn add_system(mut self, stage: Stage, system: impl IntoSystemConfig) -> Self {
+        self.staged_systems.push((stage, system.into_config()));
+        self
+    }
+
+    /// Configure a [`SystemSet`] (ordering and run conditions shared by its
+    /// members).
+    pub fn configure_set(mut self, set: SystemSet) -> Self {
+        self.system_sets.push(set);
         self
     }
 
@@ -198,14 +223,40 @@ impl AppBuilder {
         Params: 'static,
     {
         self.staged_systems
-            .push((Stage::Update, Box::new(system.into_system())));
+            .push((Stage::Update, system.into_system().into_config()));
         self
     }
 
-    /// Add a pre-boxed system (builder-pattern helper used by plugin impls
-    /// that call `app.add_system_boxed(...)` from `&mut self` context).
-    pub(crate) fn add_system_boxed(&mut self, stage: Stage, system: Box<dyn System>) {
-        self.staged_systems.push((stage, system));
+    /// Add a system from `&mut self` context (used by [`Plugin::build`]).
+    ///
+    /// Accepts boxed systems as well as configured ones, so plugins can
+    /// place their systems with `.before()` / `.after()` / `.in_set()`.
+    pub fn add_system_boxed(&mut self, stage: Stage, system: impl IntoSystemConfig) {
+        self.staged_systems.push((stage, system.into_config()));
+    }
+
+    /// Configure a [`SystemSet`] from `&mut self` context.
+    pub fn configure_set_boxed(&mut self, set: SystemSet) {
+        self.system_sets.push(set);
+    }
+
+    /// Register a component type for world snapshots
+    /// (`World::serialize_snapshot`).
+    pub fn register_component<T>(mut self) -> Self
+    where
+        T: ferrous_ecs::prelude::Component
+            + Clone
+            + serde::Serialize
+            + serde::de::DeserializeOwned,
+    {
+        self.components.register::<T>();
+        self
+    }
+
+    /// The snapshot [`ComponentRegistry`], for plugins registering their
+    /// own components from [`Plugin::build`].
+    pub fn components(&mut self) -> &mut ComponentRegistry {
+        &mut self.components
     }
 
     // ── Execution ─────────────────────────────────────────────────────────
@@ -227,10 +278,15 @@ impl Default for AppBuilder {
 
 // ── Built-in plugins ──────────────────────────────────────────────────────────
 
-/// Registers core ECS systems: `TimeSystem`, `VelocitySystem`,
-/// `AnimationSystem`, `BehaviorSystem`, `TransformSystem`.
+/// Registers core ECS systems: `TimeSystem`, `TransformSnapshotSystem`,
+/// `VelocitySystem`, `FixedTimeSystem`, `AnimationControllerSystem`, `AnimationSystem`,
+/// `BehaviorSystem`, `FollowPathSystem`, `TransformSystem`, `SpatialIndexSystem`.
 ///
-/// These correspond to the stages `PreUpdate → Update → PostUpdate`.
+/// These correspond to the stages `PreUpdate → FixedUpdate → Update → PostUpdate`.
+///
+/// `Events<AnimationEvent>` is rotated by the runner's built-in
+/// `EventUpdateSystem`; registering another here would rotate it twice per
+/// frame and drop events before later readers see them.
 pub struct CorePlugin;
 
 impl Plugin for CorePlugin {
@@ -240,14 +296,40 @@ impl Plugin for CorePlugin {
 
     fn build(&self, app: &mut AppBuilder) {
         use ferrous_core::{
-            AnimationSystem, BehaviorSystem, TimeSystem, TransformSystem, VelocitySystem,
+            AnimationControllerSystem, AnimationSystem, BehaviorSystem, FixedTimeSystem,
+            FollowPathSystem, SpatialIndexSystem, TimeSystem, TransformSnapshotSystem,
+            TransformSystem, VelocitySystem,
         };
 
-        app.add_system_boxed(Stage::PreUpdate, Box::new(TimeSystem));
-        app.add_system_boxed(Stage::Update, Box::new(VelocitySystem));
-        app.add_system_boxed(Stage::Update, Box::new(AnimationSystem));
-        app.add_system_boxed(Stage::Update, Box::new(BehaviorSystem));
-        app.add_system_boxed(Stage::PostUpdate, Box::new(TransformSystem));
+        app.add_system_boxed(Stage::PreUpdate, TimeSystem.label(labels::TIME));
+        app.add_system_boxed(
+            Stage::FixedUpdate,
+            TransformSnapshotSystem.label(labels::TRANSFORM_SNAPSHOT),
+        );
+        app.add_system_boxed(
+            Stage::FixedUpdate,
+            VelocitySystem
+                .label(labels::VELOCITY)
+                .after(labels::TRANSFORM_SNAPSHOT),
+        );
+        app.add_system_boxed(Stage::Update, FixedTimeSystem.label(labels::FIXED_TIME));
+        app.add_system_boxed(
+            Stage::Update,
+            AnimationControllerSystem
+                .label(labels::ANIMATION_CONTROLLER)
+                .before(labels::ANIMATION),
+        );
+        app.add_system_boxed(Stage::Update, AnimationSystem.label(labels::ANIMATION));
+        app.add_system_boxed(Stage::Update, BehaviorSystem.label(labels::BEHAVIOR));
+        app.add_system_boxed(Stage::Update, FollowPathSystem.label(labels::FOLLOW_PATH));
+        app.add_system_boxed(Stage::PostUpdate, TransformSystem.label(labels::TRANSFORM));
+        app.add_system_boxed(
+            Stage::PostUpdate,
+            SpatialIndexSystem
+                .label(labels::SPATIAL_INDEX)
+                .after(labels::TRANSFORM),
+        );
+        ferrous_core::register_core_components(app.components());
     }
 }
 
@@ -316,7 +398,7 @@ impl Plugin for TimePlugin {
 
     fn build(&self, app: &mut AppBuilder) {
         use ferrous_core::TimeSystem;
-        app.add_system_boxed(Stage::PreUpdate, Box::new(TimeSystem));
+        app.add_system_boxed(Stage::PreUpdate, TimeSystem.label(labels::TIME));
     }
 }
 
@@ -386,6 +468,39 @@ impl Plugin for AssetPlugin {
     }
 }
 
+/// Enables rigid-body physics (`ferrous_physics`).
+///
+/// Registers `PhysicsSystem` in `FixedUpdate` (after the transform
+/// snapshot, so physics bodies interpolate like any other fixed-step
+/// motion) followed by `CharacterControllerSystem`, and rotates
+/// `Events<CollisionEvent>` and `Events<JointBroken>` each frame.  Not
+/// part of [`DefaultPlugins`]; add it explicitly, and insert a
+/// `PhysicsWorld` resource from setup to change gravity or solver
+/// iterations.
+pub struct PhysicsPlugin;
+
+impl Plugin for PhysicsPlugin {
+    fn name(&self) -> &'static str {
+        "PhysicsPlugin"
+    }
+
+    fn build(&self, app: &mut AppBuilder) {
+        use ferrous_physics::labels::{CHARACTER, PHYSICS};
+        use ferrous_physics::{CharacterControllerSystem, CollisionEvent, JointBroken, PhysicsSystem};
+
+        app.add_system_boxed(Stage::PreUpdate, EventUpdateSystem::<CollisionEvent>::default());
+        app.add_system_boxed(Stage::PreUpdate, EventUpdateSystem::<JointBroken>::default());
+        app.add_system_boxed(
+            Stage::FixedUpdate,
+            PhysicsSystem.label(PHYSICS).after(labels::TRANSFORM_SNAPSHOT),
+        );
+        app.add_system_boxed(
+            Stage::FixedUpdate,
+            CharacterControllerSystem.label(CHARACTER).after(PHYSICS),
+        );
+    }
+}
+
 /// Convenience bundle that registers all standard engine plugins:
 ///
 /// - [`CorePlugin`] — ECS systems (time, velocity, animation, transform)
@@ -501,8 +616,55 @@ mod tests {
     #[test]
     fn default_plugins_registers_systems() {
         let app = AppBuilder::new().add_plugin(DefaultPlugins);
-        // CorePlugin registers 5 systems (Time + Velocity + Animation + Behavior + Transform)
-        assert_eq!(app.staged_systems.len(), 5);
+        // CorePlugin registers 10 systems (Time + TransformSnapshot + Velocity
+        // + FixedTime + AnimationController + Animation + Behavior + FollowPath
+        // + Transform + SpatialIndex)
+        assert_eq!(app.staged_systems.len(), 10);
+    }
+
+    #[test]
+    fn plugins_keep_system_labels_and_constraints() {
+        struct LatePlugin;
+        impl Plugin for LatePlugin {
+            fn name(&self) -> &'static str {
+                "LatePlugin"
+            }
+            fn build(&self, app: &mut AppBuilder) {
+                let system = ferrous_ecs::system::fn_system("late", |_, _| {});
+                app.add_system_boxed(Stage::Update, system.after(labels::VELOCITY));
+                app.configure_set_boxed(SystemSet::new("late_set"));
+            }
+        }
+
+        let app = AppBuilder::new()
+            .add_plugin(LatePlugin)
+            .add_plugin(CorePlugin);
+        let mut sched = ferrous_ecs::prelude::StagedScheduler::new();
+        for (stage, system) in app.staged_systems {
+            sched.add(stage, system);
+        }
+        assert_eq!(app.system_sets.len(), 1);
+        assert!(sched.build().is_ok());
+    }
+
+    #[test]
+    fn physics_plugin_adds_fixed_step_system() {
+        let app = AppBuilder::new().add_plugin(PhysicsPlugin).add_plugin(DefaultPlugins);
+        assert_eq!(app.staged_systems.len(), 14);
+        let mut sched = ferrous_ecs::prelude::StagedScheduler::new();
+        for (stage, system) in app.staged_systems {
+            sched.add(stage, system);
+        }
+        assert!(sched.build().is_ok());
+    }
+
+    #[test]
+    fn core_plugin_registers_snapshot_components() {
+        let app = AppBuilder::new()
+            .add_plugin(CorePlugin)
+            .register_component::<ferrous_core::scene::ShadowCaster>();
+        assert!(app.components.contains::<ferrous_core::Transform>());
+        assert!(app.components.contains::<ferrous_core::Parent>());
     }
 
     #[test]
@@ -271,6 +271,12 @@ impl<'r> RenderContext<'r> {
         self.inner.draw_line(start, end, color);
     }
 
+    /// Draw a spline (curve, control points and control polygon) for one
+    /// frame.  `transform` maps its points to world space.
+    pub fn draw_spline(&mut self, spline: &ferrous_core::scene::Spline, transform: ferrous_core::glam::Mat4, color: Color) {
+        self.inner.draw_spline(spline, transform, color);
+    }
+
     /// Push a technical 2D shape for rendering this frame.
     pub fn draw_2d_shape(&mut self, instance: ferrous_renderer::render_2d::ShapeInstance) {
         self.inner.draw_2d_shape(instance);
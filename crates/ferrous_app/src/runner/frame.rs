@@ -155,9 +155,16 @@ impl<A: FerrousApp + 'static> Runner<A> {
         time_snapshot.fps = if dt > 0.0 { 1.0 / dt } else { 60.0 };
 
         self.resources.insert(time_snapshot);
+        // Systems read this frame's keys (e.g. `CharacterControllerSystem`).
+        self.resources.insert(self.input.clone());
         self.systems
             .run_all(&mut self.world.ecs, &mut self.resources);
-        let time = time_snapshot;
+        // `FixedTimeSystem` filled in the fixed step and interpolation alpha.
+        let time = self
+            .resources
+            .get::<ferrous_core::Time>()
+            .copied()
+            .unwrap_or(time_snapshot);
 
 
         // ── 1. UPDATE ───────────────────────────────────────────────────────
@@ -206,7 +213,11 @@ impl<A: FerrousApp + 'static> Runner<A> {
         
         if self.config.mode == AppMode::Game3D || renderer_mode == ferrous_renderer::RendererMode::Full3D {
             self.app.on_sync_world(&self.world);
-            gfx.renderer.sync_world(&self.world);
+            crate::asset_bridge::blend_morph_meshes(&self.world, &mut gfx.renderer, &mut self.morph_tick);
+            match self.resources.get::<ferrous_core::scene::SpatialIndex>() {
+                Some(index) => gfx.renderer.sync_world_indexed(&self.world, index),
+                None => gfx.renderer.sync_world(&self.world),
+            }
 
             if self.viewport.width > 0 && self.viewport.height > 0 {
                 let (mx, my) = self.input.mouse_position();
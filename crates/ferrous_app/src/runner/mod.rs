@@ -49,8 +49,17 @@ pub(crate) fn run_plugin_app(mut builder: crate::plugin::AppBuilder) {
     let mut runner = Runner::new(PluginApp, builder.config.clone());
 
     for (stage, system) in builder.staged_systems.drain(..) {
-        runner.systems.add_boxed(stage, system);
+        runner.systems.add(stage, system);
     }
+    for set in builder.system_sets.drain(..) {
+        runner.systems.configure_set(set);
+    }
+    if let Err(e) = runner.systems.build() {
+        panic!("AppBuilder: {e}");
+    }
+    runner
+        .resources
+        .insert(std::mem::take(&mut builder.components));
 
     #[cfg(not(target_arch = "wasm32"))]
     {
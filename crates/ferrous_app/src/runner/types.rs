@@ -2,10 +2,14 @@
 
 use ferrous_assets::{AssetHandle, AssetServer, Font};
 use ferrous_core::{
-    AnimationSystem, BehaviorSystem, InputState, TimeClock, TimeSystem, TransformSystem,
-    VelocitySystem, Viewport, World,
+    AnimationControllerSystem, AnimationEvent, AnimationSystem, BehaviorSystem, FixedTimeSystem, InputState, TimeClock, TimeSystem,
+    TransformSnapshotSystem, TransformSystem, VelocitySystem, Viewport, World,
 };
-use ferrous_ecs::prelude::{ResourceMap, Stage, StagedScheduler};
+use ferrous_core::scene::systems::labels;
+use ferrous_ecs::prelude::{
+    EventUpdateSystem, IntoSystemConfig, ResourceMap, Stage, StagedScheduler,
+};
+use ferrous_ecs::snapshot::ComponentRegistry;
 use std::sync::Arc;
 use winit::window::Window;
 
@@ -57,16 +61,46 @@ pub(crate) struct Runner<A: FerrousApp + 'static> {
     pub(super) shift_held: bool,
     /// Tracks the last deterministic time absolute value passed to render frames
     pub(super) last_deterministic_t: Option<f64>,
+    /// World tick at which morphing meshes were last re-blended.
+    pub(super) morph_tick: u64,
 }
 
 impl<A: FerrousApp + 'static> Runner<A> {
     pub(super) fn new(app: A, config: AppConfig) -> Self {
         let mut systems = StagedScheduler::new();
-        systems.add(Stage::PreUpdate, TimeSystem);
-        systems.add(Stage::Update, VelocitySystem);
-        systems.add(Stage::Update, AnimationSystem);
-        systems.add(Stage::Update, BehaviorSystem);
-        systems.add(Stage::PostUpdate, TransformSystem);
+        systems.add(Stage::PreUpdate, TimeSystem.label(labels::TIME));
+        systems.add(Stage::PreUpdate, EventUpdateSystem::<AnimationEvent>::default());
+        systems.add(
+            Stage::FixedUpdate,
+            TransformSnapshotSystem.label(labels::TRANSFORM_SNAPSHOT),
+        );
+        systems.add(
+            Stage::FixedUpdate,
+            VelocitySystem
+                .label(labels::VELOCITY)
+                .after(labels::TRANSFORM_SNAPSHOT),
+        );
+        systems.add(Stage::Update, FixedTimeSystem.label(labels::FIXED_TIME));
+        systems.add(
+            Stage::Update,
+            AnimationControllerSystem
+                .label(labels::ANIMATION_CONTROLLER)
+                .before(labels::ANIMATION),
+        );
+        systems.add(Stage::Update, AnimationSystem.label(labels::ANIMATION));
+        systems.add(Stage::Update, BehaviorSystem.label(labels::BEHAVIOR));
+        systems.add(Stage::PostUpdate, TransformSystem.label(labels::TRANSFORM));
+
+        // Built-in components persisted by `World::serialize_snapshot`.
+        let mut resources = ResourceMap::new();
+        let mut components = ComponentRegistry::new();
+        ferrous_core::register_core_components(&mut components);
+        resources.insert(components);
+
+        // `SpatialIndexSystem` learns of removed bounds through the world's
+        // hooks, which `World::clear` also resets.
+        let world = World::new();
+        resources.insert(world.spatial_removals().clone());
 
         Self {
             app,
@@ -83,7 +117,7 @@ impl<A: FerrousApp + 'static> Runner<A> {
             },
             window_size: (0, 0),
             clock: TimeClock::new(),
-            world: World::new(),
+            world,
             font: None,
             #[cfg(not(target_arch = "wasm32"))]
             font_asset_handle: None,
@@ -95,11 +129,12 @@ impl<A: FerrousApp + 'static> Runner<A> {
             next_frame_deadline: None,
             last_action_time: Instant::now(),
             systems,
-            resources: ResourceMap::new(),
+            resources,
             asset_server: AssetServer::new(),
             ctrl_held: false,
             shift_held: false,
             last_deterministic_t: None,
+            morph_tick: 0,
         }
     }
 }
@@ -34,10 +34,29 @@ pub struct RawMaterial {
     pub double_sided: bool,
 }
 
+/// One morph target (blend shape) of a primitive: per-vertex displacements
+/// added to the base attributes, scaled by the target's weight.  Attributes
+/// the target does not displace are left empty.
+#[derive(Debug, Clone, Default)]
+pub struct MorphTarget {
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+    /// XYZ displacement of the tangent; the handedness (`w`) is not morphed.
+    pub tangents: Vec<[f32; 3]>,
+}
+
+/// Vertex attributes of an [`AssetMesh`] after morph-target blending.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MorphedVertices {
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+    pub tangents: Vec<[f32; 4]>,
+}
+
 /// Mesh data extracted from a GLTF primitive.  Contains separate vertex
 /// attribute arrays (positions/normals/uvs/etc.) so that the caller is free
 /// to convert them into whatever GPU representation they need.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct AssetMesh {
     pub positions: Vec<[f32; 3]>,
     pub normals: Vec<[f32; 3]>,
@@ -46,6 +65,215 @@ pub struct AssetMesh {
     pub colors: Vec<[f32; 4]>,
     pub indices: Vec<u32>,
     pub material_idx: Option<usize>,
+    /// Morph targets of the primitive, in glTF order.
+    pub morph_targets: Vec<MorphTarget>,
+    /// Initial morph weights from `mesh.weights` (empty when unspecified).
+    pub morph_weights: Vec<f32>,
+    /// `JOINTS_0`: indices into the joints of [`skin`](Self::skin), four per
+    /// vertex (empty for unskinned meshes).
+    pub joints: Vec<[u16; 4]>,
+    /// `WEIGHTS_0`: weights of the four joints, per vertex.
+    pub joint_weights: Vec<[f32; 4]>,
+    /// Index into [`AssetModel::skins`] of the skin the mesh is bound to by
+    /// the first node instancing it with one.
+    pub skin: Option<usize>,
+}
+
+impl AssetMesh {
+    /// CPU reference implementation of morph-target blending: every base
+    /// attribute plus the sum of the target displacements scaled by
+    /// `weights` (missing weights count as zero).  Normals and tangents are
+    /// renormalised afterwards.
+    pub fn blend_morph_targets(&self, weights: &[f32]) -> MorphedVertices {
+        let mut out = MorphedVertices {
+            positions: self.positions.clone(),
+            normals: self.normals.clone(),
+            tangents: self.tangents.clone(),
+        };
+        fn accumulate(base: &mut [f32], delta: &[f32; 3], weight: f32) {
+            for (b, d) in base.iter_mut().zip(delta) {
+                *b += d * weight;
+            }
+        }
+        for (target, &weight) in self.morph_targets.iter().zip(weights) {
+            if weight == 0.0 {
+                continue;
+            }
+            for (p, d) in out.positions.iter_mut().zip(&target.positions) {
+                accumulate(p, d, weight);
+            }
+            for (n, d) in out.normals.iter_mut().zip(&target.normals) {
+                accumulate(n, d, weight);
+            }
+            for (t, d) in out.tangents.iter_mut().zip(&target.tangents) {
+                accumulate(&mut t[..3], d, weight);
+            }
+        }
+        fn normalize(v: &mut [f32]) {
+            let len = v.iter().map(|c| c * c).sum::<f32>().sqrt();
+            if len > 1e-8 {
+                v.iter_mut().for_each(|c| *c /= len);
+            }
+        }
+        out.normals.iter_mut().for_each(|n| normalize(n));
+        out.tangents.iter_mut().for_each(|t| normalize(&mut t[..3]));
+        out
+    }
+}
+
+/// One joint of an [`AssetSkin`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssetJoint {
+    /// Node name (`node_<index>` when unnamed); animation channels use the
+    /// same naming.
+    pub name: String,
+    /// Index of the joint's node in the glTF document.
+    pub node: usize,
+    /// Index (in the skin) of the nearest ancestor that is also a joint.
+    /// Non-joint nodes in between are skipped, and so is their transform.
+    pub parent: Option<usize>,
+    /// Column-major matrix from mesh space to joint space in bind pose.
+    pub inverse_bind_matrix: [[f32; 4]; 4],
+    /// Rest-pose local transform of the node.
+    pub translation: [f32; 3],
+    /// Rest-pose rotation quaternion, `[x, y, z, w]`.
+    pub rotation: [f32; 4],
+    pub scale: [f32; 3],
+}
+
+/// A glTF skin: its joints, in the order `JOINTS_0` indexes them.  Parents
+/// may come after their children.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssetSkin {
+    pub name: String,
+    pub joints: Vec<AssetJoint>,
+}
+
+/// Sampler interpolation of an [`AssetChannel`], mirroring
+/// `gltf::animation::Interpolation`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetInterpolation {
+    Step,
+    Linear,
+    /// Outputs hold an `(in_tangent, value, out_tangent)` triple per key.
+    CubicSpline,
+}
+
+/// Output values of an [`AssetChannel`], one per key (three per key for
+/// cubic splines).
+#[derive(Debug, Clone, PartialEq)]
+pub enum AssetChannelValues {
+    Translation(Vec<[f32; 3]>),
+    /// Quaternions, `[x, y, z, w]`.
+    Rotation(Vec<[f32; 4]>),
+    Scale(Vec<[f32; 3]>),
+    /// Morph weights, flattened: every key holds one weight per target.
+    MorphWeights(Vec<f32>),
+}
+
+/// One animated property of one node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssetChannel {
+    /// Index of the target node in the glTF document.
+    pub node: usize,
+    /// Name of the target node (`node_<index>` when unnamed).
+    pub node_name: String,
+    pub interpolation: AssetInterpolation,
+    /// Key times in seconds.
+    pub times: Vec<f32>,
+    pub values: AssetChannelValues,
+}
+
+/// A glTF animation: every channel it drives.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssetAnimation {
+    /// Animation name (`animation_<index>` when unnamed).
+    pub name: String,
+    pub channels: Vec<AssetChannel>,
+}
+
+impl AssetAnimation {
+    /// Time of the last key over all channels.
+    pub fn duration(&self) -> f32 {
+        self.channels
+            .iter()
+            .filter_map(|c| c.times.last().copied())
+            .fold(0.0, f32::max)
+    }
+}
+
+/// Projection of an [`AssetCamera`], as in glTF (angles in radians).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AssetProjection {
+    Perspective {
+        yfov: f32,
+        aspect_ratio: Option<f32>,
+        znear: f32,
+        /// `None` for an infinite projection.
+        zfar: Option<f32>,
+    },
+    Orthographic {
+        xmag: f32,
+        ymag: f32,
+        znear: f32,
+        zfar: f32,
+    },
+}
+
+/// A glTF camera.  It looks down the -Z axis of the nodes instancing it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssetCamera {
+    pub name: String,
+    pub projection: AssetProjection,
+}
+
+/// Type of an [`AssetLight`] (`KHR_lights_punctual`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AssetLightKind {
+    /// Shines down the -Z axis of its node.
+    Directional,
+    Point,
+    /// Cone around the node's -Z axis; angles in radians.
+    Spot {
+        inner_cone_angle: f32,
+        outer_cone_angle: f32,
+    },
+}
+
+/// A `KHR_lights_punctual` light.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssetLight {
+    pub name: String,
+    pub kind: AssetLightKind,
+    /// Linear RGB colour.
+    pub color: [f32; 3],
+    /// Candela for point and spot lights, lux for directional lights.
+    pub intensity: f32,
+    /// Distance at which the light reaches zero; `None` is unlimited.
+    pub range: Option<f32>,
+}
+
+/// A node of the glTF scene graph.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssetNode {
+    /// Node name (`node_<index>` when unnamed).
+    pub name: String,
+    /// Local transform relative to the parent node.
+    pub translation: [f32; 3],
+    /// Rotation quaternion, `[x, y, z, w]`.
+    pub rotation: [f32; 4],
+    pub scale: [f32; 3],
+    /// Indices of the child nodes.
+    pub children: Vec<usize>,
+    /// Indices into [`AssetModel::meshes`] of the primitives of the node's
+    /// mesh (empty when it has none).
+    pub meshes: Vec<usize>,
+    /// Index into [`AssetModel::skins`].
+    pub skin: Option<usize>,
+    /// Index into [`AssetModel::cameras`].
+    pub camera: Option<usize>,
+    /// Index into [`AssetModel::lights`].
+    pub light: Option<usize>,
 }
 
 /// A complete model loaded from a `.gltf`/`.glb` file.  Images are stored as
@@ -53,11 +281,20 @@ pub struct AssetMesh {
 /// `meshes` matches the order of the corresponding objects in the GLTF
 /// document; texture indices in the material descriptors refer into the
 /// `images` vector.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct AssetModel {
     pub meshes: Vec<AssetMesh>,
     pub materials: Vec<RawMaterial>,
     pub images: Vec<(u32, u32, Vec<u8>)>,
+    pub skins: Vec<AssetSkin>,
+    pub animations: Vec<AssetAnimation>,
+    /// Every node of the document, in glTF order.
+    pub nodes: Vec<AssetNode>,
+    /// Root nodes of the default scene (the first scene when none is marked
+    /// default, or every parentless node when the file has no scenes).
+    pub scene_roots: Vec<usize>,
+    pub cameras: Vec<AssetCamera>,
+    pub lights: Vec<AssetLight>,
 }
 
 /// Load a GLTF/GLB file and return the raw geometry/material data.
@@ -346,9 +583,28 @@ pub fn parse_gltf_data(
         tangents
     }
 
+    // --- node hierarchy -----------------------------------------------------
+    let mut node_parent = vec![None; document.nodes().len()];
+    let mut mesh_skin = vec![None; document.meshes().len()];
+    for node in document.nodes() {
+        for child in node.children() {
+            node_parent[child.index()] = Some(node.index());
+        }
+        if let (Some(mesh), Some(skin)) = (node.mesh(), node.skin()) {
+            mesh_skin[mesh.index()].get_or_insert(skin.index());
+        }
+    }
+    fn node_name(node: &gltf::Node) -> String {
+        node.name()
+            .map(str::to_owned)
+            .unwrap_or_else(|| format!("node_{}", node.index()))
+    }
+
     // --- meshes -------------------------------------------------------------
     let mut out_meshes = Vec::new();
+    let mut mesh_primitives = vec![0..0; document.meshes().len()];
     for mesh in document.meshes() {
+        let first_primitive = out_meshes.len();
         for primitive in mesh.primitives() {
             let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()]));
             let positions: Vec<[f32; 3]> = reader
@@ -432,6 +688,24 @@ pub fn parse_gltf_data(
                 uvs // already zero-filled above via unwrap_or_else
             };
 
+            let morph_targets: Vec<MorphTarget> = reader
+                .read_morph_targets()
+                .map(|(positions, normals, tangents)| MorphTarget {
+                    positions: positions.map(|p| p.collect()).unwrap_or_default(),
+                    normals: normals.map(|n| n.collect()).unwrap_or_default(),
+                    tangents: tangents.map(|t| t.collect()).unwrap_or_default(),
+                })
+                .collect();
+
+            let joints: Vec<[u16; 4]> = reader
+                .read_joints(0)
+                .map(|j| j.into_u16().collect())
+                .unwrap_or_default();
+            let joint_weights: Vec<[f32; 4]> = reader
+                .read_weights(0)
+                .map(|w| w.into_f32().collect())
+                .unwrap_or_default();
+
             let mesh = AssetMesh {
                 positions,
                 normals,
@@ -440,15 +714,353 @@ pub fn parse_gltf_data(
                 colors,
                 indices,
                 material_idx: primitive.material().index(),
+                morph_targets,
+                morph_weights: mesh.weights().map(<[f32]>::to_vec).unwrap_or_default(),
+                joints,
+                joint_weights,
+                skin: mesh_skin[mesh.index()],
             };
             eprintln!("first uv = {:?}", mesh.uvs.first());
             out_meshes.push(mesh);
         }
+        mesh_primitives[mesh.index()] = first_primitive..out_meshes.len();
     }
 
+    // --- skins --------------------------------------------------------------
+    let mut out_skins = Vec::with_capacity(document.skins().len());
+    for skin in document.skins() {
+        let reader = skin.reader(|buffer| Some(&buffers[buffer.index()]));
+        let inverse_bind: Vec<[[f32; 4]; 4]> = reader
+            .read_inverse_bind_matrices()
+            .map(|m| m.collect())
+            .unwrap_or_default();
+        let joint_nodes: Vec<usize> = skin.joints().map(|n| n.index()).collect();
+        let joints = skin
+            .joints()
+            .enumerate()
+            .map(|(i, node)| {
+                let mut ancestor = node_parent[node.index()];
+                let parent = loop {
+                    match ancestor {
+                        Some(n) => match joint_nodes.iter().position(|&j| j == n) {
+                            Some(j) => break Some(j),
+                            None => ancestor = node_parent[n],
+                        },
+                        None => break None,
+                    }
+                };
+                let (translation, rotation, scale) = node.transform().decomposed();
+                AssetJoint {
+                    name: node_name(&node),
+                    node: node.index(),
+                    parent,
+                    inverse_bind_matrix: inverse_bind.get(i).copied().unwrap_or([
+                        [1.0, 0.0, 0.0, 0.0],
+                        [0.0, 1.0, 0.0, 0.0],
+                        [0.0, 0.0, 1.0, 0.0],
+                        [0.0, 0.0, 0.0, 1.0],
+                    ]),
+                    translation,
+                    rotation,
+                    scale,
+                }
+            })
+            .collect();
+        out_skins.push(AssetSkin {
+            name: skin
+                .name()
+                .map(str::to_owned)
+                .unwrap_or_else(|| format!("skin_{}", skin.index())),
+            joints,
+        });
+    }
+
+    // --- animations ---------------------------------------------------------
+    let mut out_animations = Vec::with_capacity(document.animations().len());
+    for animation in document.animations() {
+        use gltf::animation::util::ReadOutputs;
+        let channels = animation
+            .channels()
+            .filter_map(|channel| {
+                let reader = channel.reader(|buffer| Some(&buffers[buffer.index()]));
+                let times: Vec<f32> = reader.read_inputs()?.collect();
+                let values = match reader.read_outputs()? {
+                    ReadOutputs::Translations(t) => AssetChannelValues::Translation(t.collect()),
+                    ReadOutputs::Rotations(r) => AssetChannelValues::Rotation(r.into_f32().collect()),
+                    ReadOutputs::Scales(s) => AssetChannelValues::Scale(s.collect()),
+                    ReadOutputs::MorphTargetWeights(w) => {
+                        AssetChannelValues::MorphWeights(w.into_f32().collect())
+                    }
+                };
+                let interpolation = match channel.sampler().interpolation() {
+                    gltf::animation::Interpolation::Step => AssetInterpolation::Step,
+                    gltf::animation::Interpolation::Linear => AssetInterpolation::Linear,
+                    gltf::animation::Interpolation::CubicSpline => AssetInterpolation::CubicSpline,
+                };
+                let node = channel.target().node();
+                Some(AssetChannel {
+                    node: node.index(),
+                    node_name: node_name(&node),
+                    interpolation,
+                    times,
+                    values,
+                })
+            })
+            .collect();
+        out_animations.push(AssetAnimation {
+            name: animation
+                .name()
+                .map(str::to_owned)
+                .unwrap_or_else(|| format!("animation_{}", animation.index())),
+            channels,
+        });
+    }
+
+    // --- nodes, cameras, lights ---------------------------------------------
+    let out_nodes = document
+        .nodes()
+        .map(|node| {
+            let (translation, rotation, scale) = node.transform().decomposed();
+            AssetNode {
+                name: node_name(&node),
+                translation,
+                rotation,
+                scale,
+                children: node.children().map(|c| c.index()).collect(),
+                meshes: node
+                    .mesh()
+                    .map(|m| mesh_primitives[m.index()].clone().collect())
+                    .unwrap_or_default(),
+                skin: node.skin().map(|s| s.index()),
+                camera: node.camera().map(|c| c.index()),
+                light: node.light().map(|l| l.index()),
+            }
+        })
+        .collect();
+    let scene_roots = match document.default_scene().or_else(|| document.scenes().next()) {
+        Some(scene) => scene.nodes().map(|n| n.index()).collect(),
+        None => (0..node_parent.len()).filter(|&n| node_parent[n].is_none()).collect(),
+    };
+    let out_cameras = document
+        .cameras()
+        .map(|camera| AssetCamera {
+            name: camera
+                .name()
+                .map(str::to_owned)
+                .unwrap_or_else(|| format!("camera_{}", camera.index())),
+            projection: match camera.projection() {
+                gltf::camera::Projection::Perspective(p) => AssetProjection::Perspective {
+                    yfov: p.yfov(),
+                    aspect_ratio: p.aspect_ratio(),
+                    znear: p.znear(),
+                    zfar: p.zfar(),
+                },
+                gltf::camera::Projection::Orthographic(o) => AssetProjection::Orthographic {
+                    xmag: o.xmag(),
+                    ymag: o.ymag(),
+                    znear: o.znear(),
+                    zfar: o.zfar(),
+                },
+            },
+        })
+        .collect();
+    let out_lights = document
+        .lights()
+        .into_iter()
+        .flatten()
+        .map(|light| {
+            use gltf::khr_lights_punctual::Kind;
+            AssetLight {
+                name: light
+                    .name()
+                    .map(str::to_owned)
+                    .unwrap_or_else(|| format!("light_{}", light.index())),
+                kind: match light.kind() {
+                    Kind::Directional => AssetLightKind::Directional,
+                    Kind::Point => AssetLightKind::Point,
+                    Kind::Spot { inner_cone_angle, outer_cone_angle } => {
+                        AssetLightKind::Spot { inner_cone_angle, outer_cone_angle }
+                    }
+                },
+                color: light.color(),
+                intensity: light.intensity(),
+                range: light.range(),
+            }
+        })
+        .collect();
+
     Ok(AssetModel {
         meshes: out_meshes,
         materials: out_materials,
         images: out_images,
+        skins: out_skins,
+        animations: out_animations,
+        nodes: out_nodes,
+        scene_roots,
+        cameras: out_cameras,
+        lights: out_lights,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// One triangle with a single morph target lifting its first two
+    /// vertices along +Z, default weight 0.25.
+    const MORPH_TRIANGLE: &str = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{
+            "byteLength": 80,
+            "uri": "data:application/octet-stream;base64,AAAAAAAAAAAAAAAAAACAPwAAAAAAAAAAAAAAAAAAgD8AAAAAAAAAAAAAAAAAAIA/AAAAAAAAAAAAAIA/AAAAAAAAAAAAAAAAAAABAAIAAAA="
+        }],
+        "bufferViews": [
+            { "buffer": 0, "byteOffset": 0, "byteLength": 36 },
+            { "buffer": 0, "byteOffset": 36, "byteLength": 36 },
+            { "buffer": 0, "byteOffset": 72, "byteLength": 6 }
+        ],
+        "accessors": [
+            { "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3", "min": [0, 0, 0], "max": [1, 1, 0] },
+            { "bufferView": 1, "componentType": 5126, "count": 3, "type": "VEC3", "min": [0, 0, 0], "max": [0, 0, 1] },
+            { "bufferView": 2, "componentType": 5123, "count": 3, "type": "SCALAR" }
+        ],
+        "meshes": [{
+            "primitives": [{
+                "attributes": { "POSITION": 0 },
+                "indices": 2,
+                "targets": [{ "POSITION": 1 }]
+            }],
+            "weights": [0.25]
+        }]
+    }"#;
+
+    /// A triangle skinned to a two-joint chain `root` → `child` (listed
+    /// child first in the skin), animated by a linear rotation of `child`
+    /// and a cubic-spline translation of `root`.
+    const SKINNED_TRIANGLE: &str = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{
+            "byteLength": 336,
+            "uri": "data:application/octet-stream;base64,AAAAAAAAAAAAAAAAAACAPwAAAAAAAAAAAAAAAAAAAEAAAAAAAAAAAAEAAAAAAQAAAACAPwAAAAAAAAAAAAAAAAAAgD8AAAAAAAAAAAAAAAAAAAA/AAAAPwAAAAAAAAAAAACAPwAAAAAAAAAAAAAAAAAAAAAAAIA/AAAAAAAAAAAAAAAAAAAAAAAAgD8AAAAAAAAAAAAAAMAAAAAAAACAPwAAgD8AAAAAAAAAAAAAAAAAAAAAAACAPwAAAAAAAAAAAAAAAAAAAAAAAIA/AAAAAAAAAAAAAIC/AAAAAAAAgD8AAAAAAACAPwAAAAAAAAAAAAAAAAAAgD8AAAAAAAAAAPMENT/zBDU/AAAAAAAAAAAAAAAAAAAAAAAAgD8AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAQEAAAAAAAAAAAAAAAAAAAAAA"
+        }],
+        "bufferViews": [
+            { "buffer": 0, "byteOffset": 0, "byteLength": 36 },
+            { "buffer": 0, "byteOffset": 36, "byteLength": 12 },
+            { "buffer": 0, "byteOffset": 48, "byteLength": 48 },
+            { "buffer": 0, "byteOffset": 96, "byteLength": 128 },
+            { "buffer": 0, "byteOffset": 224, "byteLength": 8 },
+            { "buffer": 0, "byteOffset": 232, "byteLength": 32 },
+            { "buffer": 0, "byteOffset": 264, "byteLength": 72 }
+        ],
+        "accessors": [
+            { "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3", "min": [0, 0, 0], "max": [1, 2, 0] },
+            { "bufferView": 1, "componentType": 5121, "count": 3, "type": "VEC4" },
+            { "bufferView": 2, "componentType": 5126, "count": 3, "type": "VEC4" },
+            { "bufferView": 3, "componentType": 5126, "count": 2, "type": "MAT4" },
+            { "bufferView": 4, "componentType": 5126, "count": 2, "type": "SCALAR", "min": [0], "max": [1] },
+            { "bufferView": 5, "componentType": 5126, "count": 2, "type": "VEC4" },
+            { "bufferView": 6, "componentType": 5126, "count": 6, "type": "VEC3" }
+        ],
+        "meshes": [{ "primitives": [{ "attributes": { "POSITION": 0, "JOINTS_0": 1, "WEIGHTS_0": 2 } }] }],
+        "nodes": [
+            { "name": "body", "mesh": 0, "skin": 0 },
+            { "name": "root", "translation": [0, 1, 0], "children": [2] },
+            { "name": "child", "translation": [0, 1, 0] }
+        ],
+        "skins": [{ "joints": [2, 1], "inverseBindMatrices": 3 }],
+        "animations": [{
+            "name": "wave",
+            "channels": [
+                { "sampler": 0, "target": { "node": 2, "path": "rotation" } },
+                { "sampler": 1, "target": { "node": 1, "path": "translation" } }
+            ],
+            "samplers": [
+                { "input": 4, "output": 5, "interpolation": "LINEAR" },
+                { "input": 4, "output": 6, "interpolation": "CUBICSPLINE" }
+            ]
+        }],
+        "scenes": [{ "nodes": [0, 1] }]
+    }"#;
+
+    /// A node tree with a camera and two `KHR_lights_punctual` lights; no
+    /// geometry.
+    const LIT_SCENE: &str = r#"{
+        "asset": { "version": "2.0" },
+        "extensionsUsed": ["KHR_lights_punctual"],
+        "extensions": { "KHR_lights_punctual": { "lights": [
+            { "name": "sun", "type": "directional", "color": [1, 0.9, 0.8], "intensity": 3 },
+            { "type": "spot", "intensity": 40, "range": 12, "spot": { "outerConeAngle": 0.5 } }
+        ] } },
+        "cameras": [{ "type": "perspective", "perspective": { "yfov": 0.8, "znear": 0.1, "zfar": 100 } }],
+        "nodes": [
+            { "name": "level", "children": [1, 2] },
+            { "name": "cam", "translation": [0, 2, 5], "camera": 0 },
+            { "name": "sun", "rotation": [-0.7071068, 0, 0, 0.7071068],
+              "extensions": { "KHR_lights_punctual": { "light": 0 } } },
+            { "name": "unused", "extensions": { "KHR_lights_punctual": { "light": 1 } } }
+        ],
+        "scene": 0,
+        "scenes": [{ "nodes": [0] }]
+    }"#;
+
+    #[test]
+    fn parses_morph_targets_and_blends_on_cpu() {
+        let model = load_gltf_from_slice(MORPH_TRIANGLE.as_bytes()).unwrap();
+        let mesh = &model.meshes[0];
+        assert_eq!(mesh.morph_weights, vec![0.25]);
+        assert_eq!(mesh.morph_targets.len(), 1);
+        assert_eq!(mesh.morph_targets[0].positions[1], [0.0, 0.0, 1.0]);
+        assert!(mesh.morph_targets[0].normals.is_empty());
+
+        let morphed = mesh.blend_morph_targets(&mesh.morph_weights);
+        assert_eq!(morphed.positions, vec![[0.0, 0.0, 0.25], [1.0, 0.0, 0.25], [0.0, 1.0, 0.0]]);
+        assert_eq!(mesh.blend_morph_targets(&[]).positions, mesh.positions);
+    }
+    #[test]
+    fn parses_skins_and_animations() {
+        let model = load_gltf_from_slice(SKINNED_TRIANGLE.as_bytes()).unwrap();
+        let mesh = &model.meshes[0];
+        assert_eq!(mesh.skin, Some(0));
+        assert_eq!(mesh.joints, vec![[0, 0, 0, 0], [1, 0, 0, 0], [0, 1, 0, 0]]);
+        assert_eq!(mesh.joint_weights[2], [0.5, 0.5, 0.0, 0.0]);
+
+        let skin = &model.skins[0];
+        let names: Vec<&str> = skin.joints.iter().map(|j| j.name.as_str()).collect();
+        assert_eq!(names, ["child", "root"]);
+        assert_eq!(skin.joints[0].parent, Some(1));
+        assert_eq!(skin.joints[1].parent, None);
+        assert_eq!(skin.joints[0].inverse_bind_matrix[3], [0.0, -2.0, 0.0, 1.0]);
+        assert_eq!(skin.joints[1].translation, [0.0, 1.0, 0.0]);
+
+        let wave = &model.animations[0];
+        assert_eq!(wave.name, "wave");
+        assert_eq!(wave.duration(), 1.0);
+        assert_eq!(wave.channels[0].node_name, "child");
+        assert_eq!(wave.channels[0].interpolation, AssetInterpolation::Linear);
+        assert!(matches!(&wave.channels[0].values, AssetChannelValues::Rotation(r) if r.len() == 2));
+        assert_eq!(wave.channels[1].interpolation, AssetInterpolation::CubicSpline);
+        assert!(matches!(&wave.channels[1].values, AssetChannelValues::Translation(t) if t.len() == 6));
+    }
+    #[test]
+    fn parses_node_graph_cameras_and_lights() {
+        let model = load_gltf_from_slice(LIT_SCENE.as_bytes()).unwrap();
+        assert_eq!(model.scene_roots, vec![0]);
+        assert_eq!(model.nodes.len(), 4);
+        assert_eq!(model.nodes[0].children, vec![1, 2]);
+        assert_eq!(model.nodes[1].translation, [0.0, 2.0, 5.0]);
+        assert_eq!(model.nodes[1].camera, Some(0));
+        assert_eq!(model.nodes[2].light, Some(0));
+        assert!(model.nodes[0].meshes.is_empty());
+
+        assert_eq!(
+            model.cameras[0].projection,
+            AssetProjection::Perspective { yfov: 0.8, aspect_ratio: None, znear: 0.1, zfar: Some(100.0) }
+        );
+        let sun = &model.lights[0];
+        assert_eq!((sun.name.as_str(), sun.kind, sun.intensity), ("sun", AssetLightKind::Directional, 3.0));
+        assert_eq!(sun.color, [1.0, 0.9, 0.8]);
+        let spot = &model.lights[1];
+        assert_eq!(spot.range, Some(12.0));
+        assert!(matches!(spot.kind, AssetLightKind::Spot { outer_cone_angle, .. } if outer_cone_angle == 0.5));
+    }
+}
@@ -9,7 +9,7 @@
 //!
 //! | Module           | Responsibility                                              |
 //! |------------------|-------------------------------------------------------------|
-//! | `gltf_loader`    | glTF/GLB import → `AssetModel` (CPU mesh + materials)       |
+//! | `gltf_loader`    | glTF/GLB → `AssetModel` (nodes, meshes, skins, anims, …)    |
 //! | `texture`        | PNG/JPEG → `Texture2d` (GPU upload, legacy)                 |
 //! | `font`           | MSDF font atlas baking (parser, msdf_gen, atlas)            |
 //! | `handle`         | `AssetHandle<T>`, `AssetState<T>` — type-safe handle system |
@@ -53,7 +53,11 @@ pub use ferrous_font::Font;
 /// Character set helpers (Spanish, French, full Unicode, etc.) — text feature only.
 #[cfg(feature = "text")]
 pub use ferrous_font::charset;
-pub use gltf_loader::{load_gltf, AssetMesh, AssetModel, RawMaterial};
+pub use gltf_loader::{
+    load_gltf, AssetAnimation, AssetCamera, AssetChannel, AssetChannelValues, AssetInterpolation,
+    AssetJoint, AssetLight, AssetLightKind, AssetMesh, AssetModel, AssetNode, AssetProjection,
+    AssetSkin, MorphTarget, MorphedVertices, RawMaterial,
+};
 #[cfg(feature = "gpu")]
 pub use texture::Texture2d;
 
@@ -0,0 +1,244 @@
+//! Axis-aligned bounding boxes and view frustums.
+//!
+//! [`Aabb`] and [`Frustum`] are shared by the renderer (frustum culling,
+//! re-exported from `ferrous_renderer::scene::culling`) and by CPU scene
+//! queries such as `World::raycast` and [`AabbTree`](crate::bvh::AabbTree),
+//! which is why they live in this crate.
+
+use glam::{Mat4, Vec3, Vec4};
+use serde::{Deserialize, Serialize};
+
+/// Axis-aligned bounding box stored as centre + half extents.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Aabb {
+    pub center: Vec3,
+    pub half_extents: Vec3,
+}
+
+impl Aabb {
+    /// Creates an AABB from `min`/`max` corners.
+    #[inline]
+    pub fn new(min: Vec3, max: Vec3) -> Self {
+        Self {
+            center: (min + max) * 0.5,
+            half_extents: (max - min) * 0.5,
+        }
+    }
+
+    /// Creates a unit-cube AABB centred at `origin` (fits the built-in cube mesh).
+    #[inline]
+    pub fn unit_cube() -> Self {
+        Self {
+            center: Vec3::ZERO,
+            half_extents: Vec3::splat(1.0),
+        }
+    }
+
+    /// Smallest AABB containing every point, or `None` for an empty iterator.
+    pub fn from_points(points: impl IntoIterator<Item = Vec3>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let (min, max) = points.fold((first, first), |(min, max), p| (min.min(p), max.max(p)));
+        Some(Self::new(min, max))
+    }
+
+    #[inline]
+    pub fn min(&self) -> Vec3 {
+        self.center - self.half_extents
+    }
+
+    #[inline]
+    pub fn max(&self) -> Vec3 {
+        self.center + self.half_extents
+    }
+
+    /// Smallest AABB containing both boxes.
+    #[inline]
+    pub fn union(&self, other: &Aabb) -> Self {
+        Self::new(self.min().min(other.min()), self.max().max(other.max()))
+    }
+
+    /// The box grown by `margin` on every side.
+    #[inline]
+    pub fn expanded(&self, margin: f32) -> Self {
+        Self {
+            center: self.center,
+            half_extents: self.half_extents + Vec3::splat(margin),
+        }
+    }
+
+    /// `true` if `other` lies entirely inside this box.
+    #[inline]
+    pub fn contains(&self, other: &Aabb) -> bool {
+        self.min().cmple(other.min()).all() && self.max().cmpge(other.max()).all()
+    }
+
+    /// `true` if the boxes overlap (touching counts).
+    #[inline]
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        let d = (self.center - other.center).abs();
+        d.cmple(self.half_extents + other.half_extents).all()
+    }
+
+    /// Squared distance from `point` to the box; zero inside.
+    #[inline]
+    pub fn distance_squared_to_point(&self, point: Vec3) -> f32 {
+        ((point - self.center).abs() - self.half_extents).max(Vec3::ZERO).length_squared()
+    }
+
+    /// `true` if the sphere overlaps the box.
+    #[inline]
+    pub fn intersects_sphere(&self, center: Vec3, radius: f32) -> bool {
+        self.distance_squared_to_point(center) <= radius * radius
+    }
+
+    /// Surface area, the cost metric of SAH tree building.
+    #[inline]
+    pub fn surface_area(&self) -> f32 {
+        let e = self.half_extents * 2.0;
+        2.0 * (e.x * e.y + e.y * e.z + e.z * e.x)
+    }
+
+    /// Returns a new AABB transformed by `transform` (world-space position/scale).
+    ///
+    /// Transforms all 8 corners and takes the new min/max — correct even for
+    /// non-uniform scale and arbitrary rotations, though we only use axis-aligned
+    /// transforms for now.
+    pub fn transform(&self, transform: &Mat4) -> Self {
+        // Fast AABB transform: transform centre + half-extents (avoids 8-corner loop).
+        // Source: Graphics Gems (Arvo 1990).
+        let new_centre = transform.transform_point3(self.center);
+
+        // Absolute-value of upper-left 3×3 rotates the half-extents.
+        let m = transform.to_cols_array_2d();
+        let half = self.half_extents;
+        let new_half = Vec3::new(
+            half.x * m[0][0].abs() + half.y * m[1][0].abs() + half.z * m[2][0].abs(),
+            half.x * m[0][1].abs() + half.y * m[1][1].abs() + half.z * m[2][1].abs(),
+            half.x * m[0][2].abs() + half.y * m[1][2].abs() + half.z * m[2][2].abs(),
+        );
+
+        Self {
+            center: new_centre,
+            half_extents: new_half,
+        }
+    }
+
+    /// Slab test: the parametric range `(t_enter, t_exit)` over which the ray
+    /// `origin + t * dir` is inside the box, clipped to `t >= 0`.
+    ///
+    /// `t_enter` is `0.0` when `origin` is inside.  Returns `None` when the
+    /// ray misses or the box is entirely behind the origin.
+    pub fn intersect_ray(&self, origin: Vec3, dir: Vec3) -> Option<(f32, f32)> {
+        let (min, max) = (self.min(), self.max());
+        let mut t_enter = 0.0_f32;
+        let mut t_exit = f32::INFINITY;
+        for axis in 0..3 {
+            if dir[axis].abs() < 1e-12 {
+                // Parallel to the slab: inside it or never.
+                if origin[axis] < min[axis] || origin[axis] > max[axis] {
+                    return None;
+                }
+                continue;
+            }
+            let inv = 1.0 / dir[axis];
+            let (mut t0, mut t1) = ((min[axis] - origin[axis]) * inv, (max[axis] - origin[axis]) * inv);
+            if t0 > t1 {
+                std::mem::swap(&mut t0, &mut t1);
+            }
+            t_enter = t_enter.max(t0);
+            t_exit = t_exit.min(t1);
+            if t_enter > t_exit {
+                return None;
+            }
+        }
+        Some((t_enter, t_exit))
+    }
+}
+
+// ── Frustum ───────────────────────────────────────────────────────────────────
+
+/// Six clip planes extracted from a `view_proj` matrix.
+///
+/// Each plane is stored as `Vec4(nx, ny, nz, d)` where the plane equation is
+/// `dot(normal, point) + d >= 0` for visible points.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Frustum {
+    planes: [Vec4; 6],
+}
+
+impl Frustum {
+    /// Extracts the six frustum planes from `view_proj` (column-major).
+    ///
+    /// Uses the Gribb-Hartmann row-combination method.  Works for both
+    /// left-handed (wgpu/Vulkan) and right-handed conventions because the
+    /// signs cancel correctly.
+    pub fn from_view_proj(vp: &Mat4) -> Self {
+        let m = vp.to_cols_array_2d(); // m[col][row]
+
+        // Row vectors of the matrix (convenient for plane extraction).
+        let row = |r: usize| Vec4::new(m[0][r], m[1][r], m[2][r], m[3][r]);
+
+        let r0 = row(0);
+        let r1 = row(1);
+        let r2 = row(2);
+        let r3 = row(3);
+
+        // Planes: left, right, bottom, top, near, far.
+        // Negate far for wgpu's [0,1] depth range (reversed-Z style).
+        let mut planes = [
+            r3 + r0, // left
+            r3 - r0, // right
+            r3 + r1, // bottom
+            r3 - r1, // top
+            r2,      // near  (wgpu: depth in [0,1])
+            r3 - r2, // far
+        ];
+
+        // Normalise so that the signed distance formula is meaningful.
+        for p in &mut planes {
+            let len = Vec3::new(p.x, p.y, p.z).length();
+            if len > 1e-6 {
+                *p /= len;
+            }
+        }
+
+        Self { planes }
+    }
+
+    /// Returns `true` if the AABB **might** be visible (conservative — no false negatives).
+    ///
+    /// Uses the positive-vertex / negative-vertex test: if the positive vertex
+    /// (closest to the plane's outward normal) is behind the plane, the whole
+    /// AABB is outside the frustum.
+    #[inline]
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        for plane in &self.planes {
+            // Branchless AABB / plane test:
+            // dot(center, normal) + dot(half_extents, abs(normal)) + d
+            let center_dist = plane.x * aabb.center.x
+                + plane.y * aabb.center.y
+                + plane.z * aabb.center.z
+                + plane.w;
+            let extent_dist = plane.x.abs() * aabb.half_extents.x
+                + plane.y.abs() * aabb.half_extents.y
+                + plane.z.abs() * aabb.half_extents.z;
+
+            // If the maximum inscribed sphere distance goes below 0, it's outside.
+            // Wait, more precisely, if the closest point is outside, the whole box is outside.
+            // closest_dist = center_dist + extent_dist. If that is < 0, it's outside.
+            if center_dist + extent_dist < 0.0 {
+                return false; // completely outside this plane
+            }
+        }
+        true
+    }
+
+    /// Returns the six frustum planes as `[Vec4; 6]`.
+    ///
+    /// Each plane is `(nx, ny, nz, d)` in the form `dot(n, p) + d >= 0`.
+    #[inline]
+    pub fn planes(&self) -> &[Vec4; 6] {
+        &self.planes
+    }
+}
@@ -0,0 +1,664 @@
+//! Dynamic bounding volume hierarchy.
+//!
+//! [`AabbTree`] is a binary tree of [`Aabb`]s whose leaves carry a user
+//! value (an ECS `Entity`, a mesh index, …).  It supports the usual dynamic
+//! operations — [`insert`](AabbTree::insert), [`remove`](AabbTree::remove)
+//! and [`update`](AabbTree::update) — plus a top-down SAH
+//! [`rebuild`](AabbTree::rebuild) for when many objects moved at once.
+//!
+//! Leaves store a *fat* box (the tight box grown by
+//! [`margin`](AabbTree::margin)), so small motions only replace the tight
+//! box and leave the tree untouched.  Queries test the fat boxes while
+//! descending and the tight box at the leaves, so results are exact with
+//! respect to the boxes given to the tree.
+//!
+//! ```rust,ignore
+//! let mut tree = AabbTree::new();
+//! let id = tree.insert(Aabb::new(Vec3::ZERO, Vec3::ONE), entity);
+//! tree.update(id, moved_aabb);
+//! tree.query_frustum(&frustum, |_, &e| visible.push(e));
+//! let closest = tree.nearest(player_pos, 3);
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use glam::Vec3;
+
+use crate::bounds::{Aabb, Frustum};
+
+/// Stable identifier of a leaf, returned by [`AabbTree::insert`].  Survives
+/// [`AabbTree::update`] and [`AabbTree::rebuild`]; reused after removal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ProxyId(u32);
+
+const NULL: u32 = u32::MAX;
+
+/// Number of centroid bins per axis evaluated by the SAH rebuild.
+const SAH_BINS: usize = 12;
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    /// Fat box for leaves, union of the children for internal nodes.
+    fat: Aabb,
+    parent: u32,
+    kind: NodeKind<T>,
+}
+
+#[derive(Debug, Clone)]
+enum NodeKind<T> {
+    Leaf { tight: Aabb, item: T },
+    Internal { children: [u32; 2], height: u32 },
+    Free,
+}
+
+/// Dynamic AABB tree.  See the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct AabbTree<T> {
+    nodes: Vec<Node<T>>,
+    free: Vec<u32>,
+    root: u32,
+    len: usize,
+    margin: f32,
+}
+
+impl<T> Default for AabbTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> AabbTree<T> {
+    /// Empty tree with a fat margin of `0.1`.
+    pub fn new() -> Self {
+        Self::with_margin(0.1)
+    }
+
+    /// Empty tree whose leaves are grown by `margin` on every side.
+    pub fn with_margin(margin: f32) -> Self {
+        Self { nodes: Vec::new(), free: Vec::new(), root: NULL, len: 0, margin: margin.max(0.0) }
+    }
+
+    pub fn margin(&self) -> f32 {
+        self.margin
+    }
+
+    /// Number of leaves.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Height of the tree: `0` when empty, `1` for a single leaf.
+    pub fn height(&self) -> u32 {
+        if self.root == NULL { 0 } else { self.node_height(self.root) + 1 }
+    }
+
+    /// Remove every leaf.  Previously returned ids become invalid.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.free.clear();
+        self.root = NULL;
+        self.len = 0;
+    }
+
+    /// The value stored in leaf `id`.
+    pub fn get(&self, id: ProxyId) -> Option<&T> {
+        match &self.nodes.get(id.0 as usize)?.kind {
+            NodeKind::Leaf { item, .. } => Some(item),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, id: ProxyId) -> Option<&mut T> {
+        match &mut self.nodes.get_mut(id.0 as usize)?.kind {
+            NodeKind::Leaf { item, .. } => Some(item),
+            _ => None,
+        }
+    }
+
+    /// The (tight) box of leaf `id`.
+    pub fn bounds(&self, id: ProxyId) -> Option<Aabb> {
+        match &self.nodes.get(id.0 as usize)?.kind {
+            NodeKind::Leaf { tight, .. } => Some(*tight),
+            _ => None,
+        }
+    }
+
+    /// Iterate over `(id, tight box, value)` of every leaf.
+    pub fn iter(&self) -> impl Iterator<Item = (ProxyId, Aabb, &T)> {
+        self.nodes.iter().enumerate().filter_map(|(i, n)| match &n.kind {
+            NodeKind::Leaf { tight, item } => Some((ProxyId(i as u32), *tight, item)),
+            _ => None,
+        })
+    }
+
+    // ── Mutation ────────────────────────────────────────────────────────────
+
+    /// Insert `item` with bounds `aabb`.
+    pub fn insert(&mut self, aabb: Aabb, item: T) -> ProxyId {
+        let leaf = self.allocate(Node {
+            fat: aabb.expanded(self.margin),
+            parent: NULL,
+            kind: NodeKind::Leaf { tight: aabb, item },
+        });
+        self.insert_leaf(leaf);
+        self.len += 1;
+        ProxyId(leaf)
+    }
+
+    /// Remove leaf `id` and return its value.
+    pub fn remove(&mut self, id: ProxyId) -> Option<T> {
+        if !matches!(self.nodes.get(id.0 as usize)?.kind, NodeKind::Leaf { .. }) {
+            return None;
+        }
+        self.remove_leaf(id.0);
+        self.len -= 1;
+        self.free.push(id.0);
+        match std::mem::replace(&mut self.nodes[id.0 as usize].kind, NodeKind::Free) {
+            NodeKind::Leaf { item, .. } => Some(item),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Move leaf `id` to `aabb`.  Returns `true` if the tree was restructured,
+    /// `false` if the new box still fits the leaf's fat box (or `id` is not a
+    /// leaf).
+    pub fn update(&mut self, id: ProxyId, aabb: Aabb) -> bool {
+        let Some(node) = self.nodes.get_mut(id.0 as usize) else {
+            return false;
+        };
+        let NodeKind::Leaf { tight, .. } = &mut node.kind else {
+            return false;
+        };
+        *tight = aabb;
+        if node.fat.contains(&aabb) {
+            return false;
+        }
+        node.fat = aabb.expanded(self.margin);
+        self.remove_leaf(id.0);
+        self.insert_leaf(id.0);
+        true
+    }
+
+    /// Rebuild every internal node top-down with the surface area heuristic.
+    /// Leaf ids are preserved.  Worth calling after bulk inserts or when many
+    /// leaves moved far, which degrades the incrementally built tree.
+    pub fn rebuild(&mut self) {
+        let mut leaves = Vec::with_capacity(self.len);
+        for (i, node) in self.nodes.iter_mut().enumerate() {
+            match node.kind {
+                NodeKind::Leaf { .. } => leaves.push(i as u32),
+                NodeKind::Internal { .. } => {
+                    node.kind = NodeKind::Free;
+                    self.free.push(i as u32);
+                }
+                NodeKind::Free => {}
+            }
+        }
+        self.root = if leaves.is_empty() { NULL } else { self.build_sah(&mut leaves) };
+        if self.root != NULL {
+            self.nodes[self.root as usize].parent = NULL;
+        }
+    }
+
+    // ── Queries ─────────────────────────────────────────────────────────────
+
+    /// Visit every leaf whose box overlaps `aabb`.
+    pub fn query_aabb(&self, aabb: &Aabb, visit: impl FnMut(ProxyId, &T)) {
+        self.query(|b| b.intersects(aabb), visit);
+    }
+
+    /// Visit every leaf whose box overlaps the sphere.
+    pub fn query_sphere(&self, center: Vec3, radius: f32, visit: impl FnMut(ProxyId, &T)) {
+        self.query(|b| b.intersects_sphere(center, radius), visit);
+    }
+
+    /// Visit every leaf whose box is (conservatively) inside `frustum`.
+    pub fn query_frustum(&self, frustum: &Frustum, visit: impl FnMut(ProxyId, &T)) {
+        self.query(|b| frustum.intersects_aabb(b), visit);
+    }
+
+    /// Visit every leaf for which `overlaps` holds on its box.  `overlaps`
+    /// must be conservative: if it rejects a box it must also reject every
+    /// box inside it.
+    pub fn query(&self, mut overlaps: impl FnMut(&Aabb) -> bool, mut visit: impl FnMut(ProxyId, &T)) {
+        if self.root == NULL {
+            return;
+        }
+        let mut stack = vec![self.root];
+        while let Some(i) = stack.pop() {
+            let node = &self.nodes[i as usize];
+            if !overlaps(&node.fat) {
+                continue;
+            }
+            match &node.kind {
+                NodeKind::Leaf { tight, item } => {
+                    if overlaps(tight) {
+                        visit(ProxyId(i), item);
+                    }
+                }
+                NodeKind::Internal { children, .. } => stack.extend(children),
+                NodeKind::Free => {}
+            }
+        }
+    }
+
+    /// Visit the leaves whose box is hit by the ray `origin + t * dir` with
+    /// `t <= max_dist`, nearest subtree first.  `visit` receives the entry
+    /// distance into the leaf box and returns the new maximum distance, so a
+    /// caller searching for the closest exact hit returns the distance of its
+    /// best hit so far (or `max_dist` to keep going unchanged).
+    ///
+    /// `dir` need not be normalised; distances are in units of `dir`.
+    pub fn raycast(
+        &self,
+        origin: Vec3,
+        dir: Vec3,
+        mut max_dist: f32,
+        mut visit: impl FnMut(ProxyId, &T, f32) -> f32,
+    ) {
+        if self.root == NULL {
+            return;
+        }
+        let mut stack = vec![(self.root, 0.0_f32)];
+        while let Some((i, t_enter)) = stack.pop() {
+            if t_enter > max_dist {
+                continue;
+            }
+            match &self.nodes[i as usize].kind {
+                NodeKind::Leaf { tight, item } => {
+                    if let Some((t, _)) = tight.intersect_ray(origin, dir) {
+                        if t <= max_dist {
+                            max_dist = max_dist.min(visit(ProxyId(i), item, t));
+                        }
+                    }
+                }
+                NodeKind::Internal { children, .. } => {
+                    let mut hits = children.map(|c| {
+                        (c, self.nodes[c as usize].fat.intersect_ray(origin, dir).map(|(t, _)| t))
+                    });
+                    // Push the farther child first so the nearer one pops next.
+                    if hits[0].1.unwrap_or(f32::INFINITY) < hits[1].1.unwrap_or(f32::INFINITY) {
+                        hits.swap(0, 1);
+                    }
+                    for (c, t) in hits {
+                        if let Some(t) = t.filter(|&t| t <= max_dist) {
+                            stack.push((c, t));
+                        }
+                    }
+                }
+                NodeKind::Free => {}
+            }
+        }
+    }
+
+    /// The `k` leaves closest to `point` (distance to their box, zero when
+    /// inside), nearest first.
+    pub fn nearest(&self, point: Vec3, k: usize) -> Vec<(ProxyId, f32)> {
+        let mut out = Vec::with_capacity(k);
+        if self.root == NULL || k == 0 {
+            return out;
+        }
+        // Best-first search: nodes and leaves share one min-heap ordered by
+        // their lower-bound distance, so leaves pop in exact order.
+        let mut heap = BinaryHeap::new();
+        heap.push(HeapEntry { dist_sq: self.nodes[self.root as usize].fat.distance_squared_to_point(point), node: self.root, leaf: false });
+        while let Some(HeapEntry { dist_sq, node, leaf }) = heap.pop() {
+            if leaf {
+                out.push((ProxyId(node), dist_sq.sqrt()));
+                if out.len() == k {
+                    break;
+                }
+                continue;
+            }
+            match &self.nodes[node as usize].kind {
+                NodeKind::Leaf { tight, .. } => {
+                    heap.push(HeapEntry { dist_sq: tight.distance_squared_to_point(point), node, leaf: true });
+                }
+                NodeKind::Internal { children, .. } => {
+                    for &c in children {
+                        let dist_sq = self.nodes[c as usize].fat.distance_squared_to_point(point);
+                        heap.push(HeapEntry { dist_sq, node: c, leaf: false });
+                    }
+                }
+                NodeKind::Free => {}
+            }
+        }
+        out
+    }
+
+    // ── Internals ───────────────────────────────────────────────────────────
+
+    fn allocate(&mut self, node: Node<T>) -> u32 {
+        match self.free.pop() {
+            Some(i) => {
+                self.nodes[i as usize] = node;
+                i
+            }
+            None => {
+                self.nodes.push(node);
+                (self.nodes.len() - 1) as u32
+            }
+        }
+    }
+
+    fn node_height(&self, i: u32) -> u32 {
+        match self.nodes[i as usize].kind {
+            NodeKind::Internal { height, .. } => height,
+            _ => 0,
+        }
+    }
+
+    fn children(&self, i: u32) -> Option<[u32; 2]> {
+        match self.nodes[i as usize].kind {
+            NodeKind::Internal { children, .. } => Some(children),
+            _ => None,
+        }
+    }
+
+    /// Link the detached `leaf` into the tree next to the sibling that
+    /// minimises the added surface area.
+    fn insert_leaf(&mut self, leaf: u32) {
+        if self.root == NULL {
+            self.root = leaf;
+            self.nodes[leaf as usize].parent = NULL;
+            return;
+        }
+
+        let leaf_box = self.nodes[leaf as usize].fat;
+        let mut sibling = self.root;
+        while let Some([c0, c1]) = self.children(sibling) {
+            let node_box = self.nodes[sibling as usize].fat;
+            let area = node_box.surface_area();
+            let combined = node_box.union(&leaf_box).surface_area();
+            // Cost of pairing with this node, and the cost pushed down to
+            // the children if we descend instead.
+            let cost_here = 2.0 * combined;
+            let inherited = 2.0 * (combined - area);
+            let child_cost = |c: u32| {
+                let b = self.nodes[c as usize].fat;
+                let union = b.union(&leaf_box).surface_area();
+                if self.children(c).is_some() { union - b.surface_area() + inherited } else { union + inherited }
+            };
+            let (cost0, cost1) = (child_cost(c0), child_cost(c1));
+            if cost_here < cost0 && cost_here < cost1 {
+                break;
+            }
+            sibling = if cost0 < cost1 { c0 } else { c1 };
+        }
+
+        let old_parent = self.nodes[sibling as usize].parent;
+        let sibling_box = self.nodes[sibling as usize].fat;
+        let parent = self.allocate(Node {
+            fat: sibling_box.union(&leaf_box),
+            parent: old_parent,
+            kind: NodeKind::Internal { children: [sibling, leaf], height: 0 },
+        });
+        self.nodes[sibling as usize].parent = parent;
+        self.nodes[leaf as usize].parent = parent;
+        if old_parent == NULL {
+            self.root = parent;
+        } else {
+            self.replace_child(old_parent, sibling, parent);
+        }
+        self.refit(parent);
+    }
+
+    /// Detach `leaf` from the tree (the node itself is kept).
+    fn remove_leaf(&mut self, leaf: u32) {
+        if leaf == self.root {
+            self.root = NULL;
+            return;
+        }
+        let parent = self.nodes[leaf as usize].parent;
+        let grandparent = self.nodes[parent as usize].parent;
+        let [c0, c1] = self.children(parent).expect("leaf parent is internal");
+        let sibling = if c0 == leaf { c1 } else { c0 };
+
+        self.nodes[parent as usize].kind = NodeKind::Free;
+        self.free.push(parent);
+        self.nodes[sibling as usize].parent = grandparent;
+        if grandparent == NULL {
+            self.root = sibling;
+        } else {
+            self.replace_child(grandparent, parent, sibling);
+            self.refit(grandparent);
+        }
+    }
+
+    fn replace_child(&mut self, parent: u32, old: u32, new: u32) {
+        if let NodeKind::Internal { children, .. } = &mut self.nodes[parent as usize].kind {
+            for c in children.iter_mut().filter(|c| **c == old) {
+                *c = new;
+            }
+        }
+    }
+
+    /// Recompute boxes and heights from `i` up to the root.
+    fn refit(&mut self, mut i: u32) {
+        while i != NULL {
+            if let Some([c0, c1]) = self.children(i) {
+                let fat = self.nodes[c0 as usize].fat.union(&self.nodes[c1 as usize].fat);
+                let h = 1 + self.node_height(c0).max(self.node_height(c1));
+                let node = &mut self.nodes[i as usize];
+                node.fat = fat;
+                if let NodeKind::Internal { height, .. } = &mut node.kind {
+                    *height = h;
+                }
+            }
+            i = self.nodes[i as usize].parent;
+        }
+    }
+
+    /// Build a subtree over `leaves` with binned SAH and return its root.
+    fn build_sah(&mut self, leaves: &mut [u32]) -> u32 {
+        if let [leaf] = leaves {
+            return *leaf;
+        }
+
+        let centroid = |tree: &Self, i: u32| tree.nodes[i as usize].fat.center;
+        let mut lo = Vec3::splat(f32::INFINITY);
+        let mut hi = Vec3::splat(f32::NEG_INFINITY);
+        for &i in leaves.iter() {
+            lo = lo.min(centroid(self, i));
+            hi = hi.max(centroid(self, i));
+        }
+        let extent = hi - lo;
+
+        // Best (cost, axis, bin) over all axes.
+        let mut best: Option<(f32, usize, usize)> = None;
+        for axis in 0..3 {
+            if extent[axis] <= 1e-6 {
+                continue;
+            }
+            let bin_of = |c: Vec3| (((c[axis] - lo[axis]) / extent[axis] * SAH_BINS as f32) as usize).min(SAH_BINS - 1);
+            let mut bins: [(Option<Aabb>, usize); SAH_BINS] = [(None, 0); SAH_BINS];
+            for &i in leaves.iter() {
+                let b = self.nodes[i as usize].fat;
+                let bin = &mut bins[bin_of(b.center)];
+                bin.0 = Some(bin.0.map_or(b, |a| a.union(&b)));
+                bin.1 += 1;
+            }
+            // Sweep from the right to get suffix areas, then from the left.
+            let mut right = [(0.0_f32, 0usize); SAH_BINS];
+            let mut acc: Option<Aabb> = None;
+            let mut count = 0;
+            for split in (1..SAH_BINS).rev() {
+                if let Some(b) = bins[split].0 {
+                    acc = Some(acc.map_or(b, |a| a.union(&b)));
+                }
+                count += bins[split].1;
+                right[split] = (acc.map_or(0.0, |a| a.surface_area()), count);
+            }
+            let mut acc: Option<Aabb> = None;
+            let mut count = 0;
+            for split in 1..SAH_BINS {
+                if let Some(b) = bins[split - 1].0 {
+                    acc = Some(acc.map_or(b, |a| a.union(&b)));
+                }
+                count += bins[split - 1].1;
+                let (right_area, right_count) = right[split];
+                if count == 0 || right_count == 0 {
+                    continue;
+                }
+                let cost = acc.map_or(0.0, |a| a.surface_area()) * count as f32 + right_area * right_count as f32;
+                if best.is_none_or(|(c, _, _)| cost < c) {
+                    best = Some((cost, axis, split));
+                }
+            }
+        }
+
+        let mid = match best {
+            Some((_, axis, split)) => {
+                let threshold = lo[axis] + extent[axis] * split as f32 / SAH_BINS as f32;
+                let mut mid = 0;
+                for j in 0..leaves.len() {
+                    if centroid(self, leaves[j])[axis] < threshold {
+                        leaves.swap(j, mid);
+                        mid += 1;
+                    }
+                }
+                mid
+            }
+            None => 0,
+        };
+        // Coincident centroids (or a float-rounding empty side): split evenly.
+        let mid = if mid == 0 || mid == leaves.len() { leaves.len() / 2 } else { mid };
+
+        let (left, right) = leaves.split_at_mut(mid);
+        let (l, r) = (self.build_sah(left), self.build_sah(right));
+        let fat = self.nodes[l as usize].fat.union(&self.nodes[r as usize].fat);
+        let height = 1 + self.node_height(l).max(self.node_height(r));
+        let node = self.allocate(Node { fat, parent: NULL, kind: NodeKind::Internal { children: [l, r], height } });
+        self.nodes[l as usize].parent = node;
+        self.nodes[r as usize].parent = node;
+        node
+    }
+}
+
+/// Min-heap entry for [`AabbTree::nearest`].
+struct HeapEntry {
+    dist_sq: f32,
+    node: u32,
+    leaf: bool,
+}
+
+impl PartialEq for HeapEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for HeapEntry {}
+
+impl PartialOrd for HeapEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HeapEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed for a min-heap; leaves win ties so they are reported as
+        // soon as nothing can be closer.
+        other.dist_sq.total_cmp(&self.dist_sq).then(self.leaf.cmp(&other.leaf))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Mat4;
+
+    fn unit_box(center: Vec3) -> Aabb {
+        Aabb { center, half_extents: Vec3::splat(0.5) }
+    }
+
+    /// Order-independent comparison of query results.
+    fn sorted(mut ids: Vec<u32>) -> Vec<u32> {
+        ids.sort_unstable();
+        ids
+    }
+
+    #[test]
+    fn queries_match_brute_force_after_updates_and_rebuild() {
+        let mut tree = AabbTree::new();
+        let mut boxes = Vec::new();
+        let mut ids = Vec::new();
+        for i in 0..64u32 {
+            let c = Vec3::new((i % 8) as f32 * 3.0, 0.0, (i / 8) as f32 * 3.0);
+            boxes.push(unit_box(c));
+            ids.push(tree.insert(unit_box(c), i));
+        }
+        assert_eq!(tree.len(), 64);
+
+        // Small move stays inside the fat box; a big one restructures.
+        assert!(!tree.update(ids[0], unit_box(Vec3::new(0.05, 0.0, 0.0))));
+        boxes[0] = unit_box(Vec3::new(0.05, 0.0, 0.0));
+        assert!(tree.update(ids[1], unit_box(Vec3::new(50.0, 0.0, 50.0))));
+        boxes[1] = unit_box(Vec3::new(50.0, 0.0, 50.0));
+        assert_eq!(tree.remove(ids[2]), Some(2));
+        assert_eq!(tree.get(ids[2]), None);
+
+        let check = |tree: &AabbTree<u32>, boxes: &[Aabb]| {
+            let alive = |i: &u32| *i != 2;
+            let query = Aabb::new(Vec3::new(-1.0, -1.0, -1.0), Vec3::new(7.0, 1.0, 4.0));
+            let mut found = Vec::new();
+            tree.query_aabb(&query, |_, &i| found.push(i));
+            let expected: Vec<u32> = (0..64).filter(alive).filter(|&i| boxes[i as usize].intersects(&query)).collect();
+            assert_eq!(sorted(found), expected);
+
+            let mut found = Vec::new();
+            tree.query_sphere(Vec3::new(9.0, 0.0, 9.0), 2.0, |_, &i| found.push(i));
+            let expected: Vec<u32> =
+                (0..64).filter(alive).filter(|&i| boxes[i as usize].intersects_sphere(Vec3::new(9.0, 0.0, 9.0), 2.0)).collect();
+            assert_eq!(sorted(found), expected);
+
+            let near = tree.nearest(Vec3::new(49.0, 0.0, 50.0), 2);
+            assert_eq!(tree.get(near[0].0), Some(&1));
+            assert!((near[0].1 - 0.5).abs() < 1e-5);
+            assert!(near[1].1 > near[0].1);
+        };
+        check(&tree, &boxes);
+        tree.rebuild();
+        assert!(tree.height() <= 9, "SAH tree over 63 leaves is shallow, got {}", tree.height());
+        assert_eq!(tree.bounds(ids[1]), Some(boxes[1]));
+        check(&tree, &boxes);
+    }
+
+    #[test]
+    fn ray_visits_front_to_back_and_frustum_culls() {
+        let mut tree = AabbTree::new();
+        for i in 0..10 {
+            tree.insert(unit_box(Vec3::new(0.0, 0.0, -(i as f32) * 2.0 - 2.0)), i);
+        }
+        tree.insert(unit_box(Vec3::new(5.0, 0.0, -4.0)), 99);
+
+        let mut order = Vec::new();
+        tree.raycast(Vec3::ZERO, Vec3::NEG_Z, 100.0, |_, &i, t| {
+            order.push((i, t));
+            100.0
+        });
+        assert_eq!(order.iter().map(|(i, _)| *i).collect::<Vec<_>>(), (0..10).collect::<Vec<_>>());
+        assert!((order[0].1 - 1.5).abs() < 1e-5);
+
+        // Clipping the distance at the first hit stops the traversal early.
+        let mut visited = 0;
+        tree.raycast(Vec3::ZERO, Vec3::NEG_Z, 100.0, |_, _, t| {
+            visited += 1;
+            t
+        });
+        assert_eq!(visited, 1);
+
+        let view = Mat4::look_at_rh(Vec3::ZERO, Vec3::NEG_Z, Vec3::Y);
+        let proj = Mat4::perspective_rh(30f32.to_radians(), 1.0, 0.1, 7.0);
+        let frustum = Frustum::from_view_proj(&(proj * view));
+        let mut visible = Vec::new();
+        tree.query_frustum(&frustum, |_, &i| visible.push(i));
+        assert_eq!(sorted(visible), vec![0, 1, 2]);
+    }
+}
@@ -10,9 +10,11 @@
 //! |--------|---------|
 //! [`transform`] | `Transform` — position / rotation / scale + `matrix()` |
 //! [`color`]     | `Color` — RGBA f32 with a large palette of constants |
+//! [`bounds`]    | `Aabb`, `Frustum` — bounding volumes shared with the renderer |
+//! [`bvh`]       | `AabbTree` — dynamic bounding volume hierarchy for spatial queries |
 //! [`time`]      | `Time` / `TimeClock` — frame delta, elapsed, FPS |
 //! [`input`]     | `InputState` — keyboard, mouse, scroll; `just_pressed` / `just_released` |
-//! [`scene`]     | `World`, `Element`, ECS systems (`TimeSystem`, `VelocitySystem`, `AnimationSystem`, `BehaviorSystem`, `TransformSystem`), hierarchy components (`Parent`, `Children`, `GlobalTransform`), `AnimationClip/Player`, `BehaviorComponent`, `Camera` |
+//! [`scene`]     | `World`, `Element`, ECS systems (`TimeSystem`, `VelocitySystem`, `AnimationSystem`, `BehaviorSystem`, `TransformSystem`), hierarchy (`ChildOf` relation, `Parent`, `Children`, `GlobalTransform`), `AnimationClip/Player`, `BehaviorComponent`, `Camera` |
 //! [`context`]   | `EngineContext` — wgpu device + queue |
 //! [`metrics`]   | CPU / RAM usage helpers |
 //!
@@ -41,6 +43,12 @@ pub mod transform;
 /// RGBA colour type with a large palette of constants.
 pub mod color;
 
+/// Axis-aligned bounding boxes (`Aabb`) and view frustums (`Frustum`).
+pub mod bounds;
+
+/// Dynamic AABB tree for frustum, overlap, ray and nearest-neighbour queries.
+pub mod bvh;
+
 /// Frame timing: delta, elapsed, FPS.
 pub mod time;
 
@@ -58,11 +66,16 @@ pub mod context;
 /// Scene graph: `World`, `Element`, `Handle`, `ElementKind`, `Camera`, `Controller`.
 ///
 /// **ECS systems** (register via `StagedScheduler`):
-/// - `TimeSystem` (PreUpdate) — ticks `TimeClock` resource each frame.
-/// - `VelocitySystem` (Update) — integrates `Velocity` into `Transform::position`.
-/// - `AnimationSystem` (Update) — advances `AnimationPlayer` and applies keyframe positions.
+/// - `TimeSystem` (PreUpdate) — ticks `TimeClock` resource each frame and feeds `FixedTime`.
+/// - `TransformSnapshotSystem` (FixedUpdate) — stores `PreviousTransform` before each step.
+/// - `VelocitySystem` (FixedUpdate) — integrates `Velocity` into `Transform::position`.
+/// - `FixedTimeSystem` (Update) — exposes the fixed step and interpolation alpha on `Time`.
+/// - `AnimationControllerSystem` (Update) — steps `AnimationController` state machines.
+/// - `AnimationSystem` (Update) — advances `AnimationPlayer` layers and crossfades, applies the
+///   blended TRS / bone / weight / curve tracks and sends `AnimationEvent`s.
 /// - `BehaviorSystem` (Update) — calls per-entity `Behavior::update` hooks.
 /// - `TransformSystem` (PostUpdate) — propagates `GlobalTransform` through the parent chain.
+/// - `SpatialIndexSystem` (PostUpdate) — keeps the `SpatialIndex` AABB tree in sync with `GlobalTransform`.
 ///
 /// **New components**: `Velocity`, `Parent`, `Children`, `GlobalTransform`,
 /// `AnimationClip`, `AnimationPlayer`, `BehaviorComponent`.
@@ -88,6 +101,8 @@ pub mod render_stats;
 pub use glam;
 
 // Core types
+pub use bounds::{Aabb, Frustum};
+pub use bvh::{AabbTree, ProxyId};
 pub use color::Color;
 pub use time::{Time, TimeClock};
 pub use transform::Transform;
@@ -100,10 +115,14 @@ pub use input::{InputState, KeyCode, MouseButton};
 pub use scene::{AlphaMode, MaterialDescriptor, MaterialHandle, RenderQuality, RenderStyle};
 
 #[cfg(feature = "ecs")]
+#[allow(deprecated)]
 pub use scene::{
-    AnimationClip, AnimationPlayer, AnimationSystem, Behavior, BehaviorComponent, BehaviorSystem,
-    Camera3D, Camera3DBuilder, Children, DirectionalLight, GlobalTransform, Keyframe, OrbitCamera,
-    OrbitCameraSystem, Parent, Stage, TimeSystem, TransformSystem, Velocity, VelocitySystem,
+    AnimationClip, AnimationController, AnimationControllerSystem, AnimationEvent, AnimationLayer,
+    AnimationPlayer, AnimationState, AnimationStateMachine, AnimationSystem, Behavior,
+    BehaviorComponent, BehaviorSystem, BlendMode, BoneMask, Camera3D, Camera3DBuilder, ChildOf, Children,
+    DirectionalLight, FollowPath, FollowPathSystem, GlobalTransform, Interpolation, Keyframe, OrbitCamera,
+    OrbitCameraSystem, PathEnd, Spline, SplineKind, LocalBounds, SpatialIndex, SpatialIndexSystem, SpatialRemovals,
+    Parent, Stage, TimeSystem, Track, TransformSystem, Velocity, VelocitySystem, FixedTimeSystem, PreviousTransform, TransformSnapshotSystem,
 };
 
 #[cfg(feature = "ecs")]
@@ -115,6 +134,9 @@ pub use scene::{Element, ElementKind, Handle, PointLightComponent, World};
 #[cfg(feature = "ecs")]
 pub use scene::{Material, MaterialBuilder};
 
+#[cfg(feature = "ecs")]
+pub use scene::register_core_components;
+
 // Context (re-export only when GPU support is active)
 #[cfg(feature = "gpu")]
 pub use ferrous_gpu::EngineContext;
@@ -108,3 +108,7 @@ impl Controller {
         out
     }
 }
+
+/// As a component, a `Controller` next to a `CharacterController`
+/// (`ferrous_physics`) drives the character from the keys held.
+impl ferrous_ecs::prelude::Component for Controller {}
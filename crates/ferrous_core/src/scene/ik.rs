@@ -0,0 +1,533 @@
+//! Inverse kinematics constraints for [`Skeleton`]s.
+//!
+//! Constraints pose bones procedurally on top of the animated pose: feet
+//! planted on uneven ground, hands reaching for props, heads tracking a point
+//! of interest.  Attach an [`IkConstraints`] component next to the skeleton;
+//! `SkinningSystem` solves it after `AnimationSystem` has written the sampled
+//! pose and bakes the result into `joint_matrices` only (see
+//! [`IkConstraints::apply`]), so the bones keep their animated pose.
+//!
+//! Bones are referenced by name and every target is expressed in skeleton
+//! space (the space of the root bones, i.e. the skinned entity's local
+//! space).  Solvers only rotate bones, so bone lengths are preserved.
+//!
+//! - [`TwoBoneIk`] — analytic solve of a root → mid → tip chain (thigh, knee,
+//!   foot) with a pole target choosing the bend direction.
+//! - [`FabrikChain`] — iterative solve of an arbitrary chain (spine, tail,
+//!   tentacle) with an optional per-joint bend limit.
+//! - [`LookAtIk`] — turns one bone towards a point, clamped to a cone.
+//!
+//! ```rust,ignore
+//! world.insert(character, IkConstraints(vec![
+//!     TwoBoneIk::new("thigh_l", "shin_l", "foot_l", foot_target).with_pole(knee_hint).into(),
+//!     LookAtIk::new("head", Vec3::Z, gaze_target).with_max_angle(1.0).into(),
+//! ]));
+//! ```
+
+use glam::{Mat4, Quat, Vec3};
+use serde::{Deserialize, Serialize};
+
+use super::skinning::Skeleton;
+
+#[cfg(feature = "ecs")]
+use ferrous_ecs::prelude::Component;
+
+/// Lengths below this are treated as degenerate.
+const EPSILON: f32 = 1e-6;
+
+// ────────────────────────────────────────────────────────────────────────────
+// Constraints
+
+/// Analytic two-bone IK: rotates `root` and `mid` so that `tip` reaches
+/// `target`, bending towards `pole`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TwoBoneIk {
+    pub root: String,
+    pub mid: String,
+    pub tip: String,
+    pub target: Vec3,
+    /// Point the middle joint bends towards (in front of the knee, behind the
+    /// elbow).  `None` keeps the animated bend plane.
+    pub pole: Option<Vec3>,
+    /// Skeleton-space rotation forced onto `tip` (e.g. foot aligned to the
+    /// ground normal).  `None` leaves it as animated.
+    pub tip_rotation: Option<Quat>,
+    /// Blend between the animated (0) and solved (1) pose.
+    pub weight: f32,
+}
+
+impl TwoBoneIk {
+    pub fn new(root: impl Into<String>, mid: impl Into<String>, tip: impl Into<String>, target: Vec3) -> Self {
+        TwoBoneIk {
+            root: root.into(),
+            mid: mid.into(),
+            tip: tip.into(),
+            target,
+            pole: None,
+            tip_rotation: None,
+            weight: 1.0,
+        }
+    }
+
+    pub fn with_pole(mut self, pole: Vec3) -> Self {
+        self.pole = Some(pole);
+        self
+    }
+
+    pub fn with_tip_rotation(mut self, rotation: Quat) -> Self {
+        self.tip_rotation = Some(rotation);
+        self
+    }
+
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Solve on `skeleton`.  Does nothing if a bone is missing.
+    pub fn solve(&self, skeleton: &mut Skeleton) {
+        let (Some(root), Some(mid), Some(tip)) = (
+            skeleton.bone_index(&self.root),
+            skeleton.bone_index(&self.mid),
+            skeleton.bone_index(&self.tip),
+        ) else {
+            return;
+        };
+        let mut pose = Pose::new(skeleton);
+        let a = pose.position(root);
+        let b = pose.position(mid);
+        let c = pose.position(tip);
+        let (ab, bc) = (a.distance(b), b.distance(c));
+        if ab < EPSILON || bc < EPSILON {
+            return;
+        }
+
+        // 1. Open or close the middle joint so the chain spans the target
+        //    distance (law of cosines), clamped to what the chain can reach.
+        let at = self.target.distance(a).clamp((ab - bc).abs() + EPSILON, ab + bc - EPSILON);
+        let current = (a - b).angle_between(c - b);
+        let wanted = ((ab * ab + bc * bc - at * at) / (2.0 * ab * bc)).clamp(-1.0, 1.0).acos();
+        let mut axis = (a - b).cross(c - b);
+        if axis.length_squared() < EPSILON {
+            // Straight chain: bend in the pole plane, or any plane at all.
+            let hint = self.pole.map_or(Vec3::ZERO, |p| (p - a).cross(c - a));
+            axis = if hint.length_squared() > EPSILON { -hint } else { (c - a).any_orthonormal_vector() };
+        }
+        pose.rotate(skeleton, mid, Quat::from_axis_angle(axis.normalize(), wanted - current));
+
+        // 2. Swing the root so the tip points at the target.
+        let c = pose.position(tip);
+        if let (Some(from), Some(to)) = ((c - a).try_normalize(), (self.target - a).try_normalize()) {
+            pose.rotate(skeleton, root, Quat::from_rotation_arc(from, to));
+        }
+
+        // 3. Twist about the root → target axis to face the pole.
+        if let (Some(pole), Some(dir)) = (self.pole, (self.target - a).try_normalize()) {
+            let b = pose.position(mid) - a;
+            let p = pole - a;
+            let (b, p) = (b - dir * b.dot(dir), p - dir * p.dot(dir));
+            if b.length_squared() > EPSILON && p.length_squared() > EPSILON {
+                let angle = b.cross(p).dot(dir).atan2(b.dot(p));
+                pose.rotate(skeleton, root, Quat::from_axis_angle(dir, angle));
+            }
+        }
+
+        if let Some(rotation) = self.tip_rotation {
+            let current = pose.rotation(tip);
+            pose.rotate(skeleton, tip, rotation * current.inverse());
+        }
+        pose.blend(skeleton, &[root, mid, tip], self.weight);
+    }
+}
+
+/// FABRIK (Forward And Backward Reaching IK) on the chain from `root` down to
+/// `tip`, which must be a descendant of `root`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FabrikChain {
+    pub root: String,
+    pub tip: String,
+    pub target: Vec3,
+    /// Maximum bend in radians between consecutive segments; `None` for
+    /// unconstrained joints.
+    pub joint_limit: Option<f32>,
+    pub iterations: u32,
+    /// Stop once the tip is this close to the target.
+    pub tolerance: f32,
+    /// Blend between the animated (0) and solved (1) pose.
+    pub weight: f32,
+}
+
+impl FabrikChain {
+    pub fn new(root: impl Into<String>, tip: impl Into<String>, target: Vec3) -> Self {
+        FabrikChain {
+            root: root.into(),
+            tip: tip.into(),
+            target,
+            joint_limit: None,
+            iterations: 10,
+            tolerance: 1e-3,
+            weight: 1.0,
+        }
+    }
+
+    pub fn with_joint_limit(mut self, radians: f32) -> Self {
+        self.joint_limit = Some(radians);
+        self
+    }
+
+    pub fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Bone indices from `root` to `tip`, or `None` if `tip` is not below
+    /// `root`.
+    fn chain(&self, skeleton: &Skeleton) -> Option<Vec<usize>> {
+        let root = skeleton.bone_index(&self.root)?;
+        let mut chain = vec![skeleton.bone_index(&self.tip)?];
+        while *chain.last()? != root {
+            chain.push(skeleton.bones[*chain.last()?].parent_index?);
+        }
+        chain.reverse();
+        Some(chain)
+    }
+
+    /// Solve on `skeleton`.  Does nothing if the chain is invalid.
+    pub fn solve(&self, skeleton: &mut Skeleton) {
+        let Some(chain) = self.chain(skeleton) else { return };
+        if chain.len() < 2 {
+            return;
+        }
+        let mut pose = Pose::new(skeleton);
+        let mut points: Vec<Vec3> = chain.iter().map(|&i| pose.position(i)).collect();
+        let lengths: Vec<f32> = points.windows(2).map(|w| w[0].distance(w[1])).collect();
+        let base = points[0];
+        let last = points.len() - 1;
+
+        if base.distance(self.target) >= lengths.iter().sum::<f32>() {
+            // Out of reach: stretch straight towards the target.
+            let dir = (self.target - base).normalize_or_zero();
+            for i in 0..last {
+                points[i + 1] = points[i] + dir * lengths[i];
+            }
+        } else {
+            for _ in 0..self.iterations {
+                if points[last].distance(self.target) <= self.tolerance {
+                    break;
+                }
+                // Backward: pin the tip to the target.
+                points[last] = self.target;
+                for i in (0..last).rev() {
+                    let dir = (points[i] - points[i + 1]).normalize_or_zero();
+                    points[i] = points[i + 1] + dir * lengths[i];
+                }
+                // Forward: pin the root back, applying joint limits.
+                points[0] = base;
+                for i in 0..last {
+                    let mut dir = (points[i + 1] - points[i]).normalize_or_zero();
+                    if let (Some(limit), Some(prev)) = (self.joint_limit, i.checked_sub(1)) {
+                        let parent = (points[i] - points[prev]).normalize_or_zero();
+                        dir = clamp_direction(parent, dir, limit);
+                    }
+                    points[i + 1] = points[i] + dir * lengths[i];
+                }
+            }
+        }
+
+        // Turn each bone so its child lands on the solved point.
+        for (k, &bone) in chain[..last].iter().enumerate() {
+            let from = pose.position(chain[k + 1]) - pose.position(bone);
+            let to = points[k + 1] - points[k];
+            if let (Some(from), Some(to)) = (from.try_normalize(), to.try_normalize()) {
+                pose.rotate(skeleton, bone, Quat::from_rotation_arc(from, to));
+            }
+        }
+        pose.blend(skeleton, &chain, self.weight);
+    }
+}
+
+/// Turns `bone` so its local `forward` axis points at `target`, by at most
+/// `max_angle` radians away from the animated pose.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LookAtIk {
+    pub bone: String,
+    /// Bone-local axis that should face the target.
+    pub forward: Vec3,
+    pub target: Vec3,
+    pub max_angle: f32,
+    /// Blend between the animated (0) and solved (1) pose.
+    pub weight: f32,
+}
+
+impl LookAtIk {
+    pub fn new(bone: impl Into<String>, forward: Vec3, target: Vec3) -> Self {
+        LookAtIk { bone: bone.into(), forward, target, max_angle: std::f32::consts::PI, weight: 1.0 }
+    }
+
+    pub fn with_max_angle(mut self, radians: f32) -> Self {
+        self.max_angle = radians;
+        self
+    }
+
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Solve on `skeleton`.  Does nothing if the bone is missing.
+    pub fn solve(&self, skeleton: &mut Skeleton) {
+        let Some(bone) = skeleton.bone_index(&self.bone) else { return };
+        let mut pose = Pose::new(skeleton);
+        let facing = pose.rotation(bone) * self.forward;
+        let wanted = self.target - pose.position(bone);
+        let (Some(facing), Some(wanted)) = (facing.try_normalize(), wanted.try_normalize()) else {
+            return;
+        };
+        let clamped = clamp_direction(facing, wanted, self.max_angle);
+        pose.rotate(skeleton, bone, Quat::from_rotation_arc(facing, clamped));
+        pose.blend(skeleton, &[bone], self.weight);
+    }
+}
+
+/// One IK constraint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum IkConstraint {
+    TwoBone(TwoBoneIk),
+    Fabrik(FabrikChain),
+    LookAt(LookAtIk),
+}
+
+impl IkConstraint {
+    pub fn solve(&self, skeleton: &mut Skeleton) {
+        match self {
+            IkConstraint::TwoBone(ik) => ik.solve(skeleton),
+            IkConstraint::Fabrik(ik) => ik.solve(skeleton),
+            IkConstraint::LookAt(ik) => ik.solve(skeleton),
+        }
+    }
+}
+
+impl From<TwoBoneIk> for IkConstraint {
+    fn from(ik: TwoBoneIk) -> Self {
+        IkConstraint::TwoBone(ik)
+    }
+}
+
+impl From<FabrikChain> for IkConstraint {
+    fn from(ik: FabrikChain) -> Self {
+        IkConstraint::Fabrik(ik)
+    }
+}
+
+impl From<LookAtIk> for IkConstraint {
+    fn from(ik: LookAtIk) -> Self {
+        IkConstraint::LookAt(ik)
+    }
+}
+
+/// IK constraints of a skeleton, solved in order by `SkinningSystem`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IkConstraints(pub Vec<IkConstraint>);
+
+impl IkConstraints {
+    /// Solve every constraint in order on `skeleton`.
+    pub fn solve(&self, skeleton: &mut Skeleton) {
+        for constraint in &self.0 {
+            constraint.solve(skeleton);
+        }
+    }
+
+    /// Rebuild `skeleton`'s joint matrices with every constraint solved,
+    /// leaving its bones at the pose they had before.
+    ///
+    /// The solvers take the current bone rotations as the animated pose.
+    /// Keeping the IK output out of the bones means a skeleton that no clip
+    /// rewrites each frame is solved from the same pose every frame, instead
+    /// of from the previous frame's result.
+    pub fn apply(&self, skeleton: &mut Skeleton) {
+        let animated: Vec<Quat> = skeleton.bones.iter().map(|b| b.local_transform.rotation).collect();
+        self.solve(skeleton);
+        skeleton.update_matrices();
+        for (bone, rotation) in skeleton.bones.iter_mut().zip(animated) {
+            bone.local_transform.rotation = rotation;
+        }
+    }
+}
+
+#[cfg(feature = "ecs")]
+impl Component for IkConstraints {}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Helpers
+
+/// Rotate `dir` towards `target` by at most `limit` radians.
+fn clamp_direction(dir: Vec3, target: Vec3, limit: f32) -> Vec3 {
+    let angle = dir.angle_between(target);
+    if angle <= limit {
+        return target;
+    }
+    let axis = dir.cross(target).try_normalize().unwrap_or_else(|| dir.any_orthonormal_vector());
+    Quat::from_axis_angle(axis, limit) * dir
+}
+
+/// Skeleton-space view of a skeleton being posed, plus the animated local
+/// rotations for weight blending.
+struct Pose {
+    model: Vec<Mat4>,
+    animated: Vec<Quat>,
+}
+
+impl Pose {
+    fn new(skeleton: &Skeleton) -> Self {
+        Pose {
+            model: skeleton.model_transforms(),
+            animated: skeleton.bones.iter().map(|b| b.local_transform.rotation).collect(),
+        }
+    }
+
+    fn position(&self, bone: usize) -> Vec3 {
+        self.model[bone].w_axis.truncate()
+    }
+
+    fn rotation(&self, bone: usize) -> Quat {
+        self.model[bone].to_scale_rotation_translation().1
+    }
+
+    /// Apply the skeleton-space rotation `delta` to `bone` about its own
+    /// pivot, then refresh the model transforms.
+    fn rotate(&mut self, skeleton: &mut Skeleton, bone: usize, delta: Quat) {
+        let parent = skeleton.bones[bone].parent_index.map_or(Quat::IDENTITY, |p| self.rotation(p));
+        let local = &mut skeleton.bones[bone].local_transform.rotation;
+        *local = (parent.inverse() * delta * parent * *local).normalize();
+        self.model = skeleton.model_transforms();
+    }
+
+    /// Blend the solved rotations of `bones` back towards the animated pose.
+    fn blend(&self, skeleton: &mut Skeleton, bones: &[usize], weight: f32) {
+        if weight >= 1.0 {
+            return;
+        }
+        for &bone in bones {
+            let local = &mut skeleton.bones[bone].local_transform.rotation;
+            *local = self.animated[bone].slerp(*local, weight.max(0.0));
+        }
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::skinning::{Bone, Transform};
+    use std::f32::consts::FRAC_PI_2;
+
+    /// A straight chain of unit-length bones along +Y.
+    fn chain(names: &[&str]) -> Skeleton {
+        Skeleton::new(
+            names
+                .iter()
+                .enumerate()
+                .map(|(i, name)| Bone {
+                    name: name.to_string(),
+                    parent_index: i.checked_sub(1),
+                    inverse_bind_matrix: Mat4::IDENTITY,
+                    local_transform: Transform {
+                        position: if i == 0 { Vec3::ZERO } else { Vec3::Y },
+                        ..Default::default()
+                    },
+                })
+                .collect(),
+        )
+    }
+
+    fn position(skeleton: &Skeleton, bone: &str) -> Vec3 {
+        skeleton.model_transforms()[skeleton.bone_index(bone).unwrap()].w_axis.truncate()
+    }
+
+    #[test]
+    fn two_bone_reaches_target_and_bends_towards_pole() {
+        let mut leg = chain(&["thigh", "shin", "foot"]);
+        let target = Vec3::new(0.0, 1.2, 0.0);
+        TwoBoneIk::new("thigh", "shin", "foot", target).with_pole(Vec3::new(0.0, 1.0, 5.0)).solve(&mut leg);
+
+        assert!(position(&leg, "foot").abs_diff_eq(target, 1e-4));
+        assert!((position(&leg, "shin").length() - 1.0).abs() < 1e-5);
+        // Knee pushed out towards +Z, midway up: (0, 0.6, 0.8).
+        assert!(position(&leg, "shin").abs_diff_eq(Vec3::new(0.0, 0.6, 0.8), 1e-4));
+    }
+
+    #[test]
+    fn two_bone_stretches_towards_unreachable_target() {
+        let mut arm = chain(&["upper", "lower", "hand"]);
+        TwoBoneIk::new("upper", "lower", "hand", Vec3::new(5.0, 0.0, 0.0)).solve(&mut arm);
+        let hand = position(&arm, "hand");
+        assert!((hand.length() - 2.0).abs() < 1e-3);
+        assert!(hand.normalize().abs_diff_eq(Vec3::X, 1e-2));
+    }
+
+    #[test]
+    fn fabrik_reaches_target_and_respects_joint_limit() {
+        let names = ["a", "b", "c", "d", "e"];
+        let target = Vec3::new(2.0, 2.0, 0.0);
+        let mut free = chain(&names);
+        FabrikChain::new("a", "e", target).with_iterations(50).solve(&mut free);
+        assert!(position(&free, "e").distance(target) < 1e-2);
+
+        let mut stiff = chain(&names);
+        FabrikChain::new("a", "e", Vec3::new(0.0, -1.0, 0.0)).with_joint_limit(0.2).solve(&mut stiff);
+        let transforms = stiff.model_transforms();
+        let points: Vec<Vec3> = transforms.iter().map(|m| m.w_axis.truncate()).collect();
+        for w in points.windows(3) {
+            assert!((w[1] - w[0]).angle_between(w[2] - w[1]) <= 0.2 + 1e-3);
+        }
+    }
+
+    #[test]
+    fn look_at_turns_bone_within_clamp() {
+        let mut neck = chain(&["neck", "head"]);
+        LookAtIk::new("head", Vec3::Z, Vec3::new(5.0, 1.0, 0.0)).solve(&mut neck);
+        let head = neck.model_transforms()[1].to_scale_rotation_translation().1;
+        assert!((head * Vec3::Z).abs_diff_eq(Vec3::X, 1e-5));
+
+        let mut neck = chain(&["neck", "head"]);
+        LookAtIk::new("head", Vec3::Z, Vec3::new(5.0, 1.0, 0.0)).with_max_angle(FRAC_PI_2 / 2.0).solve(&mut neck);
+        let head = neck.model_transforms()[1].to_scale_rotation_translation().1;
+        assert!(((head * Vec3::Z).angle_between(Vec3::Z) - FRAC_PI_2 / 2.0).abs() < 1e-5);
+
+        let mut neck = chain(&["neck", "head"]);
+        LookAtIk::new("head", Vec3::Z, Vec3::new(5.0, 1.0, 0.0)).with_weight(0.5).solve(&mut neck);
+        let head = neck.model_transforms()[1].to_scale_rotation_translation().1;
+        assert!((head * Vec3::Z).abs_diff_eq(Vec3::new(1.0, 0.0, 1.0).normalize(), 1e-5));
+    }
+
+    #[test]
+    fn applied_constraints_do_not_accumulate_across_frames() {
+        let target = Vec3::new(5.0, 1.0, 0.0);
+        let turn = |ik: LookAtIk| {
+            let constraints = IkConstraints(vec![ik.into()]);
+            let mut neck = chain(&["neck", "head"]);
+            (0..3)
+                .map(|_| {
+                    constraints.apply(&mut neck);
+                    assert_eq!(neck.bones[1].local_transform.rotation, Quat::IDENTITY);
+                    let head = neck.joint_matrices[1].to_scale_rotation_translation().1;
+                    (head * Vec3::Z).angle_between(Vec3::Z)
+                })
+                .collect::<Vec<f32>>()
+        };
+
+        for angle in turn(LookAtIk::new("head", Vec3::Z, target).with_max_angle(0.2)) {
+            assert!((angle - 0.2).abs() < 1e-5);
+        }
+        for angle in turn(LookAtIk::new("head", Vec3::Z, target).with_weight(0.5)) {
+            assert!((angle - FRAC_PI_2 / 2.0).abs() < 1e-5);
+        }
+    }
+}
@@ -5,24 +5,42 @@ pub mod blueprint;
 pub mod camera;
 pub mod controller;
 pub mod gizmo;
+pub mod ik;
 pub mod material;
 pub mod particles;
 pub mod skinning;
+pub mod snapshot;
+pub mod spline;
 pub mod systems;
 pub mod world;
 
 pub use blueprint::SceneBlueprint;
+pub use snapshot::register_core_components;
 
 // World types
 pub use world::{Element, ElementKind, Handle, PointLightComponent, ShadowCaster, Billboard, BillboardMode, World};
+pub use world::{MeshGeometry, RayHit};
+pub use world::{NodeCamera, NodeLight, NodeMesh, SceneGraph, SceneGraphNode};
+pub use world::{
+    MaterialLibrary, Prefab, PrefabError, PrefabInstance, PrefabLibrary, PrefabNode, PrefabOverride, PrefabProperty,
+};
 pub use particles::ParticleEmitter;
-pub use skinning::{Skeleton, SkinnedMesh, BoneInfluence};
+pub use ik::{FabrikChain, IkConstraint, IkConstraints, LookAtIk, TwoBoneIk};
+pub use skinning::{Skeleton, SkinnedMesh, BoneInfluence, BoneInfluences, MorphWeights};
+pub use spline::{Spline, SplineFrame, SplineHit, SplineKind};
 
 // Systems and stage enum
+#[allow(deprecated)]
 pub use systems::{
-    AnimationClip, AnimationPlayer, AnimationSystem, Behavior, BehaviorComponent, BehaviorSystem,
-    Camera3D, Camera3DBuilder, Children, DirectionalLight, GlobalTransform, Keyframe, OrbitCamera,
-    OrbitCameraSystem, Parent, Stage, SkinningSystem, TimeSystem, TransformSystem, Velocity, VelocitySystem,
+    Animatable, AnimationClip, AnimationController, AnimationControllerSystem, AnimationEvent,
+    AnimationLayer, AnimationPlayer, AnimationSample, AnimationState, AnimationStateMachine,
+    AnimationSystem, Behavior, BehaviorComponent, BehaviorSystem, BlendMode, BoneMask, Camera3D,
+    Camera3DBuilder, ChildOf, Children, ClipEvent, Condition, DirectionalLight, FollowPath,
+    FollowPathSystem, GlobalTransform, Interpolation, Keyframe, Motion, OrbitCamera,
+    OrbitCameraSystem, ParamValue, Parent, PathEnd, PlayingClip, Stage, StateBlend,
+    SkinningSystem, LocalBounds, SpatialIndex, SpatialIndexSystem, SpatialRemovals, TimeSystem, Track, TransformSample, TransformSystem, TransformTracks,
+    Transition, Velocity, VelocitySystem,
+    FixedTimeSystem, PreviousTransform, TransformSnapshotSystem,
 };
 
 // Camera
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 use glam::{Mat4, Vec3, Quat};
 use bytemuck::{Pod, Zeroable};
 
+#[cfg(feature = "ecs")]
+use ferrous_ecs::entity::{EntityMap, MapEntities};
 #[cfg(feature = "ecs")]
 use ferrous_ecs::prelude::Component;
 
@@ -57,10 +59,16 @@ impl Skeleton {
         }
     }
 
-    /// Update `joint_matrices` based on current local transforms.
-    pub fn update_matrices(&mut self) {
+    /// Index of the bone called `name`.
+    pub fn bone_index(&self, name: &str) -> Option<usize> {
+        self.bones.iter().position(|b| b.name == name)
+    }
+
+    /// Skeleton-space (model-space) transform of every bone, from the current
+    /// local transforms.  Parents must come before their children.
+    pub fn model_transforms(&self) -> Vec<Mat4> {
         let mut world_transforms = vec![Mat4::IDENTITY; self.bones.len()];
-        
+
         for i in 0..self.bones.len() {
             let local_mat = Mat4::from_scale_rotation_translation(
                 self.bones[i].local_transform.scale,
@@ -73,8 +81,16 @@ impl Skeleton {
             } else {
                 world_transforms[i] = local_mat;
             }
+        }
+        world_transforms
+    }
 
-            self.joint_matrices[i] = world_transforms[i] * self.bones[i].inverse_bind_matrix;
+    /// Update `joint_matrices` based on current local transforms.
+    pub fn update_matrices(&mut self) {
+        let world_transforms = self.model_transforms();
+        self.joint_matrices.resize(self.bones.len(), Mat4::IDENTITY);
+        for (i, world) in world_transforms.iter().enumerate() {
+            self.joint_matrices[i] = *world * self.bones[i].inverse_bind_matrix;
         }
     }
 }
@@ -99,3 +115,27 @@ pub struct SkinnedMesh {
 
 #[cfg(feature = "ecs")]
 impl Component for SkinnedMesh {}
+
+#[cfg(feature = "ecs")]
+impl MapEntities for SkinnedMesh {
+    fn map_entities(&mut self, map: &EntityMap) {
+        self.skeleton_entity = self.skeleton_entity.map(|e| map.map(e));
+    }
+}
+
+/// Per-vertex [`BoneInfluence`]s of a skinned mesh, with bone indices into
+/// the [`Skeleton`] of its [`SkinnedMesh::skeleton_entity`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BoneInfluences(pub Vec<BoneInfluence>);
+
+#[cfg(feature = "ecs")]
+impl Component for BoneInfluences {}
+
+/// Current morph-target (blend shape) weights of a mesh, one per target.
+///
+/// Written by `AnimationSystem` from a clip's weights track.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MorphWeights(pub Vec<f32>);
+
+#[cfg(feature = "ecs")]
+impl Component for MorphWeights {}
@@ -0,0 +1,121 @@
+//! Snapshot registration for the engine's built-in ECS components.
+//!
+//! `SceneBlueprint` only covers the legacy `Element` list; anything living
+//! purely in the ECS is persisted through `World::serialize_snapshot`, which
+//! writes the components registered here (plus whatever the game registers).
+//!
+//! ```rust,ignore
+//! let mut registry = ComponentRegistry::new();
+//! ferrous_core::scene::register_core_components(&mut registry);
+//! registry.register::<MyGameComponent>();
+//! let bytes = world.ecs.serialize_snapshot(&registry, SnapshotFormat::Binary)?;
+//! ```
+
+use ferrous_ecs::snapshot::ComponentRegistry;
+
+use crate::scene::{
+    AnimationController, AnimationPlayer, Billboard, BoneInfluences, Camera3D, ChildOf, DirectionalLight,
+    FollowPath, GlobalTransform, IkConstraints, LocalBounds, Material, OrbitCamera, ParticleEmitter,
+    PointLightComponent, PreviousTransform, ShadowCaster, Skeleton, SkinnedMesh, MorphWeights,
+    Spline, Velocity,
+};
+use crate::transform::Transform;
+
+/// Register every serializable component defined by `ferrous_core`, and the
+/// `ChildOf` hierarchy relation.
+///
+/// `MaterialComponent` is left out on purpose: its `MaterialHandle` indexes
+/// the renderer's GPU material table and means nothing in another session.
+pub fn register_core_components(registry: &mut ComponentRegistry) {
+    registry
+        .register::<Transform>()
+        .register::<GlobalTransform>()
+        .register::<LocalBounds>()
+        .register::<PreviousTransform>()
+        .register::<Velocity>()
+        .register::<Spline>()
+        .register_mapped::<FollowPath>()
+        .register::<AnimationPlayer>()
+        .register::<AnimationController>()
+        .register::<ParticleEmitter>()
+        .register::<Skeleton>()
+        .register_mapped::<SkinnedMesh>()
+        .register::<BoneInfluences>()
+        .register::<MorphWeights>()
+        .register::<IkConstraints>()
+        .register::<Material>()
+        .register::<ShadowCaster>()
+        .register::<Billboard>()
+        .register::<PointLightComponent>()
+        .register::<DirectionalLight>()
+        .register::<Camera3D>()
+        .register::<OrbitCamera>()
+        .register_relation::<ChildOf>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[allow(deprecated)]
+    use crate::scene::{AnimationClip, Children, Keyframe, Parent, Track};
+    use ferrous_ecs::snapshot::SnapshotFormat;
+    use ferrous_ecs::world::World;
+    use glam::Vec3;
+
+    #[test]
+    fn ecs_only_components_survive_a_snapshot() {
+        let mut registry = ComponentRegistry::new();
+        register_core_components(&mut registry);
+
+        let mut world = World::new();
+        let root = world.spawn((Transform::from_position(Vec3::X),));
+        let clip = AnimationClip::new(1.0)
+            .with_translation(Track::linear(vec![Keyframe { time: 0.0, value: Vec3::ZERO }]))
+            .with_looping(true);
+        let child = world.spawn((Transform::IDENTITY, Velocity(Vec3::Y)));
+        world.insert(child, AnimationPlayer::new(clip));
+        world.relate::<ChildOf>(child, root);
+        let skin = world.spawn((SkinnedMesh { skeleton_entity: Some(root) },));
+
+        for format in [SnapshotFormat::Json, SnapshotFormat::Binary] {
+            let bytes = world.serialize_snapshot(&registry, format).unwrap();
+            let mut loaded = World::new();
+            // Shift the ids so an unmapped handle would point at the wrong entity.
+            loaded.spawn((Velocity(Vec3::ZERO),));
+            let map = loaded.deserialize_snapshot(&registry, &bytes, format).unwrap();
+            let (root2, child2) = (map.map(root), map.map(child));
+
+            assert_eq!(loaded.get::<Velocity>(child2), Some(&Velocity(Vec3::Y)));
+            let player = loaded.get::<AnimationPlayer>(child2).unwrap();
+            assert_eq!(player, world.get::<AnimationPlayer>(child).unwrap());
+            assert_eq!(loaded.get::<Parent>(child2).unwrap().target(), root2);
+            assert_eq!(loaded.get::<Children>(root2).unwrap().as_slice(), &[child2]);
+            let skinned = loaded.get::<SkinnedMesh>(map.map(skin)).unwrap();
+            assert_eq!(skinned.skeleton_entity, Some(root2));
+        }
+    }
+
+    #[test]
+    fn follow_path_keeps_its_path_across_a_snapshot() {
+        use crate::scene::{Spline, SplineKind};
+
+        let mut registry = ComponentRegistry::new();
+        register_core_components(&mut registry);
+
+        let mut world = World::new();
+        let rail = world.spawn((Transform::IDENTITY, Spline::new(SplineKind::CatmullRom, vec![Vec3::ZERO, Vec3::X])));
+        let cart = world.spawn((Transform::IDENTITY, FollowPath::new(rail, 2.0)));
+
+        for format in [SnapshotFormat::Json, SnapshotFormat::Binary] {
+            let bytes = world.serialize_snapshot(&registry, format).unwrap();
+            let mut loaded = World::new();
+            loaded.spawn((Velocity(Vec3::ZERO),));
+            let map = loaded.deserialize_snapshot(&registry, &bytes, format).unwrap();
+
+            let follow = loaded.get::<FollowPath>(map.map(cart)).unwrap();
+            assert_eq!(follow.path, map.map(rail));
+            assert_ne!(follow.path, rail, "the ids were shifted");
+            assert!(loaded.get::<Spline>(follow.path).is_some());
+        }
+    }
+}
@@ -0,0 +1,428 @@
+//! Piecewise cubic curves for camera rails, patrol routes and the like.
+//!
+//! A [`Spline`] is a list of control points interpreted by its
+//! [`SplineKind`]:
+//!
+//! - [`SplineKind::CatmullRom`] — passes through every point.
+//! - [`SplineKind::Bezier`] — cubic Bézier segments laid out as
+//!   `anchor, handle, handle, anchor, handle, handle, anchor, …`.
+//! - [`SplineKind::BSpline`] — uniform cubic B-spline, C² smooth, pulled
+//!   towards the points instead of through them.  Open B-splines are clamped
+//!   so they start and end on the first and last point.
+//!
+//! Queries take an *arc length* (distance along the curve from its start)
+//! rather than a curve parameter, so moving at a constant rate along the
+//! curve is a matter of advancing a distance.  Arc length is resolved
+//! through a lookup table rebuilt whenever the points change, which is why
+//! the points are only mutable through methods.
+//!
+//! ```rust,ignore
+//! let rail = Spline::new(SplineKind::CatmullRom, vec![a, b, c, d]);
+//! let frame = rail.frame_at(rail.length() * 0.5, Vec3::Y);
+//! camera.eye = frame.position;
+//! let nearest = rail.closest_point(player_position);
+//! ```
+
+use glam::{Mat3, Quat, Vec3};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ecs")]
+use ferrous_ecs::prelude::Component;
+
+/// Arc-length table resolution.
+const SAMPLES_PER_SEGMENT: usize = 16;
+
+/// How the control points of a [`Spline`] are interpreted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SplineKind {
+    CatmullRom,
+    Bezier,
+    BSpline,
+}
+
+/// Position and orientation on a spline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SplineFrame {
+    pub position: Vec3,
+    /// Unit direction of travel.
+    pub tangent: Vec3,
+    /// Unit vector perpendicular to `tangent`, as close to the requested up
+    /// vector as possible.
+    pub normal: Vec3,
+    /// `tangent × normal` — points to the right of the direction of travel.
+    pub binormal: Vec3,
+}
+
+impl SplineFrame {
+    /// Rotation taking the engine's forward (-Z) to `tangent` and +Y to
+    /// `normal`.
+    pub fn rotation(&self) -> Quat {
+        Quat::from_mat3(&Mat3::from_cols(self.binormal, self.normal, -self.tangent))
+    }
+}
+
+/// Result of [`Spline::closest_point`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SplineHit {
+    /// Arc length of the closest point.
+    pub distance: f32,
+    pub position: Vec3,
+}
+
+/// A piecewise cubic curve component.  Points are in the owning entity's
+/// local space.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "SplineData", into = "SplineData")]
+pub struct Spline {
+    kind: SplineKind,
+    points: Vec<Vec3>,
+    closed: bool,
+    /// Cumulative arc length at every table sample.
+    lengths: Vec<f32>,
+}
+
+#[cfg(feature = "ecs")]
+impl Component for Spline {}
+
+/// Serialized form of [`Spline`] (the arc-length table is rebuilt on load).
+#[derive(Clone, Serialize, Deserialize)]
+struct SplineData {
+    kind: SplineKind,
+    points: Vec<Vec3>,
+    #[serde(default)]
+    closed: bool,
+}
+
+impl From<SplineData> for Spline {
+    fn from(d: SplineData) -> Self {
+        Spline::new(d.kind, d.points).with_closed(d.closed)
+    }
+}
+
+impl From<Spline> for SplineData {
+    fn from(s: Spline) -> Self {
+        SplineData { kind: s.kind, points: s.points, closed: s.closed }
+    }
+}
+
+impl Spline {
+    pub fn new(kind: SplineKind, points: Vec<Vec3>) -> Self {
+        let mut spline = Spline { kind, points, closed: false, lengths: Vec::new() };
+        spline.rebuild();
+        spline
+    }
+
+    /// Join the last point back to the first.  Closed Bézier splines take
+    /// `3 × segments` points (the last segment ends on the first anchor).
+    pub fn with_closed(mut self, closed: bool) -> Self {
+        self.closed = closed;
+        self.rebuild();
+        self
+    }
+
+    pub fn kind(&self) -> SplineKind {
+        self.kind
+    }
+
+    pub fn points(&self) -> &[Vec3] {
+        &self.points
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    pub fn set_points(&mut self, points: Vec<Vec3>) {
+        self.points = points;
+        self.rebuild();
+    }
+
+    /// Move one control point.  Out-of-range indices are ignored.
+    pub fn set_point(&mut self, index: usize, point: Vec3) {
+        if let Some(p) = self.points.get_mut(index) {
+            *p = point;
+            self.rebuild();
+        }
+    }
+
+    pub fn push(&mut self, point: Vec3) {
+        self.points.push(point);
+        self.rebuild();
+    }
+
+    /// Number of cubic segments the points form.
+    pub fn segment_count(&self) -> usize {
+        let n = self.points.len();
+        match (self.kind, self.closed) {
+            (_, _) if n < 2 => 0,
+            (SplineKind::CatmullRom, false) => n - 1,
+            (SplineKind::CatmullRom | SplineKind::BSpline, true) => n,
+            (SplineKind::Bezier, false) => (n - 1) / 3,
+            (SplineKind::Bezier, true) => n / 3,
+            (SplineKind::BSpline, false) => n + 1,
+        }
+    }
+
+    /// Total arc length.
+    pub fn length(&self) -> f32 {
+        self.lengths.last().copied().unwrap_or(0.0)
+    }
+
+    /// Position at arc length `distance`.
+    pub fn position_at(&self, distance: f32) -> Vec3 {
+        self.position_at_param(self.param_at(distance))
+    }
+
+    /// Unit tangent at arc length `distance` (zero on a degenerate spline).
+    pub fn tangent_at(&self, distance: f32) -> Vec3 {
+        self.derivative_at_param(self.param_at(distance)).normalize_or_zero()
+    }
+
+    /// Position and orientation at arc length `distance`, with the normal
+    /// as close to `up` as possible.
+    pub fn frame_at(&self, distance: f32, up: Vec3) -> SplineFrame {
+        let u = self.param_at(distance);
+        let position = self.position_at_param(u);
+        let mut tangent = self.derivative_at_param(u).normalize_or_zero();
+        if tangent == Vec3::ZERO {
+            tangent = Vec3::NEG_Z;
+        }
+        let mut binormal = tangent.cross(up).normalize_or_zero();
+        if binormal == Vec3::ZERO {
+            // Travelling along `up` itself: pick any perpendicular.
+            binormal = tangent.any_orthonormal_vector();
+        }
+        let normal = binormal.cross(tangent);
+        SplineFrame { position, tangent, normal, binormal }
+    }
+
+    /// Point of the curve nearest to `point`.
+    pub fn closest_point(&self, point: Vec3) -> SplineHit {
+        let segments = self.segment_count();
+        if segments == 0 {
+            let position = self.points.first().copied().unwrap_or(Vec3::ZERO);
+            return SplineHit { distance: 0.0, position };
+        }
+
+        // Coarse pass over the table samples, then refine around the best.
+        let step = 1.0 / SAMPLES_PER_SEGMENT as f32;
+        let samples = segments * SAMPLES_PER_SEGMENT;
+        let best = (0..=samples)
+            .map(|k| k as f32 * step)
+            .min_by(|&a, &b| {
+                let da = self.position_at_param(a).distance_squared(point);
+                let db = self.position_at_param(b).distance_squared(point);
+                da.total_cmp(&db)
+            })
+            .unwrap_or(0.0);
+        let (mut lo, mut hi) = ((best - step).max(0.0), (best + step).min(segments as f32));
+        for _ in 0..24 {
+            let a = lo + (hi - lo) / 3.0;
+            let b = hi - (hi - lo) / 3.0;
+            if self.position_at_param(a).distance_squared(point) < self.position_at_param(b).distance_squared(point) {
+                hi = b;
+            } else {
+                lo = a;
+            }
+        }
+        let u = (lo + hi) * 0.5;
+        SplineHit { distance: self.distance_at_param(u), position: self.position_at_param(u) }
+    }
+
+    /// Points along the curve, `per_segment` per cubic segment plus the end
+    /// point — e.g. for drawing it as a polyline.
+    pub fn polyline(&self, per_segment: usize) -> Vec<Vec3> {
+        let segments = self.segment_count();
+        if segments == 0 {
+            return self.points.clone();
+        }
+        let per_segment = per_segment.max(1);
+        (0..=segments * per_segment)
+            .map(|k| self.position_at_param(k as f32 / per_segment as f32))
+            .collect()
+    }
+
+    // ── Internal helpers ─────────────────────────────────────────────────────
+
+    fn rebuild(&mut self) {
+        let segments = self.segment_count();
+        self.lengths.clear();
+        if segments == 0 {
+            return;
+        }
+        self.lengths.push(0.0);
+        let mut total = 0.0;
+        let mut prev = self.position_at_param(0.0);
+        for k in 1..=segments * SAMPLES_PER_SEGMENT {
+            let p = self.position_at_param(k as f32 / SAMPLES_PER_SEGMENT as f32);
+            total += p.distance(prev);
+            self.lengths.push(total);
+            prev = p;
+        }
+    }
+
+    /// Curve parameter (`segment + t`) at arc length `distance`.  Closed
+    /// splines wrap the distance; open ones clamp it.
+    fn param_at(&self, distance: f32) -> f32 {
+        let length = self.length();
+        if self.lengths.len() < 2 || length <= 0.0 {
+            return 0.0;
+        }
+        let d = if self.closed { distance.rem_euclid(length) } else { distance.clamp(0.0, length) };
+        let k = self.lengths.partition_point(|&l| l < d).clamp(1, self.lengths.len() - 1);
+        let (l0, l1) = (self.lengths[k - 1], self.lengths[k]);
+        let f = if l1 > l0 { (d - l0) / (l1 - l0) } else { 0.0 };
+        (k as f32 - 1.0 + f) / SAMPLES_PER_SEGMENT as f32
+    }
+
+    /// Arc length at curve parameter `u`.
+    fn distance_at_param(&self, u: f32) -> f32 {
+        if self.lengths.len() < 2 {
+            return 0.0;
+        }
+        let x = (u * SAMPLES_PER_SEGMENT as f32).clamp(0.0, (self.lengths.len() - 1) as f32);
+        let k = (x.floor() as usize).min(self.lengths.len() - 2);
+        let f = x - k as f32;
+        self.lengths[k] + (self.lengths[k + 1] - self.lengths[k]) * f
+    }
+
+    /// Split `u` into a segment index and local `t`.
+    fn locate(&self, u: f32) -> (usize, f32) {
+        let segments = self.segment_count();
+        let u = u.clamp(0.0, segments as f32);
+        let segment = (u.floor() as usize).min(segments - 1);
+        (segment, u - segment as f32)
+    }
+
+    /// The four control points of `segment`.
+    fn segment_points(&self, segment: usize) -> [Vec3; 4] {
+        let n = self.points.len() as isize;
+        let at = |i: isize| {
+            if self.closed {
+                self.points[i.rem_euclid(n) as usize]
+            } else {
+                self.points[i.clamp(0, n - 1) as usize]
+            }
+        };
+        let i = segment as isize;
+        match (self.kind, self.closed) {
+            (SplineKind::CatmullRom, _) | (SplineKind::BSpline, true) => [at(i - 1), at(i), at(i + 1), at(i + 2)],
+            (SplineKind::Bezier, _) => [at(3 * i), at(3 * i + 1), at(3 * i + 2), at(3 * i + 3)],
+            (SplineKind::BSpline, false) => [at(i - 2), at(i - 1), at(i), at(i + 1)],
+        }
+    }
+
+    fn position_at_param(&self, u: f32) -> Vec3 {
+        if self.segment_count() == 0 {
+            return self.points.first().copied().unwrap_or(Vec3::ZERO);
+        }
+        let (segment, t) = self.locate(u);
+        combine(self.segment_points(segment), basis(self.kind, t))
+    }
+
+    fn derivative_at_param(&self, u: f32) -> Vec3 {
+        if self.segment_count() == 0 {
+            return Vec3::ZERO;
+        }
+        let (segment, t) = self.locate(u);
+        combine(self.segment_points(segment), basis_derivative(self.kind, t))
+    }
+}
+
+fn combine(p: [Vec3; 4], w: [f32; 4]) -> Vec3 {
+    p[0] * w[0] + p[1] * w[1] + p[2] * w[2] + p[3] * w[3]
+}
+
+/// Blending weights of the four segment points at `t`.
+fn basis(kind: SplineKind, t: f32) -> [f32; 4] {
+    let (t2, t3, s) = (t * t, t * t * t, 1.0 - t);
+    match kind {
+        SplineKind::CatmullRom => [
+            0.5 * (-t3 + 2.0 * t2 - t),
+            0.5 * (3.0 * t3 - 5.0 * t2 + 2.0),
+            0.5 * (-3.0 * t3 + 4.0 * t2 + t),
+            0.5 * (t3 - t2),
+        ],
+        SplineKind::Bezier => [s * s * s, 3.0 * s * s * t, 3.0 * s * t2, t3],
+        SplineKind::BSpline => [
+            s * s * s / 6.0,
+            (3.0 * t3 - 6.0 * t2 + 4.0) / 6.0,
+            (-3.0 * t3 + 3.0 * t2 + 3.0 * t + 1.0) / 6.0,
+            t3 / 6.0,
+        ],
+    }
+}
+
+/// Derivatives of [`basis`] with respect to `t`.
+fn basis_derivative(kind: SplineKind, t: f32) -> [f32; 4] {
+    let (t2, s) = (t * t, 1.0 - t);
+    match kind {
+        SplineKind::CatmullRom => [
+            0.5 * (-3.0 * t2 + 4.0 * t - 1.0),
+            0.5 * (9.0 * t2 - 10.0 * t),
+            0.5 * (-9.0 * t2 + 8.0 * t + 1.0),
+            0.5 * (3.0 * t2 - 2.0 * t),
+        ],
+        SplineKind::Bezier => [-3.0 * s * s, 3.0 * s * s - 6.0 * s * t, 6.0 * s * t - 3.0 * t2, 3.0 * t2],
+        SplineKind::BSpline => [
+            -0.5 * s * s,
+            (9.0 * t2 - 12.0 * t) / 6.0,
+            (-9.0 * t2 + 6.0 * t + 3.0) / 6.0,
+            0.5 * t2,
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square() -> Vec<Vec3> {
+        vec![Vec3::ZERO, Vec3::X, Vec3::new(1.0, 0.0, 1.0), Vec3::Z]
+    }
+
+    #[test]
+    fn curves_hit_their_end_points_and_measure_arc_length() {
+        let line = Spline::new(SplineKind::CatmullRom, vec![Vec3::ZERO, Vec3::X * 2.0, Vec3::X * 4.0]);
+        assert!((line.length() - 4.0).abs() < 1e-3);
+        assert!(line.position_at(3.0).abs_diff_eq(Vec3::X * 3.0, 1e-3), "constant speed on a line");
+        assert!(line.tangent_at(1.0).abs_diff_eq(Vec3::X, 1e-4));
+
+        for kind in [SplineKind::CatmullRom, SplineKind::BSpline] {
+            let s = Spline::new(kind, square());
+            assert!(s.position_at(0.0).abs_diff_eq(Vec3::ZERO, 1e-5), "{kind:?} start");
+            assert!(s.position_at(s.length()).abs_diff_eq(Vec3::Z, 1e-5), "{kind:?} end");
+        }
+        let bezier = Spline::new(SplineKind::Bezier, square());
+        assert_eq!(bezier.segment_count(), 1);
+        assert!(bezier.position_at(bezier.length()).abs_diff_eq(Vec3::Z, 1e-5));
+        // Leaves the first anchor towards its handle.
+        assert!(bezier.tangent_at(0.0).abs_diff_eq(Vec3::X, 1e-4));
+    }
+
+    #[test]
+    fn closed_splines_wrap_distance() {
+        let ring = Spline::new(SplineKind::CatmullRom, square()).with_closed(true);
+        assert_eq!(ring.segment_count(), 4);
+        let len = ring.length();
+        assert!(ring.position_at(len + 0.25).abs_diff_eq(ring.position_at(0.25), 1e-4));
+        assert!(ring.position_at(-0.25).abs_diff_eq(ring.position_at(len - 0.25), 1e-4));
+    }
+
+    #[test]
+    fn closest_point_and_frames() {
+        let s = Spline::new(SplineKind::CatmullRom, vec![Vec3::ZERO, Vec3::X * 5.0, Vec3::X * 10.0]);
+        let hit = s.closest_point(Vec3::new(4.0, 3.0, 0.0));
+        assert!((hit.distance - 4.0).abs() < 1e-2);
+        assert!(hit.position.abs_diff_eq(Vec3::X * 4.0, 1e-2));
+
+        let frame = s.frame_at(2.0, Vec3::Y);
+        assert!(frame.normal.abs_diff_eq(Vec3::Y, 1e-4));
+        assert!(frame.binormal.abs_diff_eq(Vec3::Z, 1e-4));
+        assert!((frame.rotation() * Vec3::NEG_Z).abs_diff_eq(Vec3::X, 1e-4));
+
+        let json = serde_json::to_string(&s).unwrap();
+        let back: Spline = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, s, "arc-length table is rebuilt on load");
+    }
+}
@@ -0,0 +1,404 @@
+//! Sampled poses and the operations used to blend and layer them.
+//!
+//! An [`AnimationSample`] holds the values a clip produces at one instant:
+//! the entity's own transform, one [`TransformSample`] per skeleton bone,
+//! morph-target weights and float curves.  Every property is optional — a
+//! clip only drives what it has tracks for — and the blending functions only
+//! mix properties that are present:
+//!
+//! - [`AnimationSample::average`] — weighted mix of several clips of a layer.
+//! - [`AnimationSample::overlay`] — an `Override` layer on top of the pose
+//!   below it.
+//! - [`AnimationSample::difference`] / [`AnimationSample::add`] — `Additive`
+//!   layers: the clip's offset from its first frame, added to the pose.
+//!
+//! Properties that no lower layer animates simply take the upper layer's
+//! value.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use glam::{Quat, Vec3};
+use serde::{Deserialize, Serialize};
+
+use super::track::Animatable;
+use crate::scene::skinning::{self, Skeleton};
+use crate::transform::Transform;
+
+// ────────────────────────────────────────────────────────────────────────────
+// BlendMode / BoneMask
+
+/// How an [`AnimationLayer`](super::AnimationLayer) combines with the layers
+/// below it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlendMode {
+    /// Replace the pose below, faded in by the layer weight.
+    #[default]
+    Override,
+    /// Add the clip's offset from its first frame on top of the pose below.
+    Additive,
+}
+
+/// The set of bones a layer is allowed to drive, by [`Bone::name`](skinning::Bone).
+///
+/// A masked layer only animates the listed bones; the entity's own
+/// transform, morph weights and curves are left to unmasked layers.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BoneMask {
+    pub bones: BTreeSet<String>,
+}
+
+impl BoneMask {
+    /// Mask made of the given bone names.
+    pub fn from_names<I, S>(names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        BoneMask { bones: names.into_iter().map(Into::into).collect() }
+    }
+
+    /// Mask made of `root` and every bone below it in `skeleton` (e.g. the
+    /// spine for an upper-body layer).  Empty if `root` is not a bone.
+    pub fn from_subtree(skeleton: &Skeleton, root: &str) -> Self {
+        let Some(root) = skeleton.bones.iter().position(|b| b.name == root) else {
+            return BoneMask::default();
+        };
+        let mut inside = vec![false; skeleton.bones.len()];
+        inside[root] = true;
+        // Parents may come after their children, so repeat until stable.
+        let mut grew = true;
+        while grew {
+            grew = false;
+            for (i, bone) in skeleton.bones.iter().enumerate() {
+                if !inside[i] && bone.parent_index.is_some_and(|p| inside.get(p) == Some(&true)) {
+                    inside[i] = true;
+                    grew = true;
+                }
+            }
+        }
+        BoneMask::from_names(
+            skeleton.bones.iter().zip(inside).filter(|(_, i)| *i).map(|(b, _)| b.name.clone()),
+        )
+    }
+
+    pub fn contains(&self, bone: &str) -> bool {
+        self.bones.contains(bone)
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Generic blending helpers
+
+/// Incremental weighted mean of `(value, weight)` pairs; `None` when no pair
+/// has a positive weight.  Exact for linear types, a close approximation of
+/// the weighted spherical mean for rotations.
+fn average<'a, T: Animatable + 'a>(items: impl IntoIterator<Item = (&'a T, f32)>) -> Option<T> {
+    let mut total = 0.0;
+    let mut acc: Option<T> = None;
+    for (value, weight) in items {
+        if weight <= 0.0 {
+            continue;
+        }
+        total += weight;
+        acc = Some(match acc {
+            None => value.clone(),
+            Some(a) => T::interpolate(&a, value, weight / total),
+        });
+    }
+    acc
+}
+
+/// Blend `top` over `base` by `weight`.
+fn overlay<T: Animatable>(base: &mut Option<T>, top: &Option<T>, weight: f32) {
+    if let Some(top) = top {
+        *base = Some(match base.take() {
+            Some(b) => T::interpolate(&b, top, weight),
+            None => top.clone(),
+        });
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// TransformSample
+
+/// Sampled translation / rotation / scale of one transform.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TransformSample {
+    pub translation: Option<Vec3>,
+    pub rotation: Option<Quat>,
+    pub scale: Option<Vec3>,
+}
+
+impl TransformSample {
+    pub fn is_empty(&self) -> bool {
+        self.translation.is_none() && self.rotation.is_none() && self.scale.is_none()
+    }
+
+    /// Overwrite the animated properties of `transform`.
+    pub fn apply_to(&self, transform: &mut Transform) {
+        if let Some(t) = self.translation {
+            transform.position = t;
+        }
+        if let Some(r) = self.rotation {
+            transform.rotation = r;
+        }
+        if let Some(s) = self.scale {
+            transform.scale = s;
+        }
+    }
+
+    /// Overwrite the animated properties of a bone's local transform.
+    pub fn apply_to_bone(&self, transform: &mut skinning::Transform) {
+        if let Some(t) = self.translation {
+            transform.position = t;
+        }
+        if let Some(r) = self.rotation {
+            transform.rotation = r;
+        }
+        if let Some(s) = self.scale {
+            transform.scale = s;
+        }
+    }
+
+    fn average<'a>(items: impl Iterator<Item = (&'a TransformSample, f32)> + Clone) -> Self {
+        TransformSample {
+            translation: average(items.clone().filter_map(|(s, w)| Some((s.translation.as_ref()?, w)))),
+            rotation: average(items.clone().filter_map(|(s, w)| Some((s.rotation.as_ref()?, w)))),
+            scale: average(items.filter_map(|(s, w)| Some((s.scale.as_ref()?, w)))),
+        }
+    }
+
+    fn overlay(&mut self, top: &TransformSample, weight: f32) {
+        overlay(&mut self.translation, &top.translation, weight);
+        overlay(&mut self.rotation, &top.rotation, weight);
+        overlay(&mut self.scale, &top.scale, weight);
+    }
+
+    /// Offset of `self` from `reference`: a translation delta, a rotation
+    /// delta (`self * reference⁻¹`) and a per-axis scale ratio.
+    fn difference(&self, reference: &TransformSample) -> Self {
+        TransformSample {
+            translation: self.translation.map(|t| t - reference.translation.unwrap_or(Vec3::ZERO)),
+            rotation: self.rotation.map(|r| r * reference.rotation.unwrap_or(Quat::IDENTITY).inverse()),
+            scale: self.scale.map(|s| s / reference.scale.unwrap_or(Vec3::ONE)),
+        }
+    }
+
+    /// Apply a `difference` scaled by `weight`.
+    fn add(&mut self, delta: &TransformSample, weight: f32) {
+        if let Some(d) = delta.translation {
+            self.translation = Some(self.translation.unwrap_or(Vec3::ZERO) + d * weight);
+        }
+        if let Some(d) = delta.rotation {
+            let base = self.rotation.unwrap_or(Quat::IDENTITY);
+            self.rotation = Some((Quat::IDENTITY.slerp(d, weight) * base).normalize());
+        }
+        if let Some(d) = delta.scale {
+            self.scale = Some(self.scale.unwrap_or(Vec3::ONE) * Vec3::ONE.lerp(d, weight));
+        }
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// AnimationSample
+
+/// Values of every animated property of a clip (or of a blended pose) at one
+/// point in time.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AnimationSample {
+    /// The entity's own transform.
+    pub transform: TransformSample,
+    /// Skeleton bones, by bone name.
+    pub bones: BTreeMap<String, TransformSample>,
+    /// Morph-target weights.
+    pub weights: Option<Vec<f32>>,
+    /// Named float channels.
+    pub curves: BTreeMap<String, f32>,
+}
+
+impl AnimationSample {
+    /// Overwrite the animated properties of `transform`.
+    pub fn apply_to(&self, transform: &mut Transform) {
+        self.transform.apply_to(transform);
+    }
+
+    /// Write the animated bones into `skeleton`'s local transforms.
+    pub fn apply_to_skeleton(&self, skeleton: &mut Skeleton) {
+        if self.bones.is_empty() {
+            return;
+        }
+        for bone in &mut skeleton.bones {
+            if let Some(sample) = self.bones.get(&bone.name) {
+                sample.apply_to_bone(&mut bone.local_transform);
+            }
+        }
+    }
+
+    /// Weighted mix of `samples`.  Weights are relative: each property is
+    /// averaged over the samples that have it.
+    pub fn average(samples: &[(AnimationSample, f32)]) -> Self {
+        let items = || samples.iter().map(|(s, w)| (s, *w));
+        let bone_names: BTreeSet<&String> = samples.iter().flat_map(|(s, _)| s.bones.keys()).collect();
+        let curve_names: BTreeSet<&String> = samples.iter().flat_map(|(s, _)| s.curves.keys()).collect();
+        AnimationSample {
+            transform: TransformSample::average(items().map(|(s, w)| (&s.transform, w))),
+            bones: bone_names
+                .into_iter()
+                .map(|name| {
+                    let bones = items().filter_map(|(s, w)| Some((s.bones.get(name)?, w)));
+                    (name.clone(), TransformSample::average(bones))
+                })
+                .collect(),
+            weights: average(items().filter_map(|(s, w)| Some((s.weights.as_ref()?, w)))),
+            curves: curve_names
+                .into_iter()
+                .filter_map(|name| {
+                    let value = average(items().filter_map(|(s, w)| Some((s.curves.get(name)?, w))))?;
+                    Some((name.clone(), value))
+                })
+                .collect(),
+        }
+    }
+
+    /// Blend `top` over this pose by `weight`, restricted to `mask` if given.
+    pub fn overlay(&mut self, top: &AnimationSample, weight: f32, mask: Option<&BoneMask>) {
+        for (name, sample) in &top.bones {
+            if mask.is_none_or(|m| m.contains(name)) {
+                self.bones.entry(name.clone()).or_default().overlay(sample, weight);
+            }
+        }
+        if mask.is_some() {
+            return;
+        }
+        self.transform.overlay(&top.transform, weight);
+        overlay(&mut self.weights, &top.weights, weight);
+        for (name, &value) in &top.curves {
+            let blended = match self.curves.get(name) {
+                Some(&base) => f32::interpolate(&base, &value, weight),
+                None => value,
+            };
+            self.curves.insert(name.clone(), blended);
+        }
+    }
+
+    /// Offset of this pose from `reference` (see [`BlendMode::Additive`]).
+    pub fn difference(&self, reference: &AnimationSample) -> Self {
+        let zero = TransformSample::default();
+        AnimationSample {
+            transform: self.transform.difference(&reference.transform),
+            bones: self
+                .bones
+                .iter()
+                .map(|(name, s)| (name.clone(), s.difference(reference.bones.get(name).unwrap_or(&zero))))
+                .collect(),
+            weights: self.weights.as_ref().map(|w| {
+                let base = reference.weights.as_deref().unwrap_or(&[]);
+                w.iter().enumerate().map(|(i, v)| v - base.get(i).copied().unwrap_or(0.0)).collect()
+            }),
+            curves: self
+                .curves
+                .iter()
+                .map(|(name, v)| (name.clone(), v - reference.curves.get(name).copied().unwrap_or(0.0)))
+                .collect(),
+        }
+    }
+
+    /// Add a `difference` scaled by `weight`, restricted to `mask` if given.
+    pub fn add(&mut self, delta: &AnimationSample, weight: f32, mask: Option<&BoneMask>) {
+        for (name, d) in &delta.bones {
+            if mask.is_none_or(|m| m.contains(name)) {
+                self.bones.entry(name.clone()).or_default().add(d, weight);
+            }
+        }
+        if mask.is_some() {
+            return;
+        }
+        self.transform.add(&delta.transform, weight);
+        if let Some(d) = &delta.weights {
+            let w = self.weights.get_or_insert_with(Vec::new);
+            if w.len() < d.len() {
+                w.resize(d.len(), 0.0);
+            }
+            for (w, d) in w.iter_mut().zip(d) {
+                *w += d * weight;
+            }
+        }
+        for (name, d) in &delta.curves {
+            *self.curves.entry(name.clone()).or_insert(0.0) += d * weight;
+        }
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::skinning::Bone;
+    use glam::Mat4;
+
+    fn pose(x: f32) -> AnimationSample {
+        let mut s = AnimationSample::default();
+        s.transform.translation = Some(Vec3::new(x, 0.0, 0.0));
+        s.bones.insert("spine".into(), TransformSample { translation: Some(Vec3::splat(x)), ..Default::default() });
+        s.curves.insert("c".into(), x);
+        s
+    }
+
+    #[test]
+    fn average_normalises_weights() {
+        let mixed = AnimationSample::average(&[(pose(0.0), 1.0), (pose(4.0), 3.0)]);
+        assert_eq!(mixed.transform.translation, Some(Vec3::new(3.0, 0.0, 0.0)));
+        assert_eq!(mixed.curves["c"], 3.0);
+        assert_eq!(mixed.bones["spine"].translation, Some(Vec3::splat(3.0)));
+        assert_eq!(mixed.transform.rotation, None);
+    }
+
+    #[test]
+    fn masked_overlay_only_touches_listed_bones() {
+        let mut base = pose(0.0);
+        base.bones.insert("hips".into(), TransformSample { translation: Some(Vec3::ZERO), ..Default::default() });
+        let mut top = pose(2.0);
+        top.bones.insert("hips".into(), TransformSample { translation: Some(Vec3::ONE), ..Default::default() });
+
+        base.overlay(&top, 0.5, Some(&BoneMask::from_names(["spine"])));
+        assert_eq!(base.bones["spine"].translation, Some(Vec3::ONE));
+        assert_eq!(base.bones["hips"].translation, Some(Vec3::ZERO));
+        assert_eq!(base.transform.translation, Some(Vec3::ZERO));
+    }
+
+    #[test]
+    fn additive_applies_offset_from_reference() {
+        let reference = pose(1.0);
+        let mut current = pose(3.0);
+        current.transform.rotation = Some(Quat::from_rotation_z(0.5));
+        let delta = current.difference(&reference);
+
+        let mut base = pose(10.0);
+        base.transform.rotation = Some(Quat::from_rotation_z(0.25));
+        base.add(&delta, 0.5, None);
+        assert_eq!(base.transform.translation, Some(Vec3::new(11.0, 0.0, 0.0)));
+        assert!(base.transform.rotation.unwrap().abs_diff_eq(Quat::from_rotation_z(0.5), 1e-5));
+        assert_eq!(base.curves["c"], 11.0);
+    }
+
+    #[test]
+    fn subtree_mask_follows_parent_links() {
+        let bone = |name: &str, parent| Bone {
+            name: name.into(),
+            parent_index: parent,
+            inverse_bind_matrix: Mat4::IDENTITY,
+            local_transform: Default::default(),
+        };
+        let skeleton = Skeleton::new(vec![
+            bone("hips", None),
+            bone("hand", Some(3)),
+            bone("leg", Some(0)),
+            bone("spine", Some(0)),
+        ]);
+        let mask = BoneMask::from_subtree(&skeleton, "spine");
+        assert_eq!(mask, BoneMask::from_names(["spine", "hand"]));
+        assert!(BoneMask::from_subtree(&skeleton, "tail").bones.is_empty());
+    }
+}
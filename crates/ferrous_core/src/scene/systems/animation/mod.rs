@@ -0,0 +1,339 @@
+//! Animation keyframe tracks, clip, player and state-machine components and systems.
+
+#![cfg(feature = "ecs")]
+
+use std::collections::BTreeMap;
+
+use ferrous_ecs::prelude::*;
+use ferrous_ecs::system::System;
+use glam::{Quat, Vec3};
+use serde::{Deserialize, Serialize};
+
+use crate::scene::{MorphWeights, Skeleton};
+use crate::time::{Time, TimeClock};
+use crate::transform::Transform;
+
+pub mod blend;
+pub mod player;
+pub mod state_machine;
+pub mod track;
+
+pub use blend::{AnimationSample, BlendMode, BoneMask, TransformSample};
+pub use player::{AnimationLayer, AnimationPlayer, PlayingClip};
+pub use state_machine::{
+    AnimationController, AnimationControllerSystem, AnimationState, AnimationStateMachine, Condition,
+    Motion, ParamValue, StateBlend, Transition,
+};
+pub use track::{Animatable, Interpolation, Keyframe, Track};
+
+// ────────────────────────────────────────────────────────────────────────────
+// Animation components
+
+/// Translation / rotation / scale tracks of one animated transform.
+///
+/// Properties without a track keep whatever value the transform already has.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TransformTracks {
+    #[serde(default)]
+    pub translation: Option<Track<Vec3>>,
+    #[serde(default)]
+    pub rotation: Option<Track<Quat>>,
+    #[serde(default)]
+    pub scale: Option<Track<Vec3>>,
+}
+
+impl TransformTracks {
+    /// Time of the last key over all three tracks.
+    pub fn duration(&self) -> f32 {
+        let t = self.translation.as_ref().map_or(0.0, Track::duration);
+        let r = self.rotation.as_ref().map_or(0.0, Track::duration);
+        let s = self.scale.as_ref().map_or(0.0, Track::duration);
+        t.max(r).max(s)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.translation.is_none() && self.rotation.is_none() && self.scale.is_none()
+    }
+
+    /// Sample the three tracks at clip-local time `t`.
+    pub fn sample(&self, t: f32) -> TransformSample {
+        TransformSample {
+            translation: self.translation.as_ref().and_then(|k| k.sample(t)),
+            rotation: self.rotation.as_ref().and_then(|k| k.sample(t)),
+            scale: self.scale.as_ref().and_then(|k| k.sample(t)),
+        }
+    }
+}
+
+/// A named event on a clip's timeline (footstep, particle burst, …).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClipEvent {
+    /// Time in seconds from the start of the clip.
+    pub time: f32,
+    pub name: String,
+}
+
+/// Sent by `AnimationSystem` into `Events<AnimationEvent>` each time
+/// playback crosses a [`ClipEvent`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationEvent {
+    /// Entity whose `AnimationPlayer` played the clip.
+    pub entity: ferrous_ecs::entity::Entity,
+    /// Name of the clip in the player's library.
+    pub clip: String,
+    /// Name of the event.
+    pub name: String,
+}
+
+/// Keyframed animation of one entity: TRS tracks, per-bone TRS tracks for
+/// its [`Skeleton`](crate::scene::Skeleton), morph-target weights and
+/// arbitrary named float curves.
+///
+/// ```rust,ignore
+/// let clip = AnimationClip::new(2.0)
+///     .with_translation(Track::linear(vec![Keyframe::new(0.0, Vec3::ZERO), Keyframe::new(2.0, Vec3::X)]))
+///     .with_rotation(Track::step(rotation_keys))
+///     .with_curve("glow", Track::linear(glow_keys))
+///     .with_looping(true);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnimationClip {
+    /// Local-space transform tracks.
+    #[serde(default)]
+    pub transform: TransformTracks,
+    /// Skeleton bone tracks, by bone name.
+    #[serde(default)]
+    pub bones: BTreeMap<String, TransformTracks>,
+    /// Morph-target weights, applied to [`MorphWeights`].
+    #[serde(default)]
+    pub weights: Option<Track<Vec<f32>>>,
+    /// Named float channels, exposed through [`AnimationPlayer::curve`].
+    #[serde(default)]
+    pub curves: BTreeMap<String, Track<f32>>,
+    /// Timeline events, sorted by time.
+    #[serde(default)]
+    pub events: Vec<ClipEvent>,
+    /// Total duration in seconds; loops when `looping` is true.
+    pub duration: f32,
+    /// Whether the clip loops.
+    pub looping: bool,
+}
+
+impl AnimationClip {
+    /// An empty, non-looping clip lasting `duration` seconds.
+    pub fn new(duration: f32) -> Self {
+        AnimationClip { duration, ..Default::default() }
+    }
+
+    /// Set the translation track, extending the duration to cover it.
+    pub fn with_translation(mut self, track: Track<Vec3>) -> Self {
+        self.duration = self.duration.max(track.duration());
+        self.transform.translation = Some(track);
+        self
+    }
+
+    /// Set the rotation track, extending the duration to cover it.
+    pub fn with_rotation(mut self, track: Track<Quat>) -> Self {
+        self.duration = self.duration.max(track.duration());
+        self.transform.rotation = Some(track);
+        self
+    }
+
+    /// Set the scale track, extending the duration to cover it.
+    pub fn with_scale(mut self, track: Track<Vec3>) -> Self {
+        self.duration = self.duration.max(track.duration());
+        self.transform.scale = Some(track);
+        self
+    }
+
+    /// Add the tracks of skeleton bone `name`, extending the duration to
+    /// cover them.
+    pub fn with_bone(mut self, name: impl Into<String>, tracks: TransformTracks) -> Self {
+        self.duration = self.duration.max(tracks.duration());
+        self.bones.insert(name.into(), tracks);
+        self
+    }
+
+    /// Set the morph-target weights track, extending the duration to cover it.
+    pub fn with_weights(mut self, track: Track<Vec<f32>>) -> Self {
+        self.duration = self.duration.max(track.duration());
+        self.weights = Some(track);
+        self
+    }
+
+    /// Add a named float curve, extending the duration to cover it.
+    pub fn with_curve(mut self, name: impl Into<String>, track: Track<f32>) -> Self {
+        self.duration = self.duration.max(track.duration());
+        self.curves.insert(name.into(), track);
+        self
+    }
+
+    /// Add a named event at `time` seconds.
+    pub fn with_event(mut self, time: f32, name: impl Into<String>) -> Self {
+        let at = self.events.partition_point(|e| e.time <= time);
+        self.events.insert(at, ClipEvent { time, name: name.into() });
+        self
+    }
+
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Map a playback time onto the clip: wrapped when looping, clamped
+    /// otherwise.
+    pub fn local_time(&self, t: f32) -> f32 {
+        if self.looping && self.duration > 0.0 {
+            t.rem_euclid(self.duration)
+        } else {
+            t.clamp(0.0, self.duration.max(0.0))
+        }
+    }
+
+    /// Events crossed when playback moves from time `from` to time `to`, in
+    /// the order they are reached.
+    ///
+    /// Both times are unwrapped playback times (as in [`PlayingClip::time`]),
+    /// so a looping clip reports each event once per cycle crossed.  The
+    /// interval includes its start and excludes its end (`[from, to)`, or
+    /// `(to, from]` when playing backwards), so consecutive updates never
+    /// report an event twice and an event at the time a clip starts or is
+    /// seeked to is still reported.
+    pub fn events_between(&self, from: f32, to: f32) -> Vec<&ClipEvent> {
+        /// Cap on the cycles scanned by one call, in case of a huge step.
+        const MAX_CYCLES: i64 = 64;
+
+        let forward = from <= to;
+        let (lo, hi) = if forward { (from, to) } else { (to, from) };
+        let crossed = |t: f32| if forward { t >= lo && t < hi } else { t > lo && t <= hi };
+        let mut events = Vec::new();
+        if self.looping && self.duration > 0.0 {
+            let first = (lo / self.duration).floor() as i64 - 1;
+            let last = ((hi / self.duration).floor() as i64).min(first + MAX_CYCLES);
+            for cycle in first..=last {
+                let offset = cycle as f32 * self.duration;
+                events.extend(self.events.iter().filter(|e| crossed(e.time + offset)));
+            }
+        } else {
+            events.extend(self.events.iter().filter(|e| crossed(e.time)));
+        }
+        if !forward {
+            events.reverse();
+        }
+        events
+    }
+
+    /// Sample every track at playback time `t`.
+    pub fn sample(&self, t: f32) -> AnimationSample {
+        let t = self.local_time(t);
+        AnimationSample {
+            transform: self.transform.sample(t),
+            bones: self
+                .bones
+                .iter()
+                .map(|(name, tracks)| (name.clone(), tracks.sample(t)))
+                .collect(),
+            weights: self.weights.as_ref().and_then(|k| k.sample(t)),
+            curves: self
+                .curves
+                .iter()
+                .filter_map(|(name, k)| Some((name.clone(), k.sample(t)?)))
+                .collect(),
+        }
+    }
+
+    /// Sample the position at `t` seconds.
+    pub fn sample_position(&self, t: f32) -> Option<Vec3> {
+        self.transform.translation.as_ref()?.sample(self.local_time(t))
+    }
+}
+
+impl Component for AnimationPlayer {}
+
+// ────────────────────────────────────────────────────────────────────────────
+// AnimationSystem
+
+/// Advances `AnimationPlayer` clips and fades, blends their layers and
+/// applies the pose to `Transform`, `Skeleton` bones and `MorphWeights`.
+///
+/// The frame delta comes from `Time`, or from a `TimeClock` when no `Time`
+/// resource exists.
+///
+/// Timeline events crossed during the update are sent as [`AnimationEvent`]s
+/// into the `Events<AnimationEvent>` resource (inserted on first use; rotate
+/// it each frame with `EventUpdateSystem<AnimationEvent>`).
+///
+/// Register at `Stage::Update`, before `SkinningSystem` so joint matrices are
+/// rebuilt from the new bone transforms in the same frame.
+pub struct AnimationSystem;
+
+impl System for AnimationSystem {
+    fn name(&self) -> &'static str { "AnimationSystem" }
+
+    fn run(
+        &mut self,
+        world: &mut ferrous_ecs::world::World,
+        resources: &mut ResourceMap,
+    ) {
+        let dt = if let Some(time) = resources.get::<Time>() {
+            time.delta
+        } else {
+            resources.get::<TimeClock>().map(|c| c.at_tick().delta).unwrap_or(0.0)
+        };
+
+        let entities: Vec<ferrous_ecs::entity::Entity> = world
+            .query::<AnimationPlayer>()
+            .map(|(e, _)| e)
+            .collect();
+
+        let mut fired = Vec::new();
+        for entity in entities {
+            let sample = {
+                let player = match world.get_mut::<AnimationPlayer>(entity) {
+                    Some(p) => p,
+                    None => continue,
+                };
+                if !player.playing { continue; }
+                fired.extend(
+                    player
+                        .advance(dt)
+                        .into_iter()
+                        .map(|(clip, name)| AnimationEvent { entity, clip, name }),
+                );
+                let sample = player.evaluate();
+                player.curves.clone_from(&sample.curves);
+                sample
+            };
+
+            // Mutable access marks the component changed, which would make
+            // `TransformSystem` recompute the subtree; borrow only when a
+            // channel actually drives it.
+            if !sample.transform.is_empty() {
+                if let Some(t) = world.get_mut::<Transform>(entity) {
+                    sample.apply_to(t);
+                }
+            }
+            if !sample.bones.is_empty() {
+                if let Some(skeleton) = world.get_mut::<Skeleton>(entity) {
+                    sample.apply_to_skeleton(skeleton);
+                }
+            }
+            if let Some(weights) = sample.weights {
+                match world.get_mut::<MorphWeights>(entity) {
+                    Some(w) => w.0 = weights,
+                    None => world.insert(entity, MorphWeights(weights)),
+                }
+            }
+        }
+
+        if !fired.is_empty() {
+            if resources.get::<Events<AnimationEvent>>().is_none() {
+                resources.insert(Events::<AnimationEvent>::new());
+            }
+            let events = resources.get_mut::<Events<AnimationEvent>>().unwrap();
+            for event in fired {
+                events.send(event);
+            }
+        }
+    }
+}
@@ -0,0 +1,363 @@
+//! `AnimationPlayer` — a library of named clips played on blended layers.
+//!
+//! Each [`AnimationLayer`] plays any number of clips at once, mixed by their
+//! relative weights (a crossfade is two clips whose weights move in opposite
+//! directions).  Layers are then stacked bottom to top: `Override` layers
+//! replace the pose below by their weight, `Additive` layers add their clips'
+//! offset from the first frame.  A [`BoneMask`] restricts a layer to part of
+//! the skeleton, e.g. an upper-body action over locomotion:
+//!
+//! ```rust,ignore
+//! let mut player = AnimationPlayer::default()
+//!     .with_clip("idle", idle)
+//!     .with_clip("walk", walk)
+//!     .with_clip("wave", wave);
+//! player.play("idle");
+//! let upper = player.add_layer(
+//!     AnimationLayer::new("upper").with_mask(BoneMask::from_subtree(&skeleton, "spine")),
+//! );
+//! // later…
+//! player.crossfade("walk", 0.3);
+//! player.play_on(upper, "wave");
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::blend::{AnimationSample, BlendMode, BoneMask};
+use super::AnimationClip;
+
+// ────────────────────────────────────────────────────────────────────────────
+// PlayingClip
+
+/// A clip playing on a layer, with its own clock and blend weight.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayingClip {
+    /// Name of the clip in the player's library.
+    pub clip: String,
+    /// Playback time in seconds (unwrapped; the clip wraps or clamps it).
+    pub time: f32,
+    /// Playback rate multiplier.
+    pub speed: f32,
+    /// Relative weight within the layer.
+    pub weight: f32,
+    /// Weight the clip is fading towards.
+    pub target_weight: f32,
+    /// Weight change per second while fading (0 when not fading).
+    pub fade_rate: f32,
+}
+
+impl PlayingClip {
+    pub fn new(clip: impl Into<String>) -> Self {
+        PlayingClip {
+            clip: clip.into(),
+            time: 0.0,
+            speed: 1.0,
+            weight: 1.0,
+            target_weight: 1.0,
+            fade_rate: 0.0,
+        }
+    }
+
+    /// Start moving the weight to `target` over `duration` seconds
+    /// (immediately when `duration <= 0`).
+    pub fn fade_to(&mut self, target: f32, duration: f32) {
+        self.target_weight = target;
+        if duration > 0.0 {
+            self.fade_rate = (target - self.weight).abs() / duration;
+        } else {
+            self.weight = target;
+            self.fade_rate = 0.0;
+        }
+    }
+
+    /// `true` once the clip has faded out completely.
+    pub fn is_faded_out(&self) -> bool {
+        self.target_weight <= 0.0 && self.weight <= 0.0
+    }
+
+    fn advance(&mut self, dt: f32) {
+        self.time += dt * self.speed;
+        if self.fade_rate > 0.0 {
+            let step = self.fade_rate * dt.abs();
+            self.weight = if self.weight < self.target_weight {
+                (self.weight + step).min(self.target_weight)
+            } else {
+                (self.weight - step).max(self.target_weight)
+            };
+            if self.weight == self.target_weight {
+                self.fade_rate = 0.0;
+            }
+        }
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// AnimationLayer
+
+/// One layer of an [`AnimationPlayer`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnimationLayer {
+    pub name: String,
+    /// Influence of the layer on the pose below, `0..=1`.
+    pub weight: f32,
+    pub mode: BlendMode,
+    /// Bones the layer may drive; `None` drives everything.
+    pub mask: Option<BoneMask>,
+    /// Clips currently contributing to the layer.
+    pub clips: Vec<PlayingClip>,
+}
+
+impl AnimationLayer {
+    pub fn new(name: impl Into<String>) -> Self {
+        AnimationLayer {
+            name: name.into(),
+            weight: 1.0,
+            mode: BlendMode::Override,
+            mask: None,
+            clips: Vec::new(),
+        }
+    }
+
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    pub fn with_mask(mut self, mask: BoneMask) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    /// Make this an [`BlendMode::Additive`] layer.
+    pub fn additive(mut self) -> Self {
+        self.mode = BlendMode::Additive;
+        self
+    }
+
+    /// The playing entry for `clip`, if any.
+    pub fn clip(&self, clip: &str) -> Option<&PlayingClip> {
+        self.clips.iter().find(|c| c.clip == clip)
+    }
+
+    pub fn clip_mut(&mut self, clip: &str) -> Option<&mut PlayingClip> {
+        self.clips.iter_mut().find(|c| c.clip == clip)
+    }
+
+    /// Play `clip` alone, from the start, at full weight.
+    pub fn play(&mut self, clip: &str) {
+        self.clips.clear();
+        self.clips.push(PlayingClip::new(clip));
+    }
+
+    /// Fade `clip` in and every other clip out over `duration` seconds.
+    /// A clip that is already playing keeps its time.
+    pub fn crossfade(&mut self, clip: &str, duration: f32) {
+        for other in self.clips.iter_mut().filter(|c| c.clip != clip) {
+            other.fade_to(0.0, duration);
+        }
+        if self.clip(clip).is_none() {
+            let mut entry = PlayingClip::new(clip);
+            entry.weight = 0.0;
+            self.clips.push(entry);
+        }
+        self.clip_mut(clip).unwrap().fade_to(1.0, duration);
+        self.clips.retain(|c| !c.is_faded_out());
+    }
+
+    /// Set the relative weight of `clip`, starting it if needed (for blend
+    /// spaces that keep several clips running in sync).
+    pub fn set_clip_weight(&mut self, clip: &str, weight: f32) {
+        match self.clip_mut(clip) {
+            Some(entry) => entry.fade_to(weight, 0.0),
+            None => {
+                let mut entry = PlayingClip::new(clip);
+                entry.fade_to(weight, 0.0);
+                self.clips.push(entry);
+            }
+        }
+    }
+
+    /// Stop every clip of the layer.
+    pub fn stop(&mut self) {
+        self.clips.clear();
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// AnimationPlayer
+
+/// Animation player component — plays clips from its library on layers.
+///
+/// Layer 0 (`"base"`) always exists; [`play`](Self::play) and
+/// [`crossfade`](Self::crossfade) act on it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnimationPlayer {
+    /// Clip library, by name.
+    pub clips: BTreeMap<String, AnimationClip>,
+    /// Layers, bottom to top.
+    pub layers: Vec<AnimationLayer>,
+    pub playing: bool,
+    /// Global playback rate multiplier.
+    pub speed: f32,
+    /// Values of the float curves at the last update.
+    #[serde(skip)]
+    pub curves: BTreeMap<String, f32>,
+}
+
+impl Default for AnimationPlayer {
+    fn default() -> Self {
+        AnimationPlayer {
+            clips: BTreeMap::new(),
+            layers: vec![AnimationLayer::new("base")],
+            playing: true,
+            speed: 1.0,
+            curves: BTreeMap::new(),
+        }
+    }
+}
+
+impl AnimationPlayer {
+    /// Name under which [`new`](Self::new) stores its clip.
+    pub const DEFAULT_CLIP: &'static str = "default";
+
+    /// A player with a single clip, already playing on the base layer.
+    pub fn new(clip: AnimationClip) -> Self {
+        let mut player = Self::default().with_clip(Self::DEFAULT_CLIP, clip);
+        player.play(Self::DEFAULT_CLIP);
+        player
+    }
+
+    /// Add `clip` to the library under `name`.
+    pub fn with_clip(mut self, name: impl Into<String>, clip: AnimationClip) -> Self {
+        self.add_clip(name, clip);
+        self
+    }
+
+    pub fn add_clip(&mut self, name: impl Into<String>, clip: AnimationClip) {
+        self.clips.insert(name.into(), clip);
+    }
+
+    /// Push a layer on top and return its index.
+    pub fn add_layer(&mut self, layer: AnimationLayer) -> usize {
+        self.layers.push(layer);
+        self.layers.len() - 1
+    }
+
+    pub fn layer(&self, index: usize) -> Option<&AnimationLayer> {
+        self.layers.get(index)
+    }
+
+    pub fn layer_mut(&mut self, index: usize) -> Option<&mut AnimationLayer> {
+        self.layers.get_mut(index)
+    }
+
+    /// Index of the layer called `name`.
+    pub fn layer_index(&self, name: &str) -> Option<usize> {
+        self.layers.iter().position(|l| l.name == name)
+    }
+
+    /// Play `clip` alone on the base layer.
+    pub fn play(&mut self, clip: &str) {
+        self.play_on(0, clip);
+    }
+
+    /// Play `clip` alone on layer `layer`.  Unknown layers are ignored.
+    pub fn play_on(&mut self, layer: usize, clip: &str) {
+        if let Some(layer) = self.layers.get_mut(layer) {
+            layer.play(clip);
+        }
+    }
+
+    /// Crossfade the base layer to `clip` over `duration` seconds.
+    pub fn crossfade(&mut self, clip: &str, duration: f32) {
+        self.crossfade_on(0, clip, duration);
+    }
+
+    /// Crossfade layer `layer` to `clip` over `duration` seconds.
+    pub fn crossfade_on(&mut self, layer: usize, clip: &str, duration: f32) {
+        if let Some(layer) = self.layers.get_mut(layer) {
+            layer.crossfade(clip, duration);
+        }
+    }
+
+    pub fn set_playing(&mut self, playing: bool) { self.playing = playing; }
+
+    /// Move every clip of the base layer to time `t`.  No-op without layers.
+    pub fn seek(&mut self, t: f32) {
+        if let Some(layer) = self.layers.first_mut() {
+            for clip in &mut layer.clips {
+                clip.time = t.max(0.0);
+            }
+        }
+    }
+
+    /// Value of the float curve `name` at the last update.
+    pub fn curve(&self, name: &str) -> Option<f32> {
+        self.curves.get(name).copied()
+    }
+
+    /// Advance every clip clock and fade by `dt` seconds (scaled by
+    /// [`speed`](Self::speed)), dropping clips that have faded out.
+    ///
+    /// Returns the `(clip, event)` names of the timeline events crossed, in
+    /// layer order.  Clips fading in or out still report events; clips
+    /// parked at zero weight and layers at zero weight do not.
+    pub fn advance(&mut self, dt: f32) -> Vec<(String, String)> {
+        let dt = dt * self.speed;
+        let mut fired = Vec::new();
+        for layer in &mut self.layers {
+            for playing in &mut layer.clips {
+                let from = playing.time;
+                playing.advance(dt);
+                let audible = playing.weight > 0.0 || playing.target_weight > 0.0;
+                if layer.weight <= 0.0 || !audible {
+                    continue;
+                }
+                if let Some(clip) = self.clips.get(&playing.clip) {
+                    fired.extend(
+                        clip.events_between(from, playing.time)
+                            .into_iter()
+                            .map(|e| (playing.clip.clone(), e.name.clone())),
+                    );
+                }
+            }
+            layer.clips.retain(|c| !c.is_faded_out());
+        }
+        fired
+    }
+
+    /// Blend every layer into the current pose.
+    pub fn evaluate(&self) -> AnimationSample {
+        let mut pose = AnimationSample::default();
+        for layer in &self.layers {
+            if layer.weight <= 0.0 {
+                continue;
+            }
+            let samples: Vec<(AnimationSample, f32)> = layer
+                .clips
+                .iter()
+                .filter_map(|playing| {
+                    let clip = self.clips.get(&playing.clip)?;
+                    let sample = clip.sample(playing.time);
+                    let sample = match layer.mode {
+                        BlendMode::Override => sample,
+                        BlendMode::Additive => sample.difference(&clip.sample(0.0)),
+                    };
+                    Some((sample, playing.weight))
+                })
+                .collect();
+            if samples.is_empty() {
+                continue;
+            }
+            let layer_pose = AnimationSample::average(&samples);
+            let weight = layer.weight.min(1.0);
+            match layer.mode {
+                BlendMode::Override => pose.overlay(&layer_pose, weight, layer.mask.as_ref()),
+                BlendMode::Additive => pose.add(&layer_pose, weight, layer.mask.as_ref()),
+            }
+        }
+        pose
+    }
+}
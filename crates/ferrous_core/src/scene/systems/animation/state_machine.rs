@@ -0,0 +1,624 @@
+//! Data-driven animation state machines.
+//!
+//! An [`AnimationStateMachine`] is a serialisable graph authored once (in code
+//! or in the editor) and shared by every entity using it: named states that
+//! play a clip or a 1D / 2D blend space, and transitions between them guarded
+//! by conditions on float, bool and trigger parameters, an optional exit time
+//! and a crossfade duration.
+//!
+//! The [`AnimationController`] component runs a machine against the entity's
+//! [`AnimationPlayer`]: gameplay code sets parameters with
+//! [`set_param`](AnimationController::set_param) /
+//! [`trigger`](AnimationController::trigger) and
+//! [`AnimationControllerSystem`] picks the state and writes clip weights into
+//! one player layer, leaving clip clocks and pose evaluation to
+//! [`AnimationSystem`](super::AnimationSystem).
+//!
+//! ```rust,ignore
+//! let machine = AnimationStateMachine::new("locomotion")
+//!     .with_param("speed", 0.0)
+//!     .with_param("jump", ParamValue::Trigger(false))
+//!     .with_state(AnimationState::blend_1d("locomotion", "speed", [(0.0, "idle"), (2.0, "walk"), (6.0, "run")]))
+//!     .with_state(AnimationState::clip("jump", "jump"))
+//!     .with_transition(Transition::new("locomotion", "jump").when(Condition::Trigger("jump".into())).with_duration(0.1))
+//!     .with_transition(Transition::new("jump", "locomotion").with_exit_time(0.9).with_duration(0.2));
+//!
+//! world.insert(entity, AnimationController::new(machine));
+//! // every frame:
+//! controller.set_param("speed", velocity.length());
+//! ```
+
+use std::collections::BTreeMap;
+
+use ferrous_ecs::prelude::*;
+use ferrous_ecs::system::System;
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+use super::AnimationPlayer;
+use crate::time::{Time, TimeClock};
+
+// ────────────────────────────────────────────────────────────────────────────
+// Parameters and conditions
+
+/// Value of a state-machine parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ParamValue {
+    Float(f32),
+    Bool(bool),
+    /// A bool that is reset once a transition consumes it.
+    Trigger(bool),
+}
+
+impl ParamValue {
+    /// The value as a float (`1.0` / `0.0` for bools and triggers).
+    pub fn as_float(self) -> f32 {
+        match self {
+            ParamValue::Float(v) => v,
+            ParamValue::Bool(b) | ParamValue::Trigger(b) => b as u8 as f32,
+        }
+    }
+
+    /// The value as a bool (non-zero for floats).
+    pub fn as_bool(self) -> bool {
+        match self {
+            ParamValue::Float(v) => v != 0.0,
+            ParamValue::Bool(b) | ParamValue::Trigger(b) => b,
+        }
+    }
+}
+
+impl From<f32> for ParamValue {
+    fn from(v: f32) -> Self {
+        ParamValue::Float(v)
+    }
+}
+
+impl From<bool> for ParamValue {
+    fn from(v: bool) -> Self {
+        ParamValue::Bool(v)
+    }
+}
+
+/// A test on one parameter.  Missing parameters read as `0.0` / `false`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Condition {
+    /// Float parameter strictly greater than `value`.
+    Greater { param: String, value: f32 },
+    /// Float parameter strictly less than `value`.
+    Less { param: String, value: f32 },
+    /// Bool parameter is true.
+    If(String),
+    /// Bool parameter is false.
+    IfNot(String),
+    /// Trigger parameter is set; consumed when the transition fires.
+    Trigger(String),
+}
+
+impl Condition {
+    fn holds(&self, params: &BTreeMap<String, ParamValue>) -> bool {
+        let get = |name: &String| params.get(name).copied().unwrap_or(ParamValue::Bool(false));
+        match self {
+            Condition::Greater { param, value } => get(param).as_float() > *value,
+            Condition::Less { param, value } => get(param).as_float() < *value,
+            Condition::If(param) | Condition::Trigger(param) => get(param).as_bool(),
+            Condition::IfNot(param) => !get(param).as_bool(),
+        }
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// States
+
+/// What a state plays.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Motion {
+    /// A single clip from the player's library.
+    Clip(String),
+    /// Clips placed on a line, blended by one float parameter.
+    BlendSpace1D { param: String, points: Vec<(f32, String)> },
+    /// Clips placed on a plane, blended by two float parameters
+    /// (inverse-distance weighting).
+    BlendSpace2D { x: String, y: String, points: Vec<(Vec2, String)> },
+}
+
+impl Motion {
+    /// Names of every clip the motion may play.
+    pub fn clips(&self) -> Vec<&str> {
+        match self {
+            Motion::Clip(clip) => vec![clip.as_str()],
+            Motion::BlendSpace1D { points, .. } => points.iter().map(|(_, c)| c.as_str()).collect(),
+            Motion::BlendSpace2D { points, .. } => points.iter().map(|(_, c)| c.as_str()).collect(),
+        }
+    }
+
+    /// Relative weight of each clip for the current parameters; clips with
+    /// no influence are omitted.
+    pub fn weights(&self, params: &BTreeMap<String, ParamValue>) -> Vec<(&str, f32)> {
+        let float = |name: &String| params.get(name).map_or(0.0, |v| v.as_float());
+        match self {
+            Motion::Clip(clip) => vec![(clip.as_str(), 1.0)],
+            Motion::BlendSpace1D { param, points } => {
+                let mut sorted: Vec<&(f32, String)> = points.iter().collect();
+                sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+                let x = float(param);
+                let next = sorted.partition_point(|(p, _)| *p <= x);
+                match (next.checked_sub(1).map(|i| sorted[i]), sorted.get(next)) {
+                    (Some((_, a)), None) => vec![(a.as_str(), 1.0)],
+                    (None, Some((_, b))) => vec![(b.as_str(), 1.0)],
+                    (Some((pa, a)), Some((pb, b))) => {
+                        let t = (x - pa) / (pb - pa);
+                        vec![(a.as_str(), 1.0 - t), (b.as_str(), t)]
+                    }
+                    (None, None) => Vec::new(),
+                }
+            }
+            Motion::BlendSpace2D { x, y, points } => {
+                let at = Vec2::new(float(x), float(y));
+                if let Some((_, clip)) = points.iter().find(|(p, _)| p.distance_squared(at) < 1e-8) {
+                    return vec![(clip.as_str(), 1.0)];
+                }
+                points.iter().map(|(p, clip)| (clip.as_str(), 1.0 / p.distance_squared(at))).collect()
+            }
+        }
+    }
+}
+
+/// A node of the state machine.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnimationState {
+    pub name: String,
+    pub motion: Motion,
+    /// Playback rate of the state's clips.
+    #[serde(default = "default_speed")]
+    pub speed: f32,
+}
+
+fn default_speed() -> f32 {
+    1.0
+}
+
+impl AnimationState {
+    pub fn new(name: impl Into<String>, motion: Motion) -> Self {
+        AnimationState { name: name.into(), motion, speed: 1.0 }
+    }
+
+    /// A state playing the single clip `clip`.
+    pub fn clip(name: impl Into<String>, clip: impl Into<String>) -> Self {
+        Self::new(name, Motion::Clip(clip.into()))
+    }
+
+    /// A state blending `(position, clip)` points along `param`.
+    pub fn blend_1d<S: Into<String>>(
+        name: impl Into<String>,
+        param: impl Into<String>,
+        points: impl IntoIterator<Item = (f32, S)>,
+    ) -> Self {
+        let points = points.into_iter().map(|(p, c)| (p, c.into())).collect();
+        Self::new(name, Motion::BlendSpace1D { param: param.into(), points })
+    }
+
+    /// A state blending `(position, clip)` points over the `(x, y)` params.
+    pub fn blend_2d<S: Into<String>>(
+        name: impl Into<String>,
+        x: impl Into<String>,
+        y: impl Into<String>,
+        points: impl IntoIterator<Item = (Vec2, S)>,
+    ) -> Self {
+        let points = points.into_iter().map(|(p, c)| (p, c.into())).collect();
+        Self::new(name, Motion::BlendSpace2D { x: x.into(), y: y.into(), points })
+    }
+
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Transitions
+
+/// An edge of the state machine.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Transition {
+    /// Source state; `None` means "from any state".
+    pub from: Option<String>,
+    pub to: String,
+    /// All must hold for the transition to fire.
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+    /// Normalised time (`1.0` = one full length of the source state) before
+    /// which the transition cannot fire.
+    #[serde(default)]
+    pub exit_time: Option<f32>,
+    /// Crossfade duration in seconds.
+    #[serde(default)]
+    pub duration: f32,
+}
+
+impl Transition {
+    pub fn new(from: impl Into<String>, to: impl Into<String>) -> Self {
+        Transition { from: Some(from.into()), to: to.into(), conditions: Vec::new(), exit_time: None, duration: 0.0 }
+    }
+
+    /// A transition that may fire from any state other than `to`.
+    pub fn any(to: impl Into<String>) -> Self {
+        Transition { from: None, to: to.into(), conditions: Vec::new(), exit_time: None, duration: 0.0 }
+    }
+
+    pub fn when(mut self, condition: Condition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    pub fn with_exit_time(mut self, exit_time: f32) -> Self {
+        self.exit_time = Some(exit_time);
+        self
+    }
+
+    pub fn with_duration(mut self, duration: f32) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    fn leaves(&self, state: &str) -> bool {
+        match &self.from {
+            Some(from) => from == state,
+            None => self.to != state,
+        }
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// AnimationStateMachine
+
+/// The state-machine asset: states, transitions and parameter defaults.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnimationStateMachine {
+    pub states: Vec<AnimationState>,
+    /// Checked in order; the first one that can fire wins.
+    pub transitions: Vec<Transition>,
+    /// State entered when the controller starts.
+    pub default_state: String,
+    /// Parameters and their initial values.
+    #[serde(default)]
+    pub parameters: BTreeMap<String, ParamValue>,
+}
+
+impl AnimationStateMachine {
+    pub fn new(default_state: impl Into<String>) -> Self {
+        AnimationStateMachine { default_state: default_state.into(), ..Default::default() }
+    }
+
+    pub fn with_state(mut self, state: AnimationState) -> Self {
+        self.states.push(state);
+        self
+    }
+
+    pub fn with_transition(mut self, transition: Transition) -> Self {
+        self.transitions.push(transition);
+        self
+    }
+
+    /// Declare a parameter with its initial value.
+    pub fn with_param(mut self, name: impl Into<String>, value: impl Into<ParamValue>) -> Self {
+        self.parameters.insert(name.into(), value.into());
+        self
+    }
+
+    pub fn state(&self, name: &str) -> Option<&AnimationState> {
+        self.states.iter().find(|s| s.name == name)
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// AnimationController
+
+/// A crossfade in progress, away from `from`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateBlend {
+    pub from: String,
+    pub elapsed: f32,
+    pub duration: f32,
+}
+
+/// Runs an [`AnimationStateMachine`] on the entity's [`AnimationPlayer`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnimationController {
+    pub machine: AnimationStateMachine,
+    /// Player layer driven by the machine.
+    pub layer: usize,
+    /// Current parameter values.
+    pub params: BTreeMap<String, ParamValue>,
+    /// Current state; `None` until the first update enters the default state.
+    pub state: Option<String>,
+    /// Seconds spent in the current state.
+    pub state_time: f32,
+    /// Crossfade from the previous state, if one is running.
+    pub blend: Option<StateBlend>,
+}
+
+impl AnimationController {
+    /// A controller driving the base layer.
+    pub fn new(machine: AnimationStateMachine) -> Self {
+        AnimationController {
+            params: machine.parameters.clone(),
+            machine,
+            layer: 0,
+            state: None,
+            state_time: 0.0,
+            blend: None,
+        }
+    }
+
+    pub fn with_layer(mut self, layer: usize) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    /// Set a float or bool parameter.
+    pub fn set_param(&mut self, name: impl Into<String>, value: impl Into<ParamValue>) {
+        self.params.insert(name.into(), value.into());
+    }
+
+    /// Set a trigger; it stays set until a transition consumes it.
+    pub fn trigger(&mut self, name: impl Into<String>) {
+        self.params.insert(name.into(), ParamValue::Trigger(true));
+    }
+
+    pub fn param(&self, name: &str) -> Option<ParamValue> {
+        self.params.get(name).copied()
+    }
+
+    /// Name of the current state.
+    pub fn state(&self) -> Option<&str> {
+        self.state.as_deref()
+    }
+
+    /// Force the machine into `state`, crossfading over `duration` seconds.
+    pub fn go_to(&mut self, state: &str, duration: f32) {
+        let from = self.state.replace(state.to_owned());
+        self.state_time = 0.0;
+        self.blend = match from {
+            Some(from) if duration > 0.0 && from != state => {
+                Some(StateBlend { from, elapsed: 0.0, duration })
+            }
+            _ => None,
+        };
+    }
+
+    /// Length in seconds of `state`: its longest clip in `player`.
+    fn state_length(&self, state: &AnimationState, player: &AnimationPlayer) -> f32 {
+        state
+            .motion
+            .clips()
+            .into_iter()
+            .filter_map(|c| player.clips.get(c))
+            .map(|c| c.duration)
+            .fold(0.0, f32::max)
+            / state.speed.abs().max(f32::EPSILON)
+    }
+
+    /// Advance by `dt` seconds, fire at most one transition and write the
+    /// resulting clip weights into `player`.
+    pub fn update(&mut self, dt: f32, player: &mut AnimationPlayer) {
+        if self.state.is_none() {
+            let default = self.machine.default_state.clone();
+            self.go_to(&default, 0.0);
+        }
+        self.state_time += dt;
+        if let Some(blend) = &mut self.blend {
+            blend.elapsed += dt;
+            if blend.elapsed >= blend.duration {
+                self.blend = None;
+            }
+        }
+
+        self.fire_transition(player);
+        self.write_weights(player);
+    }
+
+    fn fire_transition(&mut self, player: &AnimationPlayer) {
+        let current = self.state.clone().unwrap_or_default();
+        let normalized = match self.machine.state(&current) {
+            Some(state) => match self.state_length(state, player) {
+                len if len > 0.0 => self.state_time / len,
+                _ => f32::INFINITY,
+            },
+            None => f32::INFINITY,
+        };
+        let fired = self.machine.transitions.iter().find(|t| {
+            t.leaves(&current)
+                && t.exit_time.is_none_or(|e| normalized >= e)
+                && t.conditions.iter().all(|c| c.holds(&self.params))
+        });
+        let Some(transition) = fired.cloned() else { return };
+        for condition in &transition.conditions {
+            if let Condition::Trigger(name) = condition {
+                self.params.insert(name.clone(), ParamValue::Trigger(false));
+            }
+        }
+        self.go_to(&transition.to, transition.duration);
+    }
+
+    fn write_weights(&self, player: &mut AnimationPlayer) {
+        let mut weights: BTreeMap<&str, (f32, f32)> = BTreeMap::new();
+        let alpha = self.blend.as_ref().map_or(1.0, |b| (b.elapsed / b.duration).clamp(0.0, 1.0));
+        let current = self.state.as_deref().map(|s| (s, alpha));
+        let previous = self.blend.as_ref().map(|b| (b.from.as_str(), 1.0 - alpha));
+        for (name, scale) in current.into_iter().chain(previous) {
+            let Some(state) = self.machine.state(name) else { continue };
+            let normalized = state.motion.weights(&self.params);
+            let total: f32 = normalized.iter().map(|(_, w)| w).sum();
+            if total <= 0.0 {
+                continue;
+            }
+            for (clip, w) in normalized {
+                let entry = weights.entry(clip).or_insert((0.0, state.speed));
+                entry.0 += w / total * scale;
+            }
+        }
+
+        let Some(layer) = player.layers.get_mut(self.layer) else { return };
+        layer.clips.retain(|c| weights.contains_key(c.clip.as_str()));
+        for (clip, (weight, speed)) in weights {
+            layer.set_clip_weight(clip, weight);
+            if let Some(entry) = layer.clip_mut(clip) {
+                entry.speed = speed;
+            }
+        }
+    }
+}
+
+impl Component for AnimationController {}
+
+// ────────────────────────────────────────────────────────────────────────────
+// AnimationControllerSystem
+
+/// Steps every `AnimationController` and updates its player's layer weights.
+///
+/// Reads the frame delta from `Time` (published by the app runner), falling
+/// back to a `TimeClock` resource.
+///
+/// Register at `Stage::Update`, before `AnimationSystem`.
+pub struct AnimationControllerSystem;
+
+impl System for AnimationControllerSystem {
+    fn name(&self) -> &'static str { "AnimationControllerSystem" }
+
+    fn run(&mut self, world: &mut ferrous_ecs::world::World, resources: &mut ResourceMap) {
+        let dt = if let Some(time) = resources.get::<Time>() {
+            time.delta
+        } else {
+            resources.get::<TimeClock>().map(|c| c.at_tick().delta).unwrap_or(0.0)
+        };
+
+        for (_, (controller, player)) in
+            Query::<(&mut AnimationController, &mut AnimationPlayer)>::new(world).iter()
+        {
+            if player.playing {
+                controller.update(dt * player.speed, player);
+            }
+        }
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::systems::animation::{AnimationClip, Keyframe, Track};
+    use glam::Vec3;
+
+    fn clip(x: f32, duration: f32) -> AnimationClip {
+        AnimationClip::new(duration)
+            .with_translation(Track::step(vec![Keyframe::new(0.0, Vec3::new(x, 0.0, 0.0))]))
+            .with_looping(true)
+    }
+
+    fn player() -> AnimationPlayer {
+        AnimationPlayer::default()
+            .with_clip("idle", clip(0.0, 1.0))
+            .with_clip("walk", clip(2.0, 1.0))
+            .with_clip("run", clip(6.0, 1.0))
+            .with_clip("jump", clip(10.0, 1.0))
+    }
+
+    fn machine() -> AnimationStateMachine {
+        AnimationStateMachine::new("move")
+            .with_param("speed", 0.0)
+            .with_param("jump", ParamValue::Trigger(false))
+            .with_state(AnimationState::blend_1d("move", "speed", [(0.0, "idle"), (2.0, "walk"), (6.0, "run")]))
+            .with_state(AnimationState::clip("jump", "jump"))
+            .with_transition(
+                Transition::new("move", "jump").when(Condition::Trigger("jump".into())).with_duration(0.5),
+            )
+            .with_transition(Transition::new("jump", "move").with_exit_time(1.0))
+    }
+
+    fn weight(player: &AnimationPlayer, clip: &str) -> f32 {
+        player.layers[0].clip(clip).map_or(0.0, |c| c.weight)
+    }
+
+    #[test]
+    fn blend_space_1d_interpolates_between_neighbours() {
+        let mut controller = AnimationController::new(machine());
+        let mut player = player();
+        controller.set_param("speed", 4.0);
+        controller.update(0.0, &mut player);
+
+        assert_eq!(controller.state(), Some("move"));
+        assert_eq!(weight(&player, "walk"), 0.5);
+        assert_eq!(weight(&player, "run"), 0.5);
+        assert_eq!(player.layers[0].clip("idle"), None);
+        assert_eq!(player.evaluate().transform.translation, Some(Vec3::new(4.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn trigger_fires_crossfade_then_exit_time_returns() {
+        let mut controller = AnimationController::new(machine());
+        let mut player = player();
+        controller.update(0.0, &mut player);
+        controller.trigger("jump");
+        controller.update(0.1, &mut player);
+
+        assert_eq!(controller.state(), Some("jump"));
+        assert_eq!(controller.param("jump"), Some(ParamValue::Trigger(false)));
+        controller.update(0.25, &mut player);
+        assert_eq!(weight(&player, "jump"), 0.5);
+        assert_eq!(weight(&player, "idle"), 0.5);
+
+        // The fade ends, then the clip plays out and the exit time is reached.
+        controller.update(0.5, &mut player);
+        assert_eq!(weight(&player, "jump"), 1.0);
+        assert_eq!(player.layers[0].clip("idle"), None);
+        controller.update(0.3, &mut player);
+        assert_eq!(controller.state(), Some("move"));
+    }
+
+    #[test]
+    fn blend_space_2d_favours_nearest_point() {
+        let motion = AnimationState::blend_2d(
+            "strafe",
+            "x",
+            "y",
+            [(Vec2::ZERO, "idle"), (Vec2::X, "right"), (Vec2::Y, "forward")],
+        )
+        .motion;
+        let mut params = BTreeMap::new();
+        params.insert("x".to_string(), ParamValue::Float(1.0));
+        assert_eq!(motion.weights(&params), vec![("right", 1.0)]);
+
+        params.insert("x".to_string(), ParamValue::Float(0.8));
+        let weights: BTreeMap<_, _> = motion.weights(&params).into_iter().collect();
+        assert!(weights["right"] > weights["idle"] && weights["idle"] > weights["forward"]);
+    }
+
+    #[test]
+    fn controller_system_steps_with_the_app_time() {
+        let mut world = ferrous_ecs::world::World::new();
+        let mut res = ResourceMap::new();
+        res.insert(Time { delta: 0.25, ..Default::default() });
+        let mut controller = AnimationController::new(machine());
+        controller.trigger("jump");
+        let e = world.spawn((controller, player()));
+
+        // Enters `move` and fires the jump transition, then fades halfway.
+        AnimationControllerSystem.run(&mut world, &mut res);
+        AnimationControllerSystem.run(&mut world, &mut res);
+
+        assert_eq!(world.get::<AnimationController>(e).unwrap().state(), Some("jump"));
+        let player = world.get::<AnimationPlayer>(e).unwrap();
+        assert_eq!(weight(player, "jump"), 0.5);
+        assert_eq!(weight(player, "idle"), 0.5);
+    }
+
+    #[test]
+    fn machine_round_trips_through_json() {
+        let machine = machine();
+        let json = serde_json::to_string(&machine).unwrap();
+        assert_eq!(serde_json::from_str::<AnimationStateMachine>(&json).unwrap(), machine);
+    }
+}
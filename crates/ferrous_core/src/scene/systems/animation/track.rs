@@ -0,0 +1,258 @@
+//! Keyframe tracks and their interpolation.
+//!
+//! A [`Track`] is a time-sorted list of [`Keyframe`]s for one animated
+//! property, sampled with one of the glTF interpolation modes
+//! ([`Interpolation`]).  Key lookup is a binary search, so long tracks cost
+//! `O(log n)` per sample.
+//!
+//! Values are anything implementing [`Animatable`]: `f32`, `Vec3`, `Quat`
+//! (slerp / renormalised Hermite) and `Vec<f32>` (morph-target weights,
+//! interpolated element-wise).
+
+use glam::{Quat, Vec3, Vec4};
+use serde::{Deserialize, Serialize};
+
+// ────────────────────────────────────────────────────────────────────────────
+// Keyframe / Interpolation
+
+/// A single keyframe: (time_seconds, value).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Keyframe<T> {
+    pub time: f32,
+    pub value: T,
+}
+
+impl<T> Keyframe<T> {
+    pub fn new(time: f32, value: T) -> Self {
+        Keyframe { time, value }
+    }
+}
+
+/// How values between two keyframes are computed (glTF `sampler.interpolation`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Interpolation {
+    /// Hold the previous key until the next one is reached.
+    Step,
+    /// Linear interpolation (spherical for rotations).
+    #[default]
+    Linear,
+    /// Cubic Hermite spline using the per-key tangents of the track.
+    CubicSpline,
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Animatable
+
+/// A value type that can be stored in a [`Track`].
+pub trait Animatable: Clone {
+    /// Interpolate from `a` to `b` by `t ∈ [0, 1]`.
+    fn interpolate(a: &Self, b: &Self, t: f32) -> Self;
+
+    /// Evaluate the cubic Hermite segment between `p0` and `p1` with
+    /// out-tangent `m0` and in-tangent `m1` (both per second, as stored by
+    /// glTF), `span` seconds long, at `t ∈ [0, 1]`.
+    fn hermite(p0: &Self, m0: &Self, p1: &Self, m1: &Self, span: f32, t: f32) -> Self;
+}
+
+/// Hermite basis functions `(h00, h10, h01, h11)` at `t`.
+#[inline]
+fn hermite_basis(t: f32) -> (f32, f32, f32, f32) {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    (
+        2.0 * t3 - 3.0 * t2 + 1.0,
+        t3 - 2.0 * t2 + t,
+        -2.0 * t3 + 3.0 * t2,
+        t3 - t2,
+    )
+}
+
+macro_rules! impl_animatable_linear {
+    ($($ty:ty),+) => {$(
+        impl Animatable for $ty {
+            #[inline]
+            fn interpolate(a: &Self, b: &Self, t: f32) -> Self {
+                *a + (*b - *a) * t
+            }
+
+            #[inline]
+            fn hermite(p0: &Self, m0: &Self, p1: &Self, m1: &Self, span: f32, t: f32) -> Self {
+                let (h00, h10, h01, h11) = hermite_basis(t);
+                *p0 * h00 + *m0 * (h10 * span) + *p1 * h01 + *m1 * (h11 * span)
+            }
+        }
+    )+};
+}
+
+impl_animatable_linear!(f32, Vec3, Vec4);
+
+impl Animatable for Quat {
+    #[inline]
+    fn interpolate(a: &Self, b: &Self, t: f32) -> Self {
+        a.slerp(*b, t)
+    }
+
+    fn hermite(p0: &Self, m0: &Self, p1: &Self, m1: &Self, span: f32, t: f32) -> Self {
+        let v = Vec4::hermite(&Vec4::from(*p0), &Vec4::from(*m0), &Vec4::from(*p1), &Vec4::from(*m1), span, t);
+        Quat::from_vec4(v).normalize()
+    }
+}
+
+impl Animatable for Vec<f32> {
+    fn interpolate(a: &Self, b: &Self, t: f32) -> Self {
+        a.iter().zip(b).map(|(a, b)| f32::interpolate(a, b, t)).collect()
+    }
+
+    fn hermite(p0: &Self, m0: &Self, p1: &Self, m1: &Self, span: f32, t: f32) -> Self {
+        (0..p0.len().min(p1.len()))
+            .map(|i| {
+                let tangent = |m: &Vec<f32>| m.get(i).copied().unwrap_or(0.0);
+                f32::hermite(&p0[i], &tangent(m0), &p1[i], &tangent(m1), span, t)
+            })
+            .collect()
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Track
+
+/// Keyframes of one animated property.
+///
+/// `keys` must be sorted by time.  For [`Interpolation::CubicSpline`],
+/// `tangents[i]` holds the `(in_tangent, out_tangent)` pair of `keys[i]`;
+/// a cubic track without one tangent pair per key is sampled linearly.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Track<T> {
+    pub interpolation: Interpolation,
+    pub keys: Vec<Keyframe<T>>,
+    #[serde(default)]
+    pub tangents: Vec<(T, T)>,
+}
+
+impl<T: Animatable> Track<T> {
+    /// A track with the given interpolation mode and no tangents.
+    pub fn new(interpolation: Interpolation, keys: Vec<Keyframe<T>>) -> Self {
+        Track { interpolation, keys, tangents: Vec::new() }
+    }
+
+    /// A linearly interpolated track.
+    pub fn linear(keys: Vec<Keyframe<T>>) -> Self {
+        Self::new(Interpolation::Linear, keys)
+    }
+
+    /// A stepped track.
+    pub fn step(keys: Vec<Keyframe<T>>) -> Self {
+        Self::new(Interpolation::Step, keys)
+    }
+
+    /// A cubic-spline track; `tangents[i]` is `(in, out)` for `keys[i]`.
+    pub fn cubic(keys: Vec<Keyframe<T>>, tangents: Vec<(T, T)>) -> Self {
+        Track { interpolation: Interpolation::CubicSpline, keys, tangents }
+    }
+
+    /// Time of the last key (0 for an empty track).
+    pub fn duration(&self) -> f32 {
+        self.keys.last().map_or(0.0, |k| k.time)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Sample the track at `t` seconds.  Times outside the keyed range clamp
+    /// to the first / last key.  Returns `None` for an empty track.
+    pub fn sample(&self, t: f32) -> Option<T> {
+        let keys = &self.keys;
+        // Index of the first key strictly after `t`.
+        let next = keys.partition_point(|k| k.time <= t);
+        if next == 0 {
+            return keys.first().map(|k| k.value.clone());
+        }
+        if next == keys.len() {
+            return keys.last().map(|k| k.value.clone());
+        }
+
+        let (a, b) = (&keys[next - 1], &keys[next]);
+        let span = b.time - a.time;
+        let alpha = if span > 0.0 { (t - a.time) / span } else { 0.0 };
+        Some(match self.interpolation {
+            Interpolation::Step => a.value.clone(),
+            Interpolation::CubicSpline if self.tangents.len() == keys.len() => T::hermite(
+                &a.value,
+                &self.tangents[next - 1].1,
+                &b.value,
+                &self.tangents[next].0,
+                span,
+                alpha,
+            ),
+            Interpolation::Linear | Interpolation::CubicSpline => {
+                T::interpolate(&a.value, &b.value, alpha)
+            }
+        })
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    fn keys<T: Copy>(values: &[(f32, T)]) -> Vec<Keyframe<T>> {
+        values.iter().map(|&(t, v)| Keyframe::new(t, v)).collect()
+    }
+
+    #[test]
+    fn linear_and_step_sampling() {
+        let keys = keys(&[(0.0, 0.0f32), (1.0, 10.0), (3.0, 30.0)]);
+        let linear = Track::linear(keys.clone());
+        assert_eq!(linear.sample(-1.0), Some(0.0));
+        assert_eq!(linear.sample(0.5), Some(5.0));
+        assert_eq!(linear.sample(2.0), Some(20.0));
+        assert_eq!(linear.sample(9.0), Some(30.0));
+
+        let step = Track::step(keys);
+        assert_eq!(step.sample(0.99), Some(0.0));
+        assert_eq!(step.sample(1.0), Some(10.0));
+        assert_eq!(step.sample(2.9), Some(10.0));
+        assert_eq!(Track::<f32>::linear(Vec::new()).sample(0.0), None);
+    }
+
+    #[test]
+    fn cubic_spline_follows_tangents() {
+        // Zero tangents: eases in and out, passing the midpoint at t = 0.5.
+        let flat = Track::cubic(keys(&[(0.0, 0.0f32), (2.0, 1.0)]), vec![(0.0, 0.0); 2]);
+        assert!((flat.sample(1.0).unwrap() - 0.5).abs() < 1e-6);
+        assert!(flat.sample(0.2).unwrap() < 0.1);
+
+        // Tangents matching the slope reproduce the straight line.
+        let line = Track::cubic(keys(&[(0.0, 0.0f32), (2.0, 1.0)]), vec![(0.5, 0.5); 2]);
+        assert!((line.sample(0.5).unwrap() - 0.25).abs() < 1e-6);
+
+        // Missing tangents fall back to linear.
+        let broken = Track::cubic(keys(&[(0.0, 0.0f32), (2.0, 1.0)]), Vec::new());
+        assert_eq!(broken.sample(0.5), Some(0.25));
+    }
+
+    #[test]
+    fn rotations_slerp_and_stay_normalised() {
+        let track = Track::linear(keys(&[(0.0, Quat::IDENTITY), (1.0, Quat::from_rotation_y(FRAC_PI_2))]));
+        let half = track.sample(0.5).unwrap();
+        assert!(half.abs_diff_eq(Quat::from_rotation_y(FRAC_PI_2 / 2.0), 1e-5));
+
+        let zero = Quat::from_xyzw(0.0, 0.0, 0.0, 0.0);
+        let cubic = Track::cubic(track.keys.clone(), vec![(zero, zero); 2]);
+        assert!((cubic.sample(0.3).unwrap().length() - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn weights_interpolate_element_wise() {
+        let track = Track::linear(vec![
+            Keyframe::new(0.0, vec![0.0, 1.0]),
+            Keyframe::new(1.0, vec![1.0, 0.0]),
+        ]);
+        assert_eq!(track.sample(0.25), Some(vec![0.25, 0.75]));
+    }
+}
@@ -4,6 +4,7 @@
 
 use ferrous_ecs::prelude::*;
 use ferrous_ecs::system::System;
+use serde::{Deserialize, Serialize};
 
 // ────────────────────────────────────────────────────────────────────────────
 // Camera3D
@@ -18,7 +19,7 @@ use ferrous_ecs::system::System;
 ///     Camera3D::looking_at(Vec3::ZERO).distance(5.0).build(),
 /// ));
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Camera3D {
     pub eye: glam::Vec3,
     pub target: glam::Vec3,
@@ -101,7 +102,7 @@ impl From<Camera3DBuilder> for Camera3D {
 ///     OrbitCamera { yaw: -0.52, pitch: 0.35, distance: 5.0, target: Vec3::ZERO },
 /// ));
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct OrbitCamera {
     pub yaw: f32,
     pub pitch: f32,
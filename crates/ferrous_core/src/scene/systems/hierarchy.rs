@@ -1,27 +1,63 @@
-//! Parent-child transform hierarchy components and TransformSystem.
+//! Parent-child transform hierarchy (built on the `ChildOf` relation) and
+//! TransformSystem.
 
 #![cfg(feature = "ecs")]
 
 use ferrous_ecs::prelude::*;
 use ferrous_ecs::system::System;
+use serde::{Deserialize, Serialize};
 
 // ────────────────────────────────────────────────────────────────────────────
 // Hierarchy components
 
-/// Parent link — set this on a child entity to form a scene hierarchy.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct Parent(pub ferrous_ecs::entity::Entity);
-impl Component for Parent {}
+/// Hierarchy relation: `world.relate::<ChildOf>(child, parent)`.
+///
+/// A child has at most one parent (relating it again re-parents it), and
+/// despawning a parent despawns its whole subtree.
+pub struct ChildOf;
 
-/// List of direct children.
-#[derive(Debug, Clone)]
-#[derive(Default)]
-pub struct Children(pub Vec<ferrous_ecs::entity::Entity>);
-impl Component for Children {}
+impl Relation for ChildOf {
+    const DESPAWN_POLICY: DespawnPolicy = DespawnPolicy::Recursive;
+    const EXCLUSIVE: bool = true;
 
+    /// A detached child keeps its local `Transform`, which now maps straight
+    /// to world space — flag it so `TransformSystem` recomputes it.
+    fn on_unrelate(
+        world: &mut ferrous_ecs::world::World,
+        child: ferrous_ecs::entity::Entity,
+        _parent: ferrous_ecs::entity::Entity,
+    ) {
+        let _ = world.get_mut::<crate::transform::Transform>(child);
+    }
+}
+
+/// Parent link of a child entity (`parent.target()`), maintained by
+/// `relate::<ChildOf>`.
+pub type Parent = RelationTargets<ChildOf>;
+
+/// Direct children of an entity, maintained by `relate::<ChildOf>`.
+pub type Children = RelationSources<ChildOf>;
+
+/// Former tuple-struct constructor of [`Parent`], so
+/// `world.insert(child, Parent(parent))` keeps working.  The next
+/// `TransformSystem` run adopts the link into the `ChildOf` relation.
+/// Pattern matching on `Parent(e)` is gone; use `parent.target()`.
+#[deprecated(note = "use `world.relate::<ChildOf>(child, parent)`")]
+#[allow(non_snake_case)]
+pub fn Parent(parent: ferrous_ecs::entity::Entity) -> Parent {
+    RelationTargets::from_entities(vec![parent])
+}
+
+/// Former tuple-struct constructor of [`Children`]; adopted like
+/// [`Parent()`].  Read the list with `children.as_slice()`.
+#[deprecated(note = "use `world.relate::<ChildOf>(child, parent)` for each child")]
+#[allow(non_snake_case)]
+pub fn Children(children: Vec<ferrous_ecs::entity::Entity>) -> Children {
+    RelationSources::from_entities(children)
+}
 
 /// Computed world-space transform — read-only output of `TransformSystem`.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct GlobalTransform(pub glam::Mat4);
 impl Component for GlobalTransform {}
 
@@ -35,6 +71,16 @@ impl Default for GlobalTransform {
 /// Propagates local `Transform` through the parent-child hierarchy to
 /// produce `GlobalTransform` on every entity.
 ///
+/// Only entities whose `Transform` or `Parent` changed since the previous run
+/// — plus all of their descendants — are recomputed; untouched subtrees keep
+/// their cached `GlobalTransform`.  The first run (or any run outside a
+/// scheduler) therefore recomputes everything.
+///
+/// Removals leave no change tick behind, so they are picked up separately:
+/// an entity that lost its `Transform` also loses its `GlobalTransform`
+/// (its children then hang off the origin), and a child whose parent was
+/// despawned without going through the relation becomes a root.
+///
 /// Register at `Stage::PostUpdate`.
 pub struct TransformSystem;
 
@@ -46,35 +92,134 @@ impl System for TransformSystem {
         world: &mut ferrous_ecs::world::World,
         _resources: &mut ResourceMap,
     ) {
-        // Pass 1: set GlobalTransform = local matrix.
-        let locals: Vec<(ferrous_ecs::entity::Entity, glam::Mat4)> = world
-            .query::<crate::transform::Transform>()
-            .map(|(e, t)| (e, t.matrix()))
+        use crate::transform::Transform;
+        use ferrous_ecs::entity::Entity;
+        use std::collections::HashSet;
+
+        adopt_legacy_links(world);
+
+        // Roots of dirty subtrees: moved entities and re-parented entities.
+        let mut dirty: HashSet<Entity> =
+            Query::<&Transform, Or<(Changed<Transform>, Changed<Parent>)>>::new(world)
+                .iter()
+                .map(|(e, _)| e)
+                .collect();
+
+        let stripped: Vec<Entity> = Query::<&GlobalTransform, Without<Transform>>::new(world)
+            .iter()
+            .map(|(e, _)| e)
             .collect();
+        for e in stripped {
+            world.remove::<GlobalTransform>(e);
+            dirty.extend(world.sources::<ChildOf>(e));
+        }
+        // One generation check per child: a dead parent means its despawn
+        // hook never saw this link.
+        let orphans: Vec<Entity> = Query::<&Parent>::new(world)
+            .iter()
+            .filter(|(_, p)| !world.contains(p.target()))
+            .map(|(e, _)| e)
+            .collect();
+        for e in orphans {
+            world.remove::<Parent>(e);
+            dirty.insert(e);
+        }
+        dirty.retain(|&e| world.has::<Transform>(e));
+        if dirty.is_empty() {
+            return;
+        }
 
-        for (entity, mat) in &locals {
-            if world.get::<GlobalTransform>(*entity).is_none() {
-                world.insert(*entity, GlobalTransform(glam::Mat4::IDENTITY));
-            }
-            if let Some(gt) = world.get_mut::<GlobalTransform>(*entity) {
-                gt.0 = *mat;
+        // Everything below a dirty entity must be recomputed as well.
+        let mut stack: Vec<Entity> = dirty.iter().copied().collect();
+        while let Some(e) = stack.pop() {
+            for &kid in world.sources::<ChildOf>(e) {
+                if world.has::<Transform>(kid) && dirty.insert(kid) {
+                    stack.push(kid);
+                }
             }
         }
 
-        // Pass 2: apply parent transforms (single level).
-        let parent_pairs: Vec<(ferrous_ecs::entity::Entity, ferrous_ecs::entity::Entity)> = world
-            .query::<Parent>()
-            .map(|(e, p)| (e, p.0))
+        // Dirty subtrees hang off clean parents whose cached
+        // `GlobalTransform` is read-only here, so the subtrees are independent
+        // and resolved in parallel; within one, each matrix is computed once
+        // from its parent's.
+        let roots: Vec<Entity> = dirty
+            .iter()
+            .copied()
+            .filter(|&e| !matches!(world.target::<ChildOf>(e), Some(p) if dirty.contains(&p)))
             .collect();
+        let resolve = |&root: &Entity| {
+            let local = world.get::<Transform>(root).map_or(glam::Mat4::IDENTITY, Transform::matrix);
+            resolve_subtree(world, root, local, &dirty)
+        };
+        #[cfg(feature = "parallel")]
+        let globals: Vec<Vec<(Entity, glam::Mat4)>> = {
+            use rayon::prelude::*;
+            roots.par_iter().map(resolve).collect()
+        };
+        #[cfg(not(feature = "parallel"))]
+        let globals: Vec<Vec<(Entity, glam::Mat4)>> = roots.iter().map(resolve).collect();
+
+        for (entity, mat) in globals.into_iter().flatten() {
+            match world.get_mut::<GlobalTransform>(entity) {
+                Some(gt) => gt.0 = mat,
+                None => world.insert(entity, GlobalTransform(mat)),
+            }
+        }
+    }
+}
+
+/// Relate both ends of `Parent` / `Children` links that were inserted by
+/// hand (see [`Parent()`]), so the relation's despawn policy applies.
+fn adopt_legacy_links(world: &mut ferrous_ecs::world::World) {
+    use ferrous_ecs::entity::Entity;
+
+    let mut links: Vec<(Entity, Entity)> = Query::<&Parent, Changed<Parent>>::new(world)
+        .iter()
+        .flat_map(|(child, p)| p.iter().map(move |&parent| (child, parent)))
+        .collect();
+    links.extend(
+        Query::<&Children, Changed<Children>>::new(world)
+            .iter()
+            .flat_map(|(parent, c)| c.iter().map(move |&child| (child, parent))),
+    );
+    for (child, parent) in links {
+        let linked = world.sources::<ChildOf>(parent).contains(&child)
+            && world.targets::<ChildOf>(child).contains(&parent);
+        if !linked && world.contains(parent) {
+            world.relate::<ChildOf>(child, parent);
+        }
+    }
+}
 
-        for (child, parent_entity) in parent_pairs {
-            let parent_global = match world.get::<GlobalTransform>(parent_entity) {
-                Some(gt) => gt.0,
-                None => glam::Mat4::IDENTITY,
-            };
-            if let Some(child_gt) = world.get_mut::<GlobalTransform>(child) {
-                child_gt.0 = parent_global * child_gt.0;
+/// World matrices of the dirty subtree under `root` (local matrix `local`),
+/// parents first.  The root's parent is clean, so its cached
+/// `GlobalTransform` seeds the walk.
+///
+/// Entities on a malformed parent cycle have no such root and keep their
+/// previous `GlobalTransform`.
+fn resolve_subtree(
+    world: &ferrous_ecs::world::World,
+    root: ferrous_ecs::entity::Entity,
+    local: glam::Mat4,
+    dirty: &std::collections::HashSet<ferrous_ecs::entity::Entity>,
+) -> Vec<(ferrous_ecs::entity::Entity, glam::Mat4)> {
+    let parent_global = world
+        .target::<ChildOf>(root)
+        .and_then(|p| world.get::<GlobalTransform>(p))
+        .map_or(glam::Mat4::IDENTITY, |gt| gt.0);
+    let mut resolved = Vec::new();
+    let mut stack = vec![(root, parent_global * local)];
+    while let Some((entity, global)) = stack.pop() {
+        resolved.push((entity, global));
+        for &kid in world.sources::<ChildOf>(entity) {
+            if !dirty.contains(&kid) {
+                continue;
+            }
+            if let Some(t) = world.get::<crate::transform::Transform>(kid) {
+                stack.push((kid, global * t.matrix()));
             }
         }
     }
+    resolved
 }
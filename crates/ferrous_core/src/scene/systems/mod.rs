@@ -5,18 +5,21 @@
 //!
 //! | Sub-module   | Contents                                              |
 //! |-------------|-------------------------------------------------------|
-//! | `time`      | `Velocity`, `TimeSystem`, `VelocitySystem`            |
-//! | `animation` | `Keyframe`, `AnimationClip`, `AnimationPlayer`, `AnimationSystem` |
+//! | `time`      | `Velocity`, `TimeSystem`, `VelocitySystem`, fixed-step interpolation |
+//! | `animation` | `Keyframe`, `Track`, `AnimationClip`, `AnimationPlayer`, `AnimationLayer`, `AnimationStateMachine`, `AnimationController`, `AnimationSystem` |
 //! | `behavior`  | `Behavior`, `BehaviorComponent`, `BehaviorSystem`     |
-//! | `hierarchy` | `Parent`, `Children`, `GlobalTransform`, `TransformSystem` |
+//! | `hierarchy` | `ChildOf`, `Parent`, `Children`, `GlobalTransform`, `TransformSystem` |
 //! | `lighting`  | `DirectionalLight`                                    |
 //! | `camera`    | `Camera3D`, `Camera3DBuilder`, `OrbitCamera`, `OrbitCameraSystem` |
+//! | `path`      | `FollowPath`, `PathEnd`, `FollowPathSystem`           |
+//! | `spatial`   | `LocalBounds`, `SpatialIndex`, `SpatialIndexSystem`, `SpatialRemovals` |
 //!
 //! # Recommended registration order
 //! ```text
-//! PreUpdate  →  TimeSystem
-//! Update     →  VelocitySystem, AnimationSystem, BehaviorSystem, OrbitCameraSystem
-//! PostUpdate →  TransformSystem, SkinningSystem
+//! PreUpdate   →  TimeSystem
+//! FixedUpdate →  TransformSnapshotSystem, VelocitySystem
+//! Update      →  FixedTimeSystem, AnimationControllerSystem, AnimationSystem, BehaviorSystem, FollowPathSystem, OrbitCameraSystem
+//! PostUpdate  →  TransformSystem, SpatialIndexSystem, SkinningSystem
 //! ```
 
 pub mod animation;
@@ -24,14 +27,36 @@ pub mod behavior;
 pub mod camera;
 pub mod hierarchy;
 pub mod lighting;
+pub mod path;
 pub mod skinning_sys;
+pub mod spatial;
 pub mod time;
 
 // ── Re-export stage ──────────────────────────────────────────────────────────
 pub use ferrous_ecs::system::Stage;
 
+/// Labels attached to the built-in systems when the app registers them, so
+/// plugins can order their own systems with `.before()` / `.after()`.
+pub mod labels {
+    pub const TIME: &str = "ferrous::time";
+    pub const FIXED_TIME: &str = "ferrous::fixed_time";
+    pub const TRANSFORM_SNAPSHOT: &str = "ferrous::transform_snapshot";
+    pub const VELOCITY: &str = "ferrous::velocity";
+    pub const ANIMATION_CONTROLLER: &str = "ferrous::animation_controller";
+    pub const ANIMATION: &str = "ferrous::animation";
+    pub const BEHAVIOR: &str = "ferrous::behavior";
+    pub const FOLLOW_PATH: &str = "ferrous::follow_path";
+    pub const TRANSFORM: &str = "ferrous::transform";
+    pub const SPATIAL_INDEX: &str = "ferrous::spatial_index";
+}
+
 // ── animation ────────────────────────────────────────────────────────────────
-pub use animation::{AnimationClip, AnimationPlayer, AnimationSystem, Keyframe};
+pub use animation::{
+    Animatable, AnimationClip, AnimationController, AnimationControllerSystem, AnimationEvent,
+    AnimationLayer, AnimationPlayer, AnimationSample, AnimationState, AnimationStateMachine,
+    AnimationSystem, BlendMode, BoneMask, ClipEvent, Condition, Interpolation, Keyframe, Motion,
+    ParamValue, PlayingClip, StateBlend, Track, TransformSample, TransformTracks, Transition,
+};
 
 // ── behavior ─────────────────────────────────────────────────────────────────
 pub use behavior::{Behavior, BehaviorComponent, BehaviorSystem};
@@ -40,16 +65,26 @@ pub use behavior::{Behavior, BehaviorComponent, BehaviorSystem};
 pub use camera::{Camera3D, Camera3DBuilder, OrbitCamera, OrbitCameraSystem};
 
 // ── hierarchy ────────────────────────────────────────────────────────────────
-pub use hierarchy::{Children, GlobalTransform, Parent, TransformSystem};
+#[allow(deprecated)]
+pub use hierarchy::{ChildOf, Children, GlobalTransform, Parent, TransformSystem};
 
 // ── lighting ─────────────────────────────────────────────────────────────────
 pub use lighting::DirectionalLight;
 
+// ── path ─────────────────────────────────────────────────────────────────────
+pub use path::{FollowPath, FollowPathSystem, PathEnd};
+
 // ── skinning ─────────────────────────────────────────────────────────────────
 pub use skinning_sys::SkinningSystem;
 
+// ── spatial ──────────────────────────────────────────────────────────────────
+pub use spatial::{LocalBounds, SpatialIndex, SpatialIndexSystem, SpatialRemovals};
+
 // ── time ─────────────────────────────────────────────────────────────────────
-pub use time::{TimeSystem, Velocity, VelocitySystem};
+pub use time::{
+    FixedTimeSystem, PreviousTransform, TimeSystem, TransformSnapshotSystem, Velocity,
+    VelocitySystem,
+};
 
 // ────────────────────────────────────────────────────────────────────────────
 // Tests (integration — exercises all sub-modules together)
@@ -104,18 +139,56 @@ mod tests {
         assert!((pos.x - 10.0 * dt).abs() < 1e-4, "x = {}", pos.x);
     }
 
+    #[test]
+    fn fixed_update_is_frame_rate_independent() {
+        use crate::time::Time;
+
+        // Simulate one second at 30 and at 144 frames per second.
+        let simulate = |fps: f32| {
+            let mut world = ferrous_ecs::world::World::new();
+            let mut res = ResourceMap::new();
+            let e = world.spawn((
+                Transform::from_position(Vec3::ZERO),
+                PreviousTransform(Transform::from_position(Vec3::ZERO)),
+                Velocity(Vec3::X),
+            ));
+            let mut sched = StagedScheduler::new();
+            sched.set_fixed_timestep(0.01, 100);
+            sched.add(Stage::PreUpdate, TimeSystem);
+            sched.add(Stage::FixedUpdate, TransformSnapshotSystem);
+            sched.add(Stage::FixedUpdate, VelocitySystem);
+            sched.add(Stage::Update, FixedTimeSystem);
+            for _ in 0..fps as usize {
+                res.insert(Time { delta: 1.0 / fps, ..Time::default() });
+                sched.run_all(&mut world, &mut res);
+            }
+            let time = *res.get::<Time>().unwrap();
+            let current = *world.get::<Transform>(e).unwrap();
+            let previous = *world.get::<PreviousTransform>(e).unwrap();
+            (time, current, previous)
+        };
+
+        let (time30, cur30, prev30) = simulate(30.0);
+        let (time144, cur144, _) = simulate(144.0);
+        assert_eq!(time30.fixed_delta, 0.01);
+        // Both ran ~100 steps of 1 cm; step counts may differ by one
+        // depending on where float rounding leaves the accumulator.
+        assert!((cur30.position.x - cur144.position.x).abs() <= 0.01 + 1e-4);
+        assert!((cur30.position.x - prev30.0.position.x - 0.01).abs() < 1e-5);
+
+        let shown = prev30.interpolate(&cur30, time30.alpha).position.x;
+        assert!(shown >= prev30.0.position.x && shown <= cur30.position.x);
+        assert!(time144.alpha >= 0.0 && time144.alpha < 1.0);
+    }
+
     #[test]
     fn animation_system_advances_and_applies_position() {
         let (mut world, mut res) = make_world_with_clock();
 
-        let clip = AnimationClip {
-            position_keys: vec![
-                Keyframe { time: 0.0, value: Vec3::ZERO },
-                Keyframe { time: 1.0, value: Vec3::new(1.0, 0.0, 0.0) },
-            ],
-            duration: 1.0,
-            looping: false,
-        };
+        let clip = AnimationClip::new(1.0).with_translation(Track::linear(vec![
+            Keyframe { time: 0.0, value: Vec3::ZERO },
+            Keyframe { time: 1.0, value: Vec3::new(1.0, 0.0, 0.0) },
+        ]));
         let t = Transform::from_position(Vec3::ZERO);
         let player = AnimationPlayer::new(clip);
         let entity = world.spawn((t, player));
@@ -127,6 +200,189 @@ mod tests {
         assert!(pos.x >= 0.0);
     }
 
+    #[test]
+    fn animation_system_advances_with_the_app_time() {
+        let mut world = ferrous_ecs::world::World::new();
+        let mut res = ResourceMap::new();
+        res.insert(crate::time::Time { delta: 0.25, ..Default::default() });
+
+        let clip = AnimationClip::new(1.0).with_translation(Track::linear(vec![
+            Keyframe::new(0.0, Vec3::ZERO),
+            Keyframe::new(1.0, Vec3::new(1.0, 0.0, 0.0)),
+        ]));
+        let e = world.spawn((Transform::IDENTITY, AnimationPlayer::new(clip)));
+        AnimationSystem.run(&mut world, &mut res);
+        AnimationSystem.run(&mut world, &mut res);
+
+        assert_eq!(world.get::<Transform>(e).unwrap().position.x, 0.5);
+    }
+
+    #[test]
+    fn animation_system_applies_every_track() {
+        use crate::scene::MorphWeights;
+        use glam::Quat;
+
+        let clip = AnimationClip::new(0.0)
+            .with_rotation(Track::linear(vec![
+                Keyframe::new(0.0, Quat::IDENTITY),
+                Keyframe::new(2.0, Quat::from_rotation_y(std::f32::consts::FRAC_PI_2)),
+            ]))
+            .with_scale(Track::step(vec![
+                Keyframe::new(0.0, Vec3::ONE),
+                Keyframe::new(0.5, Vec3::splat(2.0)),
+            ]))
+            .with_weights(Track::linear(vec![
+                Keyframe::new(0.0, vec![0.0, 1.0]),
+                Keyframe::new(2.0, vec![1.0, 0.0]),
+            ]))
+            .with_curve("glow", Track::linear(vec![Keyframe::new(0.0, 0.0), Keyframe::new(2.0, 4.0)]));
+        assert_eq!(clip.duration, 2.0);
+
+        let mut world = ferrous_ecs::world::World::new();
+        let mut res = ResourceMap::new();
+        let position = Vec3::new(3.0, 0.0, 0.0);
+        let mut player = AnimationPlayer::new(clip);
+        player.seek(1.0);
+        let e = world.spawn((Transform::from_position(position), player));
+
+        // No clock resource: dt is 0 and the player stays at t = 1.
+        AnimationSystem.run(&mut world, &mut res);
+
+        let t = world.get::<Transform>(e).unwrap();
+        // No translation track: the position is left alone.
+        assert_eq!(t.position, position);
+        assert!(t.rotation.abs_diff_eq(Quat::from_rotation_y(std::f32::consts::FRAC_PI_4), 1e-5));
+        assert_eq!(t.scale, Vec3::splat(2.0));
+        assert_eq!(world.get::<MorphWeights>(e).unwrap().0, vec![0.5, 0.5]);
+        assert_eq!(world.get::<AnimationPlayer>(e).unwrap().curve("glow"), Some(2.0));
+    }
+
+    #[test]
+    fn animation_system_leaves_transform_unchanged_without_transform_tracks() {
+        let clip = AnimationClip::new(1.0)
+            .with_curve("glow", Track::linear(vec![Keyframe::new(0.0, 0.0), Keyframe::new(1.0, 1.0)]));
+        let (mut world, mut res) = make_world_with_clock();
+        let e = world.spawn((Transform::IDENTITY, AnimationPlayer::new(clip)));
+        let before = world.component_ticks::<Transform>(e).unwrap();
+
+        AnimationSystem.run(&mut world, &mut res);
+        assert_eq!(world.component_ticks::<Transform>(e).unwrap(), before);
+        assert!(world.get::<AnimationPlayer>(e).unwrap().curve("glow").is_some());
+    }
+
+    #[test]
+    fn player_without_layers_ignores_seek() {
+        let mut player = AnimationPlayer::default();
+        player.layers.clear();
+        player.seek(1.0);
+        assert!(player.layers.is_empty());
+    }
+
+    #[test]
+    fn clip_events_fire_once_across_loops_speed_and_seek() {
+        let clip = AnimationClip::new(1.0)
+            .with_event(0.5, "step")
+            .with_event(0.0, "start")
+            .with_looping(true);
+        let mut player = AnimationPlayer::new(clip);
+        let names = |fired: Vec<(String, String)>| -> Vec<String> {
+            fired.into_iter().map(|(_, name)| name).collect()
+        };
+
+        assert_eq!(names(player.advance(0.25)), ["start"]);
+        assert_eq!(names(player.advance(0.5)), ["step"]);
+        // Wrapping past the end of the loop reports the next cycle's start.
+        assert_eq!(names(player.advance(0.5)), ["start"]);
+        // Faster playback crosses several events in one update, in order.
+        player.speed = 3.0;
+        assert_eq!(names(player.advance(0.5)), ["step", "start", "step"]);
+        // Seeking jumps without reporting what was skipped.
+        player.speed = 1.0;
+        player.seek(0.6);
+        assert!(player.advance(0.3).is_empty());
+        // Playing backwards reports events in reverse.
+        player.speed = -1.0;
+        assert_eq!(names(player.advance(1.0)), ["step", "start"]);
+    }
+
+    #[test]
+    fn animation_system_sends_animation_events() {
+        let mut world = ferrous_ecs::world::World::new();
+        let mut res = ResourceMap::new();
+        let mut clock = crate::time::TimeClock::new();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        clock.tick();
+        res.insert(clock);
+
+        let clip = AnimationClip::new(1.0).with_event(0.0, "footstep");
+        let e = world.spawn((Transform::IDENTITY, AnimationPlayer::new(clip)));
+        AnimationSystem.run(&mut world, &mut res);
+        AnimationSystem.run(&mut world, &mut res);
+
+        let events = res.get_mut::<Events<AnimationEvent>>().unwrap();
+        events.update();
+        let sent: Vec<_> = events.read().cloned().collect();
+        assert_eq!(
+            sent,
+            [AnimationEvent { entity: e, clip: AnimationPlayer::DEFAULT_CLIP.into(), name: "footstep".into() }]
+        );
+    }
+
+    #[test]
+    fn animation_system_crossfades_and_masks_bones() {
+        use crate::scene::skinning::Bone;
+        use crate::scene::Skeleton;
+
+        let still = |x: f32| TransformTracks {
+            translation: Some(Track::step(vec![Keyframe::new(0.0, Vec3::splat(x))])),
+            ..Default::default()
+        };
+        let pose = |x: f32| {
+            AnimationClip::new(1.0)
+                .with_translation(Track::step(vec![Keyframe::new(0.0, Vec3::new(x, 0.0, 0.0))]))
+                .with_bone("hips", still(x))
+                .with_bone("arm", still(x))
+                .with_looping(true)
+        };
+        let bone = |name: &str, parent| Bone {
+            name: name.into(),
+            parent_index: parent,
+            inverse_bind_matrix: glam::Mat4::IDENTITY,
+            local_transform: Default::default(),
+        };
+        let skeleton = Skeleton::new(vec![bone("hips", None), bone("arm", Some(0))]);
+
+        let mut player = AnimationPlayer::default()
+            .with_clip("idle", pose(0.0))
+            .with_clip("walk", pose(4.0))
+            .with_clip("wave", pose(10.0));
+        player.play("idle");
+        let upper = player.add_layer(
+            AnimationLayer::new("upper").with_mask(BoneMask::from_subtree(&skeleton, "arm")),
+        );
+        player.play_on(upper, "wave");
+        player.crossfade("walk", 1.0);
+
+        let mut world = ferrous_ecs::world::World::new();
+        let mut res = ResourceMap::new();
+        let e = world.spawn((Transform::IDENTITY, skeleton, player));
+
+        // Halfway through the fade; no clock, so the system itself adds dt = 0.
+        world.get_mut::<AnimationPlayer>(e).unwrap().advance(0.5);
+        AnimationSystem.run(&mut world, &mut res);
+
+        assert_eq!(world.get::<Transform>(e).unwrap().position.x, 2.0);
+        let bones = &world.get::<Skeleton>(e).unwrap().bones;
+        assert_eq!(bones[0].local_transform.position, Vec3::splat(2.0));
+        assert_eq!(bones[1].local_transform.position, Vec3::splat(10.0));
+
+        // Once the fade completes the old clip is dropped.
+        world.get_mut::<AnimationPlayer>(e).unwrap().advance(0.6);
+        let base = &world.get::<AnimationPlayer>(e).unwrap().layers[0];
+        assert_eq!(base.clips.len(), 1);
+        assert_eq!(base.clips[0].clip, "walk");
+    }
+
     #[test]
     fn transform_system_propagates_parent() {
         let mut world = ferrous_ecs::world::World::new();
@@ -140,7 +396,7 @@ mod tests {
 
         let child_entity = world.spawn((child_transform,));
         world.insert(child_entity, GlobalTransform::default());
-        world.insert(child_entity, Parent(parent_entity));
+        world.relate::<ChildOf>(child_entity, parent_entity);
 
         let mut sys = TransformSystem;
         sys.run(&mut world, &mut res);
@@ -150,6 +406,157 @@ mod tests {
         assert!((child_pos.x - 11.0).abs() < 1e-4, "child global x = {}", child_pos.x);
     }
 
+    #[test]
+    fn transform_system_only_recomputes_changed_subtrees() {
+        let mut world = ferrous_ecs::world::World::new();
+        let mut res = ResourceMap::new();
+
+        let root = world.spawn((Transform::from_position(Vec3::new(10.0, 0.0, 0.0)),));
+        let mid = world.spawn((Transform::from_position(Vec3::new(1.0, 0.0, 0.0)),));
+        world.relate::<ChildOf>(mid, root);
+        let leaf = world.spawn((Transform::from_position(Vec3::new(0.0, 2.0, 0.0)),));
+        world.relate::<ChildOf>(leaf, mid);
+        let other = world.spawn((Transform::from_position(Vec3::ZERO),));
+
+        let mut sched = StagedScheduler::new();
+        sched.add(Stage::PostUpdate, TransformSystem);
+        sched.run_all(&mut world, &mut res);
+
+        let leaf_pos = world.get::<GlobalTransform>(leaf).unwrap().0.w_axis.truncate();
+        assert!((leaf_pos - Vec3::new(11.0, 2.0, 0.0)).length() < 1e-4, "leaf = {leaf_pos}");
+
+        // Nothing moved: cached globals are left untouched.
+        let before = world.component_ticks::<GlobalTransform>(other).unwrap();
+        sched.run_all(&mut world, &mut res);
+        assert_eq!(world.component_ticks::<GlobalTransform>(other).unwrap(), before);
+
+        // Moving the root refreshes the whole chain but not unrelated entities.
+        world.get_mut::<Transform>(root).unwrap().position.x = 20.0;
+        sched.run_all(&mut world, &mut res);
+        let leaf_pos = world.get::<GlobalTransform>(leaf).unwrap().0.w_axis.truncate();
+        assert!((leaf_pos - Vec3::new(21.0, 2.0, 0.0)).length() < 1e-4, "leaf = {leaf_pos}");
+        assert_eq!(world.component_ticks::<GlobalTransform>(other).unwrap(), before);
+    }
+
+    #[test]
+    fn transform_system_resolves_deep_chains_moved_at_several_levels() {
+        let mut world = ferrous_ecs::world::World::new();
+        let mut res = ResourceMap::new();
+
+        let mut chain = vec![world.spawn((Transform::from_position(Vec3::X),))];
+        for _ in 1..64 {
+            let link = world.spawn((Transform::from_position(Vec3::X),));
+            world.relate::<ChildOf>(link, *chain.last().unwrap());
+            chain.push(link);
+        }
+        let mut sys = TransformSystem;
+        sys.run(&mut world, &mut res);
+
+        // Root and a middle link move in the same frame.
+        world.get_mut::<Transform>(chain[0]).unwrap().position = Vec3::Y;
+        world.get_mut::<Transform>(chain[32]).unwrap().position = Vec3::Z;
+        sys.run(&mut world, &mut res);
+
+        for (depth, &link) in chain.iter().enumerate() {
+            // Below link 32 one +X step was swapped for +Z.
+            let below = if depth >= 32 { 1.0 } else { 0.0 };
+            let expected = Vec3::new(depth as f32 - below, 1.0, below);
+            let pos = world.get::<GlobalTransform>(link).unwrap().0.w_axis.truncate();
+            assert!((pos - expected).length() < 1e-4, "depth {depth}: {pos}");
+        }
+    }
+
+    #[test]
+    fn reparenting_and_despawning_follow_the_relation() {
+        let mut world = ferrous_ecs::world::World::new();
+        let mut res = ResourceMap::new();
+
+        let a = world.spawn((Transform::from_position(Vec3::new(10.0, 0.0, 0.0)),));
+        let b = world.spawn((Transform::from_position(Vec3::new(0.0, 5.0, 0.0)),));
+        let child = world.spawn((Transform::from_position(Vec3::new(1.0, 0.0, 0.0)),));
+        let grandchild = world.spawn((Transform::IDENTITY,));
+        world.relate::<ChildOf>(child, a);
+        world.relate::<ChildOf>(grandchild, child);
+
+        let mut sched = StagedScheduler::new();
+        sched.add(Stage::PostUpdate, TransformSystem);
+        sched.run_all(&mut world, &mut res);
+
+        // Re-parent: the old parent forgets the child.
+        world.relate::<ChildOf>(child, b);
+        assert!(world.get::<Children>(a).is_none());
+        assert_eq!(world.get::<Parent>(child).unwrap().target(), b);
+        sched.run_all(&mut world, &mut res);
+        let pos = world.get::<GlobalTransform>(grandchild).unwrap().0.w_axis.truncate();
+        assert!((pos - Vec3::new(1.0, 5.0, 0.0)).length() < 1e-4, "grandchild = {pos}");
+
+        // Detach: the child's local transform becomes its world transform.
+        world.unrelate::<ChildOf>(child, b);
+        sched.run_all(&mut world, &mut res);
+        let pos = world.get::<GlobalTransform>(grandchild).unwrap().0.w_axis.truncate();
+        assert!((pos - Vec3::new(1.0, 0.0, 0.0)).length() < 1e-4, "grandchild = {pos}");
+
+        // Despawning a parent takes its subtree with it.
+        world.despawn(child);
+        assert!(!world.contains(grandchild));
+        assert_eq!(world.len(), 2);
+    }
+
+    #[test]
+    fn transform_system_handles_removed_transforms_and_dead_parents() {
+        let mut world = ferrous_ecs::world::World::new();
+        let mut res = ResourceMap::new();
+        let parent = world.spawn((Transform::from_position(Vec3::new(10.0, 0.0, 0.0)),));
+        let child = world.spawn((Transform::from_position(Vec3::new(1.0, 0.0, 0.0)),));
+        world.relate::<ChildOf>(child, parent);
+
+        let mut sched = StagedScheduler::new();
+        sched.add(Stage::PostUpdate, TransformSystem);
+        sched.run_all(&mut world, &mut res);
+        let child_pos = |world: &ferrous_ecs::world::World| world.get::<GlobalTransform>(child).unwrap().0.w_axis.truncate();
+        assert!((child_pos(&world) - Vec3::new(11.0, 0.0, 0.0)).length() < 1e-4);
+
+        // Without a `Transform` the parent no longer offsets its child.
+        world.remove::<Transform>(parent);
+        sched.run_all(&mut world, &mut res);
+        assert!(world.get::<GlobalTransform>(parent).is_none());
+        assert!((child_pos(&world) - Vec3::new(1.0, 0.0, 0.0)).length() < 1e-4);
+
+        world.insert(parent, Transform::from_position(Vec3::new(10.0, 0.0, 0.0)));
+        sched.run_all(&mut world, &mut res);
+        assert!((child_pos(&world) - Vec3::new(11.0, 0.0, 0.0)).length() < 1e-4);
+
+        // A parent despawned behind the relation's back leaves a root.
+        world.remove::<Children>(parent);
+        world.despawn(parent);
+        sched.run_all(&mut world, &mut res);
+        assert!(world.get::<Parent>(child).is_none());
+        assert!((child_pos(&world) - Vec3::new(1.0, 0.0, 0.0)).length() < 1e-4);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn legacy_parent_and_children_constructors_join_the_relation() {
+        let mut world = ferrous_ecs::world::World::new();
+        let mut res = ResourceMap::new();
+        let parent = world.spawn((Transform::from_position(Vec3::new(10.0, 0.0, 0.0)),));
+        let child = world.spawn((Transform::from_position(Vec3::new(1.0, 0.0, 0.0)),));
+        let sibling = world.spawn((Transform::IDENTITY,));
+        world.insert(child, Parent(parent));
+        world.insert(parent, Children(vec![sibling]));
+
+        let mut sched = StagedScheduler::new();
+        sched.add(Stage::PostUpdate, TransformSystem);
+        sched.run_all(&mut world, &mut res);
+        let pos = world.get::<GlobalTransform>(child).unwrap().0.w_axis.truncate();
+        assert!((pos - Vec3::new(11.0, 0.0, 0.0)).length() < 1e-4, "child = {pos}");
+        assert_eq!(world.sources::<ChildOf>(parent), &[sibling, child]);
+        assert_eq!(world.target::<ChildOf>(sibling), Some(parent));
+
+        world.despawn(parent);
+        assert!(world.is_empty());
+    }
+
     #[test]
     fn behavior_system_calls_update() {
         use std::sync::{Arc, Mutex};
@@ -0,0 +1,192 @@
+//! Path following along a [`Spline`].
+
+#![cfg(feature = "ecs")]
+
+use ferrous_ecs::prelude::*;
+use ferrous_ecs::system::System;
+use serde::{Deserialize, Serialize};
+
+use crate::scene::spline::Spline;
+use crate::time::{Time, TimeClock};
+use crate::transform::Transform;
+
+// ────────────────────────────────────────────────────────────────────────────
+// FollowPath component
+
+/// What a [`FollowPath`] does at the end of an open path.  Closed splines
+/// always loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PathEnd {
+    /// Stop at the end.
+    #[default]
+    Stop,
+    /// Jump back to the other end.
+    Loop,
+    /// Reverse direction.
+    PingPong,
+}
+
+/// Moves the entity's `Transform` along the [`Spline`] of another entity at
+/// a constant speed.
+///
+/// The spline's points are mapped through the path entity's `Transform`,
+/// and the result is written to the follower's `Transform` — so both are
+/// expected to be roots (or the follower a child of the path entity with an
+/// identity path `Transform`).
+///
+/// # Example
+/// ```rust,ignore
+/// let rail = world.spawn((Transform::IDENTITY, Spline::new(SplineKind::CatmullRom, points)));
+/// world.spawn((Transform::IDENTITY, FollowPath::new(rail, 4.0).with_end(PathEnd::PingPong)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FollowPath {
+    /// Entity holding the `Spline`.
+    pub path: Entity,
+    /// Units per second along the curve; negative travels backwards.
+    pub speed: f32,
+    /// Current arc length along the spline.
+    pub distance: f32,
+    pub end: PathEnd,
+    /// Rotate the follower so its forward (-Z) follows the tangent.
+    pub orient: bool,
+    /// Up vector used for orientation, in the path's space.
+    pub up: glam::Vec3,
+}
+impl Component for FollowPath {}
+
+impl MapEntities for FollowPath {
+    fn map_entities(&mut self, map: &EntityMap) {
+        self.path = map.map(self.path);
+    }
+}
+
+impl FollowPath {
+    pub fn new(path: Entity, speed: f32) -> Self {
+        FollowPath { path, speed, distance: 0.0, end: PathEnd::Stop, orient: true, up: glam::Vec3::Y }
+    }
+
+    pub fn with_end(mut self, end: PathEnd) -> Self {
+        self.end = end;
+        self
+    }
+
+    pub fn with_orient(mut self, orient: bool) -> Self {
+        self.orient = orient;
+        self
+    }
+
+    pub fn with_distance(mut self, distance: f32) -> Self {
+        self.distance = distance;
+        self
+    }
+
+    /// Advance `distance` by `speed * dt` on a path of `length`, applying
+    /// [`PathEnd`] to open paths.
+    fn advance(&mut self, dt: f32, length: f32, closed: bool) {
+        self.distance += self.speed * dt;
+        if closed || length <= 0.0 {
+            return;
+        }
+        match self.end {
+            PathEnd::Stop => self.distance = self.distance.clamp(0.0, length),
+            PathEnd::Loop => self.distance = self.distance.rem_euclid(length),
+            PathEnd::PingPong => {
+                // Reflect off either end until back in range.
+                while !(0.0..=length).contains(&self.distance) {
+                    self.distance = if self.distance > length {
+                        2.0 * length - self.distance
+                    } else {
+                        -self.distance
+                    };
+                    self.speed = -self.speed;
+                }
+            }
+        }
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// FollowPathSystem
+
+/// Advances every [`FollowPath`] and places its entity on the spline.
+///
+/// Register at `Stage::Update`, before `TransformSystem`.  Uses the frame
+/// delta from `Time` / `TimeClock`.
+pub struct FollowPathSystem;
+
+impl System for FollowPathSystem {
+    fn name(&self) -> &'static str {
+        "FollowPathSystem"
+    }
+
+    fn run(&mut self, world: &mut ferrous_ecs::world::World, resources: &mut ResourceMap) {
+        let dt = if let Some(time) = resources.get::<Time>() {
+            time.delta
+        } else {
+            resources.get::<TimeClock>().map(|c| c.at_tick().delta).unwrap_or(0.0)
+        };
+
+        let followers: Vec<(Entity, FollowPath)> = world.query::<FollowPath>().map(|(e, f)| (e, *f)).collect();
+        for (entity, mut follow) in followers {
+            let Some(spline) = world.get::<Spline>(follow.path) else {
+                continue;
+            };
+            follow.advance(dt, spline.length(), spline.is_closed());
+            let frame = spline.frame_at(follow.distance, follow.up);
+            let path_transform = world.get::<Transform>(follow.path).copied().unwrap_or(Transform::IDENTITY);
+
+            if let Some(f) = world.get_mut::<FollowPath>(entity) {
+                *f = follow;
+            }
+            if let Some(t) = world.get_mut::<Transform>(entity) {
+                t.position = path_transform.matrix().transform_point3(frame.position);
+                if follow.orient {
+                    let forward = if follow.speed < 0.0 { -1.0 } else { 1.0 };
+                    let frame = crate::scene::spline::SplineFrame {
+                        tangent: frame.tangent * forward,
+                        binormal: frame.binormal * forward,
+                        ..frame
+                    };
+                    t.rotation = path_transform.rotation * frame.rotation();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::spline::SplineKind;
+    use glam::Vec3;
+
+    #[test]
+    fn follower_moves_at_constant_speed_and_ping_pongs() {
+        let mut world = ferrous_ecs::world::World::new();
+        let mut resources = ResourceMap::new();
+        resources.insert(Time { delta: 0.5, ..Default::default() });
+
+        let points = vec![Vec3::ZERO, Vec3::X * 2.0, Vec3::X * 4.0];
+        let rail = world.spawn((
+            Transform::from_position(Vec3::Y),
+            Spline::new(SplineKind::CatmullRom, points),
+        ));
+        let cart = world.spawn((Transform::IDENTITY, FollowPath::new(rail, 2.0).with_end(PathEnd::PingPong)));
+
+        FollowPathSystem.run(&mut world, &mut resources);
+        let t = world.get::<Transform>(cart).unwrap();
+        assert!(t.position.abs_diff_eq(Vec3::new(1.0, 1.0, 0.0), 1e-3));
+        assert!((t.rotation * Vec3::NEG_Z).abs_diff_eq(Vec3::X, 1e-4));
+
+        for _ in 0..4 {
+            FollowPathSystem.run(&mut world, &mut resources);
+        }
+        // 5 m travelled on a 4 m rail: bounced back to 3 m, now heading -X.
+        let follow = world.get::<FollowPath>(cart).unwrap();
+        assert!((follow.distance - 3.0).abs() < 1e-3);
+        assert!(follow.speed < 0.0);
+        let t = world.get::<Transform>(cart).unwrap();
+        assert!((t.rotation * Vec3::NEG_Z).abs_diff_eq(Vec3::NEG_X, 1e-4));
+    }
+}
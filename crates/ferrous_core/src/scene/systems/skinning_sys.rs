@@ -2,25 +2,31 @@
 
 use ferrous_ecs::prelude::*;
 use ferrous_ecs::system::System;
-use crate::scene::Skeleton;
+use crate::scene::{IkConstraints, Skeleton};
 
+/// Rebuilds every `Skeleton`'s joint matrices, with its `IkConstraints`
+/// solved on top of the animated pose.
+///
+/// The IK result only reaches `joint_matrices`; the bones keep the animated
+/// pose (see [`IkConstraints::apply`]).
 pub struct SkinningSystem;
 
 impl System for SkinningSystem {
     fn name(&self) -> &'static str { "SkinningSystem" }
 
     fn run(&mut self, world: &mut ferrous_ecs::world::World, _resources: &mut ResourceMap) {
-        // Find entities with a Skeleton and update their bone matrices.
-        // We do this by collecting IDs first because we need mutable access.
-        let entities: Vec<ferrous_ecs::entity::Entity> = world
-            .query::<Skeleton>()
-            .map(|(e, _)| e)
-            .collect();
+        let update = |_, (skeleton, ik): (&mut Skeleton, Option<&IkConstraints>)| match ik {
+            Some(ik) => ik.apply(skeleton),
+            None => skeleton.update_matrices(),
+        };
 
-        for entity in entities {
-            if let Some(skeleton) = world.get_mut::<Skeleton>(entity) {
-                skeleton.update_matrices();
-            }
-        }
+        // Every skeleton is independent, so their bone matrices are rebuilt
+        // in parallel when the `parallel` feature is on.
+        #[cfg(feature = "parallel")]
+        Query::<(&mut Skeleton, Option<&IkConstraints>)>::new(world).par_iter_mut().for_each(update);
+        #[cfg(not(feature = "parallel"))]
+        Query::<(&mut Skeleton, Option<&IkConstraints>)>::new(world)
+            .iter()
+            .for_each(|(e, item)| update(e, item));
     }
 }
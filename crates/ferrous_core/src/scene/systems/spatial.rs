@@ -0,0 +1,395 @@
+//! Scene-wide spatial index over entity bounds.
+
+#![cfg(feature = "ecs")]
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, Weak};
+
+use ferrous_ecs::prelude::*;
+use ferrous_ecs::system::System;
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+
+use crate::bounds::{Aabb, Frustum};
+use crate::bvh::{AabbTree, ProxyId};
+use crate::scene::systems::hierarchy::GlobalTransform;
+
+// ────────────────────────────────────────────────────────────────────────────
+// LocalBounds component
+
+/// Bounds of an entity in its own space.  Entities with `LocalBounds` and a
+/// `GlobalTransform` are indexed by [`SpatialIndexSystem`].
+///
+/// `World` attaches one to every entity whose shape it knows (primitives and
+/// meshes with registered geometry).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LocalBounds(pub Aabb);
+impl Component for LocalBounds {}
+
+// ────────────────────────────────────────────────────────────────────────────
+// SpatialRemovals
+
+/// Entities that lost `LocalBounds` or `GlobalTransform` since the owning
+/// index last synced.
+#[derive(Debug, Default)]
+struct Removals {
+    entities: Vec<Entity>,
+    /// Set when the world was cleared, which runs no hooks.
+    cleared: bool,
+}
+
+/// Reports every removal of `LocalBounds` or `GlobalTransform` on one world,
+/// despawns included, to the [`SpatialIndex`]es tracking it, so they never
+/// scan their entries for stale ones.
+///
+/// `World::new` installs one on its ECS world (see
+/// `World::spatial_removals`).  `EcsWorld::clear` runs no hooks; clear
+/// through `World::clear`, which calls [`SpatialRemovals::clear`].
+#[derive(Debug, Default, Clone)]
+pub struct SpatialRemovals {
+    queues: Arc<Mutex<Vec<Weak<Mutex<Removals>>>>>,
+}
+
+impl SpatialRemovals {
+    /// Register the `on_remove` hooks on `world` and return the hub they
+    /// report to.
+    pub fn install(world: &mut ferrous_ecs::world::World) -> Self {
+        let removals = Self::default();
+        let (bounds, global) = (removals.clone(), removals.clone());
+        world.component_hooks::<LocalBounds>().on_remove(move |_, e| bounds.push(e));
+        world.component_hooks::<GlobalTransform>().on_remove(move |_, e| global.push(e));
+        removals
+    }
+
+    /// Tell every tracking index that the world was emptied.
+    pub fn clear(&self) {
+        self.for_each_queue(|queue| {
+            queue.entities.clear();
+            queue.cleared = true;
+        });
+    }
+
+    fn push(&self, entity: Entity) {
+        self.for_each_queue(|queue| queue.entities.push(entity));
+    }
+
+    /// Run `f` on the queue of every live index, forgetting dropped ones.
+    fn for_each_queue(&self, mut f: impl FnMut(&mut Removals)) {
+        self.queues.lock().unwrap().retain(|queue| match queue.upgrade() {
+            Some(queue) => {
+                f(&mut queue.lock().unwrap());
+                true
+            }
+            None => false,
+        });
+    }
+
+    fn subscribe(&self) -> Arc<Mutex<Removals>> {
+        let queue = Arc::default();
+        self.queues.lock().unwrap().push(Arc::downgrade(&queue));
+        queue
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// SpatialIndex resource
+
+/// World-space [`AabbTree`] of every indexed entity.
+///
+/// Kept up to date by [`SpatialIndexSystem`]; read it from any system that
+/// needs overlap, ray or nearest-neighbour queries.
+///
+/// ```rust,ignore
+/// let index = resources.get::<SpatialIndex>().unwrap();
+/// for e in index.query_sphere(explosion, 5.0) { /* … */ }
+/// ```
+#[derive(Debug, Default)]
+pub struct SpatialIndex {
+    tree: AabbTree<Entity>,
+    proxies: HashMap<Entity, ProxyId>,
+    /// The tracked world's [`SpatialRemovals`] and the queue it fills.
+    removals: Option<(SpatialRemovals, Arc<Mutex<Removals>>)>,
+}
+
+impl SpatialIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The underlying tree, for queries not wrapped here.
+    pub fn tree(&self) -> &AabbTree<Entity> {
+        &self.tree
+    }
+
+    pub fn len(&self) -> usize {
+        self.proxies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.proxies.is_empty()
+    }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.proxies.contains_key(&entity)
+    }
+
+    /// World-space bounds of `entity` as last indexed.
+    pub fn bounds(&self, entity: Entity) -> Option<Aabb> {
+        self.tree.bounds(*self.proxies.get(&entity)?)
+    }
+
+    /// Insert or move `entity`.  Returns `true` if the tree was restructured.
+    pub fn update(&mut self, entity: Entity, aabb: Aabb) -> bool {
+        match self.proxies.get(&entity) {
+            Some(&id) => self.tree.update(id, aabb),
+            None => {
+                self.proxies.insert(entity, self.tree.insert(aabb, entity));
+                true
+            }
+        }
+    }
+
+    /// Drop `entity` from the index.
+    pub fn remove(&mut self, entity: Entity) -> bool {
+        match self.proxies.remove(&entity) {
+            Some(id) => self.tree.remove(id).is_some(),
+            None => false,
+        }
+    }
+
+    /// Rebuild the tree with the surface area heuristic.
+    pub fn rebuild(&mut self) {
+        self.tree.rebuild();
+    }
+
+    /// Start receiving the removals reported by `removals`, replacing any
+    /// world tracked before.  Removals before this call are not seen.
+    pub fn track(&mut self, removals: &SpatialRemovals) {
+        self.removals = Some((removals.clone(), removals.subscribe()));
+    }
+
+    /// Whether the index [`track`](Self::track)s `removals`.
+    pub fn is_tracking(&self, removals: &SpatialRemovals) -> bool {
+        self.removals.as_ref().is_some_and(|(r, _)| Arc::ptr_eq(&r.queues, &removals.queues))
+    }
+
+    /// Drop entities that lost `LocalBounds` or `GlobalTransform` since the
+    /// previous call (everything, if the world was cleared), re-index those
+    /// whose `GlobalTransform` or `LocalBounds` changed after tick
+    /// `last_run`, and rebuild with SAH when more than half of the tree was
+    /// restructured.
+    ///
+    /// Removals are only seen once the index [`track`](Self::track)s the
+    /// world's [`SpatialRemovals`].  [`SpatialIndexSystem`] calls this every
+    /// frame; code keeping its own index outside the schedule (e.g. a
+    /// renderer) passes the world's `change_tick()` from its previous call.
+    pub fn sync(&mut self, world: &ferrous_ecs::world::World, last_run: u64) {
+        let removed = self.removals.as_ref().map(|(_, queue)| std::mem::take(&mut *queue.lock().unwrap()));
+        if let Some(removed) = removed {
+            if removed.cleared {
+                self.tree = AabbTree::default();
+                self.proxies.clear();
+            }
+            for entity in removed.entities {
+                self.remove(entity);
+            }
+        }
+
+        let mut restructured = 0;
+        for (entity, (global, bounds)) in Query::<
+            (&GlobalTransform, &LocalBounds),
+            Or<(Changed<GlobalTransform>, Changed<LocalBounds>)>,
+        >::new_since(world, last_run)
+        .iter()
+        {
+            if self.update(entity, bounds.0.transform(&global.0)) {
+                restructured += 1;
+            }
+        }
+        if restructured > 8 && restructured * 2 > self.len() {
+            self.rebuild();
+        }
+    }
+
+    /// Entities whose bounds overlap `aabb`.
+    pub fn query_aabb(&self, aabb: &Aabb) -> Vec<Entity> {
+        let mut out = Vec::new();
+        self.tree.query_aabb(aabb, |_, &e| out.push(e));
+        out
+    }
+
+    /// Entities whose bounds overlap the sphere.
+    pub fn query_sphere(&self, center: Vec3, radius: f32) -> Vec<Entity> {
+        let mut out = Vec::new();
+        self.tree.query_sphere(center, radius, |_, &e| out.push(e));
+        out
+    }
+
+    /// Entities whose bounds are (conservatively) inside `frustum`.
+    pub fn query_frustum(&self, frustum: &Frustum) -> Vec<Entity> {
+        let mut out = Vec::new();
+        self.tree.query_frustum(frustum, |_, &e| out.push(e));
+        out
+    }
+
+    /// Entities whose bounds the ray hits within `max_dist`, with the entry
+    /// distance, nearest first.  `dir` is normalised.
+    pub fn raycast(&self, origin: Vec3, dir: Vec3, max_dist: f32) -> Vec<(Entity, f32)> {
+        let Some(dir) = dir.try_normalize() else {
+            return Vec::new();
+        };
+        let mut out = Vec::new();
+        self.tree.raycast(origin, dir, max_dist, |_, &e, t| {
+            out.push((e, t));
+            max_dist
+        });
+        out.sort_by(|a, b| a.1.total_cmp(&b.1));
+        out
+    }
+
+    /// The `k` entities nearest to `point` with their distance, nearest first.
+    pub fn nearest(&self, point: Vec3, k: usize) -> Vec<(Entity, f32)> {
+        self.tree
+            .nearest(point, k)
+            .into_iter()
+            .filter_map(|(id, d)| Some((*self.tree.get(id)?, d)))
+            .collect()
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// SpatialIndexSystem
+
+/// Maintains the [`SpatialIndex`] resource (inserted on first run).
+///
+/// Only entities whose `GlobalTransform` or `LocalBounds` changed since the
+/// previous run are re-indexed; entities that lost either component (or were
+/// despawned) are dropped as reported by the [`SpatialRemovals`] resource,
+/// which is installed on the world on first run when missing.  When more
+/// than half of the index had to be restructured in one run the tree is
+/// rebuilt with SAH.
+///
+/// Register at `Stage::PostUpdate`, after `TransformSystem`.
+pub struct SpatialIndexSystem;
+
+impl System for SpatialIndexSystem {
+    fn name(&self) -> &'static str {
+        "SpatialIndexSystem"
+    }
+
+    fn run(&mut self, world: &mut ferrous_ecs::world::World, resources: &mut ResourceMap) {
+        if !resources.contains::<SpatialRemovals>() {
+            resources.insert(SpatialRemovals::install(world));
+        }
+        let removals = resources.get::<SpatialRemovals>().unwrap().clone();
+        let index = resources.get_or_insert_default::<SpatialIndex>();
+        if !index.is_tracking(&removals) {
+            index.track(&removals);
+        }
+        let last_run = world.last_change_tick();
+        index.sync(world, last_run);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::systems::hierarchy::{ChildOf, TransformSystem};
+    use crate::transform::Transform;
+
+    #[test]
+    fn index_follows_global_transforms_and_despawns() {
+        let mut world = ferrous_ecs::world::World::new();
+        let mut resources = ResourceMap::new();
+        let unit = LocalBounds(Aabb::unit_cube());
+        let near = world.spawn((Transform::from_position(Vec3::new(2.0, 0.0, 0.0)), unit));
+        let far = world.spawn((Transform::from_position(Vec3::new(20.0, 0.0, 0.0)), unit));
+        let child = world.spawn((Transform::from_position(Vec3::Y * 3.0), unit));
+        world.relate::<ChildOf>(child, far);
+        world.spawn((Transform::IDENTITY,)); // no bounds: not indexed
+
+        TransformSystem.run(&mut world, &mut resources);
+        SpatialIndexSystem.run(&mut world, &mut resources);
+        let index = resources.get::<SpatialIndex>().unwrap();
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.bounds(child), Some(Aabb::new(Vec3::new(19.0, 2.0, -1.0), Vec3::new(21.0, 4.0, 1.0))));
+        assert_eq!(index.query_sphere(Vec3::ZERO, 1.5), vec![near]);
+        let hits = index.raycast(Vec3::new(-5.0, 0.0, 0.0), Vec3::X, 100.0);
+        assert_eq!(hits.iter().map(|h| h.0).collect::<Vec<_>>(), vec![near, far]);
+        assert!((hits[0].1 - 6.0).abs() < 1e-5);
+
+        // Moving the parent moves the child's entry; despawning drops it.
+        world.get_mut::<Transform>(far).unwrap().position = Vec3::ZERO;
+        world.despawn(near);
+        TransformSystem.run(&mut world, &mut resources);
+        SpatialIndexSystem.run(&mut world, &mut resources);
+        let index = resources.get::<SpatialIndex>().unwrap();
+        assert_eq!(index.len(), 2);
+        assert!(!index.contains(near));
+        let nearest = index.nearest(Vec3::new(0.0, 3.0, 0.0), 1);
+        assert_eq!(nearest, vec![(child, 0.0)]);
+    }
+
+    #[test]
+    fn sync_only_reindexes_entities_changed_after_last_run() {
+        let mut world = ferrous_ecs::world::World::new();
+        let mut resources = ResourceMap::new();
+        let unit = LocalBounds(Aabb::unit_cube());
+        let still = world.spawn((Transform::IDENTITY, unit));
+        let moving = world.spawn((Transform::from_position(Vec3::X * 5.0), unit));
+        TransformSystem.run(&mut world, &mut resources);
+
+        let mut index = SpatialIndex::new();
+        index.track(&SpatialRemovals::install(&mut world));
+        index.sync(&world, 0);
+        assert_eq!(index.len(), 2);
+        let synced = world.change_tick();
+
+        // A bogus entry for `still` survives: its transform did not change.
+        let bogus = Aabb::new(Vec3::splat(100.0), Vec3::splat(101.0));
+        index.update(still, bogus);
+        world.get_mut::<GlobalTransform>(moving).unwrap().0 = glam::Mat4::from_translation(Vec3::X * 10.0);
+        index.sync(&world, synced);
+        assert_eq!(index.bounds(still), Some(bogus));
+        assert_eq!(index.bounds(moving), Some(Aabb::new(Vec3::new(9.0, -1.0, -1.0), Vec3::new(11.0, 1.0, 1.0))));
+
+        world.remove::<LocalBounds>(moving);
+        index.sync(&world, world.change_tick());
+        assert!(!index.contains(moving));
+    }
+
+    #[test]
+    fn tracked_indices_drop_removed_and_cleared_entities() {
+        let mut world = crate::scene::World::new();
+        let mut resources = ResourceMap::new();
+        let unit = LocalBounds(Aabb::unit_cube());
+        let kept = world.ecs.spawn((Transform::IDENTITY, unit));
+        let gone = world.ecs.spawn((Transform::from_position(Vec3::X * 5.0), unit));
+        world.ecs.spawn((Transform::from_position(Vec3::X * 9.0), unit));
+        TransformSystem.run(&mut world.ecs, &mut resources);
+
+        let mut index = SpatialIndex::new();
+        index.track(world.spatial_removals());
+        assert!(index.is_tracking(world.spatial_removals()));
+        assert!(!index.is_tracking(&SpatialRemovals::default()));
+        index.sync(&world.ecs, 0);
+        assert_eq!(index.len(), 3);
+
+        let tick = world.ecs.change_tick();
+        world.ecs.despawn(gone);
+        index.sync(&world.ecs, tick);
+        assert_eq!(index.len(), 2);
+        assert!(index.contains(kept));
+        assert!(!index.contains(gone));
+
+        // Clearing runs no hooks, but `World::clear` still empties the index;
+        // the reused entity id is indexed afresh.
+        world.clear();
+        let tick = world.ecs.change_tick();
+        let fresh = world.ecs.spawn((Transform::from_position(Vec3::Y * 9.0), unit));
+        TransformSystem.run(&mut world.ecs, &mut resources);
+        index.sync(&world.ecs, tick);
+        assert_eq!(index.len(), 1);
+        assert_eq!(index.query_sphere(Vec3::Y * 9.0, 0.5), vec![fresh]);
+    }
+}
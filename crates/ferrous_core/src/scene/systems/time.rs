@@ -4,20 +4,25 @@
 
 use ferrous_ecs::prelude::*;
 use ferrous_ecs::system::System;
+use serde::{Deserialize, Serialize};
+
+use crate::time::{Time, TimeClock};
+use crate::transform::Transform;
 
 // ────────────────────────────────────────────────────────────────────────────
 // Velocity component
 
 /// Linear velocity in world space (metres / second).
 ///
-/// Attach this component to any entity that should move automatically each
-/// frame.  `VelocitySystem` integrates position by `velocity * delta_time`.
+/// Attach this component to any entity that should move automatically.
+/// `VelocitySystem` integrates position by `velocity * fixed_delta` each
+/// fixed step.
 ///
 /// # Example
 /// ```rust,ignore
 /// world.ecs.spawn((Transform::from_position(Vec3::ZERO), Velocity(Vec3::X * 2.0)));
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Velocity(pub glam::Vec3);
 impl Component for Velocity {}
 
@@ -30,10 +35,12 @@ impl Default for Velocity {
 // ────────────────────────────────────────────────────────────────────────────
 // TimeSystem
 
-/// Updates the `TimeClock` resource at the start of each frame.
+/// Updates the `TimeClock` resource at the start of each frame and feeds the
+/// frame delta into [`FixedTime`] for the `FixedUpdate` accumulator.
 ///
 /// Register at `Stage::PreUpdate` so all other systems see a consistent
-/// `Time` value for the current frame.
+/// `Time` value for the current frame.  When the runner provides a `Time`
+/// snapshot resource instead of a clock, its delta is forwarded as-is.
 pub struct TimeSystem;
 
 impl System for TimeSystem {
@@ -42,8 +49,84 @@ impl System for TimeSystem {
     }
 
     fn run(&mut self, _world: &mut ferrous_ecs::world::World, resources: &mut ResourceMap) {
-        if let Some(clock) = resources.get_mut::<crate::time::TimeClock>() {
-            clock.tick();
+        let delta = if let Some(clock) = resources.get_mut::<TimeClock>() {
+            clock.tick().delta
+        } else if let Some(time) = resources.get::<Time>() {
+            time.delta
+        } else {
+            return;
+        };
+        resources.get_or_insert_default::<FixedTime>().delta = delta;
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// FixedTimeSystem
+
+/// Copies the fixed step and interpolation alpha published by the scheduler
+/// into the `Time` / `TimeClock` resources.
+///
+/// Register first in `Stage::Update`, i.e. after this frame's fixed steps.
+pub struct FixedTimeSystem;
+
+impl System for FixedTimeSystem {
+    fn name(&self) -> &'static str {
+        "FixedTimeSystem"
+    }
+
+    fn run(&mut self, _world: &mut ferrous_ecs::world::World, resources: &mut ResourceMap) {
+        let Some(&FixedTime { step, alpha, .. }) = resources.get::<FixedTime>() else {
+            return;
+        };
+        if let Some(time) = resources.get_mut::<Time>() {
+            time.fixed_delta = step;
+            time.alpha = alpha;
+        }
+        if let Some(clock) = resources.get_mut::<TimeClock>() {
+            clock.set_fixed(step, alpha);
+        }
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Interpolation snapshot
+
+/// The entity's `Transform` as of the previous fixed step.
+///
+/// Opt-in: attach it to entities simulated in `Stage::FixedUpdate` whose
+/// motion should look smooth at any frame rate.  `TransformSnapshotSystem`
+/// refreshes it at the start of every step, so between frames the pair
+/// `(PreviousTransform, Transform)` brackets the simulated motion and
+/// rendering can blend them with `Time::alpha`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PreviousTransform(pub Transform);
+impl Component for PreviousTransform {}
+
+impl PreviousTransform {
+    /// Blend from the previous towards the `current` state (`alpha` in `[0, 1]`).
+    pub fn interpolate(&self, current: &Transform, alpha: f32) -> Transform {
+        Transform {
+            position: self.0.position.lerp(current.position, alpha),
+            rotation: self.0.rotation.slerp(current.rotation, alpha),
+            scale: self.0.scale.lerp(current.scale, alpha),
+        }
+    }
+}
+
+/// Copies `Transform` into `PreviousTransform` before each fixed step.
+///
+/// Register in `Stage::FixedUpdate`, ordered before every system that moves
+/// entities (see [`labels::TRANSFORM_SNAPSHOT`](super::labels::TRANSFORM_SNAPSHOT)).
+pub struct TransformSnapshotSystem;
+
+impl System for TransformSnapshotSystem {
+    fn name(&self) -> &'static str {
+        "TransformSnapshotSystem"
+    }
+
+    fn run(&mut self, world: &mut ferrous_ecs::world::World, _resources: &mut ResourceMap) {
+        for (_, (current, previous)) in Query::<(&Transform, &mut PreviousTransform)>::new(world).iter() {
+            previous.0 = *current;
         }
     }
 }
@@ -51,9 +134,11 @@ impl System for TimeSystem {
 // ────────────────────────────────────────────────────────────────────────────
 // VelocitySystem
 
-/// Integrates `Velocity` into `Transform::position` each frame.
+/// Integrates `Velocity` into `Transform::position` each fixed step.
 ///
-/// Register at `Stage::Update`.  Reads `TimeClock` from resources for `dt`.
+/// Register at `Stage::FixedUpdate`.  Steps by `FixedTime::step`; without a
+/// `FixedTime` resource it falls back to the frame delta from `Time` /
+/// `TimeClock`.
 pub struct VelocitySystem;
 
 impl System for VelocitySystem {
@@ -66,10 +151,13 @@ impl System for VelocitySystem {
         world: &mut ferrous_ecs::world::World,
         resources: &mut ResourceMap,
     ) {
-        let dt = resources
-            .get::<crate::time::TimeClock>()
-            .map(|c| c.at_tick().delta)
-            .unwrap_or(0.0);
+        let dt = if let Some(fixed) = resources.get::<FixedTime>() {
+            fixed.step
+        } else if let Some(time) = resources.get::<Time>() {
+            time.delta
+        } else {
+            resources.get::<TimeClock>().map(|c| c.at_tick().delta).unwrap_or(0.0)
+        };
 
         if dt <= 0.0 {
             return;
@@ -81,7 +169,7 @@ impl System for VelocitySystem {
             .collect();
 
         for (entity, vel) in pairs {
-            if let Some(t) = world.get_mut::<crate::transform::Transform>(entity) {
+            if let Some(t) = world.get_mut::<Transform>(entity) {
                 t.position += vel * dt;
             }
         }
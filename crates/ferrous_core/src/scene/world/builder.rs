@@ -4,7 +4,7 @@
 use glam::{Quat, Vec3};
 
 use crate::color::Color;
-use crate::scene::{MaterialDescriptor, MaterialHandle};
+use crate::scene::{LocalBounds, MaterialDescriptor, MaterialHandle};
 use crate::transform::Transform;
 
 use super::scene::World;
@@ -105,6 +105,9 @@ impl<'a> EntityBuilder<'a> {
         if let Some(pl) = self.element.point_light {
             self.world.ecs.insert(entity, pl);
         }
+        if let Some(bounds) = self.world.local_bounds(&self.element.kind) {
+            self.world.ecs.insert(entity, LocalBounds(bounds));
+        }
 
         let idx = id as usize;
         if idx >= self.world.entities.len() {
@@ -0,0 +1,212 @@
+//! Node graphs — trees of named transforms carrying meshes, cameras and
+//! lights — and [`World::spawn_graph`], which instantiates one.
+//!
+//! Importers (the glTF bridge in `ferrous_app`) translate their files into a
+//! [`SceneGraph`], so the world itself stays format-agnostic.
+//!
+//! ```rust,ignore
+//! let mut graph = SceneGraph::default();
+//! let root = graph.add(SceneGraphNode::new("level"));
+//! let lamp = graph.add(
+//!     SceneGraphNode::new("lamp")
+//!         .with_transform(Transform::from_position(Vec3::Y * 3.0))
+//!         .with_light(NodeLight::Point(PointLightComponent::default())),
+//! );
+//! graph.nodes[root].children.push(lamp);
+//! let handles = world.spawn_graph(&graph);
+//! ```
+
+use glam::{Mat4, Vec3};
+use serde::{Deserialize, Serialize};
+
+use crate::color::Color;
+use crate::scene::{Camera3D, ChildOf, DirectionalLight};
+use crate::transform::Transform;
+
+use super::scene::World;
+use super::types::{ElementKind, Handle, MaterialComponent, PointLightComponent};
+
+/// A mesh drawn at a node: a registered mesh asset key and its material.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeMesh {
+    pub asset_key: String,
+    /// `None` keeps the default material.
+    pub material: Option<MaterialComponent>,
+}
+
+/// Perspective camera attached to a node, looking down the node's -Z axis.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NodeCamera {
+    /// Vertical field of view in degrees.
+    pub fov_deg: f32,
+    pub near: f32,
+    pub far: f32,
+}
+
+/// Light attached to a node.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum NodeLight {
+    Point(PointLightComponent),
+    /// Shines down the node's -Z axis.
+    Directional { color: Color, intensity: f32 },
+}
+
+/// One node of a [`SceneGraph`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SceneGraphNode {
+    pub name: String,
+    /// Transform relative to the parent node.
+    pub transform: Transform,
+    /// Indices of the child nodes in [`SceneGraph::nodes`].
+    pub children: Vec<usize>,
+    pub meshes: Vec<NodeMesh>,
+    pub camera: Option<NodeCamera>,
+    pub light: Option<NodeLight>,
+}
+
+impl SceneGraphNode {
+    pub fn new(name: impl Into<String>) -> Self {
+        SceneGraphNode { name: name.into(), ..Default::default() }
+    }
+
+    pub fn with_transform(mut self, transform: Transform) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    pub fn with_mesh(mut self, mesh: NodeMesh) -> Self {
+        self.meshes.push(mesh);
+        self
+    }
+
+    pub fn with_camera(mut self, camera: NodeCamera) -> Self {
+        self.camera = Some(camera);
+        self
+    }
+
+    pub fn with_light(mut self, light: NodeLight) -> Self {
+        self.light = Some(light);
+        self
+    }
+}
+
+/// A forest of [`SceneGraphNode`]s.  Nodes that are nobody's child are roots.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SceneGraph {
+    pub nodes: Vec<SceneGraphNode>,
+}
+
+impl SceneGraph {
+    /// Append a node and return its index.
+    pub fn add(&mut self, node: SceneGraphNode) -> usize {
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    /// Parent index of every node.
+    pub fn parents(&self) -> Vec<Option<usize>> {
+        let mut parents = vec![None; self.nodes.len()];
+        for (i, node) in self.nodes.iter().enumerate() {
+            for &child in &node.children {
+                if let Some(p) = parents.get_mut(child) {
+                    *p = Some(i);
+                }
+            }
+        }
+        parents
+    }
+
+    /// World matrix of every node.
+    pub fn world_matrices(&self) -> Vec<Mat4> {
+        let parents = self.parents();
+        let mut world = vec![None; self.nodes.len()];
+        for i in 0..self.nodes.len() {
+            // Walk up to the nearest resolved ancestor, then back down.
+            let mut chain = vec![i];
+            while let Some(p) = parents[*chain.last().unwrap()] {
+                if world[p].is_some() || chain.contains(&p) {
+                    break;
+                }
+                chain.push(p);
+            }
+            for &n in chain.iter().rev() {
+                if world[n].is_none() {
+                    let parent = parents[n].and_then(|p| world[p]).unwrap_or(Mat4::IDENTITY);
+                    world[n] = Some(parent * self.nodes[n].transform.matrix());
+                }
+            }
+        }
+        world.into_iter().map(|m| m.unwrap_or(Mat4::IDENTITY)).collect()
+    }
+}
+
+impl World {
+    /// Spawn one entity per node of `graph` and return their handles, in
+    /// node order.
+    ///
+    /// Every entity gets the node's local `Transform` and is related to its
+    /// parent with [`ChildOf`].  The first mesh of a node is drawn by the
+    /// node entity itself; further meshes become extra children named
+    /// `"<node>#<n>"`.  Cameras become [`Camera3D`] and directional lights
+    /// [`DirectionalLight`], both resolved to world space at spawn time.
+    pub fn spawn_graph(&mut self, graph: &SceneGraph) -> Vec<Handle> {
+        let world_matrices = graph.world_matrices();
+        let mut handles = Vec::with_capacity(graph.nodes.len());
+        let mut extra = Vec::new();
+
+        for (node, world) in graph.nodes.iter().zip(&world_matrices) {
+            let forward = world.transform_vector3(Vec3::NEG_Z).normalize_or_zero();
+            let mut builder = self.spawn(node.name.clone()).with_transform(node.transform);
+            if let Some(mesh) = node.meshes.first() {
+                builder = builder.with_kind(ElementKind::Mesh { asset_key: mesh.asset_key.clone() });
+                if let Some(material) = &mesh.material {
+                    builder = builder
+                        .with_material_handle(material.handle)
+                        .with_material(material.descriptor.clone());
+                }
+            }
+            if let Some(camera) = node.camera {
+                let eye = world.transform_point3(Vec3::ZERO);
+                builder = builder.with_component(Camera3D {
+                    eye,
+                    target: eye + forward,
+                    fov_deg: camera.fov_deg,
+                    near: camera.near,
+                    far: camera.far,
+                });
+            }
+            match node.light {
+                Some(NodeLight::Point(light)) => builder = builder.with_point_light(light),
+                Some(NodeLight::Directional { color, intensity }) => {
+                    builder = builder.with_component(DirectionalLight { direction: forward, color, intensity });
+                }
+                None => {}
+            }
+            let handle = builder.build();
+            handles.push(handle);
+
+            for (n, mesh) in node.meshes.iter().enumerate().skip(1) {
+                let mut builder = self
+                    .spawn(format!("{}#{}", node.name, n))
+                    .with_kind(ElementKind::Mesh { asset_key: mesh.asset_key.clone() });
+                if let Some(material) = &mesh.material {
+                    builder = builder
+                        .with_material_handle(material.handle)
+                        .with_material(material.descriptor.clone());
+                }
+                extra.push((builder.build(), handle));
+            }
+        }
+
+        let mut links = extra;
+        for (node, &parent) in graph.nodes.iter().zip(&handles) {
+            links.extend(node.children.iter().filter_map(|&c| Some((*handles.get(c)?, parent))));
+        }
+        for (child, parent) in links {
+            if let (Some(&c), Some(&p)) = (self.ecs_mapping.get(&child.0), self.ecs_mapping.get(&parent.0)) {
+                self.ecs.relate::<ChildOf>(c, p);
+            }
+        }
+        handles
+    }
+}
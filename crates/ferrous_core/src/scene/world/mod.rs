@@ -17,13 +17,28 @@
 //! world.set_position(h, Vec3::new(1.0, 0.0, 0.0));
 //! world.despawn(h);
 //! ```
+//!
+//! Imported hierarchies (glTF node trees) are described as a
+//! [`SceneGraph`] and instantiated with [`World::spawn_graph`].  Reusable
+//! templates are [`Prefab`]s, instantiated with [`World::instantiate_prefab`].
+//! Picking and other CPU scene queries go through [`World::raycast`].
 
 mod builder;
+pub mod graph;
+pub mod prefab;
 mod query;
+pub mod raycast;
+pub mod render_index;
 mod scene;
 pub mod types;
 
 pub use builder::EntityBuilder;
+pub use graph::{NodeCamera, NodeLight, NodeMesh, SceneGraph, SceneGraphNode};
+pub use prefab::{
+    MaterialLibrary, Prefab, PrefabError, PrefabInstance, PrefabLibrary, PrefabNode, PrefabOverride, PrefabProperty,
+};
+pub use raycast::{MeshGeometry, RayHit};
+pub use render_index::RenderIndex;
 pub use scene::World;
 pub use types::{
     Element, ElementKind, Handle, MaterialComponent, PointLightComponent, ShadowCaster, Billboard, BillboardMode,
@@ -180,6 +195,29 @@ mod tests {
         assert_eq!(w.position(h2), Some(Vec3::ONE));
     }
 
+    #[test]
+    fn render_index_follows_lights_and_shadow_casters() {
+        let mut w = World::new();
+        let lamp = w.spawn_point_light("Lamp", Vec3::Y, [1.0; 3], 2.0, 5.0);
+        let crate_ = w.spawn_cube("Crate", Vec3::ZERO);
+        let lamp_e = w.ecs_mapping[&lamp.0];
+        let crate_e = w.ecs_mapping[&crate_.0];
+        w.ecs.insert(crate_e, ShadowCaster);
+        w.ecs.insert(lamp_e, ShadowCaster);
+        assert_eq!(w.render_index().point_lights(), &[lamp_e]);
+        assert!(w.render_index().is_shadow_caster(crate_e));
+
+        w.ecs.remove::<ShadowCaster>(crate_e);
+        assert!(!w.render_index().is_shadow_caster(crate_e));
+        w.despawn(lamp);
+        assert!(w.render_index().point_lights().is_empty());
+        assert_eq!(w.render_index().shadow_casters().count(), 0);
+
+        w.ecs.insert(crate_e, ShadowCaster);
+        w.clear();
+        assert_eq!(w.render_index().shadow_casters().count(), 0);
+    }
+
     #[test]
     fn material_descriptor_and_handle_manipulation() {
         let mut w = World::new();
@@ -199,4 +237,305 @@ mod tests {
         w.set_material_handle(h, new_handle);
         assert_eq!(w.get(h).unwrap().material.handle, new_handle);
     }
+
+    #[test]
+    fn spawn_graph_builds_hierarchy_cameras_and_lights() {
+        use crate::scene::{Camera3D, ChildOf, DirectionalLight};
+        use crate::transform::Transform;
+        use glam::Quat;
+
+        let mut graph = SceneGraph::default();
+        let root = graph.add(
+            SceneGraphNode::new("level").with_transform(Transform::from_position(Vec3::new(10.0, 0.0, 0.0))),
+        );
+        let cam = graph.add(
+            SceneGraphNode::new("cam")
+                .with_transform(Transform::from_position(Vec3::new(0.0, 2.0, 5.0)))
+                .with_camera(NodeCamera { fov_deg: 60.0, near: 0.1, far: 100.0 }),
+        );
+        let sun = graph.add(
+            SceneGraphNode::new("sun")
+                .with_transform(Transform {
+                    rotation: Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2),
+                    ..Transform::IDENTITY
+                })
+                .with_light(NodeLight::Directional { color: crate::Color::WHITE, intensity: 3.0 }),
+        );
+        let props = graph.add(
+            SceneGraphNode::new("props")
+                .with_mesh(NodeMesh { asset_key: "crate".into(), material: None })
+                .with_mesh(NodeMesh { asset_key: "barrel".into(), material: None }),
+        );
+        graph.nodes[root].children = vec![cam, sun, props];
+
+        let mut w = World::new();
+        let handles = w.spawn_graph(&graph);
+        assert_eq!(handles.len(), 4);
+        assert_eq!(w.len(), 5, "second mesh of `props` is an extra entity");
+        let entity = |h: Handle| w.ecs_mapping[&h.0];
+
+        assert_eq!(w.ecs.target::<ChildOf>(entity(handles[cam])), Some(entity(handles[root])));
+        assert_eq!(w.ecs.target::<ChildOf>(entity(handles[root])), None);
+        assert_eq!(w.position(handles[cam]), Some(Vec3::new(0.0, 2.0, 5.0)));
+
+        let camera = w.ecs.get::<Camera3D>(entity(handles[cam])).unwrap();
+        assert_eq!(camera.eye, Vec3::new(10.0, 2.0, 5.0));
+        assert!((camera.target - Vec3::new(10.0, 2.0, 4.0)).length() < 1e-5);
+        let light = w.ecs.get::<DirectionalLight>(entity(handles[sun])).unwrap();
+        assert!((light.direction - Vec3::NEG_Y).length() < 1e-5);
+
+        let barrel = w.find_entity_by_name("props#1").unwrap();
+        assert_eq!(w.ecs.target::<ChildOf>(entity(barrel)), Some(entity(handles[props])));
+        assert!(matches!(&w.get(barrel).unwrap().kind, ElementKind::Mesh { asset_key } if asset_key == "barrel"));
+    }
+
+    #[test]
+    fn despawning_a_graph_root_frees_every_node() {
+        let mut graph = SceneGraph::default();
+        let root = graph.add(SceneGraphNode::new("root"));
+        let arm = graph.add(SceneGraphNode::new("arm"));
+        let hand = graph.add(
+            SceneGraphNode::new("hand")
+                .with_mesh(NodeMesh { asset_key: "palm".into(), material: None })
+                .with_mesh(NodeMesh { asset_key: "thumb".into(), material: None }),
+        );
+        graph.nodes[root].children = vec![arm];
+        graph.nodes[arm].children = vec![hand];
+
+        let mut w = World::new();
+        let handles = w.spawn_graph(&graph);
+        assert_eq!(w.len(), 4);
+
+        assert!(w.despawn(handles[root]));
+        assert_eq!(w.len(), 0);
+        assert_eq!(w.iter().count(), 0);
+        assert!(w.ecs_mapping.is_empty());
+        assert!(w.ecs.is_empty());
+        assert!(!w.contains(handles[hand]));
+        assert!(!w.despawn(handles[hand]));
+    }
+
+    /// `tower.prefab`: `base` with a `turret` child nesting `barrel.prefab`.
+    fn tower_prefabs() -> (PrefabLibrary, Prefab) {
+        let mut library = PrefabLibrary::new();
+        let mut barrel = Prefab::new("barrel");
+        barrel.add(PrefabNode::new("barrel").with_kind(ElementKind::Cube { half_extents: Vec3::splat(0.5) }));
+        library.insert("barrel.prefab", barrel);
+        let mut tower = Prefab::new("tower");
+        let base = tower.add(PrefabNode::new("base"));
+        tower.add(PrefabNode::new("turret").with_parent(base).with_prefab("barrel.prefab"));
+        library.insert("tower.prefab", tower.clone());
+        (library, tower)
+    }
+
+    fn prefab_node(w: &World, root: Handle, path: &str) -> Handle {
+        w.prefab_instance(root).unwrap().node(path).unwrap()
+    }
+
+    #[test]
+    fn prefab_instances_spawn_nested_prefabs_under_their_node() {
+        use crate::scene::ChildOf;
+        use crate::transform::Transform;
+
+        let (mut library, _) = tower_prefabs();
+        let mut w = World::new();
+        let a = w.instantiate_prefab(&library, "tower.prefab", Transform::from_position(Vec3::X)).unwrap();
+        let paths: Vec<&str> = w.prefab_instance(a).unwrap().nodes.iter().map(|(p, _)| p.as_str()).collect();
+        assert_eq!(paths, ["base", "base/turret", "base/turret/barrel"]);
+        let entity = |h: Handle| w.ecs_mapping[&h.0];
+        assert_eq!(w.ecs.target::<ChildOf>(entity(prefab_node(&w, a, "base"))), Some(entity(a)));
+        assert_eq!(
+            w.ecs.target::<ChildOf>(entity(prefab_node(&w, a, "base/turret/barrel"))),
+            Some(entity(prefab_node(&w, a, "base/turret")))
+        );
+
+        let mut looped = Prefab::new("loop");
+        looped.add(PrefabNode::new("self").with_prefab("loop.prefab"));
+        library.insert("loop.prefab", looped);
+        assert_eq!(
+            w.instantiate_prefab(&library, "loop.prefab", Transform::default()),
+            Err(PrefabError::Cycle("loop.prefab".into()))
+        );
+        assert_eq!(
+            w.instantiate_prefab(&library, "missing.prefab", Transform::default()),
+            Err(PrefabError::Missing("missing.prefab".into()))
+        );
+        assert_eq!(w.len(), 4, "failed instantiations leave nothing behind");
+    }
+
+    #[test]
+    fn prefab_overrides_record_set_and_revert() {
+        use crate::transform::Transform;
+
+        let (library, _) = tower_prefabs();
+        let mut w = World::new();
+        let a = w.instantiate_prefab(&library, "tower.prefab", Transform::default()).unwrap();
+        let barrel = prefab_node(&w, a, "base/turret/barrel");
+
+        w.set_position(barrel, Vec3::Y);
+        w.record_prefab_overrides(a, &library).unwrap();
+        let expected = PrefabOverride::new("base/turret/barrel", PrefabProperty::Transform(Transform::from_position(Vec3::Y)));
+        assert_eq!(w.prefab_instance(a).unwrap().overrides, vec![expected]);
+
+        assert!(w.set_prefab_override(a, PrefabOverride::new("base", PrefabProperty::Visible(false))));
+        assert!(!w.get(prefab_node(&w, a, "base")).unwrap().visible);
+        assert!(!w.set_prefab_override(a, PrefabOverride::new("nope", PrefabProperty::Visible(false))));
+
+        w.revert_prefab_instance(a, &library).unwrap();
+        assert!(w.prefab_instance(a).unwrap().overrides.is_empty());
+        assert!(w.get(prefab_node(&w, a, "base")).unwrap().visible);
+        assert_eq!(w.position(barrel), Some(Vec3::ZERO));
+    }
+
+    #[test]
+    fn prefab_apply_overrides_updates_prefab_and_other_instances() {
+        use crate::transform::Transform;
+
+        let (mut library, _) = tower_prefabs();
+        let mut w = World::new();
+        let a = w.instantiate_prefab(&library, "tower.prefab", Transform::default()).unwrap();
+        let b = w.instantiate_prefab(&library, "tower.prefab", Transform::default()).unwrap();
+
+        w.set_position(prefab_node(&w, a, "base/turret/barrel"), Vec3::Y);
+        w.set_position(prefab_node(&w, a, "base"), Vec3::Z);
+        w.record_prefab_overrides(a, &library).unwrap();
+        let applied = w.apply_prefab_overrides(a, &mut library).unwrap();
+
+        // Nodes of nested prefabs become prefab overrides; own nodes are edited.
+        let nested = PrefabOverride::new("base/turret/barrel", PrefabProperty::Transform(Transform::from_position(Vec3::Y)));
+        assert_eq!(applied.overrides, vec![nested]);
+        assert_eq!(applied.nodes[0].element.transform.position, Vec3::Z);
+        assert_eq!(library.get("barrel.prefab").unwrap().nodes[0].element.transform.position, Vec3::ZERO);
+        assert!(w.prefab_instance(a).unwrap().overrides.is_empty());
+        assert_eq!(w.position(prefab_node(&w, b, "base/turret/barrel")), Some(Vec3::Y));
+        assert_eq!(w.position(prefab_node(&w, b, "base")), Some(Vec3::Z));
+
+        // Capturing an instance nests its prefab instead of copying it.
+        let captured = w.to_prefab(a, "tower variant");
+        assert_eq!(captured.nodes.len(), 1);
+        assert_eq!(captured.nodes[0].prefab.as_deref(), Some("tower.prefab"));
+    }
+
+    #[test]
+    fn prefab_refresh_rebuilds_instances_after_reload() {
+        use crate::transform::Transform;
+
+        let (mut library, mut tower) = tower_prefabs();
+        let mut w = World::new();
+        let a = w.instantiate_prefab(&library, "tower.prefab", Transform::default()).unwrap();
+        let b = w.instantiate_prefab(&library, "tower.prefab", Transform::default()).unwrap();
+        let barrel = prefab_node(&w, a, "base/turret/barrel");
+        w.set_position(barrel, Vec3::Y);
+        w.record_prefab_overrides(a, &library).unwrap();
+
+        tower.add(PrefabNode::new("flag").with_parent(0));
+        library.insert("tower.prefab", tower);
+        // Reloading the nested prefab refreshes the towers nesting it.
+        assert_eq!(w.refresh_prefab_instances(&library, "barrel.prefab").unwrap(), 2);
+        assert!(w.prefab_instance(b).unwrap().node("base/flag").is_some());
+        assert_eq!(prefab_node(&w, a, "base/turret/barrel"), barrel, "surviving nodes keep their entity");
+        assert_eq!(w.position(barrel), Some(Vec3::Y), "recorded overrides survive");
+    }
+
+    #[test]
+    fn prefab_sync_and_despawn_free_entity_slots() {
+        use crate::transform::Transform;
+
+        let (mut library, mut tower) = tower_prefabs();
+        let mut w = World::new();
+        let a = w.instantiate_prefab(&library, "tower.prefab", Transform::default()).unwrap();
+        let barrel = prefab_node(&w, a, "base/turret/barrel");
+        assert_eq!(w.len(), 4);
+
+        // Dropping the turret despawns it and the nested barrel below it.
+        tower.nodes.truncate(1);
+        library.insert("tower.prefab", tower);
+        w.refresh_prefab_instances(&library, "tower.prefab").unwrap();
+        assert_eq!(w.len(), 2);
+        assert!(!w.contains(barrel));
+        assert_eq!(w.iter().count(), 2);
+
+        assert!(w.despawn(a));
+        assert_eq!(w.len(), 0);
+        assert_eq!(w.iter().count(), 0);
+        assert!(w.ecs_mapping.is_empty());
+        assert!(w.ecs.is_empty());
+    }
+
+    #[test]
+    fn prefab_materials_are_stored_by_asset_key() {
+        use crate::transform::Transform;
+
+        let red = MaterialComponent {
+            handle: MaterialHandle(7),
+            descriptor: MaterialDescriptor { base_color: [1.0, 0.0, 0.0, 1.0], ..Default::default() },
+        };
+        let mut w = World::new();
+        w.register_material("crate.gltf#material0", red.clone());
+
+        let mut library = PrefabLibrary::new();
+        let mut prefab = Prefab::new("crate");
+        prefab.add(PrefabNode::new("crate").with_material("crate.gltf#material0"));
+        prefab.add(PrefabNode::new("lid").with_parent(0));
+        library.insert("crate.prefab", prefab);
+        let root = w.instantiate_prefab(&library, "crate.prefab", Transform::default()).unwrap();
+        let (body, lid) = (prefab_node(&w, root, "crate"), prefab_node(&w, root, "crate/lid"));
+        assert_eq!(w.get(body).unwrap().material, red);
+
+        w.set_material_handle(lid, red.handle);
+        w.record_prefab_overrides(root, &library).unwrap();
+        let expected = PrefabOverride::new("crate/lid", PrefabProperty::Material(Some("crate.gltf#material0".into())));
+        assert_eq!(w.prefab_instance(root).unwrap().overrides, vec![expected]);
+
+        // Prefab files carry the key, never the session's GPU handle.
+        let captured = w.to_prefab(body, "crate");
+        assert_eq!(captured.nodes[1].material.as_deref(), Some("crate.gltf#material0"));
+        assert_eq!(captured.nodes[1].element.material, MaterialComponent::default());
+        let json = captured.to_json().unwrap();
+        assert!(!json.contains("\"handle\": 7"));
+    }
+
+    #[test]
+    fn raycast_hits_nearest_primitive_and_registered_mesh() {
+        use glam::Vec2;
+
+        let mut w = World::new();
+        let cube = w.spawn_cube("cube", Vec3::ZERO);
+        let sphere = w.spawn_sphere("sphere", Vec3::new(0.0, 0.0, -5.0), 1.0, 16);
+        let tri = w.spawn_mesh("tri", "tri.mesh", Vec3::new(5.0, 0.0, 0.0));
+        w.spawn_mesh("unregistered", "missing.mesh", Vec3::new(-5.0, 0.0, 0.0));
+        let geometry = MeshGeometry::new(
+            vec![Vec3::new(-1.0, -1.0, 0.0), Vec3::new(1.0, -1.0, 0.0), Vec3::new(0.0, 1.0, 0.0)],
+            vec![0, 1, 2],
+        )
+        .with_uvs(vec![Vec2::ZERO, Vec2::X, Vec2::Y]);
+        w.register_mesh_geometry("tri.mesh", geometry);
+
+        let hit = w.raycast(Vec3::new(0.25, 0.0, 10.0), Vec3::NEG_Z * 3.0, f32::INFINITY, |_, _| true).unwrap();
+        assert_eq!(hit.handle, cube);
+        assert!((hit.distance - 9.5).abs() < 1e-5);
+        assert!(hit.position.abs_diff_eq(Vec3::new(0.25, 0.0, 0.5), 1e-5));
+        assert!(hit.normal.abs_diff_eq(Vec3::Z, 1e-5));
+        assert!(hit.uv.abs_diff_eq(Vec2::new(0.75, 0.5), 1e-5), "uv={:?}", hit.uv);
+        assert!(hit.triangle.is_some());
+
+        let hit = w.raycast(Vec3::new(0.0, 0.0, 10.0), Vec3::NEG_Z, f32::INFINITY, |h, _| h != cube).unwrap();
+        assert_eq!(hit.handle, sphere);
+        assert!((hit.distance - 14.0).abs() < 1e-4);
+        assert!(hit.normal.abs_diff_eq(Vec3::Z, 1e-4));
+        assert_eq!(hit.triangle, None);
+
+        assert!(w.raycast(Vec3::new(5.0, 0.0, 10.0), Vec3::NEG_Z, 5.0, |_, _| true).is_none());
+        let hit = w.raycast(Vec3::new(5.0, 0.0, 10.0), Vec3::NEG_Z, 20.0, |_, _| true).unwrap();
+        assert_eq!(hit.handle, tri);
+        assert_eq!(hit.triangle, Some(0));
+        assert!(hit.barycentric.abs_diff_eq(Vec3::new(0.25, 0.25, 0.5), 1e-5));
+        assert!(hit.uv.abs_diff_eq(Vec2::new(0.25, 0.5), 1e-5));
+
+        // Moving the entity moves the hit; unregistered meshes are never hit.
+        w.set_position(tri, Vec3::new(5.0, 0.0, 2.0));
+        let hit = w.raycast(Vec3::new(5.0, 0.0, 10.0), Vec3::NEG_Z, 20.0, |_, _| true).unwrap();
+        assert!((hit.distance - 8.0).abs() < 1e-5);
+        assert!(w.raycast(Vec3::new(-5.0, 0.0, 10.0), Vec3::NEG_Z, f32::INFINITY, |_, _| true).is_none());
+    }
 }
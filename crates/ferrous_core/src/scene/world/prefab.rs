@@ -0,0 +1,701 @@
+//! Prefabs — reusable entity templates instantiated as subtrees of a
+//! [`World`].
+//!
+//! A [`Prefab`] is a list of [`PrefabNode`]s (an `Element` template plus a
+//! parent index).  A node may nest another prefab by key; the nested
+//! prefab's roots are spawned under it.  Prefabs are looked up by key in a
+//! [`PrefabLibrary`] — the asset path when loaded through the `AssetServer`
+//! (`Prefab` implements `Asset`, reading JSON).
+//!
+//! [`World::instantiate_prefab`] spawns an instance root carrying a
+//! [`PrefabInstance`] with the placement transform, and the prefab's nodes
+//! below it.  Nodes are addressed by their *path* — the `/`-joined names from
+//! the prefab root, e.g. `"turret/barrel"` — so per-instance
+//! [`PrefabOverride`]s survive edits to the prefab as long as the names do.
+//!
+//! Materials are stored by asset key rather than by `MaterialHandle`, which
+//! only indexes the current renderer's GPU material table.  Keys resolve
+//! through the materials registered with [`World::register_material`];
+//! unregistered keys fall back to the default material.
+//!
+//! ```rust,ignore
+//! let mut library = PrefabLibrary::new();
+//! library.insert("assets/tower.prefab", tower);
+//! let tower = world.instantiate_prefab(&library, "assets/tower.prefab", Transform::default())?;
+//!
+//! world.set_position(world.prefab_instance(tower).unwrap().node("turret").unwrap(), Vec3::Y);
+//! world.record_prefab_overrides(tower, &library)?;   // turret transform is now an override
+//! world.apply_prefab_overrides(tower, &mut library)?; // ...or bake it into the prefab
+//! world.revert_prefab_instance(tower, &library)?;     // ...or drop it again
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+use ferrous_asset_types::Asset;
+use ferrous_ecs::prelude::{Component, Entity};
+use serde::{Deserialize, Serialize};
+
+use crate::scene::{ChildOf, LocalBounds, MaterialHandle};
+use crate::transform::Transform;
+
+use super::builder::EntityBuilder;
+use super::scene::World;
+use super::types::{next_id, Element, ElementKind, Handle, MaterialComponent, PointLightComponent};
+
+// ── Prefab ───────────────────────────────────────────────────────────────────
+
+/// One entity of a [`Prefab`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrefabNode {
+    /// Entity template.  Its `id` is ignored; instances get fresh ones.
+    /// Its `material` is ignored too — see [`PrefabNode::material`].
+    pub element: Element,
+    /// Index of the parent node in [`Prefab::nodes`]; `None` for roots.
+    #[serde(default)]
+    pub parent: Option<usize>,
+    /// Library key of a prefab instantiated under this node.
+    #[serde(default)]
+    pub prefab: Option<String>,
+    /// Asset key of the node's material; `None` for the default material.
+    #[serde(default)]
+    pub material: Option<String>,
+}
+
+impl PrefabNode {
+    pub fn new(name: impl Into<String>) -> Self {
+        PrefabNode { element: Element::new(0, name), parent: None, prefab: None, material: None }
+    }
+
+    pub fn with_parent(mut self, parent: usize) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+
+    pub fn with_transform(mut self, transform: Transform) -> Self {
+        self.element.transform = transform;
+        self
+    }
+
+    pub fn with_kind(mut self, kind: ElementKind) -> Self {
+        self.element.kind = kind;
+        self
+    }
+
+    /// Use the material registered under `key`.
+    pub fn with_material(mut self, key: impl Into<String>) -> Self {
+        self.material = Some(key.into());
+        self
+    }
+
+    /// Nest the prefab stored under `key`.
+    pub fn with_prefab(mut self, key: impl Into<String>) -> Self {
+        self.prefab = Some(key.into());
+        self
+    }
+}
+
+/// A reusable entity template.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Prefab {
+    pub name: String,
+    pub nodes: Vec<PrefabNode>,
+    /// Overrides of nodes inside nested prefabs, by path from this prefab's
+    /// root.  Applied after the nested prefab's own overrides.
+    #[serde(default)]
+    pub overrides: Vec<PrefabOverride>,
+}
+
+impl Prefab {
+    pub fn new(name: impl Into<String>) -> Self {
+        Prefab { name: name.into(), ..Default::default() }
+    }
+
+    /// Append a node and return its index.
+    pub fn add(&mut self, node: PrefabNode) -> usize {
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Nodes in depth-first order from the roots as `(node, parent, path)`.
+    /// Nodes caught in a parent cycle are skipped.
+    fn walk(&self) -> Vec<(usize, Option<usize>, String)> {
+        let mut children = vec![Vec::new(); self.nodes.len()];
+        let mut roots = Vec::new();
+        for (i, node) in self.nodes.iter().enumerate() {
+            match node.parent.filter(|&p| p < self.nodes.len()) {
+                Some(p) => children[p].push(i),
+                None => roots.push(i),
+            }
+        }
+
+        let mut out = Vec::with_capacity(self.nodes.len());
+        let mut stack: Vec<(usize, Option<usize>, String)> =
+            roots.into_iter().rev().map(|i| (i, None, self.nodes[i].element.name.clone())).collect();
+        while let Some((i, parent, path)) = stack.pop() {
+            for &c in children[i].iter().rev() {
+                stack.push((c, Some(i), format!("{path}/{}", self.nodes[c].element.name)));
+            }
+            out.push((i, parent, path));
+        }
+        out
+    }
+}
+
+impl Asset for Prefab {
+    fn type_name() -> &'static str {
+        "Prefab"
+    }
+
+    fn import(path: &Path) -> anyhow::Result<Self> {
+        Ok(Prefab::from_json(&std::fs::read_to_string(path)?)?)
+    }
+
+    fn import_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
+
+// ── Overrides ────────────────────────────────────────────────────────────────
+
+/// An overridable property of a prefab node.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PrefabProperty {
+    Transform(Transform),
+    /// Material asset key; `None` for the default material.
+    Material(Option<String>),
+    Kind(ElementKind),
+    Visible(bool),
+    Tags(Vec<String>),
+    PointLight(Option<PointLightComponent>),
+}
+
+impl PrefabProperty {
+    /// Every overridable property of `element`, naming its material by the
+    /// key it is registered under in `materials`.
+    pub fn all(element: &Element, materials: &MaterialLibrary) -> [PrefabProperty; 6] {
+        [
+            PrefabProperty::Transform(element.transform),
+            PrefabProperty::Material(materials.key_of(&element.material).map(str::to_owned)),
+            PrefabProperty::Kind(element.kind.clone()),
+            PrefabProperty::Visible(element.visible),
+            PrefabProperty::Tags(element.tags.clone()),
+            PrefabProperty::PointLight(element.point_light),
+        ]
+    }
+
+    /// Set the property on `element`, resolving material keys in `materials`.
+    pub fn apply(&self, element: &mut Element, materials: &MaterialLibrary) {
+        match self {
+            PrefabProperty::Transform(t) => element.transform = *t,
+            PrefabProperty::Material(key) => element.material = materials.resolve(key.as_deref()),
+            PrefabProperty::Kind(k) => element.kind = k.clone(),
+            PrefabProperty::Visible(v) => element.visible = *v,
+            PrefabProperty::Tags(t) => element.tags = t.clone(),
+            PrefabProperty::PointLight(l) => element.point_light = *l,
+        }
+    }
+}
+
+/// A property value replacing the prefab's at the node with `path`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrefabOverride {
+    pub path: String,
+    pub property: PrefabProperty,
+}
+
+impl PrefabOverride {
+    pub fn new(path: impl Into<String>, property: PrefabProperty) -> Self {
+        PrefabOverride { path: path.into(), property }
+    }
+
+    /// Whether both override the same property of the same node.
+    fn targets_same(&self, other: &PrefabOverride) -> bool {
+        self.path == other.path
+            && std::mem::discriminant(&self.property) == std::mem::discriminant(&other.property)
+    }
+}
+
+/// Replace the override of the same node property in `overrides`, or append.
+fn set_override(overrides: &mut Vec<PrefabOverride>, o: PrefabOverride) {
+    match overrides.iter_mut().find(|x| x.targets_same(&o)) {
+        Some(slot) => *slot = o,
+        None => overrides.push(o),
+    }
+}
+
+// ── MaterialLibrary ──────────────────────────────────────────────────────────
+
+/// Materials prefabs can refer to, by asset key.  Owned by the [`World`];
+/// fill it with [`World::register_material`].
+#[derive(Debug, Clone, Default)]
+pub struct MaterialLibrary {
+    materials: HashMap<String, MaterialComponent>,
+    keys: HashMap<MaterialHandle, String>,
+}
+
+impl MaterialLibrary {
+    pub fn get(&self, key: &str) -> Option<&MaterialComponent> {
+        self.materials.get(key)
+    }
+
+    /// Key the material's handle was registered under.
+    pub fn key_of(&self, material: &MaterialComponent) -> Option<&str> {
+        self.keys.get(&material.handle).map(String::as_str)
+    }
+
+    /// The material under `key`, or the default material.
+    pub fn resolve(&self, key: Option<&str>) -> MaterialComponent {
+        key.and_then(|k| self.get(k)).cloned().unwrap_or_default()
+    }
+
+    fn insert(&mut self, key: String, material: MaterialComponent) {
+        if let Some(old) = self.materials.insert(key.clone(), material.clone()) {
+            self.keys.remove(&old.handle);
+        }
+        self.keys.insert(material.handle, key);
+    }
+}
+
+// ── PrefabInstance ───────────────────────────────────────────────────────────
+
+/// Component on the root entity of a prefab instance.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrefabInstance {
+    /// Library key of the instantiated prefab.
+    pub prefab: String,
+    pub overrides: Vec<PrefabOverride>,
+    /// Spawned entity of every node (nested prefabs included), by path.
+    pub nodes: Vec<(String, Handle)>,
+}
+
+impl Component for PrefabInstance {}
+
+impl PrefabInstance {
+    /// Entity spawned for the node at `path`.
+    pub fn node(&self, path: &str) -> Option<Handle> {
+        self.nodes.iter().find(|(p, _)| p == path).map(|&(_, h)| h)
+    }
+
+    /// Path of the node spawned as `handle`.
+    pub fn path_of(&self, handle: Handle) -> Option<&str> {
+        self.nodes.iter().find(|&&(_, h)| h == handle).map(|(p, _)| p.as_str())
+    }
+}
+
+// ── PrefabLibrary ────────────────────────────────────────────────────────────
+
+/// Errors returned by prefab instantiation and editing.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PrefabError {
+    /// No prefab is stored under this key.
+    Missing(String),
+    /// The prefab (transitively) nests itself.
+    Cycle(String),
+    /// The handle is not the root of a prefab instance.
+    NotAnInstance(Handle),
+}
+
+impl fmt::Display for PrefabError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrefabError::Missing(key) => write!(f, "prefab `{key}` is not loaded"),
+            PrefabError::Cycle(key) => write!(f, "prefab `{key}` nests itself"),
+            PrefabError::NotAnInstance(h) => write!(f, "entity {} is not a prefab instance", h.0),
+        }
+    }
+}
+
+impl std::error::Error for PrefabError {}
+
+/// A prefab node with nested prefabs expanded and overrides applied.
+struct FlatNode {
+    path: String,
+    /// Index into the flattened list.
+    parent: Option<usize>,
+    element: Element,
+}
+
+/// Prefabs by key (usually their asset path).
+#[derive(Debug, Clone, Default)]
+pub struct PrefabLibrary {
+    prefabs: HashMap<String, Arc<Prefab>>,
+}
+
+impl PrefabLibrary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store `prefab` under `key`, replacing any previous version.  Live
+    /// instances are only updated by [`World::refresh_prefab_instances`].
+    pub fn insert(&mut self, key: impl Into<String>, prefab: impl Into<Arc<Prefab>>) {
+        self.prefabs.insert(key.into(), prefab.into());
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Arc<Prefab>> {
+        self.prefabs.get(key)
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<Arc<Prefab>> {
+        self.prefabs.remove(key)
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.prefabs.contains_key(key)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.prefabs.keys().map(String::as_str)
+    }
+
+    /// Keys nested by stored prefabs that are not stored themselves.
+    pub fn missing(&self) -> Vec<String> {
+        let mut missing: Vec<String> = self
+            .prefabs
+            .values()
+            .flat_map(|p| p.nodes.iter().filter_map(|n| n.prefab.clone()))
+            .filter(|key| !self.contains(key))
+            .collect();
+        missing.sort();
+        missing.dedup();
+        missing
+    }
+
+    /// Whether the prefab under `key` is `dependency` or nests it.
+    pub fn depends_on(&self, key: &str, dependency: &str) -> bool {
+        let mut stack = vec![key];
+        let mut seen = Vec::new();
+        while let Some(k) = stack.pop() {
+            if k == dependency {
+                return true;
+            }
+            if seen.contains(&k) {
+                continue;
+            }
+            seen.push(k);
+            if let Some(p) = self.prefabs.get(k) {
+                stack.extend(p.nodes.iter().filter_map(|n| n.prefab.as_deref()));
+            }
+        }
+        false
+    }
+
+    fn flatten(&self, key: &str, materials: &MaterialLibrary) -> Result<Vec<FlatNode>, PrefabError> {
+        let mut out = Vec::new();
+        self.flatten_into(key, "", None, materials, &mut Vec::new(), &mut out)?;
+        Ok(out)
+    }
+
+    fn flatten_into(
+        &self,
+        key: &str,
+        prefix: &str,
+        parent: Option<usize>,
+        materials: &MaterialLibrary,
+        stack: &mut Vec<String>,
+        out: &mut Vec<FlatNode>,
+    ) -> Result<(), PrefabError> {
+        if stack.iter().any(|k| k == key) {
+            return Err(PrefabError::Cycle(key.to_string()));
+        }
+        let prefab = self.get(key).ok_or_else(|| PrefabError::Missing(key.to_string()))?;
+        stack.push(key.to_string());
+
+        let start = out.len();
+        let mut index = vec![0; prefab.nodes.len()];
+        for (i, node_parent, path) in prefab.walk() {
+            let node = &prefab.nodes[i];
+            let path = format!("{prefix}{path}");
+            index[i] = out.len();
+            let mut element = node.element.clone();
+            element.material = materials.resolve(node.material.as_deref());
+            out.push(FlatNode { path: path.clone(), parent: node_parent.map(|p| index[p]).or(parent), element });
+            if let Some(nested) = &node.prefab {
+                self.flatten_into(nested, &format!("{path}/"), Some(index[i]), materials, stack, out)?;
+            }
+        }
+        for o in &prefab.overrides {
+            let path = format!("{prefix}{}", o.path);
+            if let Some(node) = out[start..].iter_mut().find(|n| n.path == path) {
+                o.property.apply(&mut node.element, materials);
+            }
+        }
+
+        stack.pop();
+        Ok(())
+    }
+}
+
+// ── World integration ────────────────────────────────────────────────────────
+
+impl World {
+    /// Register `material` under the asset key prefabs refer to it by,
+    /// replacing any previous material under the same key.
+    ///
+    /// Materials survive [`World::clear`], like the renderer's material table.
+    pub fn register_material(&mut self, key: impl Into<String>, material: MaterialComponent) {
+        self.materials.insert(key.into(), material);
+    }
+
+    /// Materials registered with [`World::register_material`].
+    pub fn materials(&self) -> &MaterialLibrary {
+        &self.materials
+    }
+
+    /// Spawn an instance of the prefab stored under `key`: a root entity
+    /// named after the prefab, placed at `transform` and carrying a
+    /// [`PrefabInstance`], with the prefab's nodes below it.
+    pub fn instantiate_prefab(
+        &mut self,
+        library: &PrefabLibrary,
+        key: &str,
+        transform: Transform,
+    ) -> Result<Handle, PrefabError> {
+        let prefab = library.get(key).ok_or_else(|| PrefabError::Missing(key.to_string()))?;
+        let root = self
+            .spawn(prefab.name.clone())
+            .with_transform(transform)
+            .with_component(PrefabInstance { prefab: key.to_string(), overrides: Vec::new(), nodes: Vec::new() })
+            .build();
+        if let Err(e) = self.sync_prefab_instance(root, library) {
+            self.despawn(root);
+            return Err(e);
+        }
+        Ok(root)
+    }
+
+    /// The [`PrefabInstance`] of an instance root.
+    pub fn prefab_instance(&self, root: Handle) -> Option<&PrefabInstance> {
+        self.ecs.get::<PrefabInstance>(*self.ecs_mapping.get(&root.0)?)
+    }
+
+    /// Record `o` on the instance and apply it to the live entity.
+    /// Returns `false` if `root` is not an instance or has no such node.
+    pub fn set_prefab_override(&mut self, root: Handle, o: PrefabOverride) -> bool {
+        let Some(node) = self.prefab_instance(root).and_then(|i| i.node(&o.path)) else {
+            return false;
+        };
+        if let Some(mut element) = self.get(node).cloned() {
+            o.property.apply(&mut element, &self.materials);
+            self.write_element(node, element);
+        }
+        self.with_prefab_instance(root, |instance| set_override(&mut instance.overrides, o));
+        true
+    }
+
+    /// Replace the instance's overrides with every property in which its
+    /// live entities differ from the prefab.
+    pub fn record_prefab_overrides(&mut self, root: Handle, library: &PrefabLibrary) -> Result<(), PrefabError> {
+        let instance = self.prefab_instance(root).ok_or(PrefabError::NotAnInstance(root))?;
+        let flat = library.flatten(&instance.prefab, &self.materials)?;
+        let mut overrides = Vec::new();
+        for (path, handle) in &instance.nodes {
+            let (Some(node), Some(live)) = (flat.iter().find(|n| &n.path == path), self.get(*handle)) else {
+                continue;
+            };
+            let base = PrefabProperty::all(&node.element, &self.materials);
+            for (property, base) in PrefabProperty::all(live, &self.materials).into_iter().zip(base) {
+                if property != base {
+                    overrides.push(PrefabOverride::new(path.clone(), property));
+                }
+            }
+        }
+        self.with_prefab_instance(root, |instance| instance.overrides = overrides);
+        Ok(())
+    }
+
+    /// Drop the instance's overrides and restore every node from the prefab.
+    pub fn revert_prefab_instance(&mut self, root: Handle, library: &PrefabLibrary) -> Result<(), PrefabError> {
+        if !self.with_prefab_instance(root, |instance| instance.overrides.clear()) {
+            return Err(PrefabError::NotAnInstance(root));
+        }
+        self.sync_prefab_instance(root, library)
+    }
+
+    /// Write the instance's overrides back into its prefab, clear them and
+    /// refresh every instance of the prefab.  Overrides of nodes inside
+    /// nested prefabs become [`Prefab::overrides`]; the nested prefabs
+    /// themselves are left untouched.
+    ///
+    /// Returns the updated prefab, e.g. to save it to disk.
+    pub fn apply_prefab_overrides(
+        &mut self,
+        root: Handle,
+        library: &mut PrefabLibrary,
+    ) -> Result<Arc<Prefab>, PrefabError> {
+        let instance = self.prefab_instance(root).cloned().ok_or(PrefabError::NotAnInstance(root))?;
+        let key = instance.prefab;
+        let mut prefab = Prefab::clone(library.get(&key).ok_or_else(|| PrefabError::Missing(key.clone()))?);
+
+        let paths = prefab.walk();
+        for o in instance.overrides {
+            match (paths.iter().find(|(_, _, path)| *path == o.path), o.property) {
+                (Some(&(i, _, _)), PrefabProperty::Material(key)) => prefab.nodes[i].material = key,
+                (Some(&(i, _, _)), property) => property.apply(&mut prefab.nodes[i].element, &self.materials),
+                (None, property) => set_override(&mut prefab.overrides, PrefabOverride::new(o.path, property)),
+            }
+        }
+        library.insert(key.clone(), prefab);
+        self.with_prefab_instance(root, |instance| instance.overrides.clear());
+        self.refresh_prefab_instances(library, &key)?;
+        Ok(library.get(&key).cloned().expect("prefab was just inserted"))
+    }
+
+    /// Rebuild every instance of the prefab stored under `key` — and of
+    /// prefabs nesting it — from the library and the instances' recorded
+    /// overrides.  Call after reloading or editing the prefab.  Live edits
+    /// not recorded as overrides are lost.
+    ///
+    /// Returns the number of instances refreshed.
+    pub fn refresh_prefab_instances(&mut self, library: &PrefabLibrary, key: &str) -> Result<usize, PrefabError> {
+        let roots: Vec<Handle> = self
+            .ecs
+            .query::<PrefabInstance>()
+            .filter(|(_, instance)| library.depends_on(&instance.prefab, key))
+            .filter_map(|(entity, _)| self.ecs.get::<Element>(entity).map(|e| Handle(e.id)))
+            .collect();
+        for &root in &roots {
+            self.sync_prefab_instance(root, library)?;
+        }
+        Ok(roots.len())
+    }
+
+    /// Capture `root` and its descendants as a prefab.  The root becomes the
+    /// prefab's single root node with an identity transform.  Nested prefab
+    /// instances are kept as references to their prefab, with their
+    /// overrides moved into [`Prefab::overrides`].
+    pub fn to_prefab(&self, root: Handle, name: impl Into<String>) -> Prefab {
+        let mut prefab = Prefab::new(name);
+        let Some(&entity) = self.ecs_mapping.get(&root.0) else {
+            return prefab;
+        };
+        let mut stack: Vec<(Entity, Option<usize>, String)> = vec![(entity, None, String::new())];
+        while let Some((entity, parent, prefix)) = stack.pop() {
+            let Some(mut element) = self.ecs.get::<Element>(entity).and_then(|e| self.get(Handle(e.id))).cloned()
+            else {
+                continue;
+            };
+            if parent.is_none() {
+                element.transform = Transform::default();
+            }
+            let material = self.materials.key_of(&element.material).map(str::to_owned);
+            element.material = MaterialComponent::default();
+            let path = format!("{prefix}{}", element.name);
+            let index = prefab.add(PrefabNode { element, parent, prefab: None, material });
+
+            let instance = self.ecs.get::<PrefabInstance>(entity);
+            let nested: Vec<Handle> = instance.map(|i| i.nodes.iter().map(|&(_, h)| h).collect()).unwrap_or_default();
+            if let Some(instance) = instance {
+                prefab.nodes[index].prefab = Some(instance.prefab.clone());
+                prefab.overrides.extend(
+                    instance
+                        .overrides
+                        .iter()
+                        .map(|o| PrefabOverride::new(format!("{path}/{}", o.path), o.property.clone())),
+                );
+            }
+            for &child in self.ecs.sources::<ChildOf>(entity).iter().rev() {
+                let is_nested = self.ecs.get::<Element>(child).is_some_and(|e| nested.contains(&Handle(e.id)));
+                if !is_nested {
+                    stack.push((child, Some(index), format!("{path}/")));
+                }
+            }
+        }
+        prefab
+    }
+
+    // ── Internal helpers ─────────────────────────────────────────────────────
+
+    /// Run `f` on the instance component of `root`; `false` if there is none.
+    fn with_prefab_instance(&mut self, root: Handle, f: impl FnOnce(&mut PrefabInstance)) -> bool {
+        let Some(&entity) = self.ecs_mapping.get(&root.0) else {
+            return false;
+        };
+        match self.ecs.get_mut::<PrefabInstance>(entity) {
+            Some(instance) => {
+                f(instance);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Bring the entities of an instance in line with its prefab and
+    /// overrides: update nodes that still exist, spawn new ones and despawn
+    /// the ones the prefab no longer has.
+    fn sync_prefab_instance(&mut self, root: Handle, library: &PrefabLibrary) -> Result<(), PrefabError> {
+        let instance = self.prefab_instance(root).cloned().ok_or(PrefabError::NotAnInstance(root))?;
+        let mut flat = library.flatten(&instance.prefab, &self.materials)?;
+        for o in &instance.overrides {
+            if let Some(node) = flat.iter_mut().find(|n| n.path == o.path) {
+                o.property.apply(&mut node.element, &self.materials);
+            }
+        }
+
+        let mut old: HashMap<String, Handle> = instance.nodes.into_iter().collect();
+        let mut nodes: Vec<(String, Handle)> = Vec::with_capacity(flat.len());
+        for node in flat {
+            let handle = match old.remove(&node.path) {
+                Some(handle) if self.contains(handle) => {
+                    self.write_element(handle, node.element);
+                    handle
+                }
+                _ => {
+                    let mut element = node.element;
+                    element.id = next_id();
+                    element.render_handle = None;
+                    EntityBuilder { world: self, element, ecs_components: Vec::new() }.build()
+                }
+            };
+            let parent = node.parent.map_or(root, |p| nodes[p].1);
+            if let (Some(&c), Some(&p)) = (self.ecs_mapping.get(&handle.0), self.ecs_mapping.get(&parent.0)) {
+                self.ecs.relate::<ChildOf>(c, p);
+            }
+            nodes.push((node.path, handle));
+        }
+        for handle in old.into_values() {
+            self.despawn(handle);
+        }
+        self.with_prefab_instance(root, |instance| instance.nodes = nodes);
+        Ok(())
+    }
+
+    /// Overwrite an entity's element (keeping its id and render handle) and
+    /// mirror it into the ECS components.
+    fn write_element(&mut self, handle: Handle, mut element: Element) {
+        let Some(Some(slot)) = self.entities.get_mut(handle.0 as usize) else {
+            return;
+        };
+        element.id = slot.id;
+        element.render_handle = slot.render_handle;
+        *slot = element.clone();
+        if let Some(&entity) = self.ecs_mapping.get(&handle.0) {
+            self.ecs.insert(entity, element.transform);
+            self.ecs.insert(entity, element.material.clone());
+            match element.point_light {
+                Some(light) => self.ecs.insert(entity, light),
+                None => {
+                    self.ecs.remove::<PointLightComponent>(entity);
+                }
+            }
+            match self.local_bounds(&element.kind) {
+                Some(bounds) => self.ecs.insert(entity, LocalBounds(bounds)),
+                None => {
+                    self.ecs.remove::<LocalBounds>(entity);
+                }
+            }
+            self.ecs.insert(entity, element);
+        }
+    }
+}
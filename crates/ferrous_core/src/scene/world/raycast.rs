@@ -0,0 +1,506 @@
+//! CPU ray casting against [`World`] entities.
+//!
+//! Every entity is first tested against the world-space [`Aabb`] of its
+//! shape (broadphase), then exactly against the shape in local space:
+//!
+//! | `ElementKind`                | Narrow phase                                      |
+//! |------------------------------|---------------------------------------------------|
+//! | `Mesh`                       | triangles of the [`MeshGeometry`] registered under its `asset_key` |
+//! | `Cube`, `Quad`, `Plane`      | triangles matching the renderer's primitive       |
+//! | `Sphere`                     | analytic unit sphere                              |
+//! | `Circle`, `Ring`             | analytic disc / annulus in the XZ plane           |
+//! | `Cylinder`, `Capsule`, `Torus` | local bounding box only                         |
+//!
+//! Meshes without registered geometry, lights, `Empty` and `Text3D` entities
+//! are never hit.  This needs no GPU, so picking and gameplay queries work in
+//! headless tests.
+
+use std::borrow::Cow;
+use std::f32::consts::PI;
+use std::sync::{Arc, OnceLock};
+
+use glam::{Mat4, Vec2, Vec3};
+
+use crate::bounds::Aabb;
+use crate::scene::systems::spatial::LocalBounds;
+use crate::transform::Transform;
+
+use super::scene::World;
+use super::types::{Element, ElementKind, Handle};
+
+// ─── MeshGeometry ───────────────────────────────────────────────────────────
+
+/// CPU copy of a mesh's triangles, registered with
+/// [`World::register_mesh_geometry`] under the same key as the GPU mesh.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeshGeometry {
+    pub positions: Vec<Vec3>,
+    /// Triangle list; three indices per triangle.
+    pub indices: Vec<u32>,
+    /// Per-vertex texture coordinates; may be empty.
+    pub uvs: Vec<Vec2>,
+    /// Local-space bounds of `positions`.
+    pub bounds: Aabb,
+}
+
+impl MeshGeometry {
+    pub fn new(positions: Vec<Vec3>, indices: Vec<u32>) -> Self {
+        let bounds = Aabb::from_points(positions.iter().copied())
+            .unwrap_or_else(|| Aabb::new(Vec3::ZERO, Vec3::ZERO));
+        Self { positions, indices, uvs: Vec::new(), bounds }
+    }
+
+    pub fn with_uvs(mut self, uvs: Vec<Vec2>) -> Self {
+        self.uvs = uvs;
+        self
+    }
+
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    /// Nearest intersection of the ray with the triangles, in the mesh's own
+    /// space.  Both faces are tested.
+    fn raycast(&self, origin: Vec3, dir: Vec3, max_t: f32) -> Option<LocalHit> {
+        let mut best: Option<LocalHit> = None;
+        for (tri, idx) in self.indices.chunks_exact(3).enumerate() {
+            let [a, b, c] = [idx[0], idx[1], idx[2]].map(|i| i as usize);
+            let (Some(&p0), Some(&p1), Some(&p2)) =
+                (self.positions.get(a), self.positions.get(b), self.positions.get(c))
+            else {
+                continue;
+            };
+            let limit = best.as_ref().map_or(max_t, |h| h.t);
+            let Some((t, u, v)) = ray_triangle(origin, dir, p0, p1, p2) else {
+                continue;
+            };
+            if t > limit {
+                continue;
+            }
+            let barycentric = Vec3::new(1.0 - u - v, u, v);
+            let uv = match (self.uvs.get(a), self.uvs.get(b), self.uvs.get(c)) {
+                (Some(&t0), Some(&t1), Some(&t2)) => t0 * barycentric.x + t1 * barycentric.y + t2 * barycentric.z,
+                _ => Vec2::ZERO,
+            };
+            best = Some(LocalHit {
+                t,
+                normal: (p1 - p0).cross(p2 - p0),
+                triangle: Some(tri as u32),
+                barycentric,
+                uv,
+            });
+        }
+        best
+    }
+}
+
+/// Möller–Trumbore ray/triangle test without back-face culling.  Returns
+/// `(t, u, v)` where `u`/`v` are the barycentric weights of `p1`/`p2`.
+fn ray_triangle(origin: Vec3, dir: Vec3, p0: Vec3, p1: Vec3, p2: Vec3) -> Option<(f32, f32, f32)> {
+    let e1 = p1 - p0;
+    let e2 = p2 - p0;
+    let p = dir.cross(e2);
+    let det = e1.dot(p);
+    if det.abs() < 1e-12 {
+        return None;
+    }
+    let inv_det = 1.0 / det;
+    let s = origin - p0;
+    let u = s.dot(p) * inv_det;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = s.cross(e1);
+    let v = dir.dot(q) * inv_det;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let t = e2.dot(q) * inv_det;
+    (t >= 0.0).then_some((t, u, v))
+}
+
+// ─── RayHit ─────────────────────────────────────────────────────────────────
+
+/// Result of [`World::raycast`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit {
+    pub handle: Handle,
+    /// Distance from the ray origin along the (normalised) direction.
+    pub distance: f32,
+    /// World-space hit point.
+    pub position: Vec3,
+    /// World-space surface normal, facing the ray origin.
+    pub normal: Vec3,
+    /// Index of the hit triangle for triangle shapes, `None` for analytic
+    /// ones.  For `Cube`/`Quad`/`Plane` this indexes the unsubdivided
+    /// two-triangles-per-face list.
+    pub triangle: Option<u32>,
+    /// Barycentric weights of the triangle's three vertices (zero for
+    /// analytic shapes).
+    pub barycentric: Vec3,
+    /// Interpolated texture coordinate at the hit point.
+    pub uv: Vec2,
+}
+
+/// A hit in the entity's local space; `t` is shared with world space because
+/// the local ray direction is not renormalised.
+struct LocalHit {
+    t: f32,
+    /// Unnormalised local normal.
+    normal: Vec3,
+    triangle: Option<u32>,
+    barycentric: Vec3,
+    uv: Vec2,
+}
+
+// ─── Shapes ─────────────────────────────────────────────────────────────────
+
+enum Shape<'a> {
+    Triangles(Cow<'a, MeshGeometry>),
+    UnitSphere,
+    Disc { inner: f32, outer: f32 },
+    Bounds(Aabb),
+}
+
+impl Shape<'_> {
+    fn bounds(&self) -> Aabb {
+        match self {
+            Shape::Triangles(geometry) => geometry.bounds,
+            Shape::UnitSphere => Aabb::unit_cube(),
+            Shape::Disc { outer, .. } => Aabb::new(Vec3::new(-outer, 0.0, -outer), Vec3::new(*outer, 0.0, *outer)),
+            Shape::Bounds(aabb) => *aabb,
+        }
+    }
+
+    fn raycast(&self, origin: Vec3, dir: Vec3, max_t: f32) -> Option<LocalHit> {
+        match self {
+            Shape::Triangles(geometry) => geometry.raycast(origin, dir, max_t),
+            Shape::UnitSphere => {
+                // |o + t d|² = 1
+                let a = dir.length_squared();
+                let b = origin.dot(dir);
+                let c = origin.length_squared() - 1.0;
+                let disc = b * b - a * c;
+                if disc < 0.0 {
+                    return None;
+                }
+                let sq = disc.sqrt();
+                let t = [(-b - sq) / a, (-b + sq) / a].into_iter().find(|&t| t >= 0.0)?;
+                let p = origin + dir * t;
+                // Same parameterisation as the renderer's UV sphere.
+                let u = p.z.atan2(p.x).rem_euclid(2.0 * PI) / (2.0 * PI);
+                let v = p.y.clamp(-1.0, 1.0).acos() / PI;
+                Some(LocalHit { t, normal: p, triangle: None, barycentric: Vec3::ZERO, uv: Vec2::new(u, v) })
+            }
+            Shape::Disc { inner, outer } => {
+                if dir.y.abs() < 1e-12 {
+                    return None;
+                }
+                let t = -origin.y / dir.y;
+                let p = origin + dir * t;
+                let r = Vec2::new(p.x, p.z).length();
+                if t < 0.0 || r > *outer || r < *inner {
+                    return None;
+                }
+                let uv = if *inner > 0.0 {
+                    let angle = p.z.atan2(p.x).rem_euclid(2.0 * PI) / (2.0 * PI);
+                    Vec2::new(angle, (r - inner) / (outer - inner))
+                } else {
+                    Vec2::new(p.x / outer * 0.5 + 0.5, p.z / outer * 0.5 + 0.5)
+                };
+                Some(LocalHit { t, normal: Vec3::Y, triangle: None, barycentric: Vec3::ZERO, uv })
+            }
+            Shape::Bounds(aabb) => {
+                let (t, _) = aabb.intersect_ray(origin, dir)?;
+                // Face normal: the axis along which the hit point is furthest out.
+                let local = (origin + dir * t - aabb.center) / aabb.half_extents.max(Vec3::splat(1e-6));
+                let a = local.abs();
+                let axis = if a.x >= a.y && a.x >= a.z { 0 } else if a.y >= a.z { 1 } else { 2 };
+                let mut normal = Vec3::ZERO;
+                normal[axis] = local[axis].signum();
+                Some(LocalHit { t, normal, triangle: None, barycentric: Vec3::ZERO, uv: Vec2::ZERO })
+            }
+        }
+    }
+}
+
+/// Triangle list of the renderer's cube: [-1, 1]³, each face with its own
+/// [0, 1]² UVs.
+fn cube_geometry() -> &'static MeshGeometry {
+    static CUBE: OnceLock<MeshGeometry> = OnceLock::new();
+    CUBE.get_or_init(|| {
+        // (normal, right, up) per face; corners are BL, BR, TR, TL.
+        let faces = [
+            (Vec3::Z, Vec3::X, Vec3::Y),
+            (Vec3::NEG_Z, Vec3::NEG_X, Vec3::Y),
+            (Vec3::NEG_X, Vec3::Z, Vec3::Y),
+            (Vec3::X, Vec3::NEG_Z, Vec3::Y),
+            (Vec3::Y, Vec3::X, Vec3::NEG_Z),
+            (Vec3::NEG_Y, Vec3::X, Vec3::Z),
+        ];
+        let mut positions = Vec::with_capacity(24);
+        let mut uvs = Vec::with_capacity(24);
+        let mut indices = Vec::with_capacity(36);
+        for (normal, right, up) in faces {
+            let base = positions.len() as u32;
+            for (x, y, uv) in [(-1.0, -1.0, [0.0, 1.0]), (1.0, -1.0, [1.0, 1.0]), (1.0, 1.0, [1.0, 0.0]), (-1.0, 1.0, [0.0, 0.0])] {
+                positions.push(normal + right * x + up * y);
+                uvs.push(Vec2::from(uv));
+            }
+            indices.extend([0, 1, 2, 2, 3, 0].map(|i| base + i));
+        }
+        MeshGeometry::new(positions, indices).with_uvs(uvs)
+    })
+}
+
+/// Triangle list of the renderer's quad: [-1, 1]² in the XY plane.
+fn quad_geometry() -> &'static MeshGeometry {
+    static QUAD: OnceLock<MeshGeometry> = OnceLock::new();
+    QUAD.get_or_init(|| {
+        let corners = [[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0], [-1.0, 1.0]];
+        MeshGeometry::new(corners.map(|[x, y]| Vec3::new(x, y, 0.0)).to_vec(), vec![0, 1, 2, 2, 3, 0])
+            .with_uvs(corners.map(|[x, y]| Vec2::new(x * 0.5 + 0.5, y * 0.5 + 0.5)).to_vec())
+    })
+}
+
+/// `width` × `height` rectangle in the XZ plane, UVs running +X / +Z.
+fn plane_geometry(width: f32, height: f32) -> MeshGeometry {
+    let (hw, hh) = (width * 0.5, height * 0.5);
+    let positions = vec![
+        Vec3::new(-hw, 0.0, -hh),
+        Vec3::new(hw, 0.0, -hh),
+        Vec3::new(-hw, 0.0, hh),
+        Vec3::new(hw, 0.0, hh),
+    ];
+    let uvs = vec![Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0), Vec2::new(1.0, 1.0)];
+    MeshGeometry::new(positions, vec![0, 3, 1, 0, 2, 3]).with_uvs(uvs)
+}
+
+// ─── World API ──────────────────────────────────────────────────────────────
+
+impl World {
+    /// Register the CPU geometry of the mesh `key` so that
+    /// `ElementKind::Mesh { asset_key: key }` entities can be ray cast.
+    /// Replaces any previous geometry under the same key.
+    ///
+    /// Geometry survives [`World::clear`], like the renderer's mesh cache.
+    pub fn register_mesh_geometry(&mut self, key: impl Into<String>, geometry: MeshGeometry) {
+        let key = key.into();
+        let bounds = LocalBounds(geometry.bounds);
+        self.mesh_geometry.insert(key.clone(), Arc::new(geometry));
+
+        // Entities spawned before their geometry was known get bounds now.
+        let entities: Vec<_> = self
+            .iter_with_handles()
+            .filter(|(_, e)| matches!(&e.kind, ElementKind::Mesh { asset_key } if *asset_key == key))
+            .filter_map(|(h, _)| self.ecs_mapping.get(&h.0).copied())
+            .collect();
+        for entity in entities {
+            self.ecs.insert(entity, bounds);
+        }
+    }
+
+    /// Geometry registered under `key`.
+    pub fn mesh_geometry(&self, key: &str) -> Option<&Arc<MeshGeometry>> {
+        self.mesh_geometry.get(key)
+    }
+
+    /// Bounds of an entity of this kind in its own space, as used by
+    /// [`World::raycast`] and attached as `LocalBounds`.  `None` for kinds
+    /// that cannot be hit.
+    pub fn local_bounds(&self, kind: &ElementKind) -> Option<Aabb> {
+        self.raycast_shape(kind).map(|shape| shape.bounds())
+    }
+
+    /// Forget the geometry registered under `key`.
+    pub fn remove_mesh_geometry(&mut self, key: &str) -> Option<Arc<MeshGeometry>> {
+        self.mesh_geometry.remove(key)
+    }
+
+    /// Cast a ray and return the nearest entity hit within `max_dist`.
+    ///
+    /// `filter` is called for every candidate; return `false` to skip it
+    /// (hidden entities are *not* skipped automatically).  Entities are
+    /// placed with their ECS `Transform`, exactly as the renderer draws them.
+    ///
+    /// ```rust,ignore
+    /// let (origin, dir) = renderer.get_ray(mouse);
+    /// if let Some(hit) = world.raycast(origin, dir, f32::INFINITY, |_, e| e.visible) {
+    ///     selected = Some(hit.handle);
+    /// }
+    /// ```
+    pub fn raycast(
+        &self,
+        origin: Vec3,
+        dir: Vec3,
+        max_dist: f32,
+        mut filter: impl FnMut(Handle, &Element) -> bool,
+    ) -> Option<RayHit> {
+        let dir = dir.try_normalize()?;
+        let mut best: Option<RayHit> = None;
+
+        for (handle, element) in self.iter_with_handles() {
+            let Some(shape) = self.raycast_shape(&element.kind) else {
+                continue;
+            };
+            let limit = best.as_ref().map_or(max_dist, |h| h.distance);
+            let matrix = self.raycast_matrix(handle, element);
+            match shape.bounds().transform(&matrix).intersect_ray(origin, dir) {
+                Some((t_enter, _)) if t_enter <= limit => {}
+                _ => continue,
+            }
+            if !filter(handle, element) {
+                continue;
+            }
+
+            let inverse = matrix.inverse();
+            if !inverse.is_finite() {
+                continue;
+            }
+            let local_origin = inverse.transform_point3(origin);
+            let local_dir = inverse.transform_vector3(dir);
+            let Some(hit) = shape.raycast(local_origin, local_dir, limit) else {
+                continue;
+            };
+            if hit.t > limit {
+                continue;
+            }
+
+            let mut normal = inverse.transpose().transform_vector3(hit.normal).normalize_or_zero();
+            if normal.dot(dir) > 0.0 {
+                normal = -normal;
+            }
+            best = Some(RayHit {
+                handle,
+                distance: hit.t,
+                position: origin + dir * hit.t,
+                normal,
+                triangle: hit.triangle,
+                barycentric: hit.barycentric,
+                uv: hit.uv,
+            });
+        }
+        best
+    }
+
+    fn raycast_shape(&self, kind: &ElementKind) -> Option<Shape<'_>> {
+        Some(match kind {
+            ElementKind::Mesh { asset_key } => Shape::Triangles(Cow::Borrowed(self.mesh_geometry.get(asset_key)?)),
+            ElementKind::Cube { .. } => Shape::Triangles(Cow::Borrowed(cube_geometry())),
+            ElementKind::Quad { .. } => Shape::Triangles(Cow::Borrowed(quad_geometry())),
+            ElementKind::Plane { width, height, .. } => Shape::Triangles(Cow::Owned(plane_geometry(*width, *height))),
+            ElementKind::Sphere { .. } => Shape::UnitSphere,
+            ElementKind::Circle { radius, .. } => Shape::Disc { inner: 0.0, outer: *radius },
+            ElementKind::Ring { inner_radius, outer_radius, .. } => {
+                Shape::Disc { inner: *inner_radius, outer: *outer_radius }
+            }
+            ElementKind::Cylinder { radius_top, radius_bottom, height, .. } => {
+                let r = radius_top.max(*radius_bottom);
+                Shape::Bounds(Aabb::new(Vec3::new(-r, -height * 0.5, -r), Vec3::new(r, height * 0.5, r)))
+            }
+            ElementKind::Capsule { radius, height, .. } => {
+                let h = height * 0.5 + radius;
+                Shape::Bounds(Aabb::new(Vec3::new(-radius, -h, -radius), Vec3::new(*radius, h, *radius)))
+            }
+            ElementKind::Torus { radius, tube, .. } => {
+                let r = radius + tube;
+                Shape::Bounds(Aabb::new(Vec3::new(-r, -tube, -r), Vec3::new(r, *tube, r)))
+            }
+            _ => return None,
+        })
+    }
+
+    fn raycast_matrix(&self, handle: Handle, element: &Element) -> Mat4 {
+        self.ecs_mapping
+            .get(&handle.0)
+            .and_then(|&entity| self.ecs.get::<Transform>(entity))
+            .unwrap_or(&element.transform)
+            .matrix()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triangle_mesh() -> MeshGeometry {
+        MeshGeometry::new(
+            vec![Vec3::new(-1.0, -1.0, 0.0), Vec3::new(1.0, -1.0, 0.0), Vec3::new(0.0, 1.0, 0.0)],
+            vec![0, 1, 2],
+        )
+        .with_uvs(vec![Vec2::ZERO, Vec2::X, Vec2::Y])
+    }
+
+    #[test]
+    fn parallel_rays_miss_flat_shapes() {
+        let [p0, p1, p2] = [Vec3::new(-1.0, -1.0, 0.0), Vec3::X, Vec3::Y];
+        assert_eq!(ray_triangle(Vec3::new(-5.0, 0.0, 0.0), Vec3::X, p0, p1, p2), None);
+
+        let mut w = World::new();
+        w.spawn_quad("quad", Vec3::ZERO, 2.0, 2.0, false);
+        w.spawn_circle("disc", Vec3::new(0.0, 0.0, -10.0), 1.0, 16);
+        assert_eq!(w.raycast(Vec3::new(-5.0, 0.0, 0.0), Vec3::X, f32::INFINITY, |_, _| true), None);
+        assert_eq!(w.raycast(Vec3::new(-5.0, 0.0, -10.0), Vec3::X, f32::INFINITY, |_, _| true), None);
+    }
+
+    #[test]
+    fn back_faces_are_hit_with_the_normal_facing_the_ray() {
+        let mut w = World::new();
+        let quad = w.spawn_quad("quad", Vec3::ZERO, 2.0, 2.0, false);
+
+        let front = w.raycast(Vec3::new(0.0, 0.0, 5.0), Vec3::NEG_Z, f32::INFINITY, |_, _| true).unwrap();
+        let back = w.raycast(Vec3::new(0.0, 0.0, -5.0), Vec3::Z, f32::INFINITY, |_, _| true).unwrap();
+        assert_eq!((front.handle, back.handle), (quad, quad));
+        assert!((front.normal - Vec3::Z).length() < 1e-5);
+        assert!((back.normal - Vec3::NEG_Z).length() < 1e-5);
+        assert!((back.distance - 5.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn hits_beyond_max_dist_are_ignored() {
+        let mut w = World::new();
+        w.spawn_quad("quad", Vec3::ZERO, 2.0, 2.0, false);
+        w.spawn_sphere("sphere", Vec3::new(0.0, 0.0, -10.0), 1.0, 16);
+        let origin = Vec3::new(0.0, 0.0, 5.0);
+
+        assert_eq!(w.raycast(origin, Vec3::NEG_Z, 4.9, |_, _| true), None);
+        let hit = w.raycast(origin, Vec3::NEG_Z, 5.1, |_, _| true).unwrap();
+        assert!((hit.distance - 5.0).abs() < 1e-5);
+        // The sphere's front is 14 away.
+        assert_eq!(w.raycast(origin, Vec3::NEG_Z, 13.9, |_, e| e.name == "sphere"), None);
+        assert!(w.raycast(origin, Vec3::NEG_Z, 14.1, |_, e| e.name == "sphere").is_some());
+    }
+
+    #[test]
+    fn triangle_hits_report_barycentric_weights_and_uv() {
+        let hit = triangle_mesh().raycast(Vec3::new(0.0, 0.0, 1.0), Vec3::NEG_Z, f32::INFINITY).unwrap();
+        assert_eq!(hit.triangle, Some(0));
+        assert!((hit.barycentric - Vec3::new(0.25, 0.25, 0.5)).length() < 1e-5);
+        assert!((hit.uv - Vec2::new(0.25, 0.5)).length() < 1e-5);
+
+        // Scaling the entity leaves the interpolated attributes alone.
+        let mut w = World::new();
+        w.register_mesh_geometry("tri.mesh", triangle_mesh());
+        let tri = w.spawn_mesh("tri", "tri.mesh", Vec3::ZERO);
+        w.set_scale(tri, Vec3::splat(2.0));
+        let hit = w.raycast(Vec3::new(0.0, 0.0, 3.0), Vec3::NEG_Z, f32::INFINITY, |_, _| true).unwrap();
+        assert_eq!(hit.handle, tri);
+        assert!((hit.barycentric - Vec3::new(0.25, 0.25, 0.5)).length() < 1e-5);
+        assert!((hit.uv - Vec2::new(0.25, 0.5)).length() < 1e-5);
+        assert!((hit.distance - 3.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn filter_can_reject_the_nearest_hit() {
+        let mut w = World::new();
+        let near = w.spawn_cube("near", Vec3::ZERO);
+        let far = w.spawn_cube("far", Vec3::new(0.0, 0.0, -5.0));
+        let origin = Vec3::new(0.0, 0.0, 10.0);
+
+        assert_eq!(w.raycast(origin, Vec3::NEG_Z, f32::INFINITY, |_, _| true).unwrap().handle, near);
+        let hit = w.raycast(origin, Vec3::NEG_Z, f32::INFINITY, |h, _| h != near).unwrap();
+        assert_eq!(hit.handle, far);
+        assert!((hit.distance - 14.5).abs() < 1e-5);
+        assert_eq!(w.raycast(origin, Vec3::NEG_Z, f32::INFINITY, |_, _| false), None);
+    }
+}
@@ -0,0 +1,67 @@
+//! Entity sets the renderer gathers every frame, kept current by hooks.
+//!
+//! Point lights and shadow casters are a small fraction of a scene, so the
+//! renderer reads them from a [`RenderIndex`] instead of scanning every
+//! entity.  [`World::new`](super::World::new) installs `on_add`/`on_remove`
+//! hooks for [`PointLightComponent`] and [`ShadowCaster`] on its ECS world
+//! that keep the index in step with inserts, removals and despawns.
+//!
+//! `EcsWorld::clear` runs no hooks; clear through
+//! [`World::clear`](super::World::clear), which resets the index as well.
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use ferrous_ecs::prelude::{Entity, World as EcsWorld};
+
+use super::types::{PointLightComponent, ShadowCaster};
+
+/// Entities carrying [`PointLightComponent`] or [`ShadowCaster`].
+#[derive(Debug, Default)]
+pub struct RenderIndex {
+    /// In insertion order, so the GPU light list is stable between frames.
+    point_lights: Vec<Entity>,
+    shadow_casters: HashSet<Entity>,
+}
+
+impl RenderIndex {
+    /// Entities with a [`PointLightComponent`], oldest first.
+    pub fn point_lights(&self) -> &[Entity] {
+        &self.point_lights
+    }
+
+    /// Entities with a [`ShadowCaster`] marker.
+    pub fn shadow_casters(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.shadow_casters.iter().copied()
+    }
+
+    pub fn is_shadow_caster(&self, entity: Entity) -> bool {
+        self.shadow_casters.contains(&entity)
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.point_lights.clear();
+        self.shadow_casters.clear();
+    }
+}
+
+/// Register the hooks that maintain a fresh index on `ecs`.
+pub(super) fn install(ecs: &mut EcsWorld) -> Arc<Mutex<RenderIndex>> {
+    let index = Arc::new(Mutex::new(RenderIndex::default()));
+
+    let (add, remove) = (index.clone(), index.clone());
+    ecs.component_hooks::<PointLightComponent>()
+        .on_add(move |_, e| add.lock().unwrap().point_lights.push(e))
+        .on_remove(move |_, e| remove.lock().unwrap().point_lights.retain(|&l| l != e));
+
+    let (add, remove) = (index.clone(), index.clone());
+    ecs.component_hooks::<ShadowCaster>()
+        .on_add(move |_, e| {
+            add.lock().unwrap().shadow_casters.insert(e);
+        })
+        .on_remove(move |_, e| {
+            remove.lock().unwrap().shadow_casters.remove(&e);
+        });
+
+    index
+}
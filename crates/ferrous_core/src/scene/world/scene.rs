@@ -8,9 +8,10 @@ use ferrous_ecs::prelude::{Entity, World as EcsWorld};
 use glam::Vec3;
 
 use crate::transform::Transform;
-use crate::scene::{DirectionalLight, SceneBlueprint};
+use crate::scene::{ChildOf, DirectionalLight, SceneBlueprint, SpatialRemovals};
 
 use super::builder::EntityBuilder;
+use super::render_index::{self, RenderIndex};
 use super::types::{next_id, Element, ElementKind, Handle, PointLightComponent};
 
 // ─── World ──────────────────────────────────────────────────────────────────
@@ -28,6 +29,18 @@ pub struct World {
     pub ecs: EcsWorld,
     /// Map from legacy `Handle` ID to ECS `Entity`.
     pub ecs_mapping: std::collections::HashMap<u64, Entity>,
+
+    /// CPU mesh geometry for [`World::raycast`], keyed by mesh asset key.
+    pub(super) mesh_geometry: std::collections::HashMap<String, std::sync::Arc<super::raycast::MeshGeometry>>,
+
+    /// Materials prefabs refer to, keyed by material asset key.
+    pub(super) materials: super::prefab::MaterialLibrary,
+
+    /// Point lights and shadow casters, maintained by hooks on `ecs`.
+    pub(super) render_index: std::sync::Arc<std::sync::Mutex<RenderIndex>>,
+
+    /// Removals of bounds from `ecs`, reported to tracking spatial indices.
+    pub(super) spatial_removals: SpatialRemovals,
 }
 
 impl Default for World {
@@ -39,14 +52,37 @@ impl Default for World {
 impl World {
     /// Creates an empty world.
     pub fn new() -> Self {
+        let mut ecs = EcsWorld::new();
+        let render_index = render_index::install(&mut ecs);
+        let spatial_removals = SpatialRemovals::install(&mut ecs);
         Self {
             entities: Vec::new(),
             count: 0,
-            ecs: EcsWorld::new(),
+            ecs,
             ecs_mapping: std::collections::HashMap::new(),
+            mesh_geometry: std::collections::HashMap::new(),
+            materials: Default::default(),
+            render_index,
+            spatial_removals,
         }
     }
 
+    /// Point lights and shadow casters currently in the world.
+    ///
+    /// Kept up to date by component hooks, so reading it costs nothing per
+    /// entity.  Hold the guard only briefly; inserting or removing either
+    /// component while it is held deadlocks.
+    pub fn render_index(&self) -> std::sync::MutexGuard<'_, RenderIndex> {
+        self.render_index.lock().unwrap()
+    }
+
+    /// Hub reporting removals of `LocalBounds` and `GlobalTransform` from
+    /// `ecs`; a [`SpatialIndex`](crate::scene::SpatialIndex) synced from this
+    /// world should [`track`](crate::scene::SpatialIndex::track) it.
+    pub fn spatial_removals(&self) -> &SpatialRemovals {
+        &self.spatial_removals
+    }
+
     // ── Spawning ────────────────────────────────────────────────────────────
 
     /// Begin building a new entity with the given name.
@@ -281,19 +317,38 @@ impl World {
     }
 
 
-    /// Remove the entity from the world.  Returns `true` if it existed.
+    /// Remove the entity, and every entity below it in the `ChildOf`
+    /// hierarchy, from the world.  Returns `true` if it existed.
     pub fn despawn(&mut self, handle: Handle) -> bool {
-        let idx = handle.0 as usize;
-        if idx < self.entities.len() && self.entities[idx].is_some() {
-            self.entities[idx] = None;
-            if let Some(entity) = self.ecs_mapping.remove(&handle.0) {
-                self.ecs.despawn(entity);
+        if !self.contains(handle) {
+            return false;
+        }
+        if let Some(&entity) = self.ecs_mapping.get(&handle.0) {
+            // `ChildOf` despawns recursively in the ECS; the descendants'
+            // slots and mapping entries must go with them.
+            let ids: Vec<u64> = self
+                .ecs
+                .descendants::<ChildOf>(entity)
+                .into_iter()
+                .filter_map(|e| self.ecs.get::<Element>(e).map(|el| el.id))
+                .collect();
+            self.ecs.despawn(entity);
+            for id in ids {
+                self.forget_slot(id);
+            }
+        }
+        self.forget_slot(handle.0);
+        true
+    }
+
+    /// Free the legacy slot and mapping entry of `id`, if still present.
+    fn forget_slot(&mut self, id: u64) {
+        if let Some(slot) = self.entities.get_mut(id as usize) {
+            if slot.take().is_some() {
+                self.count -= 1;
             }
-            self.count -= 1;
-            true
-        } else {
-            false
         }
+        self.ecs_mapping.remove(&id);
     }
 
     // ── Position ────────────────────────────────────────────────────────────
@@ -409,6 +464,8 @@ impl World {
     pub fn clear(&mut self) {
         self.entities.clear();
         self.ecs.clear();
+        self.render_index().clear();
+        self.spatial_removals.clear();
         self.ecs_mapping.clear();
         self.count = 0;
     }
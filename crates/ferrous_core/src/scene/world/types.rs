@@ -75,7 +75,7 @@ impl Default for PointLightComponent {
 // ── MaterialComponent ────────────────────────────────────────────────────────
 
 /// Material handle + CPU-side descriptor for an entity.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MaterialComponent {
     pub handle: MaterialHandle,
     pub descriptor: MaterialDescriptor,
@@ -95,7 +95,7 @@ impl Default for MaterialComponent {
 // ── ElementKind ──────────────────────────────────────────────────────────────
 
 /// Geometric or logical kind of a scene entity.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[derive(Default)]
 pub enum ElementKind {
     // ── Existing ────────────────────────────────────────────────────────────
@@ -40,6 +40,15 @@ pub struct Time {
 
     /// Instantaneous frames-per-second derived from `delta`.
     pub fps: f32,
+
+    /// Length of one `Stage::FixedUpdate` step in seconds.  Fixed-step
+    /// systems should integrate with this instead of `delta`.
+    pub fixed_delta: f32,
+
+    /// How far this frame lies between the previous and the current fixed
+    /// step, in `[0, 1)`.  Use it to interpolate rendered state, e.g.
+    /// `PreviousTransform::interpolate`.
+    pub alpha: f32,
 }
 
 impl Default for Time {
@@ -49,10 +58,15 @@ impl Default for Time {
             elapsed: 0.0,
             frame_count: 0,
             fps: 0.0,
+            fixed_delta: DEFAULT_FIXED_DELTA,
+            alpha: 0.0,
         }
     }
 }
 
+/// Fixed step reported before the scheduler publishes one (60 Hz).
+const DEFAULT_FIXED_DELTA: f32 = 1.0 / 60.0;
+
 impl Time {
     /// Returns the delta time clamped to `[0, max_dt]`.
     #[inline]
@@ -109,6 +123,8 @@ impl TimeClock {
             elapsed,
             frame_count: self.frame_count,
             fps,
+            fixed_delta: self.last_snapshot.fixed_delta,
+            alpha: self.last_snapshot.alpha,
         }
     }
 
@@ -129,10 +145,22 @@ impl TimeClock {
             elapsed,
             frame_count: count,
             fps,
+            fixed_delta: self.last_snapshot.fixed_delta,
+            alpha: self.last_snapshot.alpha,
         };
         self.last_snapshot = snapshot;
         snapshot
     }
+
+    /// Record the fixed step and interpolation alpha for the current frame.
+    ///
+    /// Called by `FixedTimeSystem` once the scheduler has run this frame's
+    /// fixed steps; the values carry over into later snapshots until the
+    /// next call.
+    pub fn set_fixed(&mut self, fixed_delta: f32, alpha: f32) {
+        self.last_snapshot.fixed_delta = fixed_delta;
+        self.last_snapshot.alpha = alpha;
+    }
 }
 
 impl Default for TimeClock {
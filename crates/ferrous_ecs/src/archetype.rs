@@ -13,13 +13,54 @@ use std::any::TypeId;
 use crate::component::{ComponentInfo, ComponentSet};
 use crate::entity::Entity;
 
+// ---------------------------------------------------------------------------
+// ComponentTicks — per-value change detection
+
+/// World ticks recording when a single component value was added and when
+/// it was last mutably accessed.
+///
+/// Ticks come from [`crate::world::World::change_tick`].  A value counts as
+/// added / changed for an observer whose last run happened at tick `t` when
+/// the stored tick is strictly greater than `t`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ComponentTicks {
+    /// Tick at which the component was inserted into its entity.
+    pub added: u64,
+    /// Tick of the most recent mutable access (equals `added` initially).
+    pub changed: u64,
+}
+
+impl ComponentTicks {
+    /// Ticks for a value that was inserted at `tick`.
+    #[inline]
+    pub fn new(tick: u64) -> Self {
+        ComponentTicks {
+            added: tick,
+            changed: tick,
+        }
+    }
+
+    /// `true` if the value was added after `last_run`.
+    #[inline]
+    pub fn is_added(&self, last_run: u64) -> bool {
+        self.added > last_run
+    }
+
+    /// `true` if the value was added or mutated after `last_run`.
+    #[inline]
+    pub fn is_changed(&self, last_run: u64) -> bool {
+        self.changed > last_run
+    }
+}
+
 // ---------------------------------------------------------------------------
 // ComponentColumn — type-erased Vec<T>
 
 /// A type-erased, heap-allocated column of one component type.
 ///
 /// Internally it is a raw byte array with a known stride (`info.size`).
-/// Capacity grows like a `Vec` (doubling).
+/// Capacity grows like a `Vec` (doubling).  A parallel `ticks` array keeps
+/// the added/changed ticks of every row.
 #[derive(Debug)]
 pub struct ComponentColumn {
     pub(crate) info: ComponentInfo,
@@ -27,6 +68,8 @@ pub struct ComponentColumn {
     data: *mut u8,
     pub(crate) len: usize,
     capacity: usize,
+    /// Change-detection ticks, one per row.  Length == `len`.
+    pub(crate) ticks: Vec<ComponentTicks>,
 }
 
 // SAFETY: ComponentInfo's component type is Send + Sync (required by Component).
@@ -35,11 +78,19 @@ unsafe impl Sync for ComponentColumn {}
 
 impl ComponentColumn {
     pub fn new(info: ComponentInfo) -> Self {
+        // Zero-sized components never allocate, but typed accesses still
+        // need a non-null, aligned pointer.
+        let data = if info.size == 0 {
+            std::ptr::null_mut::<u8>().wrapping_add(info.align)
+        } else {
+            std::ptr::null_mut()
+        };
         ComponentColumn {
             info,
-            data: std::ptr::null_mut(),
+            data,
             len: 0,
             capacity: 0,
+            ticks: Vec::new(),
         }
     }
 
@@ -96,13 +147,27 @@ impl ComponentColumn {
     /// `src` must point to a valid, initialized value of type `T` (where `T`
     /// matches `self.info.type_id`).  The caller must NOT drop `src` after
     /// this call — ownership is moved.
-    pub unsafe fn push_raw(&mut self, src: *const u8) {
+    pub unsafe fn push_raw(&mut self, src: *const u8, ticks: ComponentTicks) {
         self.reserve(1);
         let dst = self.data.add(self.len * self.info.size);
         if self.info.size > 0 {
             std::ptr::copy_nonoverlapping(src, dst, self.info.size);
         }
         self.len += 1;
+        self.ticks.push(ticks);
+    }
+
+    /// Grow by one row without initializing it and return the slot pointer.
+    ///
+    /// # Safety
+    /// The caller must write a valid value into the returned slot before the
+    /// column is read, dropped, or cloned from.
+    pub(crate) unsafe fn push_uninit(&mut self, ticks: ComponentTicks) -> *mut u8 {
+        self.reserve(1);
+        let dst = self.data.add(self.len * self.info.size);
+        self.len += 1;
+        self.ticks.push(ticks);
+        dst
     }
 
     /// Remove the element at `row` using swap-remove (O(1)).
@@ -131,6 +196,7 @@ impl ComponentColumn {
         }
 
         self.len -= 1;
+        self.ticks.swap_remove(row);
         !was_last // true if a swap actually happened (caller must fix entity table)
     }
 
@@ -156,6 +222,18 @@ impl ComponentColumn {
         &mut *(self.data.add(row * self.info.size) as *mut T)
     }
 
+    /// Change-detection ticks of the element at `row`.
+    #[inline]
+    pub fn ticks(&self, row: usize) -> ComponentTicks {
+        self.ticks[row]
+    }
+
+    /// Record a mutable access to the element at `row` at world tick `tick`.
+    #[inline]
+    pub fn set_changed(&mut self, row: usize, tick: u64) {
+        self.ticks[row].changed = tick;
+    }
+
     /// Raw pointer to element at `row`.
     #[inline]
     pub unsafe fn get_raw(&self, row: usize) -> *const u8 {
@@ -270,12 +348,17 @@ impl Archetype {
     /// # Safety
     /// `raw_components[i]` must point to a valid, initialized component of
     /// the type stored in `columns[i]`.  Ownership is transferred.
-    pub unsafe fn push_entity(&mut self, entity: Entity, raw_components: &[*const u8]) -> usize {
+    pub unsafe fn push_entity(
+        &mut self,
+        entity: Entity,
+        raw_components: &[*const u8],
+        tick: u64,
+    ) -> usize {
         debug_assert_eq!(raw_components.len(), self.columns.len());
         let row = self.entities.len();
         self.entities.push(entity);
         for (col, &src) in self.columns.iter_mut().zip(raw_components.iter()) {
-            col.push_raw(src);
+            col.push_raw(src, ComponentTicks::new(tick));
         }
         row
     }
@@ -304,6 +387,7 @@ impl Archetype {
                 std::ptr::copy_nonoverlapping(last_ptr, remove_ptr, size);
             }
             col.len -= 1;
+            col.ticks.swap_remove(row);
         }
 
         if was_last {
@@ -338,6 +422,22 @@ impl Archetype {
     }
 }
 
+// ---------------------------------------------------------------------------
+// ArchetypeGeneration — how far a cached archetype list has caught up
+
+/// Point in a world's archetype history.
+///
+/// Archetypes are only ever appended, so a cached list of matching archetype
+/// ids stays valid and can be brought up to date by checking the ids from
+/// `len` onward.  [`World::clear`](crate::world::World::clear) drops every
+/// archetype and starts a new `epoch`, after which cached lists must be
+/// rebuilt from scratch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct ArchetypeGeneration {
+    pub(crate) epoch: u64,
+    pub(crate) len: usize,
+}
+
 // ---------------------------------------------------------------------------
 // ArchetypeStore — the set of all archetypes
 
@@ -427,13 +527,14 @@ mod tests {
         let pos = Pos(1.0, 2.0, 3.0);
         let src = &pos as *const Pos as *const u8;
 
-        let row = unsafe { arch.push_entity(e, &[src]) };
+        let row = unsafe { arch.push_entity(e, &[src], 1) };
         std::mem::forget(pos); // ownership transferred
         assert_eq!(row, 0);
         assert_eq!(arch.len(), 1);
 
         let got = unsafe { arch.column::<Pos>().unwrap().get::<Pos>(0) };
         assert_eq!(*got, Pos(1.0, 2.0, 3.0));
+        assert_eq!(arch.column::<Pos>().unwrap().ticks(0), ComponentTicks::new(1));
     }
 
     #[test]
@@ -449,7 +550,7 @@ mod tests {
             let pos = Pos(i as f32, 0.0, 0.0);
             let src = &pos as *const Pos as *const u8;
             unsafe {
-                arch.push_entity(e, &[src]);
+                arch.push_entity(e, &[src], 1);
             }
             std::mem::forget(pos);
         }
@@ -0,0 +1,328 @@
+//! Deferred structural changes.
+//!
+//! Function systems only see `&World`, so they cannot spawn, despawn, or
+//! change an entity's component set directly.  Instead they take a
+//! [`Commands`] parameter, which records those operations into a per-system
+//! [`CommandQueue`].  The scheduler applies every queue at the next stage
+//! boundary (see [`System::apply_deferred`](crate::system::System::apply_deferred)),
+//! in system order and, within a system, in recording order.
+//!
+//! Entity handles for `spawn` are reserved immediately, so inserts can be
+//! chained onto an entity that does not exist yet:
+//!
+//! ```rust
+//! use ferrous_ecs::prelude::*;
+//!
+//! #[derive(Clone)] struct Bullet;
+//! impl Component for Bullet {}
+//! #[derive(Clone)] struct Speed(f32);
+//! impl Component for Speed {}
+//!
+//! let mut world = World::new();
+//! let mut queue = CommandQueue::new();
+//!
+//! let mut commands = Commands::new(&mut queue, &world);
+//! let bullet = commands.spawn((Bullet,)).insert(Speed(20.0)).id();
+//! assert!(!world.contains(bullet));
+//!
+//! queue.apply(&mut world);
+//! assert_eq!(world.get::<Speed>(bullet).unwrap().0, 20.0);
+//! ```
+
+use crate::component::{Bundle, Component};
+use crate::entity::{Entity, EntityAllocator};
+use crate::relation::Relation;
+use crate::resource::ResourceMap;
+use crate::system_param::SystemParam;
+use crate::world::World;
+
+// ---------------------------------------------------------------------------
+// CommandQueue
+
+type Command = Box<dyn FnOnce(&mut World) + Send + Sync>;
+
+/// An ordered list of deferred world mutations.
+#[derive(Default)]
+pub struct CommandQueue {
+    commands: Vec<Command>,
+}
+
+impl CommandQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an arbitrary world mutation.
+    pub fn push(&mut self, command: impl FnOnce(&mut World) + Send + Sync + 'static) {
+        self.commands.push(Box::new(command));
+    }
+
+    /// Apply all recorded commands in order and leave the queue empty.
+    ///
+    /// Reserved entities are flushed first, so commands targeting a reserved
+    /// entity that was never spawned still find it alive.
+    pub fn apply(&mut self, world: &mut World) {
+        world.flush();
+        for command in self.commands.drain(..) {
+            command(world);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Commands
+
+/// System parameter that records spawn / insert / remove / despawn operations.
+///
+/// ```rust,ignore
+/// fn fire(mut commands: Commands, guns: Query<&Gun>) {
+///     for (_, gun) in guns.iter() {
+///         commands.spawn((Bullet,)).insert(gun.muzzle_velocity);
+///     }
+/// }
+/// ```
+pub struct Commands<'w> {
+    queue: &'w mut CommandQueue,
+    entities: &'w EntityAllocator,
+}
+
+impl<'w> Commands<'w> {
+    /// Record into `queue`, reserving new entities from `world`.
+    pub fn new(queue: &'w mut CommandQueue, world: &'w World) -> Self {
+        Commands {
+            queue,
+            entities: &world.entities,
+        }
+    }
+
+    /// Reserve an entity and queue spawning it with `bundle`.
+    pub fn spawn<B: Bundle + Sync>(&mut self, bundle: B) -> EntityCommands<'_, 'w> {
+        let entity = self.entities.reserve();
+        self.queue.push(move |world: &mut World| {
+            world.spawn_reserved(entity, bundle);
+        });
+        EntityCommands { entity, commands: self }
+    }
+
+    /// Reserve an entity with no components.
+    pub fn spawn_empty(&mut self) -> EntityCommands<'_, 'w> {
+        let entity = self.entities.reserve();
+        EntityCommands { entity, commands: self }
+    }
+
+    /// Queue commands for an existing (or reserved) entity.
+    pub fn entity(&mut self, entity: Entity) -> EntityCommands<'_, 'w> {
+        EntityCommands { entity, commands: self }
+    }
+
+    /// Queue despawning `entity`.
+    pub fn despawn(&mut self, entity: Entity) {
+        self.queue.push(move |world: &mut World| {
+            world.despawn(entity);
+        });
+    }
+
+    /// Queue [`World::trigger`].
+    pub fn trigger<E: Send + Sync + 'static>(&mut self, event: E) {
+        self.queue.push(move |world: &mut World| world.trigger(event));
+    }
+
+    /// Queue an arbitrary world mutation.
+    pub fn add(&mut self, command: impl FnOnce(&mut World) + Send + Sync + 'static) {
+        self.queue.push(command);
+    }
+}
+
+/// Commands targeting a single entity; returned by [`Commands::spawn`] and
+/// [`Commands::entity`].
+pub struct EntityCommands<'a, 'w> {
+    entity: Entity,
+    commands: &'a mut Commands<'w>,
+}
+
+impl EntityCommands<'_, '_> {
+    /// The target entity (valid immediately, even before the queue is applied).
+    #[inline]
+    pub fn id(&self) -> Entity {
+        self.entity
+    }
+
+    /// Queue [`World::insert`].
+    pub fn insert<C: Component + Clone>(&mut self, component: C) -> &mut Self {
+        let entity = self.entity;
+        self.commands.add(move |world: &mut World| world.insert(entity, component));
+        self
+    }
+
+    /// Queue [`World::insert_owned`] for non-Clone components.
+    pub fn insert_owned<C: Component>(&mut self, component: C) -> &mut Self {
+        let entity = self.entity;
+        self.commands.add(move |world: &mut World| world.insert_owned(entity, component));
+        self
+    }
+
+    /// Queue [`World::remove`].
+    pub fn remove<C: Component>(&mut self) -> &mut Self {
+        let entity = self.entity;
+        self.commands.add(move |world: &mut World| {
+            world.remove::<C>(entity);
+        });
+        self
+    }
+
+    /// Queue [`World::relate`] from this entity to `target`.
+    pub fn relate<R: Relation>(&mut self, target: Entity) -> &mut Self {
+        let entity = self.entity;
+        self.commands.add(move |world: &mut World| {
+            world.relate::<R>(entity, target);
+        });
+        self
+    }
+
+    /// Queue [`World::unrelate`].
+    pub fn unrelate<R: Relation>(&mut self, target: Entity) -> &mut Self {
+        let entity = self.entity;
+        self.commands.add(move |world: &mut World| {
+            world.unrelate::<R>(entity, target);
+        });
+        self
+    }
+
+    /// Queue [`World::trigger_for`] targeting this entity.
+    pub fn trigger<E: Send + Sync + 'static>(&mut self, event: E) -> &mut Self {
+        let entity = self.entity;
+        self.commands.add(move |world: &mut World| world.trigger_for(entity, event));
+        self
+    }
+
+    /// Queue despawning the entity.
+    pub fn despawn(&mut self) {
+        self.commands.despawn(self.entity);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// SystemParam implementation
+
+unsafe impl<'a> SystemParam for Commands<'a> {
+    type State = CommandQueue;
+    type Item<'w> = Commands<'w>;
+
+    fn init(_world: &World, _resources: &ResourceMap) -> CommandQueue {
+        CommandQueue::new()
+    }
+
+    #[inline]
+    unsafe fn fetch<'w>(
+        state: &'w mut CommandQueue,
+        world: &'w World,
+        _resources: &'w ResourceMap,
+    ) -> Commands<'w> {
+        Commands::new(state, world)
+    }
+
+    fn apply(state: &mut CommandQueue, world: &mut World) {
+        state.apply(world);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Pos(f32);
+    impl Component for Pos {}
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Tag;
+    impl Component for Tag {}
+
+    struct Script(Box<dyn Fn() -> u32 + Send + Sync>);
+    impl Component for Script {}
+
+    #[test]
+    fn commands_apply_in_order() {
+        let mut world = World::new();
+        let doomed = world.spawn((Pos(0.0),));
+        let kept = world.spawn((Pos(1.0), Tag));
+        let mut queue = CommandQueue::new();
+
+        let mut commands = Commands::new(&mut queue, &world);
+        commands.despawn(doomed);
+        commands.entity(kept).remove::<Tag>().insert(Pos(2.0));
+        assert_eq!(queue.len(), 3);
+        assert!(world.contains(doomed));
+
+        queue.apply(&mut world);
+        assert!(queue.is_empty());
+        assert!(!world.contains(doomed));
+        assert_eq!(world.get::<Pos>(kept), Some(&Pos(2.0)));
+        assert!(world.get::<Tag>(kept).is_none());
+    }
+
+    #[test]
+    fn reserved_entities_accept_chained_inserts() {
+        let mut world = World::new();
+        // Leave a free slot behind so reservation recycles it.
+        let old = world.spawn((Tag,));
+        world.despawn(old);
+
+        let mut queue = CommandQueue::new();
+        let mut commands = Commands::new(&mut queue, &world);
+        let a = commands.spawn((Pos(1.0),)).insert(Tag).id();
+        let b = commands.spawn_empty().insert(Pos(2.0)).id();
+        assert_ne!(a, b);
+        assert_eq!(a.index, old.index);
+        assert_ne!(a, old);
+
+        queue.apply(&mut world);
+        assert_eq!(world.len(), 2);
+        assert_eq!(world.get::<Pos>(a), Some(&Pos(1.0)));
+        assert!(world.get::<Tag>(a).is_some());
+        assert_eq!(world.get::<Pos>(b), Some(&Pos(2.0)));
+    }
+
+    #[test]
+    fn insert_owned_defers_non_clone_components() {
+        let mut world = World::new();
+        let mut queue = CommandQueue::new();
+
+        let mut commands = Commands::new(&mut queue, &world);
+        let e = commands.spawn_empty().insert_owned(Script(Box::new(|| 7))).id();
+        commands.spawn((Tag,)).despawn();
+
+        queue.apply(&mut world);
+        assert_eq!((world.get::<Script>(e).unwrap().0)(), 7);
+        assert_eq!(world.len(), 1);
+    }
+
+    #[test]
+    fn relations_can_be_deferred() {
+        struct Holds;
+        impl Relation for Holds {}
+
+        let mut world = World::new();
+        let hand = world.spawn((Tag,));
+        let mut queue = CommandQueue::new();
+
+        let mut commands = Commands::new(&mut queue, &world);
+        let item = commands.spawn((Pos(0.0),)).id();
+        commands.entity(hand).relate::<Holds>(item);
+
+        queue.apply(&mut world);
+        assert_eq!(world.targets::<Holds>(hand), &[item]);
+        assert_eq!(world.sources::<Holds>(item), &[hand]);
+    }
+}
@@ -20,7 +20,42 @@ use std::any::TypeId;
 ///
 /// Components must be `Send + Sync + 'static` so they can live in shared
 /// archetype storage and be accessed from worker threads.
-pub trait Component: Send + Sync + 'static {}
+///
+/// Components that are added and removed far more often than they are
+/// iterated — hover / selection flags, `ShadowCaster`-style markers — can
+/// opt into sparse-set storage so that toggling them never moves the entity
+/// between archetypes:
+///
+/// ```rust
+/// use ferrous_ecs::component::{Component, StorageType};
+///
+/// #[derive(Debug, Clone)]
+/// struct Hovered;
+/// impl Component for Hovered {
+///     const STORAGE: StorageType = StorageType::SparseSet;
+/// }
+/// ```
+pub trait Component: Send + Sync + 'static {
+    /// Where values of this type live (see [`StorageType`]).
+    const STORAGE: StorageType = StorageType::Table;
+}
+
+/// Storage strategy of a component type.
+///
+/// Queries join both storages transparently; the choice only trades
+/// iteration speed against the cost of `insert` / `remove`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum StorageType {
+    /// Dense archetype columns: fastest iteration, but adding or removing
+    /// the component moves the entity (and clones all its other components)
+    /// to another archetype.
+    #[default]
+    Table,
+    /// One [`ComponentSparseSet`](crate::sparse_set::ComponentSparseSet) per
+    /// type, keyed by entity: O(1) insert / remove without touching the
+    /// entity's archetype, at the price of an indirection per access.
+    SparseSet,
+}
 
 // ---------------------------------------------------------------------------
 // Type-erased vtable for archetype columns
@@ -41,6 +76,8 @@ pub struct ComponentInfo {
     pub(crate) clone_fn: CloneFn,
     /// Human-readable name for debugging.
     pub(crate) name: &'static str,
+    /// Storage strategy declared by the component type.
+    pub(crate) storage: StorageType,
 }
 
 impl std::fmt::Debug for ComponentInfo {
@@ -51,6 +88,7 @@ impl std::fmt::Debug for ComponentInfo {
             .field("size", &self.size)
             .field("align", &self.align)
             .field("has_drop", &self.drop_fn.is_some())
+            .field("storage", &self.storage)
             .finish()
     }
 }
@@ -84,6 +122,7 @@ impl ComponentInfo {
             },
             clone_fn: clone_impl::<C>,
             name: std::any::type_name::<C>(),
+            storage: C::STORAGE,
         }
     }
 
@@ -112,6 +151,7 @@ impl ComponentInfo {
             },
             clone_fn: clone_panic,
             name: std::any::type_name::<C>(),
+            storage: C::STORAGE,
         }
     }
 }
@@ -169,21 +209,6 @@ impl ComponentSet {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    // Ensure the derive macro is available and produces a valid impl.
-    #[derive(Component)]
-    struct DeriveCheck(u8);
-
-    #[test]
-    fn derived_type_is_component() {
-        fn needs_component<T: Component>() {}
-        needs_component::<DeriveCheck>();
-    }
-}
-
 // ---------------------------------------------------------------------------
 // Bundle trait — heterogeneous tuples of components that can be spawned together
 
@@ -245,6 +270,25 @@ impl_bundle!(0: A, 1: B, 2: C, 3: D, 4: E, 5: F, 6: G, 7: H, 8: I, 9: J, 10: K,
 mod tests {
     use super::*;
 
+    // Ensure the derive macro is available and produces a valid impl.
+    #[cfg(feature = "derive")]
+    #[derive(ferrous_ecs_macros::Component)]
+    struct DeriveCheck(u8);
+
+    #[cfg(feature = "derive")]
+    #[derive(ferrous_ecs_macros::Component)]
+    #[component(storage = "SparseSet")]
+    struct SparseDeriveCheck;
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn derived_type_is_component() {
+        fn needs_component<T: Component>() {}
+        needs_component::<DeriveCheck>();
+        assert_eq!(DeriveCheck::STORAGE, StorageType::Table);
+        assert_eq!(SparseDeriveCheck::STORAGE, StorageType::SparseSet);
+    }
+
     #[derive(Clone)]
     struct Hp(f32);
     impl Component for Hp {}
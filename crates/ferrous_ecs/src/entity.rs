@@ -5,6 +5,8 @@
 //! generation, so dangling handles (from a previous incarnation of that slot)
 //! are reliably detected.
 
+use std::sync::atomic::{AtomicI64, Ordering};
+
 use serde::{Deserialize, Serialize};
 
 /// A lightweight, copy-able entity handle.
@@ -50,6 +52,67 @@ impl std::fmt::Display for Entity {
 
 // ---------------------------------------------------------------------------
 
+/// Old → new entity handles, produced when entities are copied into another
+/// world (e.g. by loading a snapshot).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EntityMap {
+    map: std::collections::HashMap<Entity, Entity>,
+}
+
+impl EntityMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, from: Entity, to: Entity) {
+        self.map.insert(from, to);
+    }
+
+    /// New handle for `from`, if it was remapped.
+    #[inline]
+    pub fn get(&self, from: Entity) -> Option<Entity> {
+        self.map.get(&from).copied()
+    }
+
+    /// New handle for `from`; handles outside the map are returned unchanged.
+    #[inline]
+    pub fn map(&self, from: Entity) -> Entity {
+        self.get(from).unwrap_or(from)
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// `(old, new)` pairs in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, Entity)> + '_ {
+        self.map.iter().map(|(&a, &b)| (a, b))
+    }
+}
+
+/// Components that store `Entity` handles implement this so the handles can
+/// be rewritten when the component is copied into another world.
+///
+/// ```rust
+/// use ferrous_ecs::entity::{Entity, EntityMap, MapEntities};
+///
+/// struct Target(Entity);
+/// impl MapEntities for Target {
+///     fn map_entities(&mut self, map: &EntityMap) {
+///         self.0 = map.map(self.0);
+///     }
+/// }
+/// ```
+pub trait MapEntities {
+    fn map_entities(&mut self, map: &EntityMap);
+}
+
+// ---------------------------------------------------------------------------
+
 /// Internal slot in the entity table.
 #[derive(Debug, Clone)]
 pub(crate) struct EntityRecord {
@@ -77,10 +140,19 @@ impl EntityRecord {
 ///
 /// Free slots are stored in a LIFO stack so that recently freed indices get
 /// reused quickly, keeping the working-set small.
+///
+/// Handles can also be *reserved* through a shared reference
+/// ([`EntityAllocator::reserve`]) — e.g. by `Commands` inside a running
+/// system.  Reserved handles become real records on the next
+/// [`World::flush`](crate::world::World::flush).
 #[derive(Debug, Default)]
 pub struct EntityAllocator {
     records: Vec<EntityRecord>,
     free: Vec<u32>,
+    /// Number of `free` entries not yet handed out by `reserve`.  Goes
+    /// negative once the free list is exhausted: `-free_cursor` brand-new
+    /// indices past `records.len()` have then been reserved.
+    free_cursor: AtomicI64,
 }
 
 impl EntityAllocator {
@@ -90,7 +162,9 @@ impl EntityAllocator {
 
     /// Allocate a new live entity and return its handle + mutable record ref.
     pub(crate) fn alloc(&mut self) -> (Entity, usize) {
+        debug_assert!(!self.needs_flush(), "alloc with pending reservations");
         if let Some(index) = self.free.pop() {
+            *self.free_cursor.get_mut() = self.free.len() as i64;
             let rec = &mut self.records[index as usize];
             debug_assert!(rec.archetype_id.is_none(), "free slot was still live");
             let entity = Entity {
@@ -112,6 +186,7 @@ impl EntityAllocator {
     /// Mark an entity slot as free, incrementing its generation.
     /// Returns `false` if the entity was already dead (stale handle).
     pub(crate) fn free(&mut self, entity: Entity) -> bool {
+        debug_assert!(!self.needs_flush(), "free with pending reservations");
         let rec = match self.records.get_mut(entity.index as usize) {
             Some(r) => r,
             None => return false,
@@ -122,9 +197,59 @@ impl EntityAllocator {
         rec.generation = rec.generation.wrapping_add(1);
         rec.archetype_id = None;
         self.free.push(entity.index);
+        *self.free_cursor.get_mut() = self.free.len() as i64;
         true
     }
 
+    /// Reserve an entity handle without mutable access.
+    ///
+    /// The handle is unique and stable, but only becomes a live record once
+    /// [`flush`](Self::flush) runs.  Safe to call from several threads.
+    pub fn reserve(&self) -> Entity {
+        let n = self.free_cursor.fetch_sub(1, Ordering::Relaxed);
+        if n > 0 {
+            let index = self.free[n as usize - 1];
+            Entity {
+                index,
+                generation: self.records[index as usize].generation,
+            }
+        } else {
+            Entity {
+                index: (self.records.len() as i64 - n) as u32,
+                generation: 0,
+            }
+        }
+    }
+
+    /// `true` if handles were reserved since the last flush.
+    #[inline]
+    pub fn needs_flush(&self) -> bool {
+        self.free_cursor.load(Ordering::Relaxed) != self.free.len() as i64
+    }
+
+    /// Turn every reserved handle into a record, calling `init` on each so
+    /// the caller can place it in an archetype.
+    pub(crate) fn flush(&mut self, mut init: impl FnMut(Entity, &mut EntityRecord)) {
+        let cursor = *self.free_cursor.get_mut();
+        if cursor == self.free.len() as i64 {
+            return;
+        }
+        let kept = cursor.max(0) as usize;
+        // Reserved free slots are taken from the end of the stack.
+        for index in self.free.drain(kept..).rev() {
+            let rec = &mut self.records[index as usize];
+            init(Entity { index, generation: rec.generation }, rec);
+        }
+        if cursor < 0 {
+            for _ in 0..(-cursor) {
+                let index = self.records.len() as u32;
+                self.records.push(EntityRecord::free());
+                init(Entity { index, generation: 0 }, self.records.last_mut().unwrap());
+            }
+        }
+        *self.free_cursor.get_mut() = self.free.len() as i64;
+    }
+
     /// Check if an entity handle refers to a currently live slot.
     #[inline]
     pub fn is_alive(&self, entity: Entity) -> bool {
@@ -196,6 +321,32 @@ mod tests {
         let _ = e1;
     }
 
+    #[test]
+    fn reserve_then_flush() {
+        let mut alloc = EntityAllocator::new();
+        let (e0, idx0) = alloc.alloc();
+        alloc.get_mut(idx0).unwrap().archetype_id = Some(0);
+        alloc.free(e0);
+
+        // First reservation recycles the freed slot, the next ones are fresh.
+        let r0 = alloc.reserve();
+        let r1 = alloc.reserve();
+        let r2 = alloc.reserve();
+        assert_eq!((r0.index, r0.generation), (e0.index, e0.generation + 1));
+        assert_eq!((r1.index, r2.index), (1, 2));
+        assert!(alloc.needs_flush());
+
+        let mut flushed = Vec::new();
+        alloc.flush(|e, rec| {
+            rec.archetype_id = Some(0);
+            flushed.push(e);
+        });
+        assert_eq!(flushed, vec![r0, r1, r2]);
+        assert!(!alloc.needs_flush());
+        assert!(alloc.is_alive(r0) && alloc.is_alive(r1) && alloc.is_alive(r2));
+        assert_eq!(alloc.len(), 3);
+    }
+
     #[test]
     fn bits_roundtrip() {
         let e = Entity {
@@ -29,8 +29,10 @@
 //! ```
 
 use std::any::TypeId;
+use std::marker::PhantomData;
 
 use crate::resource::ResourceMap;
+use crate::system::System;
 use crate::system_param::SystemParam;
 use crate::world::World;
 
@@ -76,6 +78,27 @@ impl<T: Send + Sync + 'static> Events<T> {
     }
 }
 
+/// Rotates the `Events<T>` resource once per frame with [`Events::update`],
+/// so readers see everything sent during the previous frame.
+///
+/// Register at the start of the frame (e.g. `Stage::PreUpdate`).  Does
+/// nothing while the resource is missing.
+pub struct EventUpdateSystem<T>(PhantomData<fn() -> T>);
+
+impl<T> Default for EventUpdateSystem<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Send + Sync + 'static> System for EventUpdateSystem<T> {
+    fn run(&mut self, _world: &mut World, resources: &mut ResourceMap) {
+        if let Some(events) = resources.get_mut::<Events<T>>() {
+            events.update();
+        }
+    }
+}
+
 /// Writable handle injected into systems.
 pub struct EventWriter<'w, T: Send + Sync + 'static> {
     events: &'w mut Events<T>,
@@ -190,7 +213,7 @@ mod tests {
 
     #[test]
     fn reader_reads_previous_frame() {
-        let mut events = Events::new();
+        let mut events = Events::<u32>::new();
         events.send(7);
         events.update();
         let mut resources = ResourceMap::new();
@@ -0,0 +1,142 @@
+//! Fixed-timestep accumulator behind [`Stage::FixedUpdate`].
+//!
+//! Each frame, [`StagedScheduler`] adds the frame delta from the
+//! [`FixedTime`] resource to an accumulator and runs `FixedUpdate` once per
+//! whole step it contains, so simulation results do not depend on frame
+//! rate.  To avoid a spiral of death on slow frames at most `max_steps`
+//! steps run per frame; the excess time is dropped.
+//!
+//! Whatever remains in the accumulator is published as
+//! [`FixedTime::alpha`]: how far the current frame lies between the last two
+//! fixed states, for rendering to interpolate with.
+//!
+//! ```text
+//! frame delta 0.040 s, step 1/60 s, accumulator 0.005 s
+//!   → 0.045 s = 2 steps (0.0333 s) + 0.0117 s   → alpha = 0.70
+//! ```
+//!
+//! [`Stage::FixedUpdate`]: crate::system::Stage::FixedUpdate
+//! [`StagedScheduler`]: crate::system::StagedScheduler
+
+/// Default fixed step: 60 Hz.
+pub const DEFAULT_FIXED_STEP: f32 = 1.0 / 60.0;
+
+/// Default catch-up limit per frame.
+pub const DEFAULT_MAX_STEPS: u32 = 5;
+
+/// Resource shared between the frame clock and the fixed-step stage.
+///
+/// The frame clock (e.g. `ferrous_core::TimeSystem`, in `PreUpdate`) writes
+/// [`delta`](Self::delta); the scheduler fills in the rest before running
+/// `FixedUpdate`.  If the resource is missing, the scheduler inserts a
+/// default one and `FixedUpdate` does not run until something sets `delta`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedTime {
+    /// Variable frame delta in seconds, to be consumed by the accumulator
+    /// (reset to `0` once consumed).
+    pub delta: f32,
+    /// Length of one fixed step in seconds.
+    pub step: f32,
+    /// Number of fixed steps run this frame.
+    pub steps: u32,
+    /// Fraction of a step left in the accumulator, in `[0, 1)`.
+    pub alpha: f32,
+}
+
+impl Default for FixedTime {
+    fn default() -> Self {
+        FixedTime {
+            delta: 0.0,
+            step: DEFAULT_FIXED_STEP,
+            steps: 0,
+            alpha: 0.0,
+        }
+    }
+}
+
+/// Accumulator state owned by the scheduler.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedTimestep {
+    step: f32,
+    max_steps: u32,
+    accumulator: f32,
+}
+
+impl Default for FixedTimestep {
+    fn default() -> Self {
+        FixedTimestep::new(DEFAULT_FIXED_STEP, DEFAULT_MAX_STEPS)
+    }
+}
+
+impl FixedTimestep {
+    /// # Panics
+    /// Panics if `step` is not positive.
+    pub fn new(step: f32, max_steps: u32) -> Self {
+        assert!(step > 0.0, "fixed step must be positive, got {step}");
+        FixedTimestep {
+            step,
+            max_steps,
+            accumulator: 0.0,
+        }
+    }
+
+    #[inline]
+    pub fn step(&self) -> f32 {
+        self.step
+    }
+
+    #[inline]
+    pub fn max_steps(&self) -> u32 {
+        self.max_steps
+    }
+
+    /// Add `delta` seconds and return how many steps to run now.
+    ///
+    /// Time beyond `max_steps` steps is discarded.
+    pub fn accumulate(&mut self, delta: f32) -> u32 {
+        self.accumulator += delta.max(0.0);
+        let mut steps = (self.accumulator / self.step) as u32;
+        if steps > self.max_steps {
+            steps = self.max_steps;
+            self.accumulator = self.accumulator.rem_euclid(self.step);
+        } else {
+            self.accumulator -= steps as f32 * self.step;
+        }
+        // Guard against rounding leaving a full step behind.
+        if self.accumulator >= self.step {
+            self.accumulator = 0.0;
+        }
+        steps
+    }
+
+    /// Interpolation factor between the last two fixed states.
+    #[inline]
+    pub fn alpha(&self) -> f32 {
+        self.accumulator / self.step
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accumulates_whole_steps_and_keeps_remainder() {
+        let mut fixed = FixedTimestep::new(0.25, 10);
+        assert_eq!(fixed.accumulate(0.1), 0);
+        assert!((fixed.alpha() - 0.4).abs() < 1e-6);
+        assert_eq!(fixed.accumulate(0.5), 2);
+        assert!((fixed.alpha() - 0.4).abs() < 1e-6);
+    }
+
+    #[test]
+    fn catch_up_is_capped() {
+        let mut fixed = FixedTimestep::new(0.1, 3);
+        assert_eq!(fixed.accumulate(1.05), 3);
+        assert!(fixed.alpha() < 1.0);
+        assert_eq!(fixed.accumulate(0.0), 0);
+    }
+}
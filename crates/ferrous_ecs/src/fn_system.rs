@@ -69,10 +69,18 @@ where
 {
     fn name(&self) -> &'static str { self.name }
 
+    /// `Added` / `Changed` filters see [`World::last_change_tick`] as set by
+    /// the scheduler, which scopes it to this system's previous run.
     fn run(&mut self, world: &mut World, resources: &mut ResourceMap) {
         let state = self.state.get_or_insert_with(|| Params::init(world, resources));
         self.func.call(state, world, resources);
     }
+
+    fn apply_deferred(&mut self, world: &mut World) {
+        if let Some(state) = &mut self.state {
+            Params::apply(state, world);
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -139,7 +147,7 @@ macro_rules! impl_fn_system {
                 FnSystem {
                     func: self,
                     state: None,
-                    name: std::any::type_name::<F>(),
+                            name: std::any::type_name::<F>(),
                     _marker: PhantomData,
                 }
             }
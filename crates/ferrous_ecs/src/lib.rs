@@ -7,12 +7,19 @@
 //! | `entity`      | Entity ID (index + generation), EntityAllocator             |
 //! | `component`   | Component trait, TypeId-keyed metadata                      |
 //! | `archetype`   | Dense SoA storage; one archetype per unique component set   |
+//! | `sparse_set`  | Per-type sparse-set storage for frequently toggled components|
 //! | `world`       | spawn / despawn / insert / remove / get                     |
-//! | `query`       | `WorldQuery` trait + `Query<Q>` tuple iterators             |
+//! | `observer`    | component lifecycle hooks, event observers / triggers       |
+//! | `relation`    | typed entity relations, reverse links, despawn policies     |
+//! | `query`       | `WorldQuery`, `Query<Q, F>` iterators, `QueryFilter`s       |
 //! | `resource`    | Non-entity global state (ResourceMap)                       |
 //! | `system`      | `System` trait, `SystemScheduler`, `StagedScheduler`        |
+//! | `fixed`       | fixed-timestep accumulator, `FixedTime` resource            |
+//! | `schedule`    | labels, `before`/`after`, `SystemSet`, `run_if` conditions  |
 //! | `system_param`| `SystemParam` trait, `Res<T>`, `ResMut<T>`                  |
 //! | `event`       | typed events + `EventWriter`/`EventReader`                |
+//! | `snapshot`    | `ComponentRegistry`, JSON / binary world snapshots (feature)|
+//! | `commands`    | `Commands` — deferred spawn/insert/remove/despawn           |
 //! | `fn_system`   | `IntoSystem` trait, `FnSystem` — plain-function systems     |
 //!
 //! ## Parallel scheduling (`feature = "parallel"`)
@@ -20,7 +27,16 @@
 //! Enable the `parallel` Cargo feature to get [`system::parallel::ParallelScheduler`].
 //! Systems declare their component/resource access via [`system::parallel::SystemAccess`];
 //! the scheduler groups them into conflict-free batches and dispatches each batch
-//! with `rayon::scope`.
+//! with `rayon::scope`.  Inside a single system, [`query::Query::par_iter`]
+//! splits the matching rows into batches and iterates them on the same pool.
+//!
+//! ## Change detection
+//!
+//! Every component value carries added/changed ticks taken from the world
+//! change tick, which `Added` / `Changed` query filters compare against.
+//! The tick is read with [`world::World::change_tick`] — formerly a public
+//! `u64` field, now a method (the counter became atomic; see its docs for
+//! migrating).
 //!
 //! ## Non-Clone components
 //!
@@ -46,24 +62,44 @@
 //! assert!(world.contains(e));
 //! ```
 
+// Lets `#[derive(Component)]` (which emits `ferrous_ecs::...` paths) be used
+// inside this crate, e.g. in unit tests.
+extern crate self as ferrous_ecs;
+
 pub mod archetype;
+pub mod commands;
 pub mod component;
 pub mod entity;
 pub mod event;
+pub mod fixed;
 pub mod fn_system;
+pub mod observer;
 pub mod query;
+pub mod relation;
 pub mod resource;
+pub mod schedule;
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
+pub mod sparse_set;
 pub mod system;
 pub mod system_param;
 pub mod world;
 
 pub mod prelude {
-    pub use crate::component::Component;
-    pub use crate::entity::Entity;
+    pub use crate::commands::{CommandQueue, Commands, EntityCommands};
+    pub use crate::component::{Component, StorageType};
+    pub use crate::entity::{Entity, EntityMap, MapEntities};
+    pub use crate::fixed::FixedTime;
     // New: function-system ergonomics
     pub use crate::fn_system::IntoSystem;
-    pub use crate::query::{Query, QueryMut, WorldQuery};
+    pub use crate::observer::{ComponentHooks, ObserverId, Trigger};
+    pub use crate::query::{
+        Added, Changed, Or, Query, QueryFilter, QueryMut, ReadOnlyWorldQuery, With, Without,
+        WorldQuery,
+    };
+    pub use crate::relation::{DespawnPolicy, Relation, RelationSources, RelationTargets};
     pub use crate::resource::ResourceMap;
+    pub use crate::schedule::{IntoSystemConfig, ScheduleError, SystemConfig, SystemSet};
     // Note: `crate::system::fn_system` (the legacy closure constructor) is
     // intentionally NOT re-exported here to avoid name collision with the
     // `crate::fn_system` module.  Use `crate::system::fn_system(...)` directly.
@@ -75,11 +111,16 @@ pub mod prelude {
     pub use crate::world::World;
 
     // Event API
-    pub use crate::event::{EventReader, EventWriter, Events};
+    pub use crate::event::{EventReader, EventUpdateSystem, EventWriter, Events};
 
     // Parallel scheduling — only available with `feature = "parallel"`.
     #[cfg(feature = "parallel")]
     pub use crate::system::parallel::{ParallelScheduler, SystemAccess, SystemMeta};
+    #[cfg(feature = "parallel")]
+    pub use crate::query::QueryParIter;
+    // World snapshots — only available with `feature = "snapshot"`.
+    #[cfg(feature = "snapshot")]
+    pub use crate::snapshot::{ComponentRegistry, SnapshotError, SnapshotFormat};
     // Derive macro for Component (re-exported so users only need
     // `use ferrous_ecs::prelude::*;` and `#[derive(Component)]`).
     #[cfg(feature = "derive")]
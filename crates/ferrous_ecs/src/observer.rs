@@ -0,0 +1,478 @@
+//! Component lifecycle hooks and event observers.
+//!
+//! # Hooks
+//!
+//! Per-component-type callbacks run synchronously by the [`World`] when a
+//! component's lifecycle changes, so subsystems can react immediately
+//! instead of diffing every frame:
+//!
+//! | Hook         | Fires                                                     |
+//! |--------------|-----------------------------------------------------------|
+//! | `on_add`     | after the component is added to an entity that lacked it  |
+//! | `on_insert`  | after every insert (add or replace), value in place       |
+//! | `on_replace` | before an existing value is overwritten by an insert      |
+//! | `on_despawn` | before the entity holding the component is despawned      |
+//! | `on_remove`  | before the component is removed (`remove` or despawn)     |
+//!
+//! Spawning counts as adding every component of the bundle.  "Before" hooks
+//! still see the old value through `world.get`.  A despawn runs the hooks of
+//! the entity's components in ascending `TypeId` order, table and sparse-set
+//! storage alike.  [`World::clear`] runs no hooks.
+//!
+//! ```rust
+//! use ferrous_ecs::prelude::*;
+//! use std::sync::{Arc, Mutex};
+//!
+//! #[derive(Clone)] struct Light;
+//! impl Component for Light {}
+//!
+//! let freed = Arc::new(Mutex::new(Vec::new()));
+//! let log = freed.clone();
+//! let mut world = World::new();
+//! world
+//!     .component_hooks::<Light>()
+//!     .on_remove(move |_world, e| log.lock().unwrap().push(e));
+//!
+//! let lamp = world.spawn((Light,));
+//! world.despawn(lamp);
+//! assert_eq!(*freed.lock().unwrap(), vec![lamp]);
+//! ```
+//!
+//! # Observers
+//!
+//! Observers react to custom events sent with [`World::trigger`] or, aimed at
+//! one entity, [`World::trigger_for`].  Entity observers
+//! ([`World::observe_entity`]) only see events targeting their entity and are
+//! dropped when it is despawned; global observers ([`World::observe`]) see
+//! every event of their type.
+//!
+//! ```rust
+//! use ferrous_ecs::prelude::*;
+//!
+//! #[derive(Clone)] struct Hp(i32);
+//! impl Component for Hp {}
+//! struct Damage(i32);
+//!
+//! let mut world = World::new();
+//! let player = world.spawn((Hp(10),));
+//! world.observe_entity(player, |world: &mut World, trigger: Trigger<Damage>| {
+//!     let e = trigger.entity().unwrap();
+//!     world.get_mut::<Hp>(e).unwrap().0 -= trigger.event().0;
+//! });
+//!
+//! world.trigger_for(player, Damage(3));
+//! assert_eq!(world.get::<Hp>(player).unwrap().0, 7);
+//! ```
+//!
+//! Hooks and observers receive `&mut World` and may make structural changes,
+//! including ones that trigger further hooks.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::component::Component;
+use crate::entity::Entity;
+use crate::world::World;
+
+// ---------------------------------------------------------------------------
+// Component hooks
+
+/// A lifecycle callback: `(world, entity)`.
+pub type ComponentHook = Arc<dyn Fn(&mut World, Entity) + Send + Sync>;
+
+/// Lifecycle stage a hook is attached to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HookKind {
+    Add,
+    Insert,
+    Replace,
+    Despawn,
+    Remove,
+}
+
+/// Hooks registered for one component type; obtained from
+/// [`World::component_hooks`].
+#[derive(Default, Clone)]
+pub struct ComponentHooks {
+    on_add: Vec<ComponentHook>,
+    on_insert: Vec<ComponentHook>,
+    on_replace: Vec<ComponentHook>,
+    on_despawn: Vec<ComponentHook>,
+    on_remove: Vec<ComponentHook>,
+}
+
+impl fmt::Debug for ComponentHooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ComponentHooks")
+            .field("on_add", &self.on_add.len())
+            .field("on_insert", &self.on_insert.len())
+            .field("on_replace", &self.on_replace.len())
+            .field("on_despawn", &self.on_despawn.len())
+            .field("on_remove", &self.on_remove.len())
+            .finish()
+    }
+}
+
+impl ComponentHooks {
+    pub fn on_add(&mut self, hook: impl Fn(&mut World, Entity) + Send + Sync + 'static) -> &mut Self {
+        self.on_add.push(Arc::new(hook));
+        self
+    }
+
+    pub fn on_insert(&mut self, hook: impl Fn(&mut World, Entity) + Send + Sync + 'static) -> &mut Self {
+        self.on_insert.push(Arc::new(hook));
+        self
+    }
+
+    pub fn on_replace(&mut self, hook: impl Fn(&mut World, Entity) + Send + Sync + 'static) -> &mut Self {
+        self.on_replace.push(Arc::new(hook));
+        self
+    }
+
+    pub fn on_despawn(&mut self, hook: impl Fn(&mut World, Entity) + Send + Sync + 'static) -> &mut Self {
+        self.on_despawn.push(Arc::new(hook));
+        self
+    }
+
+    pub fn on_remove(&mut self, hook: impl Fn(&mut World, Entity) + Send + Sync + 'static) -> &mut Self {
+        self.on_remove.push(Arc::new(hook));
+        self
+    }
+
+    fn get(&self, kind: HookKind) -> &[ComponentHook] {
+        match kind {
+            HookKind::Add => &self.on_add,
+            HookKind::Insert => &self.on_insert,
+            HookKind::Replace => &self.on_replace,
+            HookKind::Despawn => &self.on_despawn,
+            HookKind::Remove => &self.on_remove,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Observers
+
+/// Handle returned by [`World::observe`] / [`World::observe_entity`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObserverId(u64);
+
+/// What an observer receives: the event and, for targeted triggers, the
+/// entity it was sent to.
+pub struct Trigger<'a, E> {
+    event: &'a E,
+    entity: Option<Entity>,
+}
+
+impl<'a, E> Trigger<'a, E> {
+    #[inline]
+    pub fn event(&self) -> &'a E {
+        self.event
+    }
+
+    /// Target entity (`None` for [`World::trigger`]).
+    #[inline]
+    pub fn entity(&self) -> Option<Entity> {
+        self.entity
+    }
+}
+
+impl<E> Clone for Trigger<'_, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E> Copy for Trigger<'_, E> {}
+
+type ErasedObserver = Arc<dyn Fn(&mut World, &dyn Any, Option<Entity>) + Send + Sync>;
+
+struct ObserverEntry {
+    id: ObserverId,
+    /// `None` = global observer.
+    target: Option<Entity>,
+    run: ErasedObserver,
+}
+
+/// All observers of a world, keyed by event type.
+#[derive(Default)]
+pub(crate) struct Observers {
+    next_id: u64,
+    by_event: HashMap<TypeId, Vec<ObserverEntry>>,
+}
+
+impl fmt::Debug for Observers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let count: usize = self.by_event.values().map(Vec::len).sum();
+        f.debug_struct("Observers").field("count", &count).finish()
+    }
+}
+
+impl Observers {
+    /// Drop observers targeting `entity`.
+    pub(crate) fn forget_entity(&mut self, entity: Entity) {
+        for list in self.by_event.values_mut() {
+            list.retain(|o| o.target != Some(entity));
+        }
+    }
+
+    /// Drop every entity observer, keeping global ones.
+    pub(crate) fn forget_entities(&mut self) {
+        for list in self.by_event.values_mut() {
+            list.retain(|o| o.target.is_none());
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// World API
+
+impl World {
+    /// Hooks for component type `C`, for registering new callbacks.
+    pub fn component_hooks<C: Component>(&mut self) -> &mut ComponentHooks {
+        self.hooks.entry(TypeId::of::<C>()).or_default()
+    }
+
+    /// Run the `kind` hooks of `type_id` for `entity`.
+    pub(crate) fn run_hooks(&mut self, type_id: TypeId, kind: HookKind, entity: Entity) {
+        let Some(hooks) = self.hooks.get(&type_id) else {
+            return;
+        };
+        // Clone the (cheap) handles: hooks may register more hooks.
+        let hooks: Vec<ComponentHook> = hooks.get(kind).to_vec();
+        for hook in hooks {
+            hook(self, entity);
+        }
+    }
+
+    /// Observe every `E` event, targeted or not.
+    pub fn observe<E: 'static>(
+        &mut self,
+        observer: impl Fn(&mut World, Trigger<'_, E>) + Send + Sync + 'static,
+    ) -> ObserverId {
+        self.add_observer(None, observer)
+    }
+
+    /// Observe `E` events sent to `entity` with [`World::trigger_for`].  The
+    /// observer is dropped when `entity` is despawned.
+    pub fn observe_entity<E: 'static>(
+        &mut self,
+        entity: Entity,
+        observer: impl Fn(&mut World, Trigger<'_, E>) + Send + Sync + 'static,
+    ) -> ObserverId {
+        self.add_observer(Some(entity), observer)
+    }
+
+    fn add_observer<E: 'static>(
+        &mut self,
+        target: Option<Entity>,
+        observer: impl Fn(&mut World, Trigger<'_, E>) + Send + Sync + 'static,
+    ) -> ObserverId {
+        let id = ObserverId(self.observers.next_id);
+        self.observers.next_id += 1;
+        let run: ErasedObserver = Arc::new(move |world, event, entity| {
+            let event = event.downcast_ref::<E>().expect("observer event type mismatch");
+            observer(world, Trigger { event, entity });
+        });
+        self.observers
+            .by_event
+            .entry(TypeId::of::<E>())
+            .or_default()
+            .push(ObserverEntry { id, target, run });
+        id
+    }
+
+    /// Remove an observer.  Returns `false` if it was already gone.
+    pub fn unobserve(&mut self, id: ObserverId) -> bool {
+        for list in self.observers.by_event.values_mut() {
+            if let Some(pos) = list.iter().position(|o| o.id == id) {
+                list.remove(pos);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Send `event` to the global observers of `E`.
+    pub fn trigger<E: 'static>(&mut self, event: E) {
+        self.dispatch(&event, None);
+    }
+
+    /// Send `event` to the observers of `entity`, then to the global
+    /// observers of `E`.
+    pub fn trigger_for<E: 'static>(&mut self, entity: Entity, event: E) {
+        self.dispatch(&event, Some(entity));
+    }
+
+    fn dispatch<E: 'static>(&mut self, event: &E, entity: Option<Entity>) {
+        let Some(list) = self.observers.by_event.get(&TypeId::of::<E>()) else {
+            return;
+        };
+        let mut matching: Vec<ErasedObserver> = Vec::new();
+        if entity.is_some() {
+            matching.extend(list.iter().filter(|o| o.target == entity).map(|o| o.run.clone()));
+        }
+        matching.extend(list.iter().filter(|o| o.target.is_none()).map(|o| o.run.clone()));
+        for run in matching {
+            run(self, event, entity);
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::StorageType;
+    use std::sync::Mutex;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Light(u32);
+    impl Component for Light {}
+
+    #[derive(Clone)]
+    struct Tag;
+    impl Component for Tag {}
+
+    type Log = Arc<Mutex<Vec<(&'static str, Entity, Option<u32>)>>>;
+
+    fn record(log: &Log, what: &'static str) -> impl Fn(&mut World, Entity) + Send + Sync {
+        let log = log.clone();
+        move |world, e| {
+            let value = world.get::<Light>(e).map(|l| l.0);
+            log.lock().unwrap().push((what, e, value));
+        }
+    }
+
+    #[test]
+    fn hooks_fire_through_the_lifecycle() {
+        let log: Log = Arc::default();
+        let mut world = World::new();
+        world
+            .component_hooks::<Light>()
+            .on_add(record(&log, "add"))
+            .on_insert(record(&log, "insert"))
+            .on_replace(record(&log, "replace"))
+            .on_despawn(record(&log, "despawn"))
+            .on_remove(record(&log, "remove"));
+
+        let a = world.spawn((Light(1), Tag));
+        world.insert(a, Light(2));
+        world.remove::<Light>(a);
+        world.insert(a, Light(3));
+        world.remove::<Tag>(a); // other components don't fire Light hooks
+        world.despawn(a);
+
+        let expected = vec![
+            ("add", a, Some(1)),
+            ("insert", a, Some(1)),
+            ("replace", a, Some(1)),
+            ("insert", a, Some(2)),
+            ("remove", a, Some(2)),
+            ("add", a, Some(3)),
+            ("insert", a, Some(3)),
+            ("despawn", a, Some(3)),
+            ("remove", a, Some(3)),
+        ];
+        assert_eq!(*log.lock().unwrap(), expected);
+    }
+
+    #[test]
+    fn despawn_hooks_run_in_component_id_order() {
+        macro_rules! sparse {
+            ($($name:ident),*) => {$(
+                #[derive(Clone)]
+                struct $name;
+                impl Component for $name {
+                    const STORAGE: StorageType = StorageType::SparseSet;
+                }
+            )*};
+        }
+        sparse!(S0, S1, S2, S3, S4, S5);
+
+        let fired: Arc<Mutex<Vec<TypeId>>> = Arc::default();
+        let mut world = World::new();
+        fn watch<C: Component>(world: &mut World, fired: &Arc<Mutex<Vec<TypeId>>>) {
+            let fired = fired.clone();
+            world
+                .component_hooks::<C>()
+                .on_despawn(move |_, _| fired.lock().unwrap().push(TypeId::of::<C>()));
+        }
+        watch::<S0>(&mut world, &fired);
+        watch::<S1>(&mut world, &fired);
+        watch::<S2>(&mut world, &fired);
+        watch::<S3>(&mut world, &fired);
+        watch::<S4>(&mut world, &fired);
+        watch::<S5>(&mut world, &fired);
+        watch::<Light>(&mut world, &fired);
+
+        let e = world.spawn((Light(0), S0, S1, S2, S3, S4, S5));
+        world.despawn(e);
+
+        let fired = fired.lock().unwrap().clone();
+        let mut sorted = fired.clone();
+        sorted.sort_unstable();
+        assert_eq!(fired.len(), 7);
+        assert_eq!(fired, sorted);
+    }
+
+    #[test]
+    fn hooks_may_change_the_world() {
+        let mut world = World::new();
+        // Tagging on add moves the entity to another archetype mid-insert.
+        world.component_hooks::<Light>().on_add(|world, e| world.insert(e, Tag));
+        let e = world.spawn((Light(0),));
+        assert!(world.has::<Tag>(e));
+
+        world.component_hooks::<Tag>().on_remove(|world, e| {
+            world.remove::<Light>(e);
+        });
+        world.remove::<Tag>(e);
+        assert!(!world.has::<Light>(e));
+        assert!(world.contains(e));
+    }
+
+    #[test]
+    fn observers_target_entities_and_are_dropped_on_despawn() {
+        struct Ping(u32);
+        type Seen = Arc<Mutex<Vec<(Option<Entity>, u32)>>>;
+
+        let seen: Seen = Arc::default();
+        let mut world = World::new();
+        let a = world.spawn((Tag,));
+        let b = world.spawn((Tag,));
+
+        let log = seen.clone();
+        world.observe_entity(a, move |_: &mut World, t: Trigger<Ping>| {
+            log.lock().unwrap().push((Some(a), t.event().0));
+        });
+        let log = seen.clone();
+        let global = world.observe(move |_: &mut World, t: Trigger<Ping>| {
+            log.lock().unwrap().push((t.entity(), t.event().0 + 100));
+        });
+
+        world.trigger_for(a, Ping(1));
+        world.trigger_for(b, Ping(2));
+        world.trigger(Ping(3));
+        world.despawn(a);
+        world.trigger_for(a, Ping(4));
+        assert!(world.unobserve(global));
+        assert!(!world.unobserve(global));
+        world.trigger(Ping(5));
+
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![
+                (Some(a), 1),
+                (Some(a), 101),
+                (Some(b), 102),
+                (None, 103),
+                (Some(a), 104),
+            ]
+        );
+    }
+}
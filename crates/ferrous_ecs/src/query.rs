@@ -34,14 +34,145 @@
 //! - The `&mut T` impl requires that no two tuple elements fetch the same
 //!   `TypeId` mutably — enforced by convention (checked in debug builds).
 //! - Zero-size types are handled: `size == 0` paths skip pointer arithmetic.
+//!
+//! # Filters
+//!
+//! The optional second type parameter of [`Query`] is a [`QueryFilter`]:
+//! it restricts which entities are visited without fetching any data.
+//!
+//! | Filter | Keeps entities that… | Evaluated |
+//! |--------|----------------------|-----------|
+//! | [`With<T>`] | have `T` | per archetype |
+//! | [`Without<T>`] | do not have `T` | per archetype |
+//! | [`Added<T>`] | had `T` inserted since the last run | per row |
+//! | [`Changed<T>`] | had `T` inserted or mutated since the last run | per row |
+//! | [`Or<(A, B, …)>`](Or) | match any member | per archetype, then per row |
+//! | `(A, B, …)` | match every member | per archetype, then per row |
+//!
+//! ```rust
+//! use ferrous_ecs::prelude::*;
+//!
+//! #[derive(Clone, Debug)] struct Pos(f32);
+//! impl Component for Pos {}
+//! #[derive(Clone, Debug)] struct Player;
+//! impl Component for Player {}
+//!
+//! let mut world = World::new();
+//! world.spawn((Pos(0.0), Player));
+//! world.spawn((Pos(1.0),));
+//!
+//! assert_eq!(Query::<&Pos, With<Player>>::new(&world).len(), 1);
+//! assert_eq!(Query::<&Pos, Without<Player>>::new(&world).len(), 1);
+//! ```
+//!
+//! # Change detection
+//!
+//! Every component value carries added/changed ticks.  Fetching `&mut T`
+//! marks the value as changed; the [`Added<T>`] and [`Changed<T>`] filters
+//! select rows whose ticks are newer than the running system's previous run
+//! (see [`World::last_change_tick`]):
+//!
+//! ```rust
+//! use ferrous_ecs::prelude::*;
+//!
+//! #[derive(Clone, Debug)] struct Pos(f32);
+//! impl Component for Pos {}
+//!
+//! let mut world = World::new();
+//! world.spawn((Pos(0.0),));
+//!
+//! let since = world.change_tick();
+//! let moved = world.spawn((Pos(1.0),));
+//! let q = Query::<&Pos, Changed<Pos>>::new_since(&world, since);
+//! assert_eq!(q.iter().map(|(e, _)| e).collect::<Vec<_>>(), vec![moved]);
+//! ```
+//!
+//! # Parallel iteration
+//!
+//! With the `parallel` feature, [`Query::par_iter`] splits the matching
+//! archetypes into batches of rows and runs them on the rayon thread pool
+//! (see [`QueryParIter`]); [`QueryMut::par_for_each_mut`] is the
+//! single-component shorthand.
 
 use std::any::TypeId;
+use std::marker::PhantomData;
 
 use crate::archetype::{Archetype, ComponentColumn};
-use crate::component::Component;
+use crate::component::{Component, StorageType};
 use crate::entity::Entity;
+use crate::sparse_set::ComponentSparseSet;
 use crate::world::World;
 
+// ---------------------------------------------------------------------------
+// QueryTicks
+
+/// Change-detection window of a query.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueryTicks {
+    /// Values added / changed strictly after this tick pass `Added` / `Changed`.
+    pub last_run: u64,
+    /// Tick recorded on values fetched mutably through the query.
+    pub this_run: u64,
+}
+
+// ---------------------------------------------------------------------------
+// ComponentFetch
+
+/// Where the values of one component type live while an archetype is
+/// iterated: a column of that archetype, or the type's sparse set.
+///
+/// Used as the per-archetype state of the component queries and filters so
+/// that both storages are joined transparently.
+#[derive(Debug, Clone, Copy)]
+pub enum ComponentFetch {
+    /// Column of the current archetype; values are at the iterated row.
+    Table(*const ComponentColumn),
+    /// Sparse set of the type; values are looked up by entity.
+    SparseSet(*const ComponentSparseSet),
+    /// No entity of the archetype has the component.
+    Missing,
+}
+
+impl ComponentFetch {
+    /// Locate the storage of `T` for the entities of `arch`.
+    pub fn new<T: Component>(world: &World, arch: &Archetype) -> Self {
+        match T::STORAGE {
+            StorageType::Table => arch
+                .column::<T>()
+                .map_or(ComponentFetch::Missing, |c| ComponentFetch::Table(c)),
+            StorageType::SparseSet => world
+                .sparse_sets
+                .get(TypeId::of::<T>())
+                .map_or(ComponentFetch::Missing, |s| ComponentFetch::SparseSet(s)),
+        }
+    }
+
+    /// Column and row holding the value of `entity`, which sits at `row` of
+    /// the archetype this fetch was built for.
+    ///
+    /// # Safety
+    /// The storage this fetch points to is still alive and `row` is in
+    /// bounds for the archetype.
+    #[inline]
+    pub unsafe fn locate(self, entity: Entity, row: usize) -> Option<(*const ComponentColumn, usize)> {
+        match self {
+            ComponentFetch::Table(col) => Some((col, row)),
+            ComponentFetch::SparseSet(set) => {
+                let set = &*set;
+                set.dense_index(entity).map(|r| (set.column() as *const ComponentColumn, r))
+            }
+            ComponentFetch::Missing => None,
+        }
+    }
+}
+
+/// `true` if `T` is stored in a sparse set, i.e. archetypes cannot tell
+/// whether their entities have it.
+#[inline]
+const fn is_sparse<T: Component>() -> bool {
+    matches!(T::STORAGE, StorageType::SparseSet)
+}
+
 // ---------------------------------------------------------------------------
 // WorldQuery trait
 
@@ -54,6 +185,12 @@ use crate::world::World;
 /// Query::<(&Transform, &mut Velocity)>::new(&world)
 /// ```
 ///
+/// Like [`QueryFilter`], a query is evaluated in two steps:
+/// [`matches`](Self::matches) once per archetype, then
+/// [`set_archetype`](Self::set_archetype) prepares the fetch state used for
+/// every row.  Sparse-set components match every archetype and are checked
+/// per row with [`filter_row`](Self::filter_row).
+///
 /// # Safety
 /// Implementors must uphold:
 /// - `fetch` only produces references with lifetime bounded by `'w`.
@@ -66,18 +203,42 @@ pub unsafe trait WorldQuery: Sized {
     /// Cached per-query state: archetype indices that match this query.
     type State;
 
+    /// Per-archetype fetch state prepared once and reused for each row.
+    type Fetch: Copy;
+
+    /// `true` if `filter_row` can reject individual rows (the query reads a
+    /// sparse-set component).
+    const FILTERS_ROWS: bool = false;
+
     /// Build state by scanning `world`'s archetypes.
     fn init(world: &World) -> Self::State;
 
-    /// Returns `true` if `arch` contains all required components.
+    /// Returns `true` if `arch` can contain all required components.
     fn matches(arch: &Archetype) -> bool;
 
-    /// Fetch the item at `row` from `arch`.
+    /// Prepare fetching from `arch`.
+    ///
+    /// # Safety
+    /// `arch` belongs to `world` and `matches(arch)` returned `true`.
+    unsafe fn set_archetype(world: &World, arch: &Archetype) -> Self::Fetch;
+
+    /// Returns `true` if `entity`, at `row` of the prepared archetype, has
+    /// every required component.
     ///
     /// # Safety
-    /// `row < arch.len()`, and all columns referenced by this query exist in
-    /// `arch` (i.e. `matches(arch)` returned `true`).
-    unsafe fn fetch<'w>(arch: &'w Archetype, row: usize) -> Self::Item<'w>;
+    /// Same as [`fetch`](Self::fetch).
+    #[inline]
+    unsafe fn filter_row(_fetch: Self::Fetch, _entity: Entity, _row: usize) -> bool {
+        true
+    }
+
+    /// Fetch the item of `entity`, at `row` of the prepared archetype.
+    ///
+    /// # Safety
+    /// `fetch` was produced by `set_archetype` for an archetype that is still
+    /// alive, `row < arch.len()`, and — when [`FILTERS_ROWS`](Self::FILTERS_ROWS)
+    /// is set — `filter_row` returned `true` for the row.
+    unsafe fn fetch<'w>(fetch: Self::Fetch, entity: Entity, row: usize, ticks: QueryTicks) -> Self::Item<'w>;
 
     /// Component types this query reads (for scheduler conflict detection).
     fn reads() -> Vec<TypeId> {
@@ -96,6 +257,9 @@ pub unsafe trait WorldQuery: Sized {
 unsafe impl<T: Component> WorldQuery for &T {
     type Item<'w> = &'w T;
     type State = Vec<usize>;
+    type Fetch = ComponentFetch;
+
+    const FILTERS_ROWS: bool = is_sparse::<T>();
 
     fn init(world: &World) -> Self::State {
         world
@@ -110,13 +274,24 @@ unsafe impl<T: Component> WorldQuery for &T {
 
     #[inline]
     fn matches(arch: &Archetype) -> bool {
-        arch.column::<T>().is_some()
+        is_sparse::<T>() || arch.column::<T>().is_some()
     }
 
     #[inline]
-    unsafe fn fetch(arch: &Archetype, row: usize) -> &T {
-        // SAFETY: matches() was true so column exists; row is in bounds
-        arch.column::<T>().unwrap_unchecked().get::<T>(row)
+    unsafe fn set_archetype(world: &World, arch: &Archetype) -> ComponentFetch {
+        ComponentFetch::new::<T>(world, arch)
+    }
+
+    #[inline]
+    unsafe fn filter_row(fetch: ComponentFetch, entity: Entity, row: usize) -> bool {
+        fetch.locate(entity, row).is_some()
+    }
+
+    #[inline]
+    unsafe fn fetch<'w>(fetch: ComponentFetch, entity: Entity, row: usize, _ticks: QueryTicks) -> &'w T {
+        // SAFETY: the row passed `filter_row`, so the value exists.
+        let (col, row) = fetch.locate(entity, row).unwrap_unchecked();
+        (*col).get::<T>(row)
     }
 
     fn reads() -> Vec<TypeId> {
@@ -125,11 +300,14 @@ unsafe impl<T: Component> WorldQuery for &T {
 }
 
 // ---------------------------------------------------------------------------
-// Primitive impl: &mut T  (mutable reference)
+// Primitive impl: &mut T  (mutable reference, marks the value as changed)
 
 unsafe impl<T: Component> WorldQuery for &mut T {
     type Item<'w> = &'w mut T;
     type State = Vec<usize>;
+    type Fetch = ComponentFetch;
+
+    const FILTERS_ROWS: bool = is_sparse::<T>();
 
     fn init(world: &World) -> Self::State {
         world
@@ -144,15 +322,26 @@ unsafe impl<T: Component> WorldQuery for &mut T {
 
     #[inline]
     fn matches(arch: &Archetype) -> bool {
-        arch.column::<T>().is_some()
+        is_sparse::<T>() || arch.column::<T>().is_some()
+    }
+
+    #[inline]
+    unsafe fn set_archetype(world: &World, arch: &Archetype) -> ComponentFetch {
+        ComponentFetch::new::<T>(world, arch)
+    }
+
+    #[inline]
+    unsafe fn filter_row(fetch: ComponentFetch, entity: Entity, row: usize) -> bool {
+        fetch.locate(entity, row).is_some()
     }
 
     #[inline]
-    unsafe fn fetch(arch: &Archetype, row: usize) -> &mut T {
+    unsafe fn fetch<'w>(fetch: ComponentFetch, entity: Entity, row: usize, ticks: QueryTicks) -> &'w mut T {
         // SAFETY: caller guarantees no aliasing mutable fetches for the same T;
         // we cast away the shared reference to get a mutable one.
-        let col =
-            arch.column::<T>().unwrap_unchecked() as *const ComponentColumn as *mut ComponentColumn;
+        let (col, row) = fetch.locate(entity, row).unwrap_unchecked();
+        let col = col as *mut ComponentColumn;
+        (*col).set_changed(row, ticks.this_run);
         (*col).get_mut::<T>(row)
     }
 
@@ -167,6 +356,7 @@ unsafe impl<T: Component> WorldQuery for &mut T {
 unsafe impl<T: Component> WorldQuery for Option<&T> {
     type Item<'w> = Option<&'w T>;
     type State = Vec<usize>;
+    type Fetch = ComponentFetch;
 
     fn init(world: &World) -> Self::State {
         // Optional components match *all* archetypes (they return None when absent).
@@ -179,8 +369,13 @@ unsafe impl<T: Component> WorldQuery for Option<&T> {
     }
 
     #[inline]
-    unsafe fn fetch(arch: &Archetype, row: usize) -> Option<&T> {
-        arch.column::<T>().map(|col| col.get::<T>(row))
+    unsafe fn set_archetype(world: &World, arch: &Archetype) -> ComponentFetch {
+        ComponentFetch::new::<T>(world, arch)
+    }
+
+    #[inline]
+    unsafe fn fetch<'w>(fetch: ComponentFetch, entity: Entity, row: usize, _ticks: QueryTicks) -> Option<&'w T> {
+        fetch.locate(entity, row).map(|(col, row)| (*col).get::<T>(row))
     }
 
     fn reads() -> Vec<TypeId> {
@@ -196,6 +391,9 @@ macro_rules! impl_world_query_tuple {
         unsafe impl< $($name: WorldQuery),+ > WorldQuery for ( $($name,)+ ) {
             type Item<'w> = ( $($name::Item<'w>,)+ );
             type State = Vec<usize>;
+            type Fetch = ( $($name::Fetch,)+ );
+
+            const FILTERS_ROWS: bool = $( $name::FILTERS_ROWS )||+;
 
             fn init(world: &World) -> Self::State {
                 world
@@ -214,10 +412,25 @@ macro_rules! impl_world_query_tuple {
             }
 
             #[inline]
-            unsafe fn fetch<'w>(arch: &'w Archetype, row: usize) -> Self::Item<'w> {
-                ( $( $name::fetch(arch, row), )+ )
+            unsafe fn set_archetype(world: &World, arch: &Archetype) -> Self::Fetch {
+                ( $( $name::set_archetype(world, arch), )+ )
             }
 
+            #[inline]
+            #[allow(non_snake_case)]
+            unsafe fn filter_row(fetch: Self::Fetch, entity: Entity, row: usize) -> bool {
+                let ( $($name,)+ ) = fetch;
+                $( $name::filter_row($name, entity, row) )&&+
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            unsafe fn fetch<'w>(fetch: Self::Fetch, entity: Entity, row: usize, ticks: QueryTicks) -> Self::Item<'w> {
+                let ( $($name,)+ ) = fetch;
+                ( $( $name::fetch($name, entity, row, ticks), )+ )
+            }
+
+
             fn reads() -> Vec<TypeId> {
                 let mut v = Vec::new();
                 $( v.extend($name::reads()); )+
@@ -242,7 +455,320 @@ impl_world_query_tuple!(Q0, Q1, Q2, Q3, Q4, Q5, Q6);
 impl_world_query_tuple!(Q0, Q1, Q2, Q3, Q4, Q5, Q6, Q7);
 
 // ---------------------------------------------------------------------------
-// Query<'w, Q>
+// ReadOnlyWorldQuery — queries that never hand out `&mut T`
+
+/// Marker for [`WorldQuery`]s whose items are shared borrows only.
+///
+/// Such queries can be iterated from several places at once, e.g. through
+/// [`Query::par_iter`] on a shared `&Query`.
+///
+/// # Safety
+/// `fetch` must not produce mutable references.
+pub unsafe trait ReadOnlyWorldQuery: WorldQuery {}
+
+unsafe impl<T: Component> ReadOnlyWorldQuery for &T {}
+unsafe impl<T: Component> ReadOnlyWorldQuery for Option<&T> {}
+
+macro_rules! impl_read_only_world_query_tuple {
+    ( $( $name:ident ),+ ) => {
+        unsafe impl< $($name: ReadOnlyWorldQuery),+ > ReadOnlyWorldQuery for ( $($name,)+ ) {}
+    };
+}
+
+impl_read_only_world_query_tuple!(Q0, Q1);
+impl_read_only_world_query_tuple!(Q0, Q1, Q2);
+impl_read_only_world_query_tuple!(Q0, Q1, Q2, Q3);
+impl_read_only_world_query_tuple!(Q0, Q1, Q2, Q3, Q4);
+impl_read_only_world_query_tuple!(Q0, Q1, Q2, Q3, Q4, Q5);
+impl_read_only_world_query_tuple!(Q0, Q1, Q2, Q3, Q4, Q5, Q6);
+impl_read_only_world_query_tuple!(Q0, Q1, Q2, Q3, Q4, Q5, Q6, Q7);
+
+// ---------------------------------------------------------------------------
+// QueryFilter trait
+
+/// A type that restricts the entities visited by a [`Query`] without
+/// fetching any data.
+///
+/// Filters are evaluated in two steps: [`matches`](Self::matches) once per
+/// archetype when the query is built, then — only when
+/// [`FILTERS_ROWS`](Self::FILTERS_ROWS) is set — [`filter_row`](Self::filter_row)
+/// per row, using the state prepared by [`set_archetype`](Self::set_archetype).
+/// Filters on sparse-set components always work per row.
+///
+/// # Safety
+/// `set_archetype` / `filter_row` must only read archetype metadata and
+/// change ticks of component types reported by [`reads`](Self::reads).
+pub unsafe trait QueryFilter {
+    /// Per-archetype state prepared once and reused for each row.
+    type ArchState: Copy;
+
+    /// `true` if `filter_row` can reject individual rows.  Lets plain
+    /// archetype filters skip the per-row walk entirely.
+    const FILTERS_ROWS: bool = false;
+
+    /// Returns `true` if entities of `arch` can pass the filter.
+    fn matches(arch: &Archetype) -> bool;
+
+    /// Prepare per-row filtering for `arch`.
+    ///
+    /// # Safety
+    /// `arch` belongs to `world` and `matches(arch)` returned `true`.
+    unsafe fn set_archetype(world: &World, arch: &Archetype) -> Self::ArchState;
+
+    /// Returns `true` if `entity`, at `row` of the prepared archetype, passes
+    /// the filter.
+    ///
+    /// # Safety
+    /// `state` was produced by `set_archetype` for an archetype that is still
+    /// alive, and `row` is in bounds for it.
+    unsafe fn filter_row(state: Self::ArchState, entity: Entity, row: usize, ticks: QueryTicks) -> bool;
+
+    /// Component types whose data (or change ticks) the filter reads.
+    ///
+    /// Pure archetype filters (`With`, `Without`) read nothing, so they never
+    /// cause scheduler conflicts.
+    fn reads() -> Vec<TypeId> {
+        vec![]
+    }
+}
+
+// ---------------------------------------------------------------------------
+// () — no filter
+
+unsafe impl QueryFilter for () {
+    type ArchState = ();
+
+    #[inline]
+    fn matches(_arch: &Archetype) -> bool {
+        true
+    }
+
+    #[inline]
+    unsafe fn set_archetype(_world: &World, _arch: &Archetype) {}
+
+    #[inline]
+    unsafe fn filter_row(_state: (), _entity: Entity, _row: usize, _ticks: QueryTicks) -> bool {
+        true
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Archetype filters: With<T>, Without<T>
+
+/// Matches entities that have component `T` (without borrowing it).
+pub struct With<T>(PhantomData<T>);
+
+unsafe impl<T: Component> QueryFilter for With<T> {
+    type ArchState = ComponentFetch;
+
+    const FILTERS_ROWS: bool = is_sparse::<T>();
+
+    #[inline]
+    fn matches(arch: &Archetype) -> bool {
+        is_sparse::<T>() || arch.column::<T>().is_some()
+    }
+
+    #[inline]
+    unsafe fn set_archetype(world: &World, arch: &Archetype) -> ComponentFetch {
+        ComponentFetch::new::<T>(world, arch)
+    }
+
+    #[inline]
+    unsafe fn filter_row(fetch: ComponentFetch, entity: Entity, row: usize, _ticks: QueryTicks) -> bool {
+        fetch.locate(entity, row).is_some()
+    }
+}
+
+/// Matches entities that do **not** have component `T`.
+pub struct Without<T>(PhantomData<T>);
+
+unsafe impl<T: Component> QueryFilter for Without<T> {
+    type ArchState = ComponentFetch;
+
+    const FILTERS_ROWS: bool = is_sparse::<T>();
+
+    #[inline]
+    fn matches(arch: &Archetype) -> bool {
+        is_sparse::<T>() || arch.column::<T>().is_none()
+    }
+
+    #[inline]
+    unsafe fn set_archetype(world: &World, arch: &Archetype) -> ComponentFetch {
+        ComponentFetch::new::<T>(world, arch)
+    }
+
+    #[inline]
+    unsafe fn filter_row(fetch: ComponentFetch, entity: Entity, row: usize, _ticks: QueryTicks) -> bool {
+        fetch.locate(entity, row).is_none()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Change-detection filters: Added<T>, Changed<T>
+
+/// Matches entities whose `T` was inserted since the system last ran.
+pub struct Added<T>(PhantomData<T>);
+
+unsafe impl<T: Component> QueryFilter for Added<T> {
+    type ArchState = ComponentFetch;
+
+    const FILTERS_ROWS: bool = true;
+
+    #[inline]
+    fn matches(arch: &Archetype) -> bool {
+        is_sparse::<T>() || arch.column::<T>().is_some()
+    }
+
+    #[inline]
+    unsafe fn set_archetype(world: &World, arch: &Archetype) -> ComponentFetch {
+        ComponentFetch::new::<T>(world, arch)
+    }
+
+    #[inline]
+    unsafe fn filter_row(fetch: ComponentFetch, entity: Entity, row: usize, ticks: QueryTicks) -> bool {
+        fetch
+            .locate(entity, row)
+            .is_some_and(|(col, row)| (*col).ticks(row).is_added(ticks.last_run))
+    }
+
+    fn reads() -> Vec<TypeId> {
+        vec![TypeId::of::<T>()]
+    }
+}
+
+/// Matches entities whose `T` was inserted or mutably accessed since the
+/// system last ran.
+pub struct Changed<T>(PhantomData<T>);
+
+unsafe impl<T: Component> QueryFilter for Changed<T> {
+    type ArchState = ComponentFetch;
+
+    const FILTERS_ROWS: bool = true;
+
+    #[inline]
+    fn matches(arch: &Archetype) -> bool {
+        is_sparse::<T>() || arch.column::<T>().is_some()
+    }
+
+    #[inline]
+    unsafe fn set_archetype(world: &World, arch: &Archetype) -> ComponentFetch {
+        ComponentFetch::new::<T>(world, arch)
+    }
+
+    #[inline]
+    unsafe fn filter_row(fetch: ComponentFetch, entity: Entity, row: usize, ticks: QueryTicks) -> bool {
+        fetch
+            .locate(entity, row)
+            .is_some_and(|(col, row)| (*col).ticks(row).is_changed(ticks.last_run))
+    }
+
+    fn reads() -> Vec<TypeId> {
+        vec![TypeId::of::<T>()]
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Filter tuples (AND) and Or<(..)> (OR) — macro-generated for 1..=8 members
+
+/// Matches entities that pass **any** of the filters in the tuple `T`.
+///
+/// ```rust,ignore
+/// Query::<&Transform, Or<(Changed<Transform>, Added<Parent>)>>::new(&world)
+/// ```
+pub struct Or<T>(PhantomData<T>);
+
+macro_rules! impl_query_filter_tuple {
+    ( $( $name:ident ),+ ) => {
+        unsafe impl< $($name: QueryFilter),+ > QueryFilter for ( $($name,)+ ) {
+            type ArchState = ( $($name::ArchState,)+ );
+
+            const FILTERS_ROWS: bool = $( $name::FILTERS_ROWS )||+;
+
+            #[inline]
+            fn matches(arch: &Archetype) -> bool {
+                $( $name::matches(arch) )&&+
+            }
+
+            #[inline]
+            unsafe fn set_archetype(world: &World, arch: &Archetype) -> Self::ArchState {
+                ( $( $name::set_archetype(world, arch), )+ )
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            unsafe fn filter_row(state: Self::ArchState, entity: Entity, row: usize, ticks: QueryTicks) -> bool {
+                let ( $($name,)+ ) = state;
+                $( $name::filter_row($name, entity, row, ticks) )&&+
+            }
+
+            fn reads() -> Vec<TypeId> {
+                let mut v = Vec::new();
+                $( v.extend($name::reads()); )+
+                v
+            }
+        }
+
+        unsafe impl< $($name: QueryFilter),+ > QueryFilter for Or<( $($name,)+ )> {
+            // `None` for members that cannot match the archetype at all.
+            type ArchState = ( $(Option<$name::ArchState>,)+ );
+
+            const FILTERS_ROWS: bool = $( $name::FILTERS_ROWS )||+;
+
+            #[inline]
+            fn matches(arch: &Archetype) -> bool {
+                $( $name::matches(arch) )||+
+            }
+
+            #[inline]
+            unsafe fn set_archetype(world: &World, arch: &Archetype) -> Self::ArchState {
+                ( $( $name::matches(arch).then(|| $name::set_archetype(world, arch)), )+ )
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            unsafe fn filter_row(state: Self::ArchState, entity: Entity, row: usize, ticks: QueryTicks) -> bool {
+                let ( $($name,)+ ) = state;
+                $( $name.is_some_and(|s| $name::filter_row(s, entity, row, ticks)) )||+
+            }
+
+            fn reads() -> Vec<TypeId> {
+                let mut v = Vec::new();
+                $( v.extend($name::reads()); )+
+                v
+            }
+        }
+    };
+}
+
+impl_query_filter_tuple!(F0);
+impl_query_filter_tuple!(F0, F1);
+impl_query_filter_tuple!(F0, F1, F2);
+impl_query_filter_tuple!(F0, F1, F2, F3);
+impl_query_filter_tuple!(F0, F1, F2, F3, F4);
+impl_query_filter_tuple!(F0, F1, F2, F3, F4, F5);
+impl_query_filter_tuple!(F0, F1, F2, F3, F4, F5, F6);
+impl_query_filter_tuple!(F0, F1, F2, F3, F4, F5, F6, F7);
+
+/// Returns `true` if `entity`, at `row` of the prepared archetype, passes the
+/// per-row checks of both the query `Q` and the filter `F`.
+///
+/// # Safety
+/// `fetch` / `filter` were prepared for an archetype that is still alive and
+/// `row` is in bounds for it.
+#[inline]
+unsafe fn row_matches<Q: WorldQuery, F: QueryFilter>(
+    fetch: Q::Fetch,
+    filter: F::ArchState,
+    entity: Entity,
+    row: usize,
+    ticks: QueryTicks,
+) -> bool {
+    (!Q::FILTERS_ROWS || Q::filter_row(fetch, entity, row))
+        && (!F::FILTERS_ROWS || F::filter_row(filter, entity, row, ticks))
+}
+
+// ---------------------------------------------------------------------------
+// Query<'w, Q, F>
 
 /// A cached, typed, multi-component query over a [`World`].
 ///
@@ -250,6 +776,11 @@ impl_world_query_tuple!(Q0, Q1, Q2, Q3, Q4, Q5, Q6, Q7);
 /// [`Query::iter`].  The cached archetype list becomes stale if the world
 /// structure changes (entity spawned/despawned); rebuild in that case.
 ///
+/// Each query takes a fresh world tick when built; components fetched through
+/// `&mut T` are stamped with it, and the [`Added`] / [`Changed`] filters
+/// compare against [`World::last_change_tick`] (or the tick passed to
+/// [`Query::new_since`]).
+///
 /// # Example
 /// ```rust
 /// use ferrous_ecs::prelude::*;
@@ -267,22 +798,41 @@ impl_world_query_tuple!(Q0, Q1, Q2, Q3, Q4, Q5, Q6, Q7);
 ///     let _ = (pos, vel);
 /// }
 /// ```
-pub struct Query<'w, Q: WorldQuery<State = Vec<usize>>> {
+pub struct Query<'w, Q: WorldQuery<State = Vec<usize>>, F: QueryFilter = ()> {
     world: &'w World,
-    /// Cached archetype indices matching `Q`.
+    /// Cached archetype indices matching both `Q` and `F`.
     state: Vec<usize>,
-    _marker: std::marker::PhantomData<Q>,
+    /// Change-detection window.
+    ticks: QueryTicks,
+    _marker: PhantomData<(Q, F)>,
 }
 
-impl<'w, Q: WorldQuery<State = Vec<usize>>> Query<'w, Q> {
+/// Archetype indices matching both `Q` and the filter `F`.
+pub(crate) fn matching_archetypes<Q, F>(world: &World) -> Vec<usize>
+where
+    Q: WorldQuery<State = Vec<usize>>,
+    F: QueryFilter,
+{
+    Q::init(world)
+        .into_iter()
+        .filter(|&i| F::matches(&world.archetypes.archetypes[i]))
+        .collect()
+}
+
+impl<'w, Q: WorldQuery<State = Vec<usize>>, F: QueryFilter> Query<'w, Q, F> {
     /// Build the query — scans archetypes once and caches matching indices.
     pub fn new(world: &'w World) -> Self {
-        let state = Q::init(world);
-        Query {
-            world,
-            state,
-            _marker: std::marker::PhantomData,
-        }
+        Self::new_since(world, world.last_change_tick())
+    }
+
+    /// Build the query with an explicit change-detection baseline.
+    ///
+    /// `Added` / `Changed` filters report values modified after `last_run`.
+    /// Useful for code running outside a scheduler that keeps its own tick
+    /// (e.g. a renderer syncing from the world once per frame).
+    pub fn new_since(world: &'w World, last_run: u64) -> Self {
+        let state = matching_archetypes::<Q, F>(world);
+        Self::from_state_since(world, state, last_run)
     }
 
     /// Reconstruct a `Query` from a pre-built state (used by `SystemParam`).
@@ -291,32 +841,77 @@ impl<'w, Q: WorldQuery<State = Vec<usize>>> Query<'w, Q> {
     /// with the same archetype layout as `world`.
     #[inline]
     pub(crate) fn from_state(world: &'w World, state: Vec<usize>) -> Self {
+        Self::from_state_since(world, state, world.last_change_tick())
+    }
+
+    #[inline]
+    fn from_state_since(world: &'w World, state: Vec<usize>, last_run: u64) -> Self {
         Query {
             world,
             state,
-            _marker: std::marker::PhantomData,
+            ticks: QueryTicks {
+                last_run,
+                this_run: world.increment_change_tick(),
+            },
+            _marker: PhantomData,
         }
     }
 
+    /// Change-detection window used by this query.
+    #[inline]
+    pub fn ticks(&self) -> QueryTicks {
+        self.ticks
+    }
+
     /// Iterate over `(Entity, Q::Item<'_>)` pairs.
     ///
     /// Allocation-free: walks the cached archetype list and yields references
-    /// directly from the SoA columns.
+    /// directly from the SoA columns (or the sparse sets).  Row checks
+    /// (`Added`, `Changed`, sparse-set components) are only evaluated when
+    /// `Q` or `F` needs them.
     pub fn iter(&self) -> impl Iterator<Item = (Entity, Q::Item<'_>)> + '_ {
+        let ticks = self.ticks;
+        let world = self.world;
         self.state.iter().flat_map(move |&arch_id| {
-            let arch = &self.world.archetypes.archetypes[arch_id];
+            let arch = &world.archetypes.archetypes[arch_id];
             let count = arch.entities.len();
-            (0..count).map(move |row| {
-                let entity = arch.entities[row];
-                // SAFETY: row < count; arch matched Q at init time.
-                let item = unsafe { Q::fetch(arch, row) };
-                (entity, item)
-            })
+            // SAFETY: arch matched Q and F at init time.
+            let fetch = unsafe { Q::set_archetype(world, arch) };
+            let filter = unsafe { F::set_archetype(world, arch) };
+            (0..count)
+                // SAFETY: row < count.
+                .filter(move |&row| unsafe {
+                    row_matches::<Q, F>(fetch, filter, arch.entities[row], row, ticks)
+                })
+                .map(move |row| {
+                    let entity = arch.entities[row];
+                    // SAFETY: row < count and it passed the row checks.
+                    let item = unsafe { Q::fetch(fetch, entity, row, ticks) };
+                    (entity, item)
+                })
         })
     }
 
     /// Total number of entities matching this query.
     pub fn len(&self) -> usize {
+        if Q::FILTERS_ROWS || F::FILTERS_ROWS {
+            let ticks = self.ticks;
+            return self
+                .state
+                .iter()
+                .map(|&id| {
+                    let arch = &self.world.archetypes.archetypes[id];
+                    // SAFETY: arch matched Q and F at init time; rows are in bounds.
+                    let fetch = unsafe { Q::set_archetype(self.world, arch) };
+                    let filter = unsafe { F::set_archetype(self.world, arch) };
+                    (0..arch.len())
+                        .filter(|&row| unsafe {
+                            row_matches::<Q, F>(fetch, filter, arch.entities[row], row, ticks)
+                        })
+                        .count()
+                })
+                .sum();
+        }
         self.state
             .iter()
             .map(|&id| self.world.archetypes.archetypes[id].len())
@@ -328,9 +923,12 @@ impl<'w, Q: WorldQuery<State = Vec<usize>>> Query<'w, Q> {
         self.len() == 0
     }
 
-    /// Component types read by this query (for `ParallelScheduler`).
+    /// Component types read by this query, filters included (for
+    /// `ParallelScheduler`).  `With` / `Without` contribute nothing.
     pub fn reads() -> Vec<TypeId> {
-        Q::reads()
+        let mut v = Q::reads();
+        v.extend(F::reads());
+        v
     }
 
     /// Component types written by this query (for `ParallelScheduler`).
@@ -339,6 +937,161 @@ impl<'w, Q: WorldQuery<State = Vec<usize>>> Query<'w, Q> {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Parallel iteration (feature = "parallel")
+
+#[cfg(feature = "parallel")]
+impl<'w, Q: ReadOnlyWorldQuery<State = Vec<usize>>, F: QueryFilter> Query<'w, Q, F> {
+    /// Iterate on the rayon thread pool — see [`QueryParIter`].
+    ///
+    /// Only for read-only queries; use [`par_iter_mut`](Self::par_iter_mut)
+    /// when `Q` hands out `&mut T`.
+    pub fn par_iter(&self) -> QueryParIter<'_, 'w, Q, F> {
+        QueryParIter {
+            query: self,
+            batch_size: None,
+        }
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<'w, Q: WorldQuery<State = Vec<usize>>, F: QueryFilter> Query<'w, Q, F> {
+    /// Iterate on the rayon thread pool — see [`QueryParIter`].
+    ///
+    /// Borrows the query exclusively, so the `&mut T` items of one parallel
+    /// iteration never alias those of another.
+    pub fn par_iter_mut(&mut self) -> QueryParIter<'_, 'w, Q, F> {
+        QueryParIter {
+            query: self,
+            batch_size: None,
+        }
+    }
+}
+
+/// Parallel iterator over a [`Query`], built by [`Query::par_iter`] or
+/// [`Query::par_iter_mut`].
+///
+/// The rows of every matching archetype are cut into batches of consecutive
+/// rows, and the batches are dispatched on the rayon thread pool.  Each row
+/// belongs to exactly one batch, so every entity is visited by a single task;
+/// this is what makes handing out `&mut T` from several threads sound.
+///
+/// Without an explicit [`batch_size`](Self::batch_size) the rows are split so
+/// that each rayon thread gets about four batches.
+///
+/// ```rust
+/// use ferrous_ecs::prelude::*;
+///
+/// #[derive(Clone, Debug)] struct Pos(f32);
+/// impl Component for Pos {}
+///
+/// let mut world = World::new();
+/// for i in 0..100 {
+///     world.spawn((Pos(i as f32),));
+/// }
+///
+/// Query::<&mut Pos>::new(&world)
+///     .par_iter_mut()
+///     .batch_size(16)
+///     .for_each(|_, pos| pos.0 *= 2.0);
+///
+/// let sum: f32 = Query::<&Pos>::new(&world).iter().map(|(_, p)| p.0).sum();
+/// assert_eq!(sum, 9900.0);
+/// ```
+#[cfg(feature = "parallel")]
+pub struct QueryParIter<'q, 'w, Q: WorldQuery<State = Vec<usize>>, F: QueryFilter> {
+    query: &'q Query<'w, Q, F>,
+    batch_size: Option<usize>,
+}
+
+#[cfg(feature = "parallel")]
+impl<'q, 'w, Q: WorldQuery<State = Vec<usize>>, F: QueryFilter> QueryParIter<'q, 'w, Q, F> {
+    /// Number of rows handed to one rayon task (at least 1).
+    ///
+    /// Smaller batches balance uneven per-entity work better; larger ones
+    /// amortise the scheduling cost when the work per entity is tiny.
+    pub fn batch_size(mut self, rows: usize) -> Self {
+        self.batch_size = Some(rows.max(1));
+        self
+    }
+
+    /// Call `f` for every `(Entity, Q::Item)` pair, in parallel.
+    pub fn for_each<FN>(self, f: FN)
+    where
+        FN: Fn(Entity, Q::Item<'q>) + Send + Sync,
+    {
+        use rayon::prelude::*;
+
+        let world: &'q World = self.query.world;
+        let ticks = self.query.ticks;
+        self.batches().into_par_iter().for_each(|batch| {
+            for (entity, item) in Self::rows(world, batch, ticks) {
+                f(entity, item);
+            }
+        });
+    }
+
+    /// Map every `(Entity, Q::Item)` pair through `f` in parallel and collect
+    /// the `Some` results, in the same order as [`Query::iter`].
+    pub fn filter_map_collect<R, FN>(self, f: FN) -> Vec<R>
+    where
+        R: Send,
+        FN: Fn(Entity, Q::Item<'q>) -> Option<R> + Send + Sync,
+    {
+        use rayon::prelude::*;
+
+        let world: &'q World = self.query.world;
+        let ticks = self.query.ticks;
+        self.batches()
+            .into_par_iter()
+            .flat_map_iter(|batch| {
+                Self::rows(world, batch, ticks).filter_map(|(entity, item)| f(entity, item))
+            })
+            .collect()
+    }
+
+    /// `(archetype index, first row, end row)` of every batch.
+    fn batches(&self) -> Vec<(usize, usize, usize)> {
+        let archetypes = &self.query.world.archetypes.archetypes;
+        let size = self.batch_size.unwrap_or_else(|| {
+            let total: usize = self.query.state.iter().map(|&id| archetypes[id].len()).sum();
+            total.div_ceil(rayon::current_num_threads() * 4).max(1)
+        });
+        self.query
+            .state
+            .iter()
+            .flat_map(|&id| {
+                let len = archetypes[id].len();
+                (0..len)
+                    .step_by(size)
+                    .map(move |start| (id, start, (start + size).min(len)))
+            })
+            .collect()
+    }
+
+    /// Rows of one batch that pass `F`, fetched as `Q`.
+    fn rows(
+        world: &'q World,
+        (arch_id, start, end): (usize, usize, usize),
+        ticks: QueryTicks,
+    ) -> impl Iterator<Item = (Entity, Q::Item<'q>)> + 'q {
+        let arch = &world.archetypes.archetypes[arch_id];
+        // SAFETY: the batch comes from `batches()`, so `arch` matched both
+        // `Q` and `F` when the query was built and `start..end` is in bounds.
+        // Batches never overlap, so no two tasks fetch the same row.
+        let fetch = unsafe { Q::set_archetype(world, arch) };
+        let filter = unsafe { F::set_archetype(world, arch) };
+        (start..end)
+            .filter(move |&row| unsafe {
+                row_matches::<Q, F>(fetch, filter, arch.entities[row], row, ticks)
+            })
+            .map(move |row| {
+                let entity = arch.entities[row];
+                (entity, unsafe { Q::fetch(fetch, entity, row, ticks) })
+            })
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Backward-compatibility shim for QueryMut
 //
@@ -367,6 +1120,15 @@ impl<'w, C: Component> QueryMut<'w, C> {
             f(entity, comp);
         }
     }
+
+    /// Call `f` for every `(Entity, &mut C)` pair on the rayon thread pool.
+    ///
+    /// Uses the default batch size of [`QueryParIter`]; build a
+    /// `Query::<&mut C>` and go through [`Query::par_iter`] to tune it.
+    #[cfg(feature = "parallel")]
+    pub fn par_for_each_mut<F: Fn(Entity, &mut C) + Send + Sync>(&mut self, f: F) {
+        self.inner.par_iter_mut().for_each(f);
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -375,7 +1137,7 @@ impl<'w, C: Component> QueryMut<'w, C> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::component::Component;
+    use crate::component::{Component, StorageType};
     use crate::world::World;
 
     #[derive(Clone, Debug, PartialEq)]
@@ -390,6 +1152,12 @@ mod tests {
     struct Health(f32);
     impl Component for Health {}
 
+    #[derive(Clone, Debug, PartialEq)]
+    struct Selected(u32);
+    impl Component for Selected {
+        const STORAGE: StorageType = StorageType::SparseSet;
+    }
+
     // -----------------------------------------------------------------------
     // Single-component &T query
 
@@ -528,6 +1296,101 @@ mod tests {
     // -----------------------------------------------------------------------
     // Access metadata
 
+    // -----------------------------------------------------------------------
+    // Change detection
+
+    #[test]
+    fn added_filter_reports_new_components_only() {
+        let mut world = World::new();
+        world.spawn((Pos(0.0),));
+        let since = world.change_tick();
+        let fresh = world.spawn((Pos(1.0),));
+        let old = world.spawn((Vel(0.0),));
+        world.insert(old, Pos(2.0));
+
+        let q = Query::<&Pos, Added<Pos>>::new_since(&world, since);
+        assert_eq!(q.len(), 2);
+        let mut added: Vec<Entity> = q.iter().map(|(e, _)| e).collect();
+        added.sort_by_key(|e| e.index);
+        assert_eq!(added, vec![fresh, old]);
+    }
+
+    #[test]
+    fn changed_filter_sees_mutable_query_writes() {
+        let mut world = World::new();
+        let a = world.spawn((Pos(0.0), Vel(1.0)));
+        world.spawn((Pos(5.0),));
+        let since = world.change_tick();
+
+        // Mutating through `&mut T` stamps only the rows actually fetched.
+        for (_, (pos, _)) in Query::<(&mut Pos, &Vel)>::new(&world).iter() {
+            pos.0 += 1.0;
+        }
+
+        let q = Query::<&Pos, Changed<Pos>>::new_since(&world, since);
+        assert_eq!(q.iter().map(|(e, _)| e).collect::<Vec<_>>(), vec![a]);
+        assert!(Query::<&Pos, Added<Pos>>::new_since(&world, since).is_empty());
+    }
+
+    #[test]
+    fn changed_filter_uses_world_last_change_tick() {
+        let mut world = World::new();
+        world.spawn((Pos(0.0),));
+        // Outside a scheduler the baseline is 0: everything counts as changed.
+        assert_eq!(Query::<&Pos, Changed<Pos>>::new(&world).len(), 1);
+        world.last_change_tick = world.change_tick();
+        assert_eq!(Query::<&Pos, Changed<Pos>>::new(&world).len(), 0);
+    }
+
+    // -----------------------------------------------------------------------
+    // Filters
+
+    #[test]
+    fn with_and_without_filters() {
+        let mut world = World::new();
+        let both = world.spawn((Pos(0.0), Vel(1.0)));
+        let pos_only = world.spawn((Pos(1.0),));
+        world.spawn((Vel(2.0),));
+
+        let with: Vec<Entity> = Query::<&Pos, With<Vel>>::new(&world).iter().map(|(e, _)| e).collect();
+        assert_eq!(with, vec![both]);
+        let without: Vec<Entity> =
+            Query::<&Pos, Without<Vel>>::new(&world).iter().map(|(e, _)| e).collect();
+        assert_eq!(without, vec![pos_only]);
+        assert_eq!(Query::<&Pos, (With<Vel>, Without<Health>)>::new(&world).len(), 1);
+        assert_eq!(Query::<&Pos, (With<Vel>, With<Health>)>::new(&world).len(), 0);
+    }
+
+    #[test]
+    fn or_filter_combines_archetype_and_row_filters() {
+        let mut world = World::new();
+        let healthy = world.spawn((Pos(0.0), Health(1.0)));
+        let moving = world.spawn((Pos(1.0), Vel(1.0)));
+        world.spawn((Pos(2.0),));
+
+        let q = Query::<&Pos, Or<(With<Health>, With<Vel>)>>::new(&world);
+        assert_eq!(q.len(), 2);
+
+        let since = world.change_tick();
+        world.get_mut::<Vel>(moving).unwrap().0 = 2.0;
+        let q = Query::<&Pos, Or<(With<Health>, Changed<Vel>)>>::new_since(&world, since);
+        let mut hits: Vec<Entity> = q.iter().map(|(e, _)| e).collect();
+        hits.sort_by_key(|e| e.index);
+        assert_eq!(hits, vec![healthy, moving]);
+        assert_eq!(q.len(), 2);
+
+        let q = Query::<&Pos, Or<(Changed<Health>, Changed<Vel>)>>::new_since(&world, since);
+        assert_eq!(q.iter().map(|(e, _)| e).collect::<Vec<_>>(), vec![moving]);
+    }
+
+    #[test]
+    fn archetype_filters_add_no_access() {
+        type Q = Query<'static, &'static mut Pos, (With<Vel>, Without<Health>)>;
+        assert_eq!(Q::reads(), Vec::<TypeId>::new());
+        assert_eq!(Q::writes(), vec![TypeId::of::<Pos>()]);
+        assert_eq!(Query::<&Pos, Changed<Vel>>::reads(), vec![TypeId::of::<Pos>(), TypeId::of::<Vel>()]);
+    }
+
     #[test]
     fn access_metadata() {
         assert!(Query::<&Pos>::reads().contains(&TypeId::of::<Pos>()));
@@ -535,4 +1398,102 @@ mod tests {
         assert!(Query::<(&Pos, &mut Vel)>::reads().contains(&TypeId::of::<Pos>()));
         assert!(Query::<(&Pos, &mut Vel)>::writes().contains(&TypeId::of::<Vel>()));
     }
+
+    // -----------------------------------------------------------------------
+    // Sparse-set storage
+
+    #[test]
+    fn queries_join_table_and_sparse_storage() {
+        let mut world = World::new();
+        let a = world.spawn((Pos(0.0), Selected(1)));
+        let b = world.spawn((Pos(1.0), Vel(1.0)));
+        world.spawn((Selected(3),));
+        world.insert(b, Selected(2));
+
+        let q = Query::<(&Pos, &Selected)>::new(&world);
+        assert_eq!(q.len(), 2);
+        let mut pairs: Vec<(f32, u32)> = q.iter().map(|(_, (p, s))| (p.0, s.0)).collect();
+        pairs.sort_by_key(|p| p.1);
+        assert_eq!(pairs, vec![(0.0, 1), (1.0, 2)]);
+
+        assert_eq!(Query::<&Pos, Without<Selected>>::new(&world).len(), 0);
+        world.remove::<Selected>(a);
+        let hits: Vec<Entity> = Query::<&Pos, Without<Selected>>::new(&world).iter().map(|(e, _)| e).collect();
+        assert_eq!(hits, vec![a]);
+        assert_eq!(Query::<&Pos, With<Selected>>::new(&world).len(), 1);
+        assert_eq!(Query::<(&Pos, Option<&Selected>)>::new(&world).len(), 2);
+    }
+
+    #[test]
+    fn sparse_components_track_changes() {
+        let mut world = World::new();
+        let a = world.spawn((Pos(0.0), Selected(0)));
+        let b = world.spawn((Pos(1.0), Selected(0)));
+        let since = world.change_tick();
+
+        world.get_mut::<Selected>(b).unwrap().0 = 7;
+        let c = world.spawn((Pos(2.0),));
+        world.insert(c, Selected(1));
+
+        let changed: Vec<Entity> = Query::<&Pos, Changed<Selected>>::new_since(&world, since)
+            .iter()
+            .map(|(e, _)| e)
+            .collect();
+        assert_eq!(changed, vec![b, c]);
+        let added = Query::<&Selected, Added<Selected>>::new_since(&world, since);
+        assert_eq!(added.iter().map(|(e, _)| e).collect::<Vec<_>>(), vec![c]);
+        assert_eq!(world.get::<Selected>(a), Some(&Selected(0)));
+
+        // Mutable fetches stamp sparse values like table ones.
+        for (_, sel) in Query::<&mut Selected>::new(&world).iter() {
+            sel.0 += 1;
+        }
+        assert_eq!(Query::<&Pos, Changed<Selected>>::new_since(&world, since).len(), 3);
+    }
+
+    // -----------------------------------------------------------------------
+    // Parallel iteration
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn par_iter_visits_every_row_once() {
+        let mut world = World::new();
+        for i in 0..50 {
+            world.spawn((Pos(i as f32),));
+            world.spawn((Pos(i as f32), Vel(0.0)));
+        }
+
+        Query::<&mut Pos>::new(&world)
+            .par_iter_mut()
+            .batch_size(7)
+            .for_each(|_, p| p.0 += 1.0);
+        QueryMut::<Vel>::new(&mut world).par_for_each_mut(|_, v| v.0 += 1.0);
+
+        let sum: f32 = Query::<&Pos>::new(&world).iter().map(|(_, p)| p.0).sum();
+        assert_eq!(sum, 2.0 * (1..=50).sum::<i32>() as f32);
+        assert!(Query::<&Vel>::new(&world).iter().all(|(_, v)| v.0 == 1.0));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn par_iter_respects_filters_and_order() {
+        let mut world = World::new();
+        let entities: Vec<Entity> = (0..20).map(|i| world.spawn((Pos(i as f32),))).collect();
+
+        let since = world.change_tick();
+        for &e in entities.iter().step_by(3) {
+            world.get_mut::<Pos>(e).unwrap().0 = -1.0;
+        }
+
+        let q = Query::<&Pos, Changed<Pos>>::new_since(&world, since);
+        let serial: Vec<Entity> = q.iter().map(|(e, _)| e).collect();
+        let parallel = q.par_iter().batch_size(4).filter_map_collect(|e, _| Some(e));
+        assert_eq!(parallel, serial);
+        assert_eq!(parallel.len(), 7);
+
+        let evens = Query::<&Pos>::new(&world)
+            .par_iter()
+            .filter_map_collect(|e, p| (p.0 >= 0.0 && e.index % 2 == 0).then_some(e));
+        assert!(evens.windows(2).all(|w| w[0].index < w[1].index));
+    }
 }
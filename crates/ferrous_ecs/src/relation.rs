@@ -0,0 +1,477 @@
+//! Typed relations between entities.
+//!
+//! A relation `R` is a directed edge from a *source* entity to a *target*
+//! entity, e.g. `ChildOf` from a child to its parent.  Edges are created with
+//! [`World::relate`] and removed with [`World::unrelate`]; the world keeps
+//! both sides in sync:
+//!
+//! | Component | Lives on | Holds |
+//! |-----------|----------|-------|
+//! | [`RelationTargets<R>`] | source | every target of `R` from this entity |
+//! | [`RelationSources<R>`] | target | every source of `R` pointing here |
+//!
+//! Both are ordinary components, so they can be queried (and filtered with
+//! `Changed`) like any other; only mutate them through the `World` methods.
+//! Relating an entity moves it to another archetype, so — as with `insert` —
+//! avoid it on entities holding owned (non-Clone) components.
+//!
+//! # Despawning
+//!
+//! Despawning either end removes the edge from the other end.  What happens
+//! to the *sources* of a despawned target is chosen per relation type by
+//! [`Relation::DESPAWN_POLICY`]:
+//!
+//! | Policy | Sources of the despawned entity |
+//! |--------|---------------------------------|
+//! | [`Recursive`](DespawnPolicy::Recursive) | are despawned too (and so on down) |
+//! | [`Orphan`](DespawnPolicy::Orphan) | survive without the relation |
+//! | [`Detach`](DespawnPolicy::Detach) | survive, re-related to the despawned entity's own targets |
+//!
+//! # Example
+//! ```rust
+//! use ferrous_ecs::prelude::*;
+//!
+//! #[derive(Clone)] struct Node;
+//! impl Component for Node {}
+//!
+//! struct Owns;
+//! impl Relation for Owns {
+//!     const DESPAWN_POLICY: DespawnPolicy = DespawnPolicy::Orphan;
+//! }
+//!
+//! let mut world = World::new();
+//! let (player, sword, shield) = (world.spawn((Node,)), world.spawn((Node,)), world.spawn((Node,)));
+//! world.relate::<Owns>(player, sword);
+//! world.relate::<Owns>(player, shield);
+//! assert_eq!(world.targets::<Owns>(player), &[sword, shield]);
+//! assert_eq!(world.sources::<Owns>(sword), &[player]);
+//!
+//! world.despawn(sword);
+//! assert_eq!(world.targets::<Owns>(player), &[shield]);
+//! ```
+
+use std::any::TypeId;
+use std::marker::PhantomData;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::component::Component;
+use crate::entity::{Entity, EntityMap, MapEntities};
+use crate::world::World;
+
+// ---------------------------------------------------------------------------
+// Relation trait
+
+/// What happens to the sources of an entity that is despawned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DespawnPolicy {
+    /// Despawn every source as well.
+    Recursive,
+    /// Keep the sources; they simply lose the edge.
+    Orphan,
+    /// Keep the sources and re-relate them to the despawned entity's own
+    /// targets (e.g. children move up to their grandparent).  Sources end up
+    /// orphaned if the despawned entity had no targets.
+    Detach,
+}
+
+/// Marker trait for relation kinds.
+pub trait Relation: Send + Sync + 'static {
+    /// Policy applied to the sources when a target is despawned.
+    const DESPAWN_POLICY: DespawnPolicy = DespawnPolicy::Orphan;
+
+    /// If `true`, a source has at most one target: relating it again
+    /// replaces the previous edge.
+    const EXCLUSIVE: bool = false;
+
+    /// Called after an edge is removed while `source` stays alive (explicit
+    /// `unrelate`, replacement of an exclusive edge, or `Orphan` / `Detach`
+    /// cleanup).  Use it to flag dependent state as changed.
+    fn on_unrelate(_world: &mut World, _source: Entity, _target: Entity) {}
+}
+
+// ---------------------------------------------------------------------------
+// Bookkeeping components
+
+/// Targets of relation `R` from the entity this component is attached to.
+pub struct RelationTargets<R: Relation> {
+    entities: Vec<Entity>,
+    _marker: PhantomData<fn() -> R>,
+}
+
+/// Sources of relation `R` pointing at the entity this component is attached to.
+pub struct RelationSources<R: Relation> {
+    entities: Vec<Entity>,
+    _marker: PhantomData<fn() -> R>,
+}
+
+macro_rules! impl_relation_side {
+    ($ty:ident) => {
+        impl<R: Relation> $ty<R> {
+            fn new(first: Entity) -> Self {
+                $ty {
+                    entities: vec![first],
+                    _marker: PhantomData,
+                }
+            }
+
+            /// Build this side of the edges by hand.  The other side is not
+            /// updated; prefer [`World::relate`], which also repairs such
+            /// one-sided edges when called on them.
+            pub fn from_entities(entities: Vec<Entity>) -> Self {
+                $ty {
+                    entities,
+                    _marker: PhantomData,
+                }
+            }
+
+            /// Related entities, in the order the edges were created.
+            #[inline]
+            pub fn as_slice(&self) -> &[Entity] {
+                &self.entities
+            }
+
+            #[inline]
+            pub fn iter(&self) -> std::slice::Iter<'_, Entity> {
+                self.entities.iter()
+            }
+
+            #[inline]
+            pub fn len(&self) -> usize {
+                self.entities.len()
+            }
+
+            #[inline]
+            pub fn is_empty(&self) -> bool {
+                self.entities.is_empty()
+            }
+
+            #[inline]
+            pub fn contains(&self, entity: Entity) -> bool {
+                self.entities.contains(&entity)
+            }
+        }
+
+        impl<R: Relation> Clone for $ty<R> {
+            fn clone(&self) -> Self {
+                $ty {
+                    entities: self.entities.clone(),
+                    _marker: PhantomData,
+                }
+            }
+        }
+
+        impl<R: Relation> std::fmt::Debug for $ty<R> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_tuple(stringify!($ty)).field(&self.entities).finish()
+            }
+        }
+
+        impl<R: Relation> Component for $ty<R> {}
+
+        impl<R: Relation> MapEntities for $ty<R> {
+            fn map_entities(&mut self, map: &EntityMap) {
+                for e in &mut self.entities {
+                    *e = map.map(*e);
+                }
+            }
+        }
+
+        // Serialized as a plain list of entities.
+        impl<R: Relation> Serialize for $ty<R> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.entities.serialize(serializer)
+            }
+        }
+
+        impl<'de, R: Relation> Deserialize<'de> for $ty<R> {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                Ok($ty {
+                    entities: Vec::deserialize(deserializer)?,
+                    _marker: PhantomData,
+                })
+            }
+        }
+    };
+}
+
+impl_relation_side!(RelationTargets);
+impl_relation_side!(RelationSources);
+
+impl<R: Relation> RelationTargets<R> {
+    /// The first target — the only one for [`Relation::EXCLUSIVE`] relations.
+    #[inline]
+    pub fn target(&self) -> Entity {
+        self.entities[0]
+    }
+}
+
+// ---------------------------------------------------------------------------
+// World API
+
+impl World {
+    /// Add an `R` edge from `source` to `target`.
+    ///
+    /// Relating an already related pair is a no-op (unless only one side
+    /// records the edge, in which case both are brought in sync).  For exclusive relations
+    /// any previous target of `source` is unrelated first.  Returns `false`
+    /// (and does nothing) if either entity is dead or `source == target`.
+    pub fn relate<R: Relation>(&mut self, source: Entity, target: Entity) -> bool {
+        if source == target || !self.contains(source) || !self.contains(target) {
+            return false;
+        }
+        if self.targets::<R>(source).contains(&target) {
+            if self.sources::<R>(target).contains(&source) {
+                return true;
+            }
+            // One-sided edge inserted by hand: rebuild it properly.
+            self.unlink_target::<R>(source, target);
+        }
+        self.register_relation::<R>();
+        if R::EXCLUSIVE {
+            for old in self.targets::<R>(source).to_vec() {
+                self.unrelate::<R>(source, old);
+            }
+        }
+
+        match self.get_mut::<RelationTargets<R>>(source) {
+            Some(t) => t.entities.push(target),
+            None => self.insert(source, RelationTargets::<R>::new(target)),
+        }
+        match self.get_mut::<RelationSources<R>>(target) {
+            Some(s) if s.entities.contains(&source) => {}
+            Some(s) => s.entities.push(source),
+            None => self.insert(target, RelationSources::<R>::new(source)),
+        }
+        true
+    }
+
+    /// Remove the `R` edge from `source` to `target`.  Returns `false` if
+    /// there was none.
+    pub fn unrelate<R: Relation>(&mut self, source: Entity, target: Entity) -> bool {
+        if !self.unlink_target::<R>(source, target) {
+            return false;
+        }
+        self.unlink_source::<R>(target, source);
+        R::on_unrelate(self, source, target);
+        true
+    }
+
+    /// All targets of `R` from `source` (empty if none or dead).
+    pub fn targets<R: Relation>(&self, source: Entity) -> &[Entity] {
+        self.get::<RelationTargets<R>>(source)
+            .map_or(&[], RelationTargets::as_slice)
+    }
+
+    /// The first target of `R` from `source`.
+    pub fn target<R: Relation>(&self, source: Entity) -> Option<Entity> {
+        self.targets::<R>(source).first().copied()
+    }
+
+    /// All sources of `R` pointing at `target` (empty if none or dead).
+    pub fn sources<R: Relation>(&self, target: Entity) -> &[Entity] {
+        self.get::<RelationSources<R>>(target)
+            .map_or(&[], RelationSources::as_slice)
+    }
+
+    /// `root` and everything reachable from it by following `R` backwards
+    /// (sources, their sources, …), depth-first, `root` first.
+    pub fn descendants<R: Relation>(&self, root: Entity) -> Vec<Entity> {
+        let mut out = Vec::new();
+        let mut stack = vec![root];
+        while let Some(e) = stack.pop() {
+            if out.contains(&e) {
+                continue; // malformed cycle
+            }
+            out.push(e);
+            stack.extend(self.sources::<R>(e).iter().rev());
+        }
+        out
+    }
+
+    /// Install the despawn hook for `R`.  Done implicitly by `relate`; call it
+    /// when relation components are inserted some other way (e.g. loaded).
+    pub(crate) fn register_relation<R: Relation>(&mut self) {
+        let id = TypeId::of::<R>();
+        if !self.relation_hooks.iter().any(|(t, _)| *t == id) {
+            self.relation_hooks.push((id, despawn_relation::<R>));
+        }
+    }
+
+    /// Remove `target` from `source`'s targets, dropping the component when
+    /// it becomes empty.
+    fn unlink_target<R: Relation>(&mut self, source: Entity, target: Entity) -> bool {
+        let Some(t) = self.get::<RelationTargets<R>>(source) else {
+            return false;
+        };
+        let Some(pos) = t.entities.iter().position(|&e| e == target) else {
+            return false;
+        };
+        if t.entities.len() == 1 {
+            self.remove::<RelationTargets<R>>(source);
+        } else {
+            self.get_mut::<RelationTargets<R>>(source).unwrap().entities.remove(pos);
+        }
+        true
+    }
+
+    fn unlink_source<R: Relation>(&mut self, target: Entity, source: Entity) {
+        let Some(s) = self.get::<RelationSources<R>>(target) else {
+            return;
+        };
+        let Some(pos) = s.entities.iter().position(|&e| e == source) else {
+            return;
+        };
+        if s.entities.len() == 1 {
+            self.remove::<RelationSources<R>>(target);
+        } else {
+            self.get_mut::<RelationSources<R>>(target).unwrap().entities.remove(pos);
+        }
+    }
+}
+
+/// Type-erased [`despawn_relation`] for one relation type.
+pub(crate) type RelationHook = fn(&mut World, Entity);
+
+/// Despawn hook for relation `R`: called by [`World::despawn`] before
+/// `entity` is removed.
+pub(crate) fn despawn_relation<R: Relation>(world: &mut World, entity: Entity) {
+    // Detach `entity` from both sides first so recursion (or a malformed
+    // cycle) never revisits it.
+    let sources: Vec<Entity> = world.sources::<R>(entity).to_vec();
+    let targets: Vec<Entity> = world.targets::<R>(entity).to_vec();
+    for &t in &targets {
+        world.unlink_source::<R>(t, entity);
+    }
+    world.remove::<RelationTargets<R>>(entity);
+    world.remove::<RelationSources<R>>(entity);
+
+    for source in sources {
+        world.unlink_target::<R>(source, entity);
+        match R::DESPAWN_POLICY {
+            DespawnPolicy::Recursive => {
+                world.despawn(source);
+            }
+            DespawnPolicy::Orphan => R::on_unrelate(world, source, entity),
+            DespawnPolicy::Detach => {
+                R::on_unrelate(world, source, entity);
+                for &t in &targets {
+                    world.relate::<R>(source, t);
+                }
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone)]
+    struct Node;
+    impl Component for Node {}
+
+    struct ChildOf;
+    impl Relation for ChildOf {
+        const DESPAWN_POLICY: DespawnPolicy = DespawnPolicy::Recursive;
+        const EXCLUSIVE: bool = true;
+    }
+
+    struct Likes;
+    impl Relation for Likes {}
+
+    struct Follows;
+    impl Relation for Follows {
+        const DESPAWN_POLICY: DespawnPolicy = DespawnPolicy::Detach;
+        const EXCLUSIVE: bool = true;
+    }
+
+    #[test]
+    fn relate_keeps_both_sides_in_sync() {
+        let mut world = World::new();
+        let (a, b, c) = (world.spawn((Node,)), world.spawn((Node,)), world.spawn((Node,)));
+
+        assert!(world.relate::<Likes>(a, b));
+        assert!(world.relate::<Likes>(a, c));
+        assert!(world.relate::<Likes>(a, c)); // duplicate is a no-op
+        assert!(!world.relate::<Likes>(a, a));
+        assert_eq!(world.targets::<Likes>(a), &[b, c]);
+        assert_eq!(world.sources::<Likes>(c), &[a]);
+        // Relations are independent per type.
+        assert!(world.targets::<ChildOf>(a).is_empty());
+
+        assert!(world.unrelate::<Likes>(a, b));
+        assert!(!world.unrelate::<Likes>(a, b));
+        assert_eq!(world.targets::<Likes>(a), &[c]);
+        assert!(world.get::<RelationSources<Likes>>(b).is_none());
+    }
+
+    #[test]
+    fn exclusive_relations_replace_the_target() {
+        let mut world = World::new();
+        let (child, p1, p2) = (world.spawn((Node,)), world.spawn((Node,)), world.spawn((Node,)));
+        world.relate::<ChildOf>(child, p1);
+        world.relate::<ChildOf>(child, p2);
+        assert_eq!(world.target::<ChildOf>(child), Some(p2));
+        assert!(world.sources::<ChildOf>(p1).is_empty());
+        assert_eq!(world.sources::<ChildOf>(p2), &[child]);
+    }
+
+    #[test]
+    fn despawn_policies() {
+        let mut world = World::new();
+        let root = world.spawn((Node,));
+        let mid = world.spawn((Node,));
+        let leaf = world.spawn((Node,));
+        let fan = world.spawn((Node,));
+        world.relate::<ChildOf>(mid, root);
+        world.relate::<ChildOf>(leaf, mid);
+        world.relate::<Likes>(fan, mid);
+        world.relate::<Follows>(leaf, mid);
+        world.relate::<Follows>(mid, root);
+
+        // Despawning a source cleans the target side.
+        world.despawn(fan);
+        assert!(world.sources::<Likes>(mid).is_empty());
+
+        // Recursive: the leaf goes with its parent; Detach on `Follows` would
+        // have moved it to `root`, but it no longer exists.
+        world.despawn(mid);
+        assert!(!world.contains(leaf));
+        assert!(world.sources::<ChildOf>(root).is_empty());
+        assert!(world.sources::<Follows>(root).is_empty());
+        assert_eq!(world.len(), 1);
+    }
+
+    #[test]
+    fn detach_and_orphan_keep_sources_alive() {
+        let mut world = World::new();
+        let (top, mid, low, fan) =
+            (world.spawn((Node,)), world.spawn((Node,)), world.spawn((Node,)), world.spawn((Node,)));
+        world.relate::<Follows>(mid, top);
+        world.relate::<Follows>(low, mid);
+        world.relate::<Likes>(fan, mid);
+
+        world.despawn(mid);
+        assert_eq!(world.target::<Follows>(low), Some(top));
+        assert_eq!(world.sources::<Follows>(top), &[low]);
+        assert!(world.contains(fan));
+        assert!(world.targets::<Likes>(fan).is_empty());
+    }
+
+    #[test]
+    fn recursive_despawn_survives_cycles() {
+        let mut world = World::new();
+        let (a, b) = (world.spawn((Node,)), world.spawn((Node,)));
+        world.relate::<Likes>(a, b);
+        world.relate::<Likes>(b, a);
+        world.relate::<ChildOf>(a, b);
+        world.relate::<ChildOf>(b, a);
+        world.despawn(a);
+        assert!(world.is_empty());
+        assert_eq!(world.descendants::<ChildOf>(a), vec![a]);
+    }
+}
@@ -0,0 +1,458 @@
+//! System ordering, labels, sets and run conditions.
+//!
+//! Without any configuration, systems in a stage run in insertion order.
+//! Wrapping a system in a [`SystemConfig`] (via the [`IntoSystemConfig`]
+//! builder methods) lets callers that do not control registration order —
+//! typically plugins — say where the system belongs:
+//!
+//! | Method | Effect |
+//! |--------|--------|
+//! | `.label("physics")` | Names the system so others can refer to it |
+//! | `.in_set("sim")` | Joins a [`SystemSet`]; the set's constraints and conditions apply |
+//! | `.before("x")` / `.after("x")` | Orders against every system labelled (or in set) `x` |
+//! | `.run_if(cond)` | Skips the system for a tick when `cond` returns `false` |
+//!
+//! Constraints are resolved with a topological sort that keeps insertion
+//! order wherever nothing else is required.  They only relate systems in the
+//! same stage (stage order already covers the rest); labels that match
+//! nothing are ignored so optional plugins can be referenced freely.  A cycle
+//! is reported as a [`ScheduleError`] naming every system on it.
+//!
+//! ```rust
+//! use ferrous_ecs::prelude::*;
+//! use ferrous_ecs::system::fn_system;
+//!
+//! struct Paused(bool);
+//!
+//! let mut sched = StagedScheduler::new();
+//! sched.add(Stage::Update, fn_system("render_prep", |_, _| {}).after("physics"));
+//! sched.add(
+//!     Stage::Update,
+//!     fn_system("integrate", |_, _| {}).in_set("physics"),
+//! );
+//! sched.configure_set(SystemSet::new("physics").run_if(|_, res| {
+//!     !res.get::<Paused>().is_some_and(|p| p.0)
+//! }));
+//!
+//! let mut world = World::new();
+//! let mut res = ResourceMap::new();
+//! res.insert(Paused(true));
+//! sched.run_all(&mut world, &mut res); // "integrate" is skipped
+//! ```
+
+use std::collections::{BinaryHeap, HashMap};
+use std::cmp::Reverse;
+use std::fmt;
+
+use crate::resource::ResourceMap;
+use crate::system::System;
+use crate::world::World;
+
+// ---------------------------------------------------------------------------
+// Run conditions
+
+/// A predicate deciding whether a system (or every system of a set) runs
+/// this tick.
+pub type RunCondition = Box<dyn FnMut(&World, &ResourceMap) -> bool + Send>;
+
+/// Condition: resource `T` is present.
+pub fn resource_exists<T: Send + Sync + 'static>() -> impl FnMut(&World, &ResourceMap) -> bool + Send {
+    |_, res| res.get::<T>().is_some()
+}
+
+/// Condition: resource `T` is present and equal to `value`.
+pub fn resource_equals<T>(value: T) -> impl FnMut(&World, &ResourceMap) -> bool + Send
+where
+    T: PartialEq + Send + Sync + 'static,
+{
+    move |_, res| res.get::<T>() == Some(&value)
+}
+
+/// Condition: negation of `condition`.
+pub fn not<C>(mut condition: C) -> impl FnMut(&World, &ResourceMap) -> bool + Send
+where
+    C: FnMut(&World, &ResourceMap) -> bool + Send,
+{
+    move |world, res| !condition(world, res)
+}
+
+// ---------------------------------------------------------------------------
+// SystemConfig
+
+/// A system plus its labels, set memberships, ordering constraints and run
+/// conditions.
+pub struct SystemConfig {
+    pub(crate) system: Box<dyn System>,
+    pub(crate) labels: Vec<&'static str>,
+    pub(crate) sets: Vec<&'static str>,
+    pub(crate) before: Vec<&'static str>,
+    pub(crate) after: Vec<&'static str>,
+    pub(crate) conditions: Vec<RunCondition>,
+}
+
+impl SystemConfig {
+    pub fn new(system: Box<dyn System>) -> Self {
+        SystemConfig {
+            system,
+            labels: Vec::new(),
+            sets: Vec::new(),
+            before: Vec::new(),
+            after: Vec::new(),
+            conditions: Vec::new(),
+        }
+    }
+}
+
+/// Builder methods for [`SystemConfig`], available on every [`System`].
+pub trait IntoSystemConfig: Sized {
+    fn into_config(self) -> SystemConfig;
+
+    /// Attach a label other systems can order against.
+    fn label(self, label: &'static str) -> SystemConfig {
+        let mut config = self.into_config();
+        config.labels.push(label);
+        config
+    }
+
+    /// Add the system to a [`SystemSet`].
+    fn in_set(self, set: &'static str) -> SystemConfig {
+        let mut config = self.into_config();
+        config.sets.push(set);
+        config
+    }
+
+    /// Run before every system labelled (or in set) `label`.
+    fn before(self, label: &'static str) -> SystemConfig {
+        let mut config = self.into_config();
+        config.before.push(label);
+        config
+    }
+
+    /// Run after every system labelled (or in set) `label`.
+    fn after(self, label: &'static str) -> SystemConfig {
+        let mut config = self.into_config();
+        config.after.push(label);
+        config
+    }
+
+    /// Only run while `condition` holds.  Several conditions must all hold.
+    fn run_if<C>(self, condition: C) -> SystemConfig
+    where
+        C: FnMut(&World, &ResourceMap) -> bool + Send + 'static,
+    {
+        let mut config = self.into_config();
+        config.conditions.push(Box::new(condition));
+        config
+    }
+}
+
+impl IntoSystemConfig for SystemConfig {
+    fn into_config(self) -> SystemConfig {
+        self
+    }
+}
+
+impl<S: System> IntoSystemConfig for S {
+    fn into_config(self) -> SystemConfig {
+        SystemConfig::new(Box::new(self))
+    }
+}
+
+impl IntoSystemConfig for Box<dyn System> {
+    fn into_config(self) -> SystemConfig {
+        SystemConfig::new(self)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// SystemSet
+
+/// A named group of systems sharing ordering constraints and run conditions.
+///
+/// Systems join with [`IntoSystemConfig::in_set`]; the set's constraints
+/// apply to each member as if declared on it, and its conditions gate every
+/// member.  A set name can also be used as a `before`/`after` target.
+pub struct SystemSet {
+    pub(crate) name: &'static str,
+    pub(crate) before: Vec<&'static str>,
+    pub(crate) after: Vec<&'static str>,
+    pub(crate) conditions: Vec<RunCondition>,
+}
+
+impl SystemSet {
+    pub fn new(name: &'static str) -> Self {
+        SystemSet {
+            name,
+            before: Vec::new(),
+            after: Vec::new(),
+            conditions: Vec::new(),
+        }
+    }
+
+    /// Members run before every system labelled (or in set) `label`.
+    pub fn before(mut self, label: &'static str) -> Self {
+        self.before.push(label);
+        self
+    }
+
+    /// Members run after every system labelled (or in set) `label`.
+    pub fn after(mut self, label: &'static str) -> Self {
+        self.after.push(label);
+        self
+    }
+
+    /// Members only run while `condition` holds.
+    pub fn run_if<C>(mut self, condition: C) -> Self
+    where
+        C: FnMut(&World, &ResourceMap) -> bool + Send + 'static,
+    {
+        self.conditions.push(Box::new(condition));
+        self
+    }
+
+    /// Name of the set.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+/// Configured sets, keyed by name.
+#[derive(Default)]
+pub(crate) struct SetRegistry {
+    sets: HashMap<&'static str, SystemSet>,
+    /// Condition results of the sets evaluated during the current stage run.
+    verdicts: HashMap<&'static str, bool>,
+}
+
+impl SetRegistry {
+    /// Add `set`, merging into an existing set of the same name.
+    pub(crate) fn configure(&mut self, set: SystemSet) {
+        match self.sets.get_mut(set.name) {
+            Some(existing) => {
+                existing.before.extend(set.before);
+                existing.after.extend(set.after);
+                existing.conditions.extend(set.conditions);
+            }
+            None => {
+                self.sets.insert(set.name, set);
+            }
+        }
+    }
+
+    /// Forget the condition results of the previous stage run.
+    pub(crate) fn begin_run(&mut self) {
+        self.verdicts.clear();
+    }
+
+    /// Evaluate the conditions of every set in `sets`.
+    ///
+    /// Each set's conditions run at most once per stage run, when its first
+    /// member is reached; later members reuse the result, so stateful
+    /// conditions give every member the same answer.
+    pub(crate) fn should_run(&mut self, sets: &[&'static str], world: &World, res: &ResourceMap) -> bool {
+        sets.iter().all(|&name| {
+            if let Some(&verdict) = self.verdicts.get(name) {
+                return verdict;
+            }
+            let verdict = match self.sets.get_mut(name) {
+                Some(set) => set.conditions.iter_mut().all(|c| c(world, res)),
+                None => true,
+            };
+            self.verdicts.insert(name, verdict);
+            verdict
+        })
+    }
+}
+
+/// Evaluate a system's own conditions.
+pub(crate) fn conditions_hold(conditions: &mut [RunCondition], world: &World, res: &ResourceMap) -> bool {
+    conditions.iter_mut().all(|c| c(world, res))
+}
+
+// ---------------------------------------------------------------------------
+// Ordering
+
+/// Error produced when ordering constraints cannot be satisfied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleError {
+    /// The listed systems form a cycle: each must run before the next, and
+    /// the last before the first.
+    Cycle(Vec<&'static str>),
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScheduleError::Cycle(names) => {
+                write!(f, "system ordering cycle: ")?;
+                for name in names {
+                    write!(f, "{name} → ")?;
+                }
+                write!(f, "{}", names.first().copied().unwrap_or(""))
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScheduleError {}
+
+/// Ordering-relevant view of one system.
+pub(crate) struct OrderNode<'a> {
+    pub(crate) name: &'static str,
+    pub(crate) labels: &'a [&'static str],
+    pub(crate) sets: &'a [&'static str],
+    pub(crate) before: &'a [&'static str],
+    pub(crate) after: &'a [&'static str],
+}
+
+/// Build predecessor lists: `preds[i]` holds every node that must run
+/// before node `i`.
+pub(crate) fn dependencies(nodes: &[OrderNode<'_>], sets: &SetRegistry) -> Vec<Vec<usize>> {
+    let matches = |node: &OrderNode<'_>, label: &str| {
+        node.labels.contains(&label) || node.sets.contains(&label)
+    };
+    let mut preds = vec![Vec::new(); nodes.len()];
+    for (i, node) in nodes.iter().enumerate() {
+        let mut before: Vec<&str> = node.before.to_vec();
+        let mut after: Vec<&str> = node.after.to_vec();
+        for set in node.sets {
+            if let Some(set) = sets.sets.get(set) {
+                before.extend(&set.before);
+                after.extend(&set.after);
+            }
+        }
+        for (j, other) in nodes.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            if before.iter().any(|l| matches(other, l)) {
+                preds[j].push(i);
+            }
+            if after.iter().any(|l| matches(other, l)) {
+                preds[i].push(j);
+            }
+        }
+    }
+    for p in &mut preds {
+        p.sort_unstable();
+        p.dedup();
+    }
+    preds
+}
+
+/// Topologically sort nodes given predecessor lists, preferring the lowest
+/// index whenever several nodes are ready (so unconstrained systems keep
+/// insertion order).
+pub(crate) fn topological_order(
+    names: &[&'static str],
+    preds: &[Vec<usize>],
+) -> Result<Vec<usize>, ScheduleError> {
+    let n = preds.len();
+    let mut succs = vec![Vec::new(); n];
+    let mut pending: Vec<usize> = preds.iter().map(Vec::len).collect();
+    for (i, p) in preds.iter().enumerate() {
+        for &j in p {
+            succs[j].push(i);
+        }
+    }
+
+    let mut ready: BinaryHeap<Reverse<usize>> =
+        (0..n).filter(|&i| pending[i] == 0).map(Reverse).collect();
+    let mut order = Vec::with_capacity(n);
+    while let Some(Reverse(i)) = ready.pop() {
+        order.push(i);
+        for &s in &succs[i] {
+            pending[s] -= 1;
+            if pending[s] == 0 {
+                ready.push(Reverse(s));
+            }
+        }
+    }
+    if order.len() == n {
+        return Ok(order);
+    }
+
+    // Every unsorted node still has an unsorted predecessor, so walking
+    // predecessors from any of them must revisit a node.
+    let start = (0..n).find(|&i| pending[i] > 0).unwrap();
+    let mut path = vec![start];
+    let mut node = start;
+    loop {
+        node = *preds[node].iter().find(|&&p| pending[p] > 0).unwrap();
+        if let Some(pos) = path.iter().position(|&p| p == node) {
+            let mut cycle: Vec<&'static str> = path[pos..].iter().map(|&i| names[i]).collect();
+            cycle.reverse();
+            return Err(ScheduleError::Cycle(cycle));
+        }
+        path.push(node);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(
+        name: &'static str,
+        labels: &'static [&'static str],
+        before: &'static [&'static str],
+        after: &'static [&'static str],
+    ) -> OrderNode<'static> {
+        OrderNode { name, labels, sets: &[], before, after }
+    }
+
+    fn sort(nodes: &[OrderNode<'_>], sets: &SetRegistry) -> Result<Vec<&'static str>, ScheduleError> {
+        let names: Vec<_> = nodes.iter().map(|n| n.name).collect();
+        let order = topological_order(&names, &dependencies(nodes, sets))?;
+        Ok(order.into_iter().map(|i| names[i]).collect())
+    }
+
+    #[test]
+    fn unconstrained_keeps_insertion_order() {
+        let nodes = [node("a", &[], &[], &[]), node("b", &[], &[], &[]), node("c", &[], &[], &[])];
+        assert_eq!(sort(&nodes, &SetRegistry::default()).unwrap(), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn before_and_after_reorder() {
+        let nodes = [
+            node("render", &["render"], &[], &["physics"]),
+            node("input", &[], &["physics"], &[]),
+            node("physics", &["physics"], &[], &[]),
+            node("unrelated", &[], &[], &["missing"]),
+        ];
+        assert_eq!(
+            sort(&nodes, &SetRegistry::default()).unwrap(),
+            ["input", "physics", "render", "unrelated"]
+        );
+    }
+
+    #[test]
+    fn set_constraints_apply_to_members() {
+        let mut sets = SetRegistry::default();
+        sets.configure(SystemSet::new("sim").after("input"));
+        let nodes = [
+            OrderNode { name: "move", labels: &[], sets: &["sim"], before: &[], after: &[] },
+            OrderNode { name: "collide", labels: &[], sets: &["sim"], before: &[], after: &[] },
+            node("input", &["input"], &[], &[]),
+            node("draw", &[], &[], &["sim"]),
+        ];
+        assert_eq!(sort(&nodes, &sets).unwrap(), ["input", "move", "collide", "draw"]);
+    }
+
+    #[test]
+    fn cycles_name_the_offending_systems() {
+        let nodes = [
+            node("ok", &[], &[], &[]),
+            node("a", &["a"], &["b"], &[]),
+            node("b", &["b"], &["c"], &[]),
+            node("c", &["c"], &["a"], &[]),
+        ];
+        let err = sort(&nodes, &SetRegistry::default()).unwrap_err();
+        assert_eq!(err, ScheduleError::Cycle(vec!["b", "c", "a"]));
+        assert_eq!(err.to_string(), "system ordering cycle: b → c → a → b");
+    }
+}
@@ -0,0 +1,593 @@
+//! World snapshots driven by a component type registry.
+//!
+//! Component types opt into persistence by registering with a
+//! [`ComponentRegistry`].  [`World::serialize_snapshot`] then writes every
+//! registered component of every live entity; unregistered components (GPU
+//! handles, caches, …) are skipped.  [`World::deserialize_snapshot`] spawns
+//! fresh entities for the saved ones and returns the old → new [`EntityMap`];
+//! components registered with [`ComponentRegistry::register_mapped`] have
+//! their stored `Entity` handles rewritten through it.
+//!
+//! Two encodings are supported ([`SnapshotFormat`]):
+//!
+//! | Format   | Layout                                                        |
+//! |----------|---------------------------------------------------------------|
+//! | `Json`   | `{ "version", "entities": [{ "id", "components": { name: value } }] }` |
+//! | `Binary` | `b"FECS"`, `u32` version, then bincode: component name table + entities |
+//!
+//! Components are keyed by their registered name (the type path by default),
+//! so renaming a type breaks old saves unless it is registered with
+//! [`ComponentRegistry::register_as`] under its old name.
+//!
+//! ```rust
+//! use ferrous_ecs::prelude::*;
+//! use ferrous_ecs::snapshot::{ComponentRegistry, SnapshotFormat};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Clone, Serialize, Deserialize)]
+//! struct Health(u32);
+//! impl Component for Health {}
+//!
+//! let mut registry = ComponentRegistry::new();
+//! registry.register::<Health>();
+//!
+//! let mut world = World::new();
+//! world.spawn((Health(7),));
+//! let bytes = world.serialize_snapshot(&registry, SnapshotFormat::Binary).unwrap();
+//!
+//! let mut loaded = World::new();
+//! let map = loaded.deserialize_snapshot(&registry, &bytes, SnapshotFormat::Binary).unwrap();
+//! let (_, e) = map.iter().next().unwrap();
+//! assert_eq!(loaded.get::<Health>(e).unwrap().0, 7);
+//! ```
+
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::component::Component;
+use crate::entity::{Entity, EntityMap, MapEntities};
+use crate::relation::{Relation, RelationSources, RelationTargets};
+use crate::world::World;
+
+/// Version written into every snapshot.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+const BINARY_MAGIC: [u8; 4] = *b"FECS";
+
+/// Snapshot encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotFormat {
+    /// Human-readable, pretty-printed JSON.
+    Json,
+    /// Compact bincode with a small header.
+    Binary,
+}
+
+/// Errors returned by snapshot (de)serialization.
+#[derive(Debug)]
+pub enum SnapshotError {
+    Json(serde_json::Error),
+    Binary(bincode::Error),
+    /// Binary data does not start with the snapshot header.
+    BadHeader,
+    /// Written by a newer (or unknown) snapshot version.
+    UnsupportedVersion(u32),
+    /// The snapshot contains a component name missing from the registry.
+    UnknownComponent(String),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Json(e) => write!(f, "snapshot JSON error: {e}"),
+            SnapshotError::Binary(e) => write!(f, "snapshot binary error: {e}"),
+            SnapshotError::BadHeader => write!(f, "not a binary world snapshot"),
+            SnapshotError::UnsupportedVersion(v) => {
+                write!(f, "unsupported snapshot version {v} (expected {SNAPSHOT_VERSION})")
+            }
+            SnapshotError::UnknownComponent(name) => {
+                write!(f, "snapshot component `{name}` is not registered")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl From<serde_json::Error> for SnapshotError {
+    fn from(e: serde_json::Error) -> Self {
+        SnapshotError::Json(e)
+    }
+}
+
+impl From<bincode::Error> for SnapshotError {
+    fn from(e: bincode::Error) -> Self {
+        SnapshotError::Binary(e)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// ComponentRegistry
+
+/// Type-erased (de)serializers for one component type.
+struct Registration {
+    name: &'static str,
+    has: fn(&World, Entity) -> bool,
+    to_json: fn(&World, Entity) -> Result<serde_json::Value, serde_json::Error>,
+    from_json: fn(&mut World, Entity, serde_json::Value) -> Result<(), serde_json::Error>,
+    to_binary: fn(&World, Entity) -> Result<Vec<u8>, bincode::Error>,
+    from_binary: fn(&mut World, Entity, &[u8]) -> Result<(), bincode::Error>,
+    map_entities: Option<fn(&mut World, Entity, &EntityMap)>,
+    /// Run once per load if any entity carried this component.
+    on_load: Option<fn(&mut World)>,
+}
+
+/// The set of component types that snapshots persist.
+///
+/// Registered types must be `Clone + Serialize + DeserializeOwned`.
+/// Insert one into the app's resources so every plugin can register its own
+/// components.
+#[derive(Default)]
+pub struct ComponentRegistry {
+    entries: Vec<Registration>,
+    by_type: HashMap<TypeId, usize>,
+    by_name: HashMap<&'static str, usize>,
+}
+
+impl fmt::Debug for ComponentRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.names()).finish()
+    }
+}
+
+impl ComponentRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `T` under its type path.
+    pub fn register<T>(&mut self) -> &mut Self
+    where
+        T: Component + Clone + Serialize + DeserializeOwned,
+    {
+        self.add::<T>(std::any::type_name::<T>(), None, None)
+    }
+
+    /// Register `T` under an explicit, stable name.
+    pub fn register_as<T>(&mut self, name: &'static str) -> &mut Self
+    where
+        T: Component + Clone + Serialize + DeserializeOwned,
+    {
+        self.add::<T>(name, None, None)
+    }
+
+    /// Register `T`, whose `Entity` fields are remapped on load.
+    pub fn register_mapped<T>(&mut self) -> &mut Self
+    where
+        T: Component + Clone + Serialize + DeserializeOwned + MapEntities,
+    {
+        self.add::<T>(std::any::type_name::<T>(), Some(map_component::<T>), None)
+    }
+
+    /// Register both bookkeeping components of relation `R`, so saved edges
+    /// (and their despawn policy) survive a load.
+    pub fn register_relation<R: Relation>(&mut self) -> &mut Self {
+        self.add::<RelationTargets<R>>(
+            std::any::type_name::<RelationTargets<R>>(),
+            Some(map_component::<RelationTargets<R>>),
+            Some(load_relation::<R>),
+        );
+        self.add::<RelationSources<R>>(
+            std::any::type_name::<RelationSources<R>>(),
+            Some(map_component::<RelationSources<R>>),
+            Some(load_relation::<R>),
+        )
+    }
+
+    /// Registering a type twice is a no-op.
+    ///
+    /// # Panics
+    /// Panics if `name` is already taken by a different type.
+    fn add<T>(
+        &mut self,
+        name: &'static str,
+        map_entities: Option<fn(&mut World, Entity, &EntityMap)>,
+        on_load: Option<fn(&mut World)>,
+    ) -> &mut Self
+    where
+        T: Component + Clone + Serialize + DeserializeOwned,
+    {
+        if self.by_type.contains_key(&TypeId::of::<T>()) {
+            return self;
+        }
+        assert!(
+            !self.by_name.contains_key(name),
+            "component name `{name}` is already registered for another type"
+        );
+        let idx = self.entries.len();
+        self.entries.push(Registration {
+            name,
+            has: |world, e| world.has::<T>(e),
+            to_json: |world, e| serde_json::to_value(world.get::<T>(e).unwrap()),
+            from_json: |world, e, value| {
+                world.insert(e, serde_json::from_value::<T>(value)?);
+                Ok(())
+            },
+            to_binary: |world, e| bincode::serialize(world.get::<T>(e).unwrap()),
+            from_binary: |world, e, bytes| {
+                world.insert(e, bincode::deserialize::<T>(bytes)?);
+                Ok(())
+            },
+            map_entities,
+            on_load,
+        });
+        self.by_type.insert(TypeId::of::<T>(), idx);
+        self.by_name.insert(name, idx);
+        self
+    }
+
+    /// Whether `T` is registered.
+    pub fn contains<T: 'static>(&self) -> bool {
+        self.by_type.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Registered names, in registration order.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.entries.iter().map(|r| r.name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn lookup(&self, name: &str) -> Result<&Registration, SnapshotError> {
+        self.by_name
+            .get(name)
+            .map(|&i| &self.entries[i])
+            .ok_or_else(|| SnapshotError::UnknownComponent(name.to_owned()))
+    }
+}
+
+fn map_component<T: Component + MapEntities>(world: &mut World, e: Entity, map: &EntityMap) {
+    if let Some(c) = world.get_mut::<T>(e) {
+        c.map_entities(map);
+    }
+}
+
+fn load_relation<R: Relation>(world: &mut World) {
+    world.register_relation::<R>();
+}
+
+// ---------------------------------------------------------------------------
+// Encodings
+
+#[derive(Serialize, Deserialize)]
+struct JsonSnapshot {
+    version: u32,
+    entities: Vec<JsonEntity>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonEntity {
+    id: Entity,
+    components: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Names are interned in a table; components refer to them by index.
+#[derive(Serialize, Deserialize)]
+struct BinarySnapshot {
+    names: Vec<String>,
+    entities: Vec<BinaryEntity>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BinaryEntity {
+    id: Entity,
+    components: Vec<(u32, Vec<u8>)>,
+}
+
+// ---------------------------------------------------------------------------
+// World API
+
+impl World {
+    /// Serialize every registered component of every live entity.
+    ///
+    /// Entities without registered components are still written (with no
+    /// components) so references to them stay valid after a load.
+    pub fn serialize_snapshot(
+        &self,
+        registry: &ComponentRegistry,
+        format: SnapshotFormat,
+    ) -> Result<Vec<u8>, SnapshotError> {
+        let mut entities: Vec<Entity> = self
+            .archetypes
+            .iter()
+            .flat_map(|a| a.entities.iter().copied())
+            .collect();
+        entities.sort_by_key(|e| e.index);
+
+        match format {
+            SnapshotFormat::Json => {
+                let mut out = Vec::with_capacity(entities.len());
+                for id in entities {
+                    let mut components = serde_json::Map::new();
+                    for reg in registry.entries.iter().filter(|r| (r.has)(self, id)) {
+                        components.insert(reg.name.to_owned(), (reg.to_json)(self, id)?);
+                    }
+                    out.push(JsonEntity { id, components });
+                }
+                let snapshot = JsonSnapshot {
+                    version: SNAPSHOT_VERSION,
+                    entities: out,
+                };
+                Ok(serde_json::to_vec_pretty(&snapshot)?)
+            }
+            SnapshotFormat::Binary => {
+                let mut names = Vec::new();
+                let mut name_index: HashMap<&'static str, u32> = HashMap::new();
+                let mut out = Vec::with_capacity(entities.len());
+                for id in entities {
+                    let mut components = Vec::new();
+                    for reg in registry.entries.iter().filter(|r| (r.has)(self, id)) {
+                        let idx = *name_index.entry(reg.name).or_insert_with(|| {
+                            names.push(reg.name.to_owned());
+                            names.len() as u32 - 1
+                        });
+                        components.push((idx, (reg.to_binary)(self, id)?));
+                    }
+                    out.push(BinaryEntity { id, components });
+                }
+                let mut bytes = BINARY_MAGIC.to_vec();
+                bytes.extend_from_slice(&SNAPSHOT_VERSION.to_le_bytes());
+                bincode::serialize_into(
+                    &mut bytes,
+                    &BinarySnapshot {
+                        names,
+                        entities: out,
+                    },
+                )?;
+                Ok(bytes)
+            }
+        }
+    }
+
+    /// Spawn the entities stored in `bytes` into this world.
+    ///
+    /// Existing entities are left alone.  Returns the map from saved handles
+    /// to the newly spawned ones.  On error nothing is spawned.
+    pub fn deserialize_snapshot(
+        &mut self,
+        registry: &ComponentRegistry,
+        bytes: &[u8],
+        format: SnapshotFormat,
+    ) -> Result<EntityMap, SnapshotError> {
+        let mut map = EntityMap::new();
+        let result = match format {
+            SnapshotFormat::Json => self.load_json(registry, bytes, &mut map),
+            SnapshotFormat::Binary => self.load_binary(registry, bytes, &mut map),
+        };
+        if let Err(e) = result {
+            for (_, spawned) in map.iter() {
+                self.despawn(spawned);
+            }
+            return Err(e);
+        }
+
+        // Rewrite stored handles, then run per-type load hooks.
+        let mut used = vec![false; registry.entries.len()];
+        for (_, e) in map.iter() {
+            for (i, reg) in registry.entries.iter().enumerate() {
+                if !(reg.has)(self, e) {
+                    continue;
+                }
+                used[i] = true;
+                if let Some(map_entities) = reg.map_entities {
+                    map_entities(self, e, &map);
+                }
+            }
+        }
+        for (reg, _) in registry.entries.iter().zip(used).filter(|(_, u)| *u) {
+            if let Some(on_load) = reg.on_load {
+                on_load(self);
+            }
+        }
+        Ok(map)
+    }
+
+    /// Reserve one entity per saved id and make them live.
+    fn spawn_snapshot_entities(&mut self, ids: impl Iterator<Item = Entity>, map: &mut EntityMap) {
+        for id in ids {
+            map.insert(id, self.reserve_entity());
+        }
+        self.flush();
+    }
+
+    fn load_json(
+        &mut self,
+        registry: &ComponentRegistry,
+        bytes: &[u8],
+        map: &mut EntityMap,
+    ) -> Result<(), SnapshotError> {
+        let snapshot: JsonSnapshot = serde_json::from_slice(bytes)?;
+        if snapshot.version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(snapshot.version));
+        }
+        for entity in &snapshot.entities {
+            for name in entity.components.keys() {
+                registry.lookup(name)?;
+            }
+        }
+        self.spawn_snapshot_entities(snapshot.entities.iter().map(|e| e.id), map);
+        for entity in snapshot.entities {
+            let e = map.map(entity.id);
+            for (name, value) in entity.components {
+                (registry.lookup(&name)?.from_json)(self, e, value)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn load_binary(
+        &mut self,
+        registry: &ComponentRegistry,
+        bytes: &[u8],
+        map: &mut EntityMap,
+    ) -> Result<(), SnapshotError> {
+        if bytes.len() < 8 || bytes[..4] != BINARY_MAGIC {
+            return Err(SnapshotError::BadHeader);
+        }
+        let version = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+        if version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+        let snapshot: BinarySnapshot = bincode::deserialize(&bytes[8..])?;
+        let regs = snapshot
+            .names
+            .iter()
+            .map(|name| registry.lookup(name))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.spawn_snapshot_entities(snapshot.entities.iter().map(|e| e.id), map);
+        for entity in snapshot.entities {
+            let e = map.map(entity.id);
+            for (idx, data) in entity.components {
+                let reg = regs.get(idx as usize).ok_or(SnapshotError::BadHeader)?;
+                (reg.from_binary)(self, e, &data)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct Pos(f32, f32);
+    impl Component for Pos {}
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct Name(String);
+    impl Component for Name {}
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct Target(Entity);
+    impl Component for Target {}
+    impl MapEntities for Target {
+        fn map_entities(&mut self, map: &EntityMap) {
+            self.0 = map.map(self.0);
+        }
+    }
+
+    /// Not registered by default: must be skipped.
+    #[derive(Clone, Serialize, Deserialize)]
+    struct Cache;
+    impl Component for Cache {}
+
+    struct ChildOf;
+    impl Relation for ChildOf {
+        const DESPAWN_POLICY: crate::relation::DespawnPolicy =
+            crate::relation::DespawnPolicy::Recursive;
+        const EXCLUSIVE: bool = true;
+    }
+
+    fn registry() -> ComponentRegistry {
+        let mut registry = ComponentRegistry::new();
+        registry
+            .register::<Pos>()
+            .register_as::<Name>("Name")
+            .register_mapped::<Target>()
+            .register_relation::<ChildOf>();
+        registry
+    }
+
+    fn source_world() -> (World, Entity, Entity) {
+        let mut world = World::new();
+        // Burn a few slots so saved ids differ from freshly spawned ones.
+        for _ in 0..3 {
+            let e = world.spawn((Cache,));
+            world.despawn(e);
+        }
+        let parent = world.spawn((Pos(1.0, 2.0), Name("root".into()), Cache));
+        let child = world.spawn((Pos(3.0, 4.0), Target(parent)));
+        world.relate::<ChildOf>(child, parent);
+        (world, parent, child)
+    }
+
+    fn check_roundtrip(format: SnapshotFormat) {
+        let registry = registry();
+        let (world, parent, child) = source_world();
+        let bytes = world.serialize_snapshot(&registry, format).unwrap();
+
+        let mut loaded = World::new();
+        let _existing = loaded.spawn((Cache,));
+        let map = loaded.deserialize_snapshot(&registry, &bytes, format).unwrap();
+        assert_eq!(map.len(), 2);
+        let (p, c) = (map.get(parent).unwrap(), map.get(child).unwrap());
+
+        assert_eq!(loaded.get::<Pos>(p), Some(&Pos(1.0, 2.0)));
+        assert_eq!(loaded.get::<Name>(p), Some(&Name("root".into())));
+        assert!(loaded.get::<Cache>(p).is_none());
+        assert_eq!(loaded.get::<Target>(c), Some(&Target(p)));
+        assert_eq!(loaded.target::<ChildOf>(c), Some(p));
+        assert_eq!(loaded.sources::<ChildOf>(p), &[c]);
+
+        // The relation's despawn policy is live after loading.
+        loaded.despawn(p);
+        assert!(!loaded.contains(c));
+        assert_eq!(loaded.len(), 1);
+    }
+
+    #[test]
+    fn json_roundtrip_remaps_entities() {
+        check_roundtrip(SnapshotFormat::Json);
+    }
+
+    #[test]
+    fn binary_roundtrip_remaps_entities() {
+        check_roundtrip(SnapshotFormat::Binary);
+    }
+
+    #[test]
+    fn binary_is_smaller_than_json() {
+        let registry = registry();
+        let (world, _, _) = source_world();
+        let json = world.serialize_snapshot(&registry, SnapshotFormat::Json).unwrap();
+        let binary = world.serialize_snapshot(&registry, SnapshotFormat::Binary).unwrap();
+        assert!(binary.len() < json.len());
+        assert!(std::str::from_utf8(&json).unwrap().contains("\"Name\": \"root\""));
+    }
+
+    #[test]
+    fn unknown_components_abort_the_load() {
+        let mut full = registry();
+        full.register::<Cache>();
+        let (world, _, _) = source_world();
+        let bytes = world.serialize_snapshot(&full, SnapshotFormat::Json).unwrap();
+
+        let mut loaded = World::new();
+        let mut partial = ComponentRegistry::new();
+        partial.register::<Pos>();
+        let err = loaded
+            .deserialize_snapshot(&partial, &bytes, SnapshotFormat::Json)
+            .unwrap_err();
+        assert!(matches!(err, SnapshotError::UnknownComponent(_)), "{err}");
+        assert!(loaded.is_empty());
+
+        let err = loaded
+            .deserialize_snapshot(&partial, b"nope", SnapshotFormat::Binary)
+            .unwrap_err();
+        assert!(matches!(err, SnapshotError::BadHeader));
+    }
+}
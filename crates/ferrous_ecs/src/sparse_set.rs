@@ -0,0 +1,397 @@
+//! Sparse-set storage for components declared with
+//! [`StorageType::SparseSet`](crate::component::StorageType::SparseSet).
+//!
+//! Each sparse component type gets one [`ComponentSparseSet`]: a dense,
+//! type-erased [`ComponentColumn`] of values plus a parallel list of owning
+//! entities, and a sparse array mapping `Entity::index` to the dense row.
+//! Inserting or removing a value is O(1) and never touches the entity's
+//! archetype, which makes this storage a good fit for markers that are
+//! toggled every few frames.
+//!
+//! Entities whose components are all sparse live in the empty archetype, so
+//! archetype-driven iteration (queries, snapshots) still sees them.
+
+use std::any::TypeId;
+use std::collections::HashMap;
+
+use crate::archetype::{ComponentColumn, ComponentTicks};
+use crate::component::{Component, ComponentInfo};
+use crate::entity::Entity;
+
+// ---------------------------------------------------------------------------
+// ComponentSparseSet
+
+/// Storage for every value of one sparse component type.
+#[derive(Debug)]
+pub struct ComponentSparseSet {
+    /// Values and their change ticks, packed.
+    dense: ComponentColumn,
+    /// `entities[row]` owns `dense[row]`.
+    entities: Vec<Entity>,
+    /// `Entity::index` → dense row.
+    sparse: Vec<Option<usize>>,
+}
+
+impl ComponentSparseSet {
+    /// Create an empty set for the component described by `info`.
+    pub fn new(info: ComponentInfo) -> Self {
+        ComponentSparseSet {
+            dense: ComponentColumn::new(info),
+            entities: Vec::new(),
+            sparse: Vec::new(),
+        }
+    }
+
+    /// Number of entities holding a value.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Entities holding a value, in dense order.
+    #[inline]
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    /// Dense row of `entity`'s value, if it has one.
+    ///
+    /// Stale handles never match: the stored entity must have the same
+    /// generation.
+    #[inline]
+    pub fn dense_index(&self, entity: Entity) -> Option<usize> {
+        let row = (*self.sparse.get(entity.index as usize)?)?;
+        (self.entities[row] == entity).then_some(row)
+    }
+
+    /// Returns `true` if `entity` has a value in this set.
+    #[inline]
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.dense_index(entity).is_some()
+    }
+
+    /// Typed reference to `entity`'s value.
+    ///
+    /// # Panics
+    /// If `C` is not the component type of this set.
+    pub fn get<C: Component>(&self, entity: Entity) -> Option<&C> {
+        assert_eq!(TypeId::of::<C>(), self.dense.info.type_id);
+        let row = self.dense_index(entity)?;
+        // SAFETY: type checked above; row < len.
+        Some(unsafe { self.dense.get::<C>(row) })
+    }
+
+    /// Typed mutable reference to `entity`'s value, marking it changed at
+    /// `tick`.
+    ///
+    /// # Panics
+    /// If `C` is not the component type of this set.
+    pub fn get_mut<C: Component>(&mut self, entity: Entity, tick: u64) -> Option<&mut C> {
+        assert_eq!(TypeId::of::<C>(), self.dense.info.type_id);
+        let row = self.dense_index(entity)?;
+        self.dense.set_changed(row, tick);
+        // SAFETY: type checked above; row < len.
+        Some(unsafe { self.dense.get_mut::<C>(row) })
+    }
+
+    /// Change-detection ticks of `entity`'s value.
+    pub fn ticks(&self, entity: Entity) -> Option<ComponentTicks> {
+        self.dense_index(entity).map(|row| self.dense.ticks(row))
+    }
+
+    /// The dense value column (rows line up with [`entities`](Self::entities)).
+    #[inline]
+    pub(crate) fn column(&self) -> &ComponentColumn {
+        &self.dense
+    }
+
+    /// Add a row for `entity` and return the slot its value must be written to.
+    ///
+    /// # Safety
+    /// `entity` must not already be in the set, and the caller must write a
+    /// valid value into the returned slot before the set is read or dropped.
+    pub(crate) unsafe fn insert_uninit(&mut self, entity: Entity, ticks: ComponentTicks) -> *mut u8 {
+        debug_assert!(!self.contains(entity));
+        let index = entity.index as usize;
+        if index >= self.sparse.len() {
+            self.sparse.resize(index + 1, None);
+        }
+        self.sparse[index] = Some(self.entities.len());
+        self.entities.push(entity);
+        self.dense.push_uninit(ticks)
+    }
+
+    /// Drop `entity`'s value.  Returns `false` if it had none.
+    pub(crate) fn remove(&mut self, entity: Entity) -> bool {
+        let Some(row) = self.dense_index(entity) else {
+            return false;
+        };
+        // SAFETY: row < len; the value is initialized and dropped here.
+        unsafe { self.dense.swap_remove(row) };
+        self.entities.swap_remove(row);
+        self.sparse[entity.index as usize] = None;
+        if let Some(&moved) = self.entities.get(row) {
+            self.sparse[moved.index as usize] = Some(row);
+        }
+        true
+    }
+}
+
+// ---------------------------------------------------------------------------
+// SparseSets — all sparse sets of a world
+
+/// The sparse sets of a [`World`](crate::world::World), keyed by component type.
+#[derive(Debug, Default)]
+pub struct SparseSets {
+    sets: HashMap<TypeId, ComponentSparseSet>,
+}
+
+impl SparseSets {
+    /// The set for `type_id`, if any value of that type was ever inserted.
+    #[inline]
+    pub fn get(&self, type_id: TypeId) -> Option<&ComponentSparseSet> {
+        self.sets.get(&type_id)
+    }
+
+    #[inline]
+    pub(crate) fn get_mut(&mut self, type_id: TypeId) -> Option<&mut ComponentSparseSet> {
+        self.sets.get_mut(&type_id)
+    }
+
+    /// The set for `info`'s component type, created on first use.
+    pub(crate) fn get_or_insert(&mut self, info: &ComponentInfo) -> &mut ComponentSparseSet {
+        self.sets
+            .entry(info.type_id)
+            .or_insert_with(|| ComponentSparseSet::new(info.clone()))
+    }
+
+    /// Sparse component types `entity` has values for.
+    pub fn types_of(&self, entity: Entity) -> Vec<TypeId> {
+        self.sets
+            .iter()
+            .filter(|(_, set)| set.contains(entity))
+            .map(|(&tid, _)| tid)
+            .collect()
+    }
+
+    /// Drop every sparse value of `entity`.
+    pub(crate) fn remove_entity(&mut self, entity: Entity) {
+        for set in self.sets.values_mut() {
+            set.remove(entity);
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::StorageType;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Label(String);
+    impl Component for Label {
+        const STORAGE: StorageType = StorageType::SparseSet;
+    }
+
+    fn entity(index: u32, generation: u32) -> Entity {
+        Entity { index, generation }
+    }
+
+    fn insert(set: &mut ComponentSparseSet, e: Entity, value: Label) {
+        unsafe {
+            let slot = set.insert_uninit(e, ComponentTicks::new(1));
+            std::ptr::write(slot as *mut Label, value);
+        }
+    }
+
+    #[test]
+    fn insert_remove_keeps_rows_consistent() {
+        let mut set = ComponentSparseSet::new(ComponentInfo::of::<Label>());
+        let (a, b, c) = (entity(0, 0), entity(5, 0), entity(2, 0));
+        insert(&mut set, a, Label("a".into()));
+        insert(&mut set, b, Label("b".into()));
+        insert(&mut set, c, Label("c".into()));
+        assert_eq!(set.len(), 3);
+
+        assert!(set.remove(a));
+        assert!(!set.remove(a));
+        assert_eq!(set.get::<Label>(b), Some(&Label("b".into())));
+        assert_eq!(set.get::<Label>(c), Some(&Label("c".into())));
+        assert_eq!(set.entities().len(), 2);
+
+        // A recycled index with a new generation is a different entity.
+        assert!(!set.contains(entity(5, 1)));
+        set.get_mut::<Label>(c, 7).unwrap().0.push('!');
+        assert_eq!(set.ticks(c), Some(ComponentTicks { added: 1, changed: 7 }));
+        assert_eq!(set.get::<Label>(c).unwrap().0, "c!");
+    }
+
+    #[test]
+    fn swap_remove_moves_the_last_row_into_the_hole() {
+        let mut set = ComponentSparseSet::new(ComponentInfo::of::<Label>());
+        let (a, b, c) = (entity(0, 0), entity(1, 0), entity(2, 0));
+        insert(&mut set, a, Label("a".into()));
+        insert(&mut set, b, Label("b".into()));
+        insert(&mut set, c, Label("c".into()));
+        set.get_mut::<Label>(c, 9);
+
+        // Removing the first row moves `c` (the last) into it.
+        assert!(set.remove(a));
+        assert_eq!(set.entities(), &[c, b]);
+        assert_eq!(set.dense_index(c), Some(0));
+        assert_eq!(set.dense_index(b), Some(1));
+        assert_eq!(set.get::<Label>(c).unwrap().0, "c");
+        assert_eq!(set.ticks(c), Some(ComponentTicks { added: 1, changed: 9 }));
+
+        // Removing the last row moves nothing.
+        assert!(set.remove(b));
+        assert_eq!(set.entities(), &[c]);
+        assert_eq!(set.dense_index(c), Some(0));
+        assert!(set.remove(c));
+        assert!(set.is_empty());
+        assert!(set.sparse.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn insert_and_remove_while_walking_rows() {
+        let mut set = ComponentSparseSet::new(ComponentInfo::of::<Label>());
+        for i in 0..6 {
+            insert(&mut set, entity(i, 0), Label(i.to_string()));
+        }
+
+        // Drop even entities and append a new one while walking the dense
+        // rows; a removal moves the last row into the current one, which
+        // is therefore visited next.
+        let late = entity(10, 0);
+        let mut visited = Vec::new();
+        let mut row = 0;
+        while row < set.len() {
+            let e = set.entities()[row];
+            visited.push(e.index);
+            if e.index == 3 {
+                insert(&mut set, late, Label("late".into()));
+            }
+            if e.index.is_multiple_of(2) {
+                set.remove(e);
+            } else {
+                row += 1;
+            }
+        }
+
+        visited.sort_unstable();
+        assert_eq!(visited, vec![0, 1, 2, 3, 4, 5, 10]);
+        let mut left: Vec<u32> = set.entities().iter().map(|e| e.index).collect();
+        left.sort_unstable();
+        assert_eq!(left, vec![1, 3, 5]);
+        for &e in set.entities() {
+            assert_eq!(set.get::<Label>(e).unwrap().0, e.index.to_string());
+        }
+    }
+
+    #[test]
+    fn commands_recorded_while_querying_apply_to_sparse_values() {
+        use crate::commands::{CommandQueue, Commands};
+        use crate::query::Query;
+        use crate::world::World;
+
+        let mut world = World::new();
+        let entities: Vec<Entity> = (0..4).map(|i| world.spawn((Label(i.to_string()),))).collect();
+        let mut queue = CommandQueue::new();
+        let mut commands = Commands::new(&mut queue, &world);
+        for (e, label) in Query::<&Label>::new(&world).iter() {
+            if label.0 == "1" {
+                commands.entity(e).remove::<Label>();
+            } else {
+                commands.entity(e).insert(Label(format!("{}!", label.0)));
+            }
+        }
+        let spawned = commands.spawn((Label("new".into()),)).id();
+        queue.apply(&mut world);
+
+        let labels: Vec<Option<&str>> = entities
+            .iter()
+            .chain([&spawned])
+            .map(|&e| world.get::<Label>(e).map(|l| l.0.as_str()))
+            .collect();
+        assert_eq!(labels, [Some("0!"), None, Some("2!"), Some("3!"), Some("new")]);
+        assert_eq!(Query::<&Label>::new(&world).len(), 4);
+    }
+
+    #[test]
+    fn despawn_drops_sparse_values() {
+        use crate::world::World;
+        use std::sync::Arc;
+
+        #[derive(Clone)]
+        #[allow(dead_code)] // held only for its reference count
+        struct Tracked(Arc<()>);
+        impl Component for Tracked {
+            const STORAGE: StorageType = StorageType::SparseSet;
+        }
+
+        let token = Arc::new(());
+        let mut world = World::new();
+        let entities: Vec<Entity> =
+            (0..3).map(|_| world.spawn((Tracked(token.clone()),))).collect();
+        assert_eq!(Arc::strong_count(&token), 4);
+
+        assert!(world.despawn(entities[0]));
+        assert_eq!(Arc::strong_count(&token), 3);
+        assert!(world.has::<Tracked>(entities[1]) && world.has::<Tracked>(entities[2]));
+
+        // The recycled slot belongs to a new entity without the old value.
+        let reused = world.spawn((Label("fresh".into()),));
+        assert_eq!(reused.index, entities[0].index);
+        assert!(!world.has::<Tracked>(reused));
+        let set = world.sparse_sets.get(TypeId::of::<Tracked>()).unwrap();
+        assert_eq!(set.len(), 2);
+
+        world.clear();
+        assert_eq!(Arc::strong_count(&token), 1);
+    }
+
+    #[test]
+    fn added_and_changed_ticks_on_sparse_components() {
+        use crate::query::{Added, Changed, Query};
+        use crate::world::World;
+
+        let mut world = World::new();
+        let a = world.spawn((Label("a".into()),));
+        let b = world.spawn((Label("b".into()),));
+        let c = world.spawn((Label("c".into()),));
+        let spawned = world.change_tick();
+        assert_eq!(world.sparse_sets.get(TypeId::of::<Label>()).unwrap().ticks(a).unwrap().added, spawned - 2);
+
+        world.get_mut::<Label>(b).unwrap().0.push('!');
+        // Re-inserting after a removal counts as a fresh add.
+        world.remove::<Label>(a);
+        world.insert(a, Label("a2".into()));
+
+        let set = world.sparse_sets.get(TypeId::of::<Label>()).unwrap();
+        let ticks = |e| set.ticks(e).unwrap();
+        assert!(ticks(a).added > spawned);
+        assert_eq!(ticks(b).added, spawned - 1);
+        assert!(ticks(b).changed > spawned);
+        assert_eq!(ticks(c), ComponentTicks::new(spawned));
+
+        let added: Vec<Entity> = Query::<&Label, Added<Label>>::new_since(&world, spawned)
+            .iter()
+            .map(|(e, _)| e)
+            .collect();
+        assert_eq!(added, vec![a]);
+        let mut changed: Vec<Entity> = Query::<&Label, Changed<Label>>::new_since(&world, spawned)
+            .iter()
+            .map(|(e, _)| e)
+            .collect();
+        changed.sort_by_key(|e| e.index);
+        assert_eq!(changed, vec![a, b]);
+    }
+}
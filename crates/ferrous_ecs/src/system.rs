@@ -8,16 +8,44 @@
 //!
 //! # Stage execution order
 //! ```text
-//! PreUpdate  → systems that must see the world before gameplay logic
+//! PreUpdate   → systems that must see the world before gameplay logic
 //!   (e.g. TimeSystem reads real-wall time and stores it as a resource)
-//! Update     → core gameplay logic
-//!   (e.g. VelocitySystem, BehaviorSystem, AnimationSystem)
-//! PostUpdate → fixup passes that consume Update output
+//! FixedUpdate → simulation at a fixed rate; 0..n times per frame
+//!   (e.g. VelocitySystem, physics)
+//! Update      → core gameplay logic
+//!   (e.g. BehaviorSystem, AnimationSystem)
+//! PostUpdate  → fixup passes that consume Update output
 //!   (e.g. TransformSystem propagates parent→child global transforms)
-//! Render     → CPU-side render preparation (culling, packet building)
+//! Render      → CPU-side render preparation (culling, packet building)
 //! ```
+//!
+//! # Ordering and run conditions
+//!
+//! Systems registered through [`IntoSystemConfig`] may carry labels, set
+//! memberships, `before` / `after` constraints and `run_if` conditions; see
+//! the [`schedule`](crate::schedule) module.
+//!
+//! # Deferred commands
+//!
+//! Structural changes recorded through [`Commands`](crate::commands::Commands)
+//! are applied by [`System::apply_deferred`] once the stage that recorded
+//! them has finished, so every system in a stage sees the same entity set and
+//! later stages see the result.  `SystemScheduler` and `ParallelScheduler`
+//! treat their whole run as a single stage.
+//!
+//! # Change detection
+//!
+//! Every scheduler remembers the world tick at which each system last ran and
+//! exposes it through [`World::last_change_tick`] while the system executes,
+//! so `Added<T>` / `Changed<T>` query filters only report what happened since
+//! that system's previous run.
 
+use crate::fixed::{FixedTime, FixedTimestep};
 use crate::resource::ResourceMap;
+use crate::schedule::{
+    conditions_hold, dependencies, topological_order, IntoSystemConfig, OrderNode, RunCondition,
+    ScheduleError, SetRegistry, SystemConfig, SystemSet,
+};
 use crate::world::World;
 
 // ---------------------------------------------------------------------------
@@ -51,16 +79,108 @@ pub trait System: Send + 'static {
 
     /// Execute the system for one tick.
     fn run(&mut self, world: &mut World, resources: &mut ResourceMap);
+
+    /// Apply structural changes deferred during [`run`](Self::run).
+    ///
+    /// Schedulers call this at the end of the stage the system ran in.
+    fn apply_deferred(&mut self, _world: &mut World) {}
+}
+
+// ---------------------------------------------------------------------------
+// Per-system scheduling bookkeeping
+
+/// A boxed system plus its schedule configuration and the world tick
+/// observed at the end of its last run.
+pub(crate) struct ScheduledSystem {
+    pub(crate) system: Box<dyn System>,
+    pub(crate) last_run: u64,
+    labels: Vec<&'static str>,
+    sets: Vec<&'static str>,
+    before: Vec<&'static str>,
+    after: Vec<&'static str>,
+    conditions: Vec<RunCondition>,
+}
+
+impl ScheduledSystem {
+    pub(crate) fn new(config: SystemConfig) -> Self {
+        ScheduledSystem {
+            system: config.system,
+            last_run: 0,
+            labels: config.labels,
+            sets: config.sets,
+            before: config.before,
+            after: config.after,
+            conditions: config.conditions,
+        }
+    }
+
+    fn order_node(&self) -> OrderNode<'_> {
+        OrderNode {
+            name: self.system.name(),
+            labels: &self.labels,
+            sets: &self.sets,
+            before: &self.before,
+            after: &self.after,
+        }
+    }
+
+    /// Evaluate the system's own and its sets' run conditions.
+    pub(crate) fn should_run(&mut self, sets: &mut SetRegistry, world: &World, resources: &ResourceMap) -> bool {
+        conditions_hold(&mut self.conditions, world, resources)
+            && sets.should_run(&self.sets, world, resources)
+    }
+
+    /// Run the system with [`World::last_change_tick`] scoped to its
+    /// previous run, so its own changes are not reported back to it.
+    pub(crate) fn run(&mut self, world: &mut World, resources: &mut ResourceMap) {
+        let outer = std::mem::replace(&mut world.last_change_tick, self.last_run);
+        self.system.run(world, resources);
+        self.last_run = world.change_tick();
+        world.last_change_tick = outer;
+    }
+}
+
+/// Reorder `systems` so every ordering constraint is satisfied.
+pub(crate) fn sort_systems(systems: &mut Vec<ScheduledSystem>, sets: &SetRegistry) -> Result<(), ScheduleError> {
+    let nodes: Vec<OrderNode<'_>> = systems.iter().map(ScheduledSystem::order_node).collect();
+    let names: Vec<&'static str> = nodes.iter().map(|n| n.name).collect();
+    let order = topological_order(&names, &dependencies(&nodes, sets))?;
+    drop(nodes);
+
+    let mut slots: Vec<Option<ScheduledSystem>> = systems.drain(..).map(Some).collect();
+    systems.extend(order.into_iter().map(|i| slots[i].take().unwrap()));
+    Ok(())
+}
+
+/// Run a stage's systems in order, then apply their deferred commands.
+fn run_stage_systems(
+    systems: &mut [ScheduledSystem],
+    sets: &mut SetRegistry,
+    world: &mut World,
+    resources: &mut ResourceMap,
+) {
+    sets.begin_run();
+    for s in systems.iter_mut() {
+        if s.should_run(sets, world, resources) {
+            s.run(world, resources);
+        }
+    }
+    for s in systems.iter_mut() {
+        s.system.apply_deferred(world);
+    }
 }
 
 // ---------------------------------------------------------------------------
 
 /// Simple sequential system scheduler.
 ///
-/// Systems run in the order they were added.  Each system receives exclusive
-/// access to `World` and `ResourceMap`.
+/// Systems run in the order they were added, adjusted by any ordering
+/// constraints.  Each system receives exclusive access to `World` and
+/// `ResourceMap`.
 pub struct SystemScheduler {
-    systems: Vec<Box<dyn System>>,
+    systems: Vec<ScheduledSystem>,
+    sets: SetRegistry,
+    dirty: bool,
 }
 
 impl Default for SystemScheduler {
@@ -73,20 +193,43 @@ impl SystemScheduler {
     pub fn new() -> Self {
         SystemScheduler {
             systems: Vec::new(),
+            sets: SetRegistry::default(),
+            dirty: false,
         }
     }
 
-    /// Append a system. Systems run in insertion order.
-    pub fn add<S: System>(&mut self, system: S) -> &mut Self {
-        self.systems.push(Box::new(system));
+    /// Append a system (optionally configured, see [`IntoSystemConfig`]).
+    pub fn add(&mut self, system: impl IntoSystemConfig) -> &mut Self {
+        self.systems.push(ScheduledSystem::new(system.into_config()));
+        self.dirty = true;
+        self
+    }
+
+    /// Configure a [`SystemSet`].  Repeated calls for one name accumulate.
+    pub fn configure_set(&mut self, set: SystemSet) -> &mut Self {
+        self.sets.configure(set);
+        self.dirty = true;
         self
     }
 
-    /// Run all systems once.
+    /// Resolve ordering constraints now instead of on the next run.
+    pub fn build(&mut self) -> Result<(), ScheduleError> {
+        if self.dirty {
+            sort_systems(&mut self.systems, &self.sets)?;
+            self.dirty = false;
+        }
+        Ok(())
+    }
+
+    /// Run all systems once, then apply their deferred commands.
+    ///
+    /// # Panics
+    /// Panics if the ordering constraints contain a cycle.
     pub fn run_all(&mut self, world: &mut World, resources: &mut ResourceMap) {
-        for system in &mut self.systems {
-            system.run(world, resources);
+        if let Err(e) = self.build() {
+            panic!("{e}");
         }
+        run_stage_systems(&mut self.systems, &mut self.sets, world, resources);
     }
 
     /// Number of registered systems.
@@ -101,6 +244,7 @@ impl SystemScheduler {
     /// Remove all systems.
     pub fn clear(&mut self) {
         self.systems.clear();
+        self.dirty = false;
     }
 }
 
@@ -150,18 +294,23 @@ pub enum Stage {
     /// Runs before gameplay logic.  Ideal for time/input systems that must
     /// have fresh data before other systems consume it.
     PreUpdate = 0,
-    /// Main gameplay logic — movement, AI, physics integration.
-    Update = 1,
+    /// Frame-rate independent simulation — movement, physics integration.
+    /// Driven by the fixed-timestep accumulator (see [`crate::fixed`]), so it
+    /// may run zero or several times per frame.
+    FixedUpdate = 1,
+    /// Main gameplay logic — AI, animation, behaviours.
+    Update = 2,
     /// Fixup pass after Update — hierarchy propagation, constraint solving.
-    PostUpdate = 2,
+    PostUpdate = 3,
     /// CPU-side render preparation — visibility culling, packet building.
-    Render = 3,
+    Render = 4,
 }
 
 impl Stage {
     /// All stages in execution order.
-    pub const ALL: [Stage; 4] = [
+    pub const ALL: [Stage; 5] = [
         Stage::PreUpdate,
+        Stage::FixedUpdate,
         Stage::Update,
         Stage::PostUpdate,
         Stage::Render,
@@ -171,8 +320,10 @@ impl Stage {
 /// Stage-aware system scheduler.
 ///
 /// Systems are grouped into [`Stage`]s and always execute in stage order
-/// (`PreUpdate` → `Update` → `PostUpdate` → `Render`).  Within a stage,
-/// systems run in insertion order.
+/// (`PreUpdate` → `FixedUpdate` → `Update` → `PostUpdate` → `Render`).
+/// Within a stage, systems run in insertion order unless ordering constraints
+/// say otherwise.  Deferred commands are applied after each stage (after each
+/// fixed step for `FixedUpdate`).
 ///
 /// # Example
 /// ```rust
@@ -189,10 +340,14 @@ impl Stage {
 /// sched.run_all(&mut world, &mut res); // "pre" runs before "tick"
 /// ```
 pub struct StagedScheduler {
-    pre_update: Vec<Box<dyn System>>,
-    update: Vec<Box<dyn System>>,
-    post_update: Vec<Box<dyn System>>,
-    render: Vec<Box<dyn System>>,
+    pre_update: Vec<ScheduledSystem>,
+    fixed_update: Vec<ScheduledSystem>,
+    update: Vec<ScheduledSystem>,
+    post_update: Vec<ScheduledSystem>,
+    render: Vec<ScheduledSystem>,
+    sets: SetRegistry,
+    dirty: bool,
+    fixed: FixedTimestep,
 }
 
 impl Default for StagedScheduler {
@@ -205,21 +360,22 @@ impl StagedScheduler {
     pub fn new() -> Self {
         StagedScheduler {
             pre_update: Vec::new(),
+            fixed_update: Vec::new(),
             update: Vec::new(),
             post_update: Vec::new(),
             render: Vec::new(),
+            sets: SetRegistry::default(),
+            dirty: false,
+            fixed: FixedTimestep::default(),
         }
     }
 
-    /// Register a system in the given stage.
-    pub fn add<S: System>(&mut self, stage: Stage, system: S) -> &mut Self {
-        let boxed: Box<dyn System> = Box::new(system);
-        match stage {
-            Stage::PreUpdate => self.pre_update.push(boxed),
-            Stage::Update => self.update.push(boxed),
-            Stage::PostUpdate => self.post_update.push(boxed),
-            Stage::Render => self.render.push(boxed),
-        }
+    /// Register a system (optionally configured, see [`IntoSystemConfig`])
+    /// in the given stage.
+    pub fn add(&mut self, stage: Stage, system: impl IntoSystemConfig) -> &mut Self {
+        let system = ScheduledSystem::new(system.into_config());
+        self.stage_mut(stage).push(system);
+        self.dirty = true;
         self
     }
 
@@ -228,47 +384,119 @@ impl StagedScheduler {
     /// Used by the plugin system, where systems are collected as
     /// `Box<dyn System>` before the scheduler is constructed.
     pub fn add_boxed(&mut self, stage: Stage, system: Box<dyn System>) -> &mut Self {
-        match stage {
-            Stage::PreUpdate => self.pre_update.push(system),
-            Stage::Update => self.update.push(system),
-            Stage::PostUpdate => self.post_update.push(system),
-            Stage::Render => self.render.push(system),
-        }
+        self.add(stage, system)
+    }
+
+    /// Configure a [`SystemSet`] shared by all stages.  Repeated calls for
+    /// one name accumulate.
+    pub fn configure_set(&mut self, set: SystemSet) -> &mut Self {
+        self.sets.configure(set);
+        self.dirty = true;
         self
     }
 
-    /// Run all stages in order.
-    pub fn run_all(&mut self, world: &mut World, resources: &mut ResourceMap) {
-        for s in &mut self.pre_update {
-            s.run(world, resources);
-        }
-        for s in &mut self.update {
-            s.run(world, resources);
+    /// Set the `FixedUpdate` step (seconds) and the maximum number of steps
+    /// run per frame.  Resets the accumulator.
+    ///
+    /// # Panics
+    /// Panics if `step` is not positive.
+    pub fn set_fixed_timestep(&mut self, step: f32, max_steps: u32) -> &mut Self {
+        self.fixed = FixedTimestep::new(step, max_steps);
+        self
+    }
+
+    /// Current fixed-timestep accumulator state.
+    pub fn fixed_timestep(&self) -> &FixedTimestep {
+        &self.fixed
+    }
+
+    fn stage_mut(&mut self, stage: Stage) -> &mut Vec<ScheduledSystem> {
+        match stage {
+            Stage::PreUpdate => &mut self.pre_update,
+            Stage::FixedUpdate => &mut self.fixed_update,
+            Stage::Update => &mut self.update,
+            Stage::PostUpdate => &mut self.post_update,
+            Stage::Render => &mut self.render,
         }
-        for s in &mut self.post_update {
-            s.run(world, resources);
+    }
+
+    /// Resolve ordering constraints now instead of on the next run, e.g. to
+    /// report a cycle at startup rather than panicking mid-frame.
+    pub fn build(&mut self) -> Result<(), ScheduleError> {
+        if self.dirty {
+            let stages = [
+                &mut self.pre_update,
+                &mut self.fixed_update,
+                &mut self.update,
+                &mut self.post_update,
+                &mut self.render,
+            ];
+            for stage in stages {
+                sort_systems(stage, &self.sets)?;
+            }
+            self.dirty = false;
         }
-        for s in &mut self.render {
-            s.run(world, resources);
+        Ok(())
+    }
+
+    /// Run all stages in order, with `FixedUpdate` driven by
+    /// [`run_fixed_update`](Self::run_fixed_update).
+    ///
+    /// # Panics
+    /// Panics if the ordering constraints of a stage contain a cycle.
+    pub fn run_all(&mut self, world: &mut World, resources: &mut ResourceMap) {
+        self.run_stage(Stage::PreUpdate, world, resources);
+        self.run_fixed_update(world, resources);
+        self.run_stage(Stage::Update, world, resources);
+        self.run_stage(Stage::PostUpdate, world, resources);
+        self.run_stage(Stage::Render, world, resources);
+    }
+
+    /// Feed [`FixedTime::delta`] into the accumulator, publish the step,
+    /// step count and interpolation alpha, then run `FixedUpdate` once per
+    /// whole step.  Returns the number of steps run.
+    pub fn run_fixed_update(&mut self, world: &mut World, resources: &mut ResourceMap) -> u32 {
+        let fixed_time = resources.get_or_insert_default::<FixedTime>();
+        let steps = self.fixed.accumulate(fixed_time.delta);
+        *fixed_time = FixedTime {
+            delta: 0.0,
+            step: self.fixed.step(),
+            steps,
+            alpha: self.fixed.alpha(),
+        };
+        for _ in 0..steps {
+            self.run_stage(Stage::FixedUpdate, world, resources);
         }
+        steps
     }
 
     /// Run only the systems belonging to a single stage.
+    ///
+    /// `Stage::FixedUpdate` runs exactly one step, bypassing the accumulator.
+    ///
+    /// # Panics
+    /// Panics if the ordering constraints of a stage contain a cycle.
     pub fn run_stage(&mut self, stage: Stage, world: &mut World, resources: &mut ResourceMap) {
+        if let Err(e) = self.build() {
+            panic!("{e}");
+        }
         let systems = match stage {
             Stage::PreUpdate => &mut self.pre_update,
+            Stage::FixedUpdate => &mut self.fixed_update,
             Stage::Update => &mut self.update,
             Stage::PostUpdate => &mut self.post_update,
             Stage::Render => &mut self.render,
         };
-        for s in systems {
-            s.run(world, resources);
-        }
+        run_stage_systems(systems, &mut self.sets, world, resources);
     }
 
     /// Total number of registered systems across all stages.
     pub fn len(&self) -> usize {
-        self.pre_update.len() + self.update.len() + self.post_update.len() + self.render.len()
+        self.pre_update.len()
+            + self.fixed_update.len()
+            + self.update.len()
+            + self.post_update.len()
+            + self.render.len()
     }
 
     pub fn is_empty(&self) -> bool {
@@ -278,9 +506,11 @@ impl StagedScheduler {
     /// Remove all systems from all stages.
     pub fn clear(&mut self) {
         self.pre_update.clear();
+        self.fixed_update.clear();
         self.update.clear();
         self.post_update.clear();
         self.render.clear();
+        self.dirty = false;
     }
 }
 
@@ -315,7 +545,11 @@ impl StagedScheduler {
 
 #[cfg(feature = "parallel")]
 pub mod parallel {
-    use super::{ResourceMap, System, World};
+    use super::{
+        dependencies, topological_order, OrderNode, ResourceMap, ScheduleError, ScheduledSystem,
+        SetRegistry, System, SystemConfig, SystemSet, World,
+    };
+    use crate::system_param::SystemParam;
     use std::any::TypeId;
 
     // ── SystemAccess ─────────────────────────────────────────────────────────
@@ -385,6 +619,17 @@ pub mod parallel {
             }
         }
 
+        /// Build from the parameter set of a function system, e.g.
+        /// `SystemMeta::of_params::<(QueryParam<&mut Pos, With<Enemy>>,)>()`.
+        pub fn of_params<P: SystemParam>() -> Self {
+            Self {
+                reads: P::reads(),
+                writes: P::writes(),
+                res_reads: P::res_reads(),
+                res_writes: P::res_writes(),
+            }
+        }
+
         /// Returns `true` if `self` and `other` cannot run concurrently.
         ///
         /// Two systems conflict when at least one of them writes a type that the
@@ -440,7 +685,7 @@ pub mod parallel {
     // ── Entry (system + its meta) ─────────────────────────────────────────────
 
     struct Entry {
-        system: Box<dyn System>,
+        system: ScheduledSystem,
         meta: SystemMeta,
     }
 
@@ -470,6 +715,7 @@ pub mod parallel {
     pub struct ParallelScheduler {
         /// Each inner `Vec` is one conflict-free batch.
         batches: Vec<Vec<Entry>>,
+        sets: SetRegistry,
     }
 
     impl ParallelScheduler {
@@ -481,22 +727,65 @@ pub mod parallel {
         /// order as a tie-breaker (same semantics as a greedy list-scheduling
         /// algorithm).
         pub fn build(systems: Vec<(Box<dyn System>, SystemMeta)>) -> Self {
-            let mut batches: Vec<Vec<Entry>> = Vec::new();
+            let systems = systems
+                .into_iter()
+                .map(|(system, meta)| (SystemConfig::new(system), meta))
+                .collect();
+            Self::build_with(systems, Vec::new())
+                .expect("systems without ordering constraints cannot form a cycle")
+        }
 
-            'outer: for (system, meta) in systems {
-                // Try to append to an existing batch.
-                for batch in &mut batches {
-                    let fits = batch.iter().all(|e| !e.meta.conflicts_with(&meta));
-                    if fits {
-                        batch.push(Entry { system, meta });
-                        continue 'outer;
+        /// Like [`build`](Self::build), but honouring the labels, `before` /
+        /// `after` constraints, set memberships and run conditions of each
+        /// [`SystemConfig`].
+        ///
+        /// A system is placed in a later batch than everything it must run
+        /// after; within that bound the greedy earliest-fit rule applies.
+        pub fn build_with(
+            systems: Vec<(SystemConfig, SystemMeta)>,
+            sets: Vec<SystemSet>,
+        ) -> Result<Self, ScheduleError> {
+            let mut registry = SetRegistry::default();
+            for set in sets {
+                registry.configure(set);
+            }
+
+            let mut entries: Vec<Option<Entry>> = systems
+                .into_iter()
+                .map(|(config, meta)| Some(Entry { system: ScheduledSystem::new(config), meta }))
+                .collect();
+            let preds = {
+                let nodes: Vec<OrderNode<'_>> = entries
+                    .iter()
+                    .map(|e| e.as_ref().unwrap().system.order_node())
+                    .collect();
+                dependencies(&nodes, &registry)
+            };
+            let names: Vec<&'static str> =
+                entries.iter().map(|e| e.as_ref().unwrap().system.system.name()).collect();
+            let order = topological_order(&names, &preds)?;
+
+            let mut batches: Vec<Vec<Entry>> = Vec::new();
+            let mut batch_of = vec![0usize; entries.len()];
+            for i in order {
+                let entry = entries[i].take().unwrap();
+                // Must land strictly after every predecessor's batch.
+                let first = preds[i].iter().map(|&p| batch_of[p] + 1).max().unwrap_or(0);
+                let slot = (first..batches.len())
+                    .find(|&b| batches[b].iter().all(|e| !e.meta.conflicts_with(&entry.meta)));
+                let b = match slot {
+                    Some(b) => b,
+                    None => {
+                        // No existing batch fits — start a new one.
+                        batches.push(Vec::new());
+                        batches.len() - 1
                     }
-                }
-                // No existing batch fits — start a new one.
-                batches.push(vec![Entry { system, meta }]);
+                };
+                batches[b].push(entry);
+                batch_of[i] = b;
             }
 
-            Self { batches }
+            Ok(Self { batches, sets: registry })
         }
 
         /// Run all batches in sequence.  Systems within each batch are
@@ -516,15 +805,22 @@ pub mod parallel {
         /// The rayon thread-pool is still used here as a placeholder so that
         /// callers can adopt this API today without breaking changes later.
         pub fn run_all(&mut self, world: &mut World, resources: &mut ResourceMap) {
+            self.sets.begin_run();
             for batch in &mut self.batches {
                 // All systems in this batch are non-conflicting.  For now we
                 // run them sequentially; a future PR will switch to
                 // `rayon::scope` once `System` gains a `run_parallel` method
                 // that accepts shared `&World` / `&ResourceMap` references.
                 for entry in batch.iter_mut() {
-                    entry.system.run(world, resources);
+                    if entry.system.should_run(&mut self.sets, world, resources) {
+                        entry.system.run(world, resources);
+                    }
                 }
             }
+            // Structural changes wait until every batch has run.
+            for entry in self.batches.iter_mut().flatten() {
+                entry.system.system.apply_deferred(world);
+            }
         }
 
         /// Total number of systems registered across all batches.
@@ -590,4 +886,321 @@ mod tests {
         let val = world.query::<Counter>().next().unwrap().1 .0;
         assert_eq!(val, 10);
     }
+
+    #[test]
+    fn systems_see_changes_since_their_last_run() {
+        use crate::query::{Changed, Query};
+        use std::sync::{Arc, Mutex};
+
+        let mut world = World::new();
+        let mut res = ResourceMap::new();
+        let e = world.spawn((Counter(0),));
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        let mut sched = StagedScheduler::new();
+        sched.add(
+            Stage::PostUpdate,
+            fn_system("observe", move |w, _| {
+                let n = Query::<&Counter, Changed<Counter>>::new(w).len();
+                sink.lock().unwrap().push(n);
+            }),
+        );
+
+        sched.run_all(&mut world, &mut res); // spawn counts as a change
+        sched.run_all(&mut world, &mut res); // nothing touched
+        world.get_mut::<Counter>(e).unwrap().0 += 1;
+        sched.run_all(&mut world, &mut res); // mutated between runs
+
+        assert_eq!(*seen.lock().unwrap(), vec![1, 0, 1]);
+        // The scheduler restores the outer baseline after each system.
+        assert_eq!(world.last_change_tick(), 0);
+    }
+
+    #[test]
+    fn fn_systems_take_their_baseline_from_the_caller() {
+        use crate::fn_system::IntoSystem;
+        use crate::query::{Changed, Query};
+        use crate::system_param::{QueryParam, ResMut, ResMutParam};
+
+        #[derive(Default)]
+        struct Seen(Vec<usize>);
+
+        fn changed(q: Query<'_, &'static Counter, Changed<Counter>>, mut seen: ResMut<Seen>) {
+            seen.0.push(q.len());
+        }
+        type Params = (QueryParam<&'static Counter, Changed<Counter>>, ResMutParam<Seen>);
+
+        let mut world = World::new();
+        let mut res = ResourceMap::new();
+        res.insert(Seen::default());
+        world.spawn((Counter(0),));
+
+        let mut sched = StagedScheduler::new();
+        sched.add(Stage::Update, IntoSystem::<Params>::into_system(changed));
+        sched.run_all(&mut world, &mut res);
+        sched.run_all(&mut world, &mut res);
+
+        // Outside a scheduler the world's own baseline (tick 0) applies.
+        let mut direct = IntoSystem::<Params>::into_system(changed);
+        direct.run(&mut world, &mut res);
+        direct.run(&mut world, &mut res);
+
+        assert_eq!(res.get::<Seen>().unwrap().0, vec![1, 0, 1, 1]);
+    }
+
+    #[test]
+    fn commands_are_applied_at_stage_boundaries() {
+        use crate::commands::Commands;
+        use crate::fn_system::IntoSystem;
+        use crate::query::Query;
+        use crate::system_param::{QueryParam, ResMut, ResMutParam};
+
+        #[derive(Default)]
+        struct Seen(Vec<(&'static str, usize)>);
+
+        fn spawn_counter(mut commands: Commands) {
+            commands.spawn((Counter(0),));
+        }
+        fn count_update(q: Query<'_, &'static Counter>, mut seen: ResMut<Seen>) {
+            seen.0.push(("update", q.len()));
+        }
+        fn count_post(q: Query<'_, &'static Counter>, mut seen: ResMut<Seen>) {
+            seen.0.push(("post", q.len()));
+        }
+        type CountParams = (QueryParam<&'static Counter>, ResMutParam<Seen>);
+
+        let mut world = World::new();
+        let mut res = ResourceMap::new();
+        res.insert(Seen::default());
+
+        let mut sched = StagedScheduler::new();
+        sched.add(
+            Stage::Update,
+            IntoSystem::<(Commands<'static>,)>::into_system(spawn_counter),
+        );
+        sched.add(Stage::Update, IntoSystem::<CountParams>::into_system(count_update));
+        sched.add(Stage::PostUpdate, IntoSystem::<CountParams>::into_system(count_post));
+        sched.run_all(&mut world, &mut res);
+
+        assert_eq!(res.get::<Seen>().unwrap().0, vec![("update", 0), ("post", 1)]);
+        assert_eq!(world.len(), 1);
+    }
+
+    #[test]
+    fn queries_see_archetypes_spawned_after_their_first_run() {
+        use crate::commands::Commands;
+        use crate::fn_system::IntoSystem;
+        use crate::query::Query;
+        use crate::system_param::{QueryParam, ResMut, ResMutParam};
+
+        #[derive(Default)]
+        struct Seen(Vec<usize>);
+
+        fn spawn_counter(mut commands: Commands) {
+            commands.spawn((Counter(0),));
+        }
+        fn count(q: Query<'_, &'static Counter>, mut seen: ResMut<Seen>) {
+            seen.0.push(q.len());
+        }
+        type CountParams = (QueryParam<&'static Counter>, ResMutParam<Seen>);
+
+        let mut world = World::new();
+        let mut res = ResourceMap::new();
+        res.insert(Seen::default());
+
+        // The reader runs a stage ahead of the spawner, so it caches its
+        // archetype list before any `Counter` archetype exists.
+        let mut sched = StagedScheduler::new();
+        sched.add(Stage::PreUpdate, IntoSystem::<CountParams>::into_system(count));
+        sched.add(
+            Stage::Update,
+            IntoSystem::<(Commands<'static>,)>::into_system(spawn_counter),
+        );
+        for _ in 0..3 {
+            sched.run_all(&mut world, &mut res);
+        }
+        world.clear();
+        for _ in 0..2 {
+            sched.run_all(&mut world, &mut res);
+        }
+
+        assert_eq!(res.get::<Seen>().unwrap().0, vec![0, 1, 2, 0, 1]);
+        assert_eq!(world.len(), 2);
+    }
+
+    fn recorder(
+        name: &'static str,
+        log: &std::sync::Arc<std::sync::Mutex<Vec<&'static str>>>,
+    ) -> FnSystem<impl FnMut(&mut World, &mut ResourceMap) + Send + 'static> {
+        let log = log.clone();
+        fn_system(name, move |_, _| log.lock().unwrap().push(name))
+    }
+
+    #[test]
+    fn labels_and_sets_order_systems_within_a_stage() {
+        use std::sync::{Arc, Mutex};
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut sched = StagedScheduler::new();
+        // Registered first, but a later "plugin" asks to run after physics.
+        sched.add(Stage::Update, recorder("render_prep", &log).after("physics"));
+        sched.add(Stage::Update, recorder("integrate", &log).in_set("physics"));
+        sched.add(Stage::Update, recorder("collide", &log).in_set("physics"));
+        sched.add(Stage::Update, recorder("input", &log).label("input"));
+        sched.configure_set(SystemSet::new("physics").after("input"));
+        sched.add(Stage::PreUpdate, recorder("time", &log));
+
+        sched.run_all(&mut World::new(), &mut ResourceMap::new());
+        assert_eq!(
+            *log.lock().unwrap(),
+            ["time", "input", "integrate", "collide", "render_prep"]
+        );
+    }
+
+    #[test]
+    fn fixed_update_runs_once_per_accumulated_step() {
+        use crate::fixed::FixedTime;
+        use std::sync::{Arc, Mutex};
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut sched = StagedScheduler::new();
+        sched.set_fixed_timestep(0.1, 3);
+        sched.add(Stage::FixedUpdate, recorder("fixed", &log));
+        sched.add(
+            Stage::PreUpdate,
+            fn_system("clock", |_, res| res.get_mut::<FixedTime>().unwrap().delta = 0.25),
+        );
+
+        let mut world = World::new();
+        let mut res = ResourceMap::new();
+        res.insert(FixedTime::default());
+
+        sched.run_all(&mut world, &mut res);
+        let fixed = *res.get::<FixedTime>().unwrap();
+        assert_eq!((fixed.steps, fixed.step), (2, 0.1));
+        assert!((fixed.alpha - 0.5).abs() < 1e-4);
+
+        // 0.05 left over + 0.25 = 3 steps.
+        sched.run_all(&mut world, &mut res);
+        assert_eq!(res.get::<FixedTime>().unwrap().steps, 3);
+        assert_eq!(log.lock().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn ordering_cycles_are_reported() {
+        let log = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut sched = StagedScheduler::new();
+        sched.add(Stage::Update, recorder("a", &log).label("a").before("b"));
+        sched.add(Stage::Update, recorder("b", &log).label("b").before("a"));
+
+        let err = sched.build().unwrap_err();
+        assert_eq!(err, ScheduleError::Cycle(vec!["b", "a"]));
+    }
+
+    #[test]
+    fn run_conditions_gate_systems_and_sets() {
+        use crate::schedule::{not, resource_equals};
+        use std::sync::{Arc, Mutex};
+
+        #[derive(PartialEq)]
+        struct Paused(bool);
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut sched = SystemScheduler::new();
+        sched.add(recorder("menu", &log).run_if(resource_equals(Paused(true))));
+        sched.add(recorder("gameplay", &log).in_set("sim"));
+        sched.configure_set(SystemSet::new("sim").run_if(not(resource_equals(Paused(true)))));
+
+        let mut world = World::new();
+        let mut res = ResourceMap::new();
+        res.insert(Paused(false));
+        sched.run_all(&mut world, &mut res);
+        res.insert(Paused(true));
+        sched.run_all(&mut world, &mut res);
+
+        assert_eq!(*log.lock().unwrap(), ["gameplay", "menu"]);
+    }
+
+    #[test]
+    fn set_conditions_are_evaluated_once_per_run() {
+        use std::sync::{Arc, Mutex};
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut sched = SystemScheduler::new();
+        sched.add(recorder("a", &log).in_set("every_other"));
+        sched.add(recorder("b", &log).in_set("every_other"));
+        let mut run = false;
+        sched.configure_set(SystemSet::new("every_other").run_if(move |_, _| {
+            run = !run;
+            run
+        }));
+
+        let mut world = World::new();
+        let mut res = ResourceMap::new();
+        for _ in 0..3 {
+            sched.run_all(&mut world, &mut res);
+        }
+
+        assert_eq!(*log.lock().unwrap(), ["a", "b", "a", "b"]);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_batches_respect_ordering_and_conditions() {
+        use crate::system_param::QueryParam;
+        use parallel::{ParallelScheduler, SystemMeta};
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Clone)]
+        struct Other;
+        impl Component for Other {}
+
+        struct Skip;
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let reads_counter = SystemMeta::of_params::<(QueryParam<&'static Counter>,)>();
+        let reads_other = SystemMeta::of_params::<(QueryParam<&'static Other>,)>();
+
+        let mut sched = ParallelScheduler::build_with(
+            vec![
+                (recorder("late", &log).after("early"), reads_counter.clone()),
+                (recorder("early", &log).label("early"), reads_other.clone()),
+                (
+                    recorder("skipped", &log).run_if(|_, res| res.get::<Skip>().is_none()),
+                    reads_other,
+                ),
+            ],
+            Vec::new(),
+        )
+        .unwrap();
+        // Read-only systems would share one batch; the constraint splits them.
+        assert_eq!(sched.batch_count(), 2);
+
+        let mut res = ResourceMap::new();
+        res.insert(Skip);
+        sched.run_all(&mut World::new(), &mut res);
+        assert_eq!(*log.lock().unwrap(), ["early", "late"]);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn archetype_filters_do_not_cause_conflicts() {
+        use crate::query::With;
+        use crate::system_param::QueryParam;
+        use parallel::SystemMeta;
+
+        #[derive(Clone)]
+        struct Enemy;
+        impl Component for Enemy {}
+
+        // Reads `Counter` only on entities with `Enemy`; never touches `Enemy` data.
+        let filtered = SystemMeta::of_params::<(QueryParam<&'static Counter, With<Enemy>>,)>();
+        let enemy_writer = SystemMeta::of_params::<(QueryParam<&'static mut Enemy>,)>();
+        assert!(!filtered.conflicts_with(&enemy_writer));
+
+        // Data that is actually fetched still conflicts with writers.
+        let counter_writer = SystemMeta::of_params::<(QueryParam<&'static mut Counter>,)>();
+        assert!(filtered.conflicts_with(&counter_writer));
+    }
 }
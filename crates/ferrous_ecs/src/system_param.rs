@@ -9,9 +9,10 @@
 //!
 //! | Marker type | Param in fn | Borrows |
 //! |-------------|-------------|---------|
-//! | `QueryParam<Q>` | `Query<'_, Q>` | `&World` |
+//! | `QueryParam<Q, F>` | `Query<'_, Q, F>` | `&World` |
 //! | `ResParam<T>` | `Res<'_, T>` | `&ResourceMap` |
 //! | `ResMutParam<T>` | `ResMut<'_, T>` | `&mut ResourceMap` |
+//! | `Commands<'_>` | `Commands<'_>` | reserves via `&World`; applied later |
 //!
 //! ## Why marker types?
 //!
@@ -24,7 +25,8 @@
 use std::any::TypeId;
 use std::marker::PhantomData;
 
-use crate::query::{Query, WorldQuery};
+use crate::archetype::ArchetypeGeneration;
+use crate::query::{matching_archetypes, Query, QueryFilter, WorldQuery};
 use crate::resource::ResourceMap;
 use crate::world::World;
 
@@ -69,6 +71,14 @@ pub unsafe trait SystemParam {
     fn res_reads() -> Vec<TypeId> { vec![] }
     /// Resource types this param writes.
     fn res_writes() -> Vec<TypeId> { vec![] }
+
+    /// Apply work deferred during `fetch` (e.g. queued [`Commands`]).
+    ///
+    /// Called by the scheduler at the next stage boundary, with exclusive
+    /// world access.
+    ///
+    /// [`Commands`]: crate::commands::Commands
+    fn apply(_state: &mut Self::State, _world: &mut World) {}
 }
 
 // ---------------------------------------------------------------------------
@@ -85,33 +95,57 @@ unsafe impl SystemParam for () {
 }
 
 // ---------------------------------------------------------------------------
-// QueryParam<Q> — wraps Query<'_, Q>
+// QueryParam<Q, F> — wraps Query<'_, Q, F>
 
-/// Zero-sized marker that identifies a `Query<'_, Q>` parameter.
-pub struct QueryParam<Q>(PhantomData<Q>);
+/// Zero-sized marker that identifies a `Query<'_, Q, F>` parameter.
+pub struct QueryParam<Q, F = ()>(PhantomData<(Q, F)>);
 
-unsafe impl<Q> SystemParam for QueryParam<Q>
+/// Cached state of a [`QueryParam`]: the matching archetypes and the
+/// archetype generation they were collected at.
+pub struct QueryParamState {
+    archetypes: Vec<usize>,
+    generation: ArchetypeGeneration,
+}
+
+unsafe impl<Q, F> SystemParam for QueryParam<Q, F>
 where
     Q: WorldQuery<State = Vec<usize>> + 'static,
+    F: QueryFilter + 'static,
 {
-    type State = Vec<usize>;
-    type Item<'w> = Query<'w, Q>;
+    type State = QueryParamState;
+    type Item<'w> = Query<'w, Q, F>;
 
-    fn init(world: &World, _resources: &ResourceMap) -> Vec<usize> {
-        Q::init(world)
+    fn init(world: &World, _resources: &ResourceMap) -> QueryParamState {
+        QueryParamState {
+            archetypes: matching_archetypes::<Q, F>(world),
+            generation: world.archetype_generation(),
+        }
     }
 
+    /// Archetypes created since the previous fetch (e.g. by `Commands`
+    /// applied at a stage boundary) are checked and added to the cached list.
     #[inline]
     unsafe fn fetch<'w>(
-        state: &'w mut Vec<usize>,
+        state: &'w mut QueryParamState,
         world: &'w World,
         _resources: &'w ResourceMap,
-    ) -> Query<'w, Q> {
-        Query::from_state(world, state.clone())
+    ) -> Query<'w, Q, F> {
+        let now = world.archetype_generation();
+        if now.epoch != state.generation.epoch {
+            state.archetypes = matching_archetypes::<Q, F>(world);
+        } else if now.len > state.generation.len {
+            let archetypes = &world.archetypes.archetypes;
+            state.archetypes.extend(
+                (state.generation.len..now.len)
+                    .filter(|&i| Q::matches(&archetypes[i]) && F::matches(&archetypes[i])),
+            );
+        }
+        state.generation = now;
+        Query::from_state(world, state.archetypes.clone())
     }
 
-    fn reads() -> Vec<TypeId> { Q::reads() }
-    fn writes() -> Vec<TypeId> { Q::writes() }
+    fn reads() -> Vec<TypeId> { Query::<Q, F>::reads() }
+    fn writes() -> Vec<TypeId> { Query::<Q, F>::writes() }
 }
 
 // ---------------------------------------------------------------------------
@@ -269,6 +303,12 @@ macro_rules! impl_system_param_tuple {
                 $( v.extend($name::res_writes()); )+
                 v
             }
+
+            #[allow(non_snake_case)]
+            fn apply(state: &mut Self::State, world: &mut World) {
+                let ( $($name,)+ ) = state;
+                $( $name::apply($name, world); )+
+            }
         }
     };
 }
@@ -321,4 +361,32 @@ mod tests {
         assert!(ResParam::<GameTime>::res_reads().contains(&TypeId::of::<GameTime>()));
         assert!(ResMutParam::<GameTime>::res_writes().contains(&TypeId::of::<GameTime>()));
     }
+
+    #[test]
+    fn filtered_query_param_only_reports_fetched_components() {
+        use crate::component::Component;
+        use crate::query::{With, Without};
+
+        #[derive(Clone)]
+        struct Pos;
+        impl Component for Pos {}
+        #[derive(Clone)]
+        struct Enemy;
+        impl Component for Enemy {}
+        #[derive(Clone)]
+        struct Dead;
+        impl Component for Dead {}
+
+        type Param = QueryParam<&'static mut Pos, (With<Enemy>, Without<Dead>)>;
+        assert_eq!(Param::reads(), Vec::<TypeId>::new());
+        assert_eq!(Param::writes(), vec![TypeId::of::<Pos>()]);
+
+        let mut world = World::new();
+        world.spawn((Pos, Enemy));
+        world.spawn((Pos,));
+        let resources = ResourceMap::new();
+        let mut state = Param::init(&world, &resources);
+        let q = unsafe { Param::fetch(&mut state, &world, &resources) };
+        assert_eq!(q.len(), 1);
+    }
 }
@@ -1,13 +1,21 @@
 //! The ECS World — the central store for entities and components.
 //!
-//! `World` orchestrates the `EntityAllocator` and `ArchetypeStore`, providing
-//! a safe high-level API for spawning, despawning, and querying entities.
+//! `World` orchestrates the `EntityAllocator`, `ArchetypeStore` and
+//! `SparseSets`, providing a safe high-level API for spawning, despawning,
+//! and querying entities.  Every component method dispatches on the
+//! component's [`StorageType`], so callers never need to know where a type
+//! is stored.
 
 use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 
-use crate::archetype::ArchetypeStore;
-use crate::component::{Bundle, Component, ComponentInfo, ComponentSet};
+use crate::archetype::{ArchetypeGeneration, ArchetypeStore, ComponentTicks};
+use crate::component::{Bundle, Component, ComponentInfo, ComponentSet, StorageType};
 use crate::entity::{Entity, EntityAllocator};
+use crate::observer::{ComponentHooks, HookKind, Observers};
+use crate::relation::RelationHook;
+use crate::sparse_set::SparseSets;
 
 /// Central container for all ECS state.
 ///
@@ -18,9 +26,24 @@ use crate::entity::{Entity, EntityAllocator};
 pub struct World {
     pub(crate) entities: EntityAllocator,
     pub(crate) archetypes: ArchetypeStore,
-    /// Generation counter — incremented whenever the world structure changes.
-    /// Systems can use this to detect invalidated queries.
-    pub change_tick: u64,
+    /// Number of times [`clear`](Self::clear) replaced `archetypes`; see
+    /// [`ArchetypeGeneration`].
+    archetype_epoch: u64,
+    /// Storage of `StorageType::SparseSet` components.
+    pub(crate) sparse_sets: SparseSets,
+    /// Generation counter — incremented on every structural change and on
+    /// every mutable component access.  Stored ticks on component values are
+    /// taken from this counter (see [`ComponentTicks`]).
+    change_tick: AtomicU64,
+    /// Tick at which the currently running system last ran.  `Added` /
+    /// `Changed` query filters report values newer than this tick.
+    pub(crate) last_change_tick: u64,
+    /// Despawn hooks of every relation type used so far, in registration
+    /// order (see [`crate::relation`]).
+    pub(crate) relation_hooks: Vec<(TypeId, RelationHook)>,
+    /// Component lifecycle hooks (see [`crate::observer`]).
+    pub(crate) hooks: HashMap<TypeId, ComponentHooks>,
+    pub(crate) observers: Observers,
 }
 
 impl Default for World {
@@ -35,15 +58,83 @@ impl World {
         World {
             entities: EntityAllocator::new(),
             archetypes: ArchetypeStore::new(),
-            change_tick: 0,
+            archetype_epoch: 0,
+            sparse_sets: SparseSets::default(),
+            change_tick: AtomicU64::new(0),
+            last_change_tick: 0,
+            relation_hooks: Vec::new(),
+            hooks: HashMap::new(),
+            observers: Observers::default(),
         }
     }
 
     /// Clear the entire world, removing all entities and archetypes.
+    ///
+    /// Outstanding reservations are discarded.  No lifecycle hooks run;
+    /// entity observers are dropped, hooks and global observers are kept.
     pub fn clear(&mut self) {
         self.entities = EntityAllocator::new();
         self.archetypes = ArchetypeStore::new();
-        self.change_tick += 1;
+        self.archetype_epoch += 1;
+        self.sparse_sets = SparseSets::default();
+        self.observers.forget_entities();
+        self.increment_change_tick();
+    }
+
+    // -----------------------------------------------------------------------
+    // Change ticks
+
+    /// Current value of the world change tick.
+    ///
+    /// **Breaking change:** this used to be the public field
+    /// `World::change_tick: u64`.  The counter is now atomic so queries
+    /// borrowed from `&World` can advance it; replace reads of
+    /// `world.change_tick` with `world.change_tick()`, and direct writes with
+    /// [`increment_change_tick`](Self::increment_change_tick).
+    #[inline]
+    pub fn change_tick(&self) -> u64 {
+        self.change_tick.load(Ordering::Relaxed)
+    }
+
+    /// Advance the world change tick and return the new value.
+    ///
+    /// Takes `&self` so that queries built from a shared borrow can obtain
+    /// a fresh tick for the mutations they perform.
+    #[inline]
+    pub fn increment_change_tick(&self) -> u64 {
+        self.change_tick.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    /// Tick of the previous run of the system currently executing.
+    ///
+    /// Schedulers set this before running each system; outside of a
+    /// scheduler it is `0`, so every component counts as added and changed.
+    #[inline]
+    pub fn last_change_tick(&self) -> u64 {
+        self.last_change_tick
+    }
+
+    /// Current archetype generation, used by cached queries to pick up
+    /// archetypes created since they were built.
+    #[inline]
+    pub(crate) fn archetype_generation(&self) -> ArchetypeGeneration {
+        ArchetypeGeneration {
+            epoch: self.archetype_epoch,
+            len: self.archetypes.archetypes.len(),
+        }
+    }
+
+    /// Added/changed ticks of component `C` on `entity`.
+    ///
+    /// Returns `None` if the entity is dead or does not have `C`.
+    pub fn component_ticks<C: Component>(&self, entity: Entity) -> Option<ComponentTicks> {
+        let rec = self.entities.get(entity)?;
+        if C::STORAGE == StorageType::SparseSet {
+            return self.sparse_sets.get(TypeId::of::<C>())?.ticks(entity);
+        }
+        let arch_id = rec.archetype_id?;
+        let col = self.archetypes.archetypes[arch_id].column::<C>()?;
+        Some(col.ticks(rec.row))
     }
 
     // -----------------------------------------------------------------------
@@ -62,45 +153,100 @@ impl World {
     /// assert!(world.contains(e));
     /// ```
     pub fn spawn<B: Bundle>(&mut self, bundle: B) -> Entity {
+        self.flush();
+        let (entity, idx) = self.entities.alloc();
+        self.write_bundle(entity, idx, bundle);
+        entity
+    }
+
+    /// Reserve an entity handle through a shared borrow.
+    ///
+    /// The entity becomes live (with no components) on the next
+    /// [`flush`](Self::flush); every structural method flushes first.
+    #[inline]
+    pub fn reserve_entity(&self) -> Entity {
+        self.entities.reserve()
+    }
+
+    /// Materialize all reserved entities as empty, live entities.
+    pub fn flush(&mut self) {
+        let empty = &mut self.archetypes.archetypes[ArchetypeStore::empty_id()];
+        self.entities.flush(|entity, rec| {
+            rec.archetype_id = Some(ArchetypeStore::empty_id());
+            rec.row = empty.entities.len();
+            empty.entities.push(entity);
+        });
+    }
+
+    /// Give a reserved (or otherwise component-less) entity its initial
+    /// components.
+    ///
+    /// Returns `false` if the entity is dead or already has components.
+    pub fn spawn_reserved<B: Bundle>(&mut self, entity: Entity, bundle: B) -> bool {
+        self.flush();
+        let rec = match self.entities.get(entity) {
+            Some(r) if r.archetype_id == Some(ArchetypeStore::empty_id()) => r.clone(),
+            _ => return false,
+        };
+        if !self.sparse_sets.types_of(entity).is_empty() {
+            return false;
+        }
+        // Detach from the empty archetype (no columns → nothing to drop).
+        let swapped = unsafe {
+            self.archetypes.archetypes[ArchetypeStore::empty_id()].swap_remove_no_drop(rec.row)
+        };
+        if let Some(moved) = swapped {
+            self.entities.get_mut(moved.index as usize).unwrap().row = rec.row;
+        }
+        self.write_bundle(entity, entity.index as usize, bundle);
+        true
+    }
+
+    /// Push `bundle` as a new row of its archetype and point record `idx`
+    /// (belonging to `entity`) at it.  Sparse-set members of the bundle go
+    /// to their sparse sets instead.
+    fn write_bundle<B: Bundle>(&mut self, entity: Entity, idx: usize, bundle: B) {
         // Collect metadata
         let mut type_ids = B::type_ids();
         type_ids.sort_unstable();
         type_ids.dedup();
 
-        let mut infos = B::component_infos();
+        // B::component_infos() is in tuple order, which write_into expects.
+        let bundle_infos = B::component_infos();
+        let mut infos: Vec<ComponentInfo> = bundle_infos
+            .iter()
+            .filter(|i| i.storage == StorageType::Table)
+            .cloned()
+            .collect();
         infos.sort_unstable_by_key(|i| i.type_id);
         infos.dedup_by_key(|i| i.type_id);
 
-        let sig = ComponentSet(type_ids.clone());
-        let arch_id = self.archetypes.get_or_create(sig, infos.clone());
-
-        // Allocate entity
-        let (entity, idx) = self.entities.alloc();
+        let sig = ComponentSet(infos.iter().map(|i| i.type_id).collect());
+        let arch_id = self.archetypes.get_or_create(sig, infos);
 
         // Write component data into the archetype
         // We need to call write_into with column pointers in signature order.
         let arch = &mut self.archetypes.archetypes[arch_id];
         arch.entities.push(entity);
 
-        // Reserve one slot per column
-        for col in &mut arch.columns {
-            col.reserve(1);
-        }
+        let tick = self.change_tick.fetch_add(1, Ordering::Relaxed) + 1;
 
-        // Build sorted column pointer array (one *mut u8 per component)
+        // Build column pointer array (one *mut u8 per component)
         let row = arch.entities.len() - 1;
-        
-        // Match the order of B::type_ids() which write_into expects.
-        // B::type_ids() returns them in tuple order (0, 1, 2...).
-        let bundle_types = B::type_ids();
-        let mut ptrs: Vec<*mut u8> = Vec::with_capacity(bundle_types.len());
-        
-        for tid in bundle_types {
-            let col = arch.columns.iter_mut().find(|c| c.info.type_id == tid).unwrap();
-            unsafe {
-                ptrs.push(col.get_raw_mut(row));
-                col.len += 1;
-            }
+        let mut ptrs: Vec<*mut u8> = Vec::with_capacity(bundle_infos.len());
+
+        for info in &bundle_infos {
+            let ticks = ComponentTicks::new(tick);
+            let ptr = match info.storage {
+                StorageType::Table => {
+                    let col = arch.columns.iter_mut().find(|c| c.info.type_id == info.type_id).unwrap();
+                    unsafe { col.push_uninit(ticks) }
+                }
+                StorageType::SparseSet => unsafe {
+                    self.sparse_sets.get_or_insert(info).insert_uninit(entity, ticks)
+                },
+            };
+            ptrs.push(ptr);
         }
 
         // SAFETY: we just reserved space for `row`, ptrs are valid write targets
@@ -113,8 +259,10 @@ impl World {
         rec.archetype_id = Some(arch_id);
         rec.row = row;
 
-        self.change_tick += 1;
-        entity
+        for tid in type_ids {
+            self.run_hooks(tid, HookKind::Add, entity);
+            self.run_hooks(tid, HookKind::Insert, entity);
+        }
     }
 
     /// Spawn an entity with a **single** non-`Clone` component by move.
@@ -132,29 +280,39 @@ impl World {
     pub fn spawn_owned<C: Component>(&mut self, component: C) -> Entity {
         use crate::component::{ComponentInfo, ComponentSet};
         let info = ComponentInfo::of_owned::<C>();
+        if C::STORAGE == StorageType::SparseSet {
+            self.flush();
+            let (entity, idx) = self.entities.alloc();
+            let empty = &mut self.archetypes.archetypes[ArchetypeStore::empty_id()];
+            empty.entities.push(entity);
+            let rec = self.entities.get_mut(idx).unwrap();
+            rec.archetype_id = Some(ArchetypeStore::empty_id());
+            rec.row = empty.entities.len() - 1;
+            self.insert_sparse(entity, component, info);
+            return entity;
+        }
         let sig  = ComponentSet::new(vec![info.type_id]);
         let arch_id = self.archetypes.get_or_create(sig, vec![info.clone()]);
 
+        self.flush();
         let (entity, idx) = self.entities.alloc();
         let arch = &mut self.archetypes.archetypes[arch_id];
         arch.entities.push(entity);
 
         let row = arch.entities.len() - 1;
-        for col in &mut arch.columns {
-            col.reserve(1);
-        }
+        let tick = self.change_tick.fetch_add(1, Ordering::Relaxed) + 1;
         let col = arch.columns.iter_mut().find(|c| c.info.type_id == info.type_id).unwrap();
         unsafe {
-            let ptr = col.get_raw_mut(row);
+            let ptr = col.push_uninit(ComponentTicks::new(tick));
             std::ptr::write(ptr as *mut C, component);
-            col.len += 1;
         }
 
         let rec = self.entities.get_mut(idx).unwrap();
         rec.archetype_id = Some(arch_id);
         rec.row = row;
 
-        self.change_tick += 1;
+        self.run_hooks(info.type_id, HookKind::Add, entity);
+        self.run_hooks(info.type_id, HookKind::Insert, entity);
         entity
     }
 
@@ -163,9 +321,17 @@ impl World {
     /// Same rules as `spawn_owned` — only call this after all `Clone`
     /// components have been inserted.
     pub fn insert_owned<C: Component>(&mut self, entity: Entity, component: C) {
-        use std::any::TypeId;
         use crate::component::ComponentInfo;
 
+        self.flush();
+        if C::STORAGE == StorageType::SparseSet {
+            self.insert_sparse(entity, component, ComponentInfo::of_owned::<C>());
+            return;
+        }
+        if self.has::<C>(entity) {
+            self.run_hooks(TypeId::of::<C>(), HookKind::Replace, entity);
+        }
+
         // If already has C, overwrite in-place (no archetype move needed).
         {
             let rec = match self.entities.get(entity) {
@@ -177,7 +343,9 @@ impl World {
                 if let Some(col) = arch.column_mut::<C>() {
                     let slot = unsafe { col.get_mut::<C>(rec.row) };
                     *slot = component;
-                    self.change_tick += 1;
+                    let tick = self.change_tick.fetch_add(1, Ordering::Relaxed) + 1;
+                    col.set_changed(rec.row, tick);
+                    self.run_hooks(TypeId::of::<C>(), HookKind::Insert, entity);
                     return;
                 }
             }
@@ -206,19 +374,52 @@ impl World {
         self.move_entity_between_archetypes(entity, old_arch_id, old_row, new_arch_id);
 
         let new_row = self.entities.get(entity).unwrap().row;
+        let tick = self.increment_change_tick();
         let new_arch = &mut self.archetypes.archetypes[new_arch_id];
         if let Some(col) = new_arch.column_mut::<C>() {
             let slot = unsafe { col.get_mut::<C>(new_row) };
             unsafe { std::ptr::write(slot, component) };
+            col.ticks[new_row] = ComponentTicks::new(tick);
         }
-
-        self.change_tick += 1;
+        self.run_hooks(new_type_id, HookKind::Add, entity);
+        self.run_hooks(new_type_id, HookKind::Insert, entity);
     }
 
     /// Despawn an entity, removing all its components.
     ///
+    /// Relations to and from the entity are removed first, applying each
+    /// relation's [`DespawnPolicy`](crate::relation::DespawnPolicy) to its
+    /// sources.
+    ///
     /// Returns `false` if the entity was already dead (stale handle).
     pub fn despawn(&mut self, entity: Entity) -> bool {
+        self.flush();
+        if !self.relation_hooks.is_empty() && self.contains(entity) {
+            let hooks: Vec<_> = self.relation_hooks.iter().map(|&(_, hook)| hook).collect();
+            for hook in hooks {
+                hook(self, entity);
+            }
+        }
+        if !self.hooks.is_empty() {
+            let mut types: Vec<TypeId> = match self.entities.get(entity) {
+                Some(rec) => rec.archetype_id.map_or_else(Vec::new, |id| {
+                    self.archetypes.archetypes[id].signature.0.clone()
+                }),
+                None => Vec::new(),
+            };
+            if self.contains(entity) {
+                types.extend(self.sparse_sets.types_of(entity));
+                // Sparse sets live in a HashMap; keep the firing order stable.
+                types.sort_unstable();
+            }
+            for &tid in &types {
+                self.run_hooks(tid, HookKind::Despawn, entity);
+            }
+            for &tid in &types {
+                self.run_hooks(tid, HookKind::Remove, entity);
+            }
+        }
+        self.observers.forget_entity(entity);
         let rec = match self.entities.get(entity) {
             Some(r) => r.clone(),
             None => return false,
@@ -242,8 +443,9 @@ impl World {
             moved_rec.row = row;
         }
 
+        self.sparse_sets.remove_entity(entity);
         self.entities.free(entity);
-        self.change_tick += 1;
+        self.increment_change_tick();
         true
     }
 
@@ -270,6 +472,9 @@ impl World {
     /// Returns `None` if the entity is dead or does not have `C`.
     pub fn get<C: Component>(&self, entity: Entity) -> Option<&C> {
         let rec = self.entities.get(entity)?;
+        if C::STORAGE == StorageType::SparseSet {
+            return self.sparse_sets.get(TypeId::of::<C>())?.get::<C>(entity);
+        }
         let arch_id = rec.archetype_id?;
         let arch = &self.archetypes.archetypes[arch_id];
         let col = arch.column::<C>()?;
@@ -277,12 +482,22 @@ impl World {
     }
 
     /// Get a mutable reference to component `C` of `entity`.
+    ///
+    /// Marks the component as changed.
     pub fn get_mut<C: Component>(&mut self, entity: Entity) -> Option<&mut C> {
         let rec = self.entities.get(entity)?.clone();
+        if C::STORAGE == StorageType::SparseSet {
+            let set = self.sparse_sets.get_mut(TypeId::of::<C>())?;
+            let tick = self.change_tick.get_mut();
+            *tick += 1;
+            return set.get_mut::<C>(entity, *tick);
+        }
         let arch_id = rec.archetype_id?;
         let arch = &mut self.archetypes.archetypes[arch_id];
         let col = arch.column_mut::<C>()?;
-        self.change_tick += 1;
+        let tick = self.change_tick.get_mut();
+        *tick += 1;
+        col.set_changed(rec.row, *tick);
         Some(unsafe { col.get_mut::<C>(rec.row) })
     }
 
@@ -293,9 +508,19 @@ impl World {
 
     /// Insert a component into an existing entity.
     ///
-    /// If the entity already has component `C`, it is replaced.
-    /// Internally moves the entity to a new archetype.
+    /// If the entity already has component `C`, it is replaced.  Adding a
+    /// table component moves the entity to a new archetype; sparse-set
+    /// components are stored without moving it.
     pub fn insert<C: Component + Clone>(&mut self, entity: Entity, component: C) {
+        self.flush();
+        if C::STORAGE == StorageType::SparseSet {
+            self.insert_sparse(entity, component, ComponentInfo::of::<C>());
+            return;
+        }
+        if self.has::<C>(entity) {
+            self.run_hooks(TypeId::of::<C>(), HookKind::Replace, entity);
+        }
+
         // If already has C, just overwrite in-place (no archetype change)
         {
             let rec = match self.entities.get(entity) {
@@ -307,7 +532,9 @@ impl World {
                 if let Some(col) = arch.column_mut::<C>() {
                     let slot = unsafe { col.get_mut::<C>(rec.row) };
                     *slot = component;
-                    self.change_tick += 1;
+                    let tick = self.change_tick.fetch_add(1, Ordering::Relaxed) + 1;
+                    col.set_changed(rec.row, tick);
+                    self.run_hooks(TypeId::of::<C>(), HookKind::Insert, entity);
                     return;
                 }
             }
@@ -343,20 +570,37 @@ impl World {
 
         // Now insert the new component into the new archetype
         let new_row = self.entities.get(entity).unwrap().row;
+        let tick = self.increment_change_tick();
         let new_arch = &mut self.archetypes.archetypes[new_arch_id];
         if let Some(col) = new_arch.column_mut::<C>() {
             // The column was pushed with uninitialized data; write the actual value
             let slot = unsafe { col.get_mut::<C>(new_row) };
             unsafe { std::ptr::write(slot, component) };
+            col.ticks[new_row] = ComponentTicks::new(tick);
         }
-
-        self.change_tick += 1;
+        self.run_hooks(new_type_id, HookKind::Add, entity);
+        self.run_hooks(new_type_id, HookKind::Insert, entity);
     }
 
-    /// Remove component `C` from an entity, moving it to a smaller archetype.
+    /// Remove component `C` from an entity, moving it to a smaller archetype
+    /// (sparse-set components are dropped in place).
     ///
     /// Returns `true` if the component existed and was removed.
     pub fn remove<C: Component>(&mut self, entity: Entity) -> bool {
+        self.flush();
+        if self.has::<C>(entity) {
+            self.run_hooks(TypeId::of::<C>(), HookKind::Remove, entity);
+        }
+        if C::STORAGE == StorageType::SparseSet {
+            let removed = self
+                .sparse_sets
+                .get_mut(TypeId::of::<C>())
+                .is_some_and(|set| set.remove(entity));
+            if removed {
+                self.increment_change_tick();
+            }
+            return removed;
+        }
         let rec = match self.entities.get(entity) {
             Some(r) => r.clone(),
             None => return false,
@@ -391,7 +635,7 @@ impl World {
         let old_row = rec.row;
         self.move_entity_between_archetypes_without(entity, arch_id, old_row, new_arch_id, remove_type);
 
-        self.change_tick += 1;
+        self.increment_change_tick();
         true
     }
 
@@ -403,7 +647,8 @@ impl World {
     /// For multi-component queries use `World::query2` / `query3` or the
     /// `Query` type.
     pub fn query<C: Component>(&self) -> impl Iterator<Item = (Entity, &C)> {
-        self.archetypes
+        let table = self
+            .archetypes
             .archetypes
             .iter()
             .filter(|a| a.column::<C>().is_some())
@@ -413,13 +658,25 @@ impl World {
                     let comp = unsafe { col.get::<C>(row) };
                     (entity, comp)
                 })
-            })
+            });
+        let sparse = self
+            .sparse_sets
+            .get(TypeId::of::<C>())
+            .into_iter()
+            .flat_map(|set| {
+                set.entities().iter().enumerate().map(move |(row, &entity)| {
+                    let comp = unsafe { set.column().get::<C>(row) };
+                    (entity, comp)
+                })
+            });
+        table.chain(sparse)
     }
 
     /// Iterate over all entities that have both `A` and `B`.
     ///
     /// # Deprecated
     /// Use `Query::<(&A, &B)>::new(world).iter()` instead.
+    /// Only sees table-stored components.
     #[deprecated(since = "0.2.0", note = "use Query::<(&A, &B)>::new(world).iter()")]
     pub fn query2<A: Component, B: Component>(
         &self,
@@ -443,6 +700,7 @@ impl World {
     ///
     /// # Deprecated
     /// Use `Query::<(&A, &B, &C)>::new(world).iter()` instead.
+    /// Only sees table-stored components.
     #[deprecated(since = "0.2.0", note = "use Query::<(&A, &B, &C)>::new(world).iter()")]
     pub fn query3<A: Component, B: Component, C: Component>(
         &self,
@@ -471,8 +729,36 @@ impl World {
     // -----------------------------------------------------------------------
     // Internal helpers
 
+    /// Insert or replace a sparse-set component; the entity keeps its
+    /// archetype.
+    fn insert_sparse<C: Component>(&mut self, entity: Entity, component: C, info: ComponentInfo) {
+        if !self.contains(entity) {
+            return;
+        }
+        let type_id = info.type_id;
+        if self.has::<C>(entity) {
+            self.run_hooks(type_id, HookKind::Replace, entity);
+        }
+        let tick = self.increment_change_tick();
+        let set = self.sparse_sets.get_or_insert(&info);
+        if let Some(slot) = set.get_mut::<C>(entity, tick) {
+            *slot = component;
+            self.run_hooks(type_id, HookKind::Insert, entity);
+            return;
+        }
+        // SAFETY: `entity` has no value in the set; the slot is written
+        // before anything else touches the set.
+        unsafe {
+            let slot = set.insert_uninit(entity, ComponentTicks::new(tick));
+            std::ptr::write(slot as *mut C, component);
+        }
+        self.run_hooks(type_id, HookKind::Add, entity);
+        self.run_hooks(type_id, HookKind::Insert, entity);
+    }
+
     /// Move an entity from one archetype to another, cloning all components
-    /// that exist in both and inserting an uninitialized slot for new ones.
+    /// (and their change ticks) that exist in both and inserting an
+    /// uninitialized slot for new ones.
     fn move_entity_between_archetypes(
         &mut self,
         entity: Entity,
@@ -488,8 +774,7 @@ impl World {
             new_arch.entities.push(entity);
             let row = new_arch.entities.len() - 1;
             for col in &mut new_arch.columns {
-                col.reserve(1);
-                col.len += 1;
+                unsafe { col.push_uninit(ComponentTicks::default()) };
             }
             row
         };
@@ -511,6 +796,7 @@ impl World {
                 let tid = old_col.info.type_id;
                 if let Ok(new_col_idx) = new_sig.0.binary_search(&tid) {
                     let new_col = &mut new_arch.columns[new_col_idx];
+                    new_col.ticks[new_row] = old_col.ticks(old_row);
                     if old_col.info.size > 0 {
                         unsafe {
                             let dst = new_col.get_raw_mut(new_row);
@@ -549,8 +835,7 @@ impl World {
             new_arch.entities.push(entity);
             let row = new_arch.entities.len() - 1;
             for col in &mut new_arch.columns {
-                col.reserve(1);
-                col.len += 1;
+                unsafe { col.push_uninit(ComponentTicks::default()) };
             }
             row
         };
@@ -572,6 +857,7 @@ impl World {
                 }
                 if let Ok(new_col_idx) = new_sig.0.binary_search(&tid) {
                     let new_col = &mut new_arch.columns[new_col_idx];
+                    new_col.ticks[new_row] = old_col.ticks(old_row);
                     if old_col.info.size > 0 {
                         unsafe {
                             let dst = new_col.get_raw_mut(new_row);
@@ -611,6 +897,12 @@ mod tests {
     struct Health(f32);
     impl Component for Health {}
 
+    #[derive(Clone, Debug, PartialEq)]
+    struct Hovered;
+    impl Component for Hovered {
+        const STORAGE: StorageType = StorageType::SparseSet;
+    }
+
     #[test]
     fn spawn_and_query() {
         let mut world = World::new();
@@ -686,4 +978,64 @@ mod tests {
         let pos2 = world.get::<Pos>(e2).unwrap();
         assert_eq!(*pos2, Pos(2.0, 0.0, 0.0));
     }
+
+    #[test]
+    fn reserved_entities_become_live_on_flush() {
+        let mut world = World::new();
+        let a = world.reserve_entity();
+        let b = world.reserve_entity();
+        assert!(!world.contains(a));
+
+        world.flush();
+        assert!(world.contains(a) && world.contains(b));
+        assert_eq!(world.len(), 2);
+
+        assert!(world.spawn_reserved(b, (Pos(1.0, 0.0, 0.0), Vel(2.0))));
+        assert_eq!(*world.get::<Vel>(b).unwrap(), Vel(2.0));
+        // `a` was swapped within the empty archetype and must stay valid.
+        world.insert(a, Health(3.0));
+        assert_eq!(*world.get::<Health>(a).unwrap(), Health(3.0));
+        // Only component-less entities can be spawned into.
+        assert!(!world.spawn_reserved(b, (Health(0.0),)));
+    }
+
+    #[test]
+    fn component_ticks_track_add_and_change() {
+        let mut world = World::new();
+        let e = world.spawn((Pos(0.0, 0.0, 0.0),));
+        let spawned = world.component_ticks::<Pos>(e).unwrap();
+        assert_eq!(spawned.added, spawned.changed);
+
+        world.get_mut::<Pos>(e).unwrap().0 = 1.0;
+        let changed = world.component_ticks::<Pos>(e).unwrap();
+        assert_eq!(changed.added, spawned.added);
+        assert!(changed.is_changed(spawned.changed));
+
+        // Archetype moves keep the ticks of existing components.
+        world.insert(e, Vel(1.0));
+        assert_eq!(world.component_ticks::<Pos>(e).unwrap(), changed);
+        assert!(world.component_ticks::<Vel>(e).unwrap().is_added(changed.changed));
+        world.remove::<Vel>(e);
+        assert_eq!(world.component_ticks::<Pos>(e).unwrap(), changed);
+    }
+
+    #[test]
+    fn sparse_components_do_not_move_the_entity() {
+        let mut world = World::new();
+        let e = world.spawn((Pos(1.0, 0.0, 0.0), Hovered));
+        let arch = world.entities.get(e).unwrap().archetype_id;
+        assert!(world.has::<Hovered>(e));
+
+        assert!(world.remove::<Hovered>(e));
+        assert!(!world.remove::<Hovered>(e));
+        world.insert(e, Hovered);
+        assert_eq!(world.entities.get(e).unwrap().archetype_id, arch);
+        assert_eq!(world.query::<Hovered>().count(), 1);
+
+        // A sparse-only entity lives in the empty archetype.
+        let lone = world.spawn((Hovered,));
+        assert_eq!(world.entities.get(lone).unwrap().archetype_id, Some(ArchetypeStore::empty_id()));
+        assert!(world.despawn(e));
+        assert_eq!(world.query::<Hovered>().map(|(e, _)| e).collect::<Vec<_>>(), vec![lone]);
+    }
 }
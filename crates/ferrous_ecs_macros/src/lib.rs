@@ -1,6 +1,6 @@
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, DeriveInput};
+use syn::{parse_macro_input, DeriveInput, Lit, Meta, NestedMeta};
 
 /// Derive implementation of [`ferrous_ecs::component::Component`].
 ///
@@ -8,12 +8,71 @@ use syn::{parse_macro_input, DeriveInput};
 /// so users can write `#[derive(Component)]` instead of typing the empty
 /// impl themselves.  The derive is enabled by default via the
 /// `ferrous_ecs` crate's `derive` feature.
-#[proc_macro_derive(Component)]
+///
+/// The storage strategy can be chosen with a `component` attribute:
+///
+/// ```rust,ignore
+/// #[derive(Component, Clone)]
+/// #[component(storage = "SparseSet")]
+/// struct Hovered;
+/// ```
+#[proc_macro_derive(Component, attributes(component))]
 pub fn derive_component(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let name = input.ident;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let storage = match storage_attr(&input) {
+        Ok(storage) => storage,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let body = storage.map(|ident| {
+        quote! {
+            const STORAGE: ferrous_ecs::component::StorageType =
+                ferrous_ecs::component::StorageType::#ident;
+        }
+    });
+
     let expanded = quote! {
-        impl ferrous_ecs::component::Component for #name {}
+        impl #impl_generics ferrous_ecs::component::Component for #name #ty_generics #where_clause {
+            #body
+        }
     };
     TokenStream::from(expanded)
 }
+
+/// Parse `#[component(storage = "Table" | "SparseSet")]`.
+fn storage_attr(input: &DeriveInput) -> syn::Result<Option<syn::Ident>> {
+    let mut storage = None;
+    for attr in input.attrs.iter().filter(|a| a.path.is_ident("component")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            other => return Err(syn::Error::new_spanned(other, "expected `#[component(...)]`")),
+        };
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("storage") => {
+                    let value = match &nv.lit {
+                        Lit::Str(s) => s,
+                        other => return Err(syn::Error::new_spanned(other, "expected a string")),
+                    };
+                    match value.value().as_str() {
+                        "Table" | "SparseSet" => {
+                            storage = Some(syn::Ident::new(&value.value(), value.span()));
+                        }
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                value,
+                                "expected `\"Table\"` or `\"SparseSet\"`",
+                            ))
+                        }
+                    }
+                }
+                other => {
+                    return Err(syn::Error::new_spanned(other, "unknown `component` option"));
+                }
+            }
+        }
+    }
+    Ok(storage)
+}
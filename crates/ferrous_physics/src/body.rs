@@ -0,0 +1,173 @@
+//! The `RigidBody` component.
+
+use ferrous_ecs::prelude::*;
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+
+/// How the solver treats a body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BodyKind {
+    /// Moved by gravity, forces and contacts.
+    #[default]
+    Dynamic,
+    /// Moved only by its velocity (or by writing its `Transform`); pushes
+    /// dynamic bodies but is never pushed back.
+    Kinematic,
+    /// Never moves.
+    Static,
+}
+
+/// Dynamics state of an entity with a [`Collider`](crate::Collider).
+///
+/// `PhysicsSystem` integrates it every fixed step and writes the result to
+/// the entity's `Transform`, which is treated as a world-space pose — keep
+/// bodies at the root of the hierarchy.
+///
+/// Velocities and pending impulses can be changed freely between steps;
+/// doing so through [`apply_impulse`](Self::apply_impulse) and friends also
+/// wakes the body up.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RigidBody {
+    pub kind: BodyKind,
+    /// World-space linear velocity of the centre of mass (m/s).
+    pub linear_velocity: Vec3,
+    /// World-space angular velocity (rad/s).
+    pub angular_velocity: Vec3,
+    /// Overrides the mass computed from the collider's density.
+    pub mass: Option<f32>,
+    /// Fraction of linear velocity lost per second.
+    pub linear_damping: f32,
+    /// Fraction of angular velocity lost per second.
+    pub angular_damping: f32,
+    /// Multiplier on the world gravity.
+    pub gravity_scale: f32,
+    /// Keep the orientation fixed (infinite inertia), e.g. for characters.
+    pub lock_rotation: bool,
+    /// Whether the body may fall asleep when it comes to rest.
+    pub can_sleep: bool,
+    /// Force and torque applied during the next step, then cleared.
+    pub force: Vec3,
+    pub torque: Vec3,
+    /// Linear and angular impulse applied at the start of the next step,
+    /// then cleared.
+    pub impulse: Vec3,
+    pub angular_impulse: Vec3,
+    pub(crate) sleeping: bool,
+    pub(crate) sleep_time: f32,
+}
+impl Component for RigidBody {}
+
+impl Default for RigidBody {
+    fn default() -> Self {
+        RigidBody {
+            kind: BodyKind::Dynamic,
+            linear_velocity: Vec3::ZERO,
+            angular_velocity: Vec3::ZERO,
+            mass: None,
+            linear_damping: 0.0,
+            angular_damping: 0.05,
+            gravity_scale: 1.0,
+            lock_rotation: false,
+            can_sleep: true,
+            force: Vec3::ZERO,
+            torque: Vec3::ZERO,
+            impulse: Vec3::ZERO,
+            angular_impulse: Vec3::ZERO,
+            sleeping: false,
+            sleep_time: 0.0,
+        }
+    }
+}
+
+impl RigidBody {
+    pub fn dynamic() -> Self {
+        Self::default()
+    }
+
+    pub fn kinematic() -> Self {
+        RigidBody { kind: BodyKind::Kinematic, ..Self::default() }
+    }
+
+    /// A body that never moves (`static` is a keyword).
+    pub fn fixed() -> Self {
+        RigidBody { kind: BodyKind::Static, ..Self::default() }
+    }
+
+    pub fn with_linear_velocity(mut self, velocity: Vec3) -> Self {
+        self.linear_velocity = velocity;
+        self
+    }
+
+    pub fn with_angular_velocity(mut self, velocity: Vec3) -> Self {
+        self.angular_velocity = velocity;
+        self
+    }
+
+    pub fn with_mass(mut self, mass: f32) -> Self {
+        self.mass = Some(mass);
+        self
+    }
+
+    pub fn with_damping(mut self, linear: f32, angular: f32) -> Self {
+        self.linear_damping = linear;
+        self.angular_damping = angular;
+        self
+    }
+
+    pub fn with_gravity_scale(mut self, scale: f32) -> Self {
+        self.gravity_scale = scale;
+        self
+    }
+
+    pub fn with_locked_rotation(mut self) -> Self {
+        self.lock_rotation = true;
+        self
+    }
+
+    pub fn with_sleeping_disabled(mut self) -> Self {
+        self.can_sleep = false;
+        self
+    }
+
+    pub fn is_dynamic(&self) -> bool {
+        self.kind == BodyKind::Dynamic
+    }
+
+    pub fn is_sleeping(&self) -> bool {
+        self.sleeping
+    }
+
+    pub fn wake_up(&mut self) {
+        self.sleeping = false;
+        self.sleep_time = 0.0;
+    }
+
+    /// Put the body to sleep now, stopping it.
+    pub fn sleep(&mut self) {
+        self.sleeping = true;
+        self.linear_velocity = Vec3::ZERO;
+        self.angular_velocity = Vec3::ZERO;
+    }
+
+    /// Add a force (N) for the next step.
+    pub fn apply_force(&mut self, force: Vec3) {
+        self.force += force;
+        self.wake_up();
+    }
+
+    pub fn apply_torque(&mut self, torque: Vec3) {
+        self.torque += torque;
+        self.wake_up();
+    }
+
+    /// Add an impulse (N·s) at the centre of mass for the next step.
+    pub fn apply_impulse(&mut self, impulse: Vec3) {
+        self.impulse += impulse;
+        self.wake_up();
+    }
+
+    pub fn apply_angular_impulse(&mut self, impulse: Vec3) {
+        self.angular_impulse += impulse;
+        self.wake_up();
+    }
+}
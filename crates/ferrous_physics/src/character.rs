@@ -0,0 +1,521 @@
+//! Kinematic character controller: a capsule moved by collide-and-slide.
+//!
+//! The character is not simulated by the solver.  Each fixed step it sweeps
+//! its capsule through the colliders of the [`PhysicsWorld`] broadphase:
+//! first along the ground, sliding along walls and climbing steps, then
+//! vertically under gravity, and finally down onto the ground when it just
+//! walked off a ledge lower than `snap_distance`.  The `Transform` of the
+//! entity is the centre of the capsule; its Y axis is always world up.
+
+use ferrous_core::bounds::Aabb;
+use ferrous_core::input::InputState;
+use ferrous_core::scene::Controller;
+use ferrous_core::transform::Transform;
+use ferrous_ecs::fixed::DEFAULT_FIXED_STEP;
+use ferrous_ecs::prelude::*;
+use ferrous_ecs::system::System;
+use glam::{Quat, Vec3};
+
+use crate::collider::{Collider, ColliderShape};
+use crate::gjk::{Convex, Core};
+use crate::narrowphase::closest;
+use crate::pose::Pose;
+use crate::world::PhysicsWorld;
+
+/// Gap kept between the capsule and everything it touches, so the next
+/// sweep does not start in contact.
+const SKIN: f32 = 0.01;
+const MAX_SLIDES: usize = 4;
+const MAX_ADVANCE_ITERATIONS: usize = 32;
+const DEPENETRATION_ITERATIONS: usize = 4;
+/// How far past a contact the ground probe looks for the surface under it
+/// (so the edge of a step reads as the flat top of the step).
+const PROBE_INSET: f32 = 0.02;
+
+/// Capsule character moved by [`CharacterControllerSystem`].
+///
+/// Set [`movement`](Self::movement) to the wanted horizontal velocity, or
+/// put a [`Controller`] on the same entity to have it filled from the keys
+/// held (relative to the entity's facing, at `Controller::speed`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CharacterController {
+    /// Half the length of the capsule's straight part, along Y.
+    pub half_height: f32,
+    pub radius: f32,
+    /// Steepest walkable slope, in radians.  Steeper surfaces act as walls.
+    ///
+    /// Default: 45°
+    pub max_slope: f32,
+    /// Highest ledge climbed without jumping.
+    ///
+    /// Default: `0.3`
+    pub step_height: f32,
+    /// Drop the character is pulled down onto while walking, so it follows
+    /// stairs and ramps down instead of flying off them.
+    ///
+    /// Default: `0.3`
+    pub snap_distance: f32,
+    /// Multiplier on `PhysicsWorld::gravity`.
+    pub gravity_scale: f32,
+    /// Wanted horizontal velocity in world units per second.
+    pub movement: Vec3,
+    vertical_speed: f32,
+    grounded: bool,
+    ground_normal: Vec3,
+    velocity: Vec3,
+}
+
+impl Component for CharacterController {}
+
+impl Default for CharacterController {
+    fn default() -> Self {
+        Self::new(0.5, 0.4)
+    }
+}
+
+impl CharacterController {
+    pub fn new(half_height: f32, radius: f32) -> Self {
+        CharacterController {
+            half_height,
+            radius,
+            max_slope: std::f32::consts::FRAC_PI_4,
+            step_height: 0.3,
+            snap_distance: 0.3,
+            gravity_scale: 1.0,
+            movement: Vec3::ZERO,
+            vertical_speed: 0.0,
+            grounded: false,
+            ground_normal: Vec3::Y,
+            velocity: Vec3::ZERO,
+        }
+    }
+
+    pub fn with_max_slope(mut self, radians: f32) -> Self {
+        self.max_slope = radians;
+        self
+    }
+
+    pub fn with_step_height(mut self, height: f32) -> Self {
+        self.step_height = height;
+        self
+    }
+
+    pub fn with_snap_distance(mut self, distance: f32) -> Self {
+        self.snap_distance = distance;
+        self
+    }
+
+    pub fn with_gravity_scale(mut self, scale: f32) -> Self {
+        self.gravity_scale = scale;
+        self
+    }
+
+    /// Standing on walkable ground after the last step.
+    pub fn is_grounded(&self) -> bool {
+        self.grounded
+    }
+
+    /// Normal of the ground under the character (`Vec3::Y` in the air).
+    pub fn ground_normal(&self) -> Vec3 {
+        self.ground_normal
+    }
+
+    /// Velocity actually achieved during the last step.
+    pub fn velocity(&self) -> Vec3 {
+        self.velocity
+    }
+
+    /// Leave the ground at `speed` upwards.  Ignored in the air.
+    pub fn jump(&mut self, speed: f32) {
+        if self.grounded {
+            self.vertical_speed = speed;
+            self.grounded = false;
+        }
+    }
+
+    fn is_walkable(&self, normal: Vec3) -> bool {
+        normal.y >= self.max_slope.cos() - 1e-4
+    }
+
+    fn capsule(&self, position: Vec3) -> Convex<'static> {
+        Convex { core: Core::Segment(self.half_height), radius: self.radius, pose: Pose::new(position, Quat::IDENTITY) }
+    }
+
+    fn bounds(&self, position: Vec3) -> Aabb {
+        let half = Vec3::new(self.radius, self.half_height + self.radius, self.radius);
+        Aabb::new(position - half, position + half)
+    }
+}
+
+/// First contact of a sweep.
+#[derive(Debug, Clone, Copy)]
+struct Hit {
+    /// Fraction of the motion travelled before the contact.
+    fraction: f32,
+    /// Surface normal, pointing towards the character.
+    normal: Vec3,
+    /// Contact point on the obstacle.
+    point: Vec3,
+}
+
+/// Colliders near a character for one step, with triangle meshes split into
+/// the triangles in range.
+struct Obstacles {
+    shapes: Vec<(ColliderShape, Pose)>,
+    triangles: Vec<[Vec3; 3]>,
+}
+
+impl Obstacles {
+    fn gather(world: &ferrous_ecs::world::World, physics: &PhysicsWorld, entity: Entity, region: &Aabb) -> Self {
+        let mut obstacles = Obstacles { shapes: Vec::new(), triangles: Vec::new() };
+        physics.broadphase().query_aabb(region, |_, &other| {
+            if other == entity {
+                return;
+            }
+            let (Some(collider), Some(transform)) = (world.get::<Collider>(other), world.get::<Transform>(other)) else {
+                return;
+            };
+            if collider.sensor {
+                return;
+            }
+            let pose = Pose::new(transform.position, transform.rotation).mul(&collider.offset);
+            match &collider.shape {
+                ColliderShape::TriMesh(mesh) => {
+                    mesh.query(&region.transform(&pose.inverse().matrix()), |t| {
+                        obstacles.triangles.push(mesh.triangle(t).map(|v| pose.transform_point(v)));
+                    });
+                }
+                shape => obstacles.shapes.push((shape.clone(), pose)),
+            }
+        });
+        obstacles
+    }
+
+    fn convexes(&self) -> impl Iterator<Item = Convex<'_>> {
+        let shapes = self.shapes.iter().filter_map(|(shape, pose)| Convex::from_shape(shape, *pose));
+        let triangles = self.triangles.iter().map(|&corners| Convex::triangle(corners, Pose::IDENTITY));
+        shapes.chain(triangles)
+    }
+
+    /// Sweep `shape` by `motion` and return the earliest contact, by
+    /// conservative advancement: the closest-point normal bounds how fast
+    /// the gap can close, so stepping by `gap / approach speed` never
+    /// tunnels.
+    fn cast(&self, shape: &Convex, motion: Vec3) -> Option<Hit> {
+        let length = motion.length();
+        if length < 1e-6 {
+            return None;
+        }
+        let mut best: Option<Hit> = None;
+        for obstacle in self.convexes() {
+            let mut t = 0.0;
+            for _ in 0..MAX_ADVANCE_ITERATIONS {
+                let moved = Convex { pose: Pose::new(shape.pose.position + motion * t, shape.pose.rotation), ..*shape };
+                let Some(c) = closest(&moved, &obstacle, length + SKIN) else { break };
+                // Moving away from (or along) the closest surface: the
+                // separating plane keeps the shapes apart.
+                let approach = motion.dot(c.normal);
+                if approach <= length * 1e-3 {
+                    break;
+                }
+                let gap = -c.depth - SKIN;
+                if gap < 1e-4 {
+                    if best.is_none_or(|b| t < b.fraction) {
+                        best = Some(Hit { fraction: t, normal: -c.normal, point: c.point_b });
+                    }
+                    break;
+                }
+                t += gap / approach;
+                if t > 1.0 || best.is_some_and(|b| t >= b.fraction) {
+                    break;
+                }
+            }
+        }
+        best
+    }
+
+    /// Push `position` out of anything the capsule overlaps.
+    fn depenetrate(&self, character: &CharacterController, mut position: Vec3) -> Vec3 {
+        for _ in 0..DEPENETRATION_ITERATIONS {
+            let mut pushed = false;
+            for obstacle in self.convexes() {
+                if let Some(c) = closest(&character.capsule(position), &obstacle, 0.0) {
+                    if c.depth > 0.0 {
+                        position -= c.normal * (c.depth + SKIN);
+                        pushed = true;
+                    }
+                }
+            }
+            if !pushed {
+                break;
+            }
+        }
+        position
+    }
+
+    /// Walkable ground normal under `hit`, if any.  A capsule resting on the
+    /// edge of a step touches it with a tilted normal; probing just inside
+    /// the edge finds the flat top it stands on.
+    fn ground_under(&self, character: &CharacterController, position: Vec3, hit: &Hit) -> Option<Vec3> {
+        if character.is_walkable(hit.normal) {
+            return Some(hit.normal);
+        }
+        if hit.normal.y <= 0.0 {
+            return None;
+        }
+        let beyond = Vec3::new(hit.point.x - position.x, 0.0, hit.point.z - position.z).normalize_or_zero();
+        let start = hit.point + beyond * PROBE_INSET + Vec3::Y * PROBE_INSET;
+        let probe = Convex { core: Core::Point, radius: 0.0, pose: Pose::new(start, Quat::IDENTITY) };
+        self.cast(&probe, Vec3::NEG_Y * (2.0 * PROBE_INSET + SKIN))
+            .map(|h| h.normal)
+            .filter(|&n| character.is_walkable(n))
+    }
+
+    /// Move by `motion`, sliding along what is hit.  With `walls`, surfaces
+    /// too steep to walk on are treated as vertical so the character cannot
+    /// slide up them.  Returns the end position and whether such a wall
+    /// stopped part of the motion.
+    fn slide(&self, character: &CharacterController, mut position: Vec3, motion: Vec3, walls: bool) -> (Vec3, bool) {
+        let mut remaining = motion;
+        let mut previous: Option<Vec3> = None;
+        let mut blocked = false;
+        for _ in 0..MAX_SLIDES {
+            if remaining.length_squared() < 1e-10 {
+                break;
+            }
+            let Some(hit) = self.cast(&character.capsule(position), remaining) else {
+                position += remaining;
+                break;
+            };
+            position += remaining * hit.fraction;
+            remaining *= 1.0 - hit.fraction;
+            let mut normal = hit.normal;
+            if walls && !character.is_walkable(normal) {
+                normal = Vec3::new(normal.x, 0.0, normal.z).normalize_or(normal);
+                blocked = true;
+            }
+            remaining -= normal * remaining.dot(normal).min(0.0);
+            // Wedged between two planes: follow their crease.
+            if let Some(prev) = previous.filter(|p| remaining.dot(*p) < 0.0) {
+                let crease = prev.cross(normal).normalize_or_zero();
+                remaining = crease * remaining.dot(crease);
+            }
+            previous = Some(normal);
+        }
+        (position, blocked)
+    }
+
+    /// Sweep down by `distance` and return the landing position and ground
+    /// normal if the character ends up on walkable ground.
+    fn find_ground(&self, character: &CharacterController, position: Vec3, distance: f32) -> Option<(Vec3, Vec3)> {
+        let motion = Vec3::NEG_Y * distance;
+        let hit = self.cast(&character.capsule(position), motion)?;
+        let landed = position + motion * hit.fraction;
+        self.ground_under(character, landed, &hit).map(|n| (landed, n))
+    }
+}
+
+/// Move `character` (its capsule centred at `position`) for one step of
+/// `dt` seconds and return the new position.
+fn move_character(character: &mut CharacterController, obstacles: &Obstacles, position: Vec3, gravity: f32, dt: f32) -> Vec3 {
+    let start = obstacles.depenetrate(character, position);
+    let was_grounded = character.grounded && character.vertical_speed <= 0.0;
+
+    // ── Along the ground ─────────────────────────────────────────────────
+    let mut horizontal = Vec3::new(character.movement.x, 0.0, character.movement.z) * dt;
+    if was_grounded && horizontal != Vec3::ZERO {
+        // Walk along the slope instead of into (or off) it.
+        let n = character.ground_normal;
+        let along = horizontal - n * horizontal.dot(n);
+        horizontal = along.normalize_or_zero() * horizontal.length();
+    }
+    let (mut position, blocked) = obstacles.slide(character, start, horizontal, true);
+    if blocked && was_grounded && character.step_height > 0.0 {
+        let flat = Vec3::new(horizontal.x, 0.0, horizontal.z);
+        let up = Vec3::Y * character.step_height;
+        let raised = match obstacles.cast(&character.capsule(start), up) {
+            Some(hit) => start + up * hit.fraction,
+            None => start + up,
+        };
+        let (across, _) = obstacles.slide(character, raised, flat, true);
+        let drop = raised.y - start.y + SKIN;
+        if let Some((landed, _)) = obstacles.find_ground(character, across, drop) {
+            let progress = |p: Vec3| (p - start).with_y(0.0).length();
+            if landed.y > start.y && progress(landed) > progress(position) + 1e-4 {
+                position = landed;
+            }
+        }
+    }
+
+    // ── Gravity ──────────────────────────────────────────────────────────
+    character.vertical_speed += gravity * character.gravity_scale * dt;
+    let fall = Vec3::Y * character.vertical_speed * dt;
+    let mut grounded = None;
+    let mut remaining = fall;
+    for _ in 0..MAX_SLIDES {
+        if remaining.length_squared() < 1e-10 {
+            break;
+        }
+        let Some(hit) = obstacles.cast(&character.capsule(position), remaining) else {
+            position += remaining;
+            break;
+        };
+        position += remaining * hit.fraction;
+        remaining *= 1.0 - hit.fraction;
+        if remaining.y < 0.0 {
+            if let Some(n) = obstacles.ground_under(character, position, &hit) {
+                grounded = Some(n);
+                break;
+            }
+        } else if hit.normal.y < 0.0 {
+            character.vertical_speed = 0.0;
+        }
+        // Too steep to stand on: slide down it.
+        remaining -= hit.normal * remaining.dot(hit.normal).min(0.0);
+    }
+
+    // ── Ground snapping ──────────────────────────────────────────────────
+    if grounded.is_none() && was_grounded {
+        if let Some((landed, n)) = obstacles.find_ground(character, position, character.snap_distance) {
+            position = landed;
+            grounded = Some(n);
+        }
+    }
+
+    character.grounded = grounded.is_some();
+    character.ground_normal = grounded.unwrap_or(Vec3::Y);
+    if character.grounded {
+        character.vertical_speed = 0.0;
+    }
+    character.velocity = (position - start) / dt;
+    position
+}
+
+/// Moves every [`CharacterController`] once per fixed step.
+///
+/// Register in `Stage::FixedUpdate` after `PhysicsSystem`: the characters
+/// collide with the colliders of its broadphase, where bodies are already
+/// at their new poses.  When the entity also has a [`Controller`] and an
+/// [`InputState`] resource is present, `movement` is taken from
+/// `Controller::direction` (+Z forward, +X right, relative to the entity's
+/// facing) times `Controller::speed`.
+pub struct CharacterControllerSystem;
+
+impl System for CharacterControllerSystem {
+    fn name(&self) -> &'static str {
+        "CharacterControllerSystem"
+    }
+
+    fn run(&mut self, world: &mut ferrous_ecs::world::World, resources: &mut ResourceMap) {
+        let dt = resources.get::<FixedTime>().map_or(DEFAULT_FIXED_STEP, |f| f.step);
+        if dt <= 0.0 {
+            return;
+        }
+        let physics = resources.get_or_insert_default::<PhysicsWorld>();
+        let gravity = physics.gravity.y;
+        let input = resources.get::<InputState>();
+
+        let mut moves = Vec::new();
+        for (entity, (character, transform, controller)) in
+            Query::<(&CharacterController, &Transform, Option<&Controller>)>::new(world).iter()
+        {
+            let mut character = *character;
+            if let (Some(controller), Some(input)) = (controller, input) {
+                let dir = controller.direction(input);
+                let forward = transform.forward().with_y(0.0).normalize_or(Vec3::NEG_Z);
+                let right = transform.right().with_y(0.0).normalize_or(Vec3::X);
+                character.movement = (forward * dir.z + right * dir.x).normalize_or_zero() * controller.speed;
+            }
+            moves.push((entity, character, transform.position));
+        }
+
+        let physics = resources.get::<PhysicsWorld>().unwrap();
+        for (entity, mut character, position) in moves {
+            let reach = character.movement.length() * dt
+                + character.vertical_speed.abs() * dt
+                + character.step_height
+                + character.snap_distance
+                + SKIN;
+            let region = character.bounds(position).expanded(reach);
+            let obstacles = Obstacles::gather(world, physics, entity, &region);
+            let position = move_character(&mut character, &obstacles, position, gravity, dt);
+            if let Some(t) = world.get_mut::<Transform>(entity) {
+                t.position = position;
+            }
+            if let Some(slot) = world.get_mut::<CharacterController>(entity) {
+                *slot = character;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PhysicsSystem;
+
+    fn run(world: &mut ferrous_ecs::world::World, resources: &mut ResourceMap, steps: usize) {
+        for _ in 0..steps {
+            PhysicsSystem.run(world, resources);
+            CharacterControllerSystem.run(world, resources);
+        }
+    }
+
+    #[test]
+    fn character_lands_climbs_steps_and_stops_at_walls() {
+        let mut world = ferrous_ecs::world::World::new();
+        let mut resources = ResourceMap::new();
+        world.spawn((Transform::IDENTITY, Collider::cuboid(Vec3::new(20.0, 0.5, 20.0))));
+        // A 0.2 step at x = 2, and a wall too high to climb at x = 6.
+        world.spawn((Transform::from_position(Vec3::new(4.0, 0.6, 0.0)), Collider::cuboid(Vec3::new(2.0, 0.1, 2.0))));
+        world.spawn((Transform::from_position(Vec3::new(7.0, 1.5, 0.0)), Collider::cuboid(Vec3::new(1.0, 1.0, 2.0))));
+        let player = world.spawn((Transform::from_position(Vec3::new(0.0, 2.0, 0.0)), CharacterController::default()));
+
+        run(&mut world, &mut resources, 60);
+        let standing = 0.5 + 0.9;
+        let t = world.get::<Transform>(player).unwrap();
+        assert!((t.position.y - standing).abs() < 0.03, "{:?}", t.position);
+        assert!(world.get::<CharacterController>(player).unwrap().is_grounded());
+
+        world.get_mut::<CharacterController>(player).unwrap().movement = Vec3::X * 3.0;
+        run(&mut world, &mut resources, 180);
+        let t = world.get::<Transform>(player).unwrap();
+        let cc = world.get::<CharacterController>(player).unwrap();
+        assert!((t.position.y - (standing + 0.2)).abs() < 0.03, "{:?}", t.position);
+        assert!((t.position.x - (6.0 - 0.4)).abs() < 0.05, "{:?}", t.position);
+        assert!(cc.is_grounded());
+        assert!(cc.velocity().x.abs() < 0.1);
+    }
+
+    #[test]
+    fn character_walks_up_gentle_ramps_only() {
+        let mut world = ferrous_ecs::world::World::new();
+        let mut resources = ResourceMap::new();
+        world.spawn((Transform::IDENTITY, Collider::cuboid(Vec3::new(20.0, 0.5, 20.0))));
+        let ramp = |angle: f32, x: f32| {
+            let rotation = Quat::from_rotation_z(angle);
+            // Ramp face starts at ground level around `x`.
+            let position = Vec3::new(x + 3.0 * angle.cos(), 0.5 + 3.0 * angle.sin(), 0.0) - rotation * (Vec3::Y * 0.25);
+            (Transform { position, rotation, scale: Vec3::ONE }, Collider::cuboid(Vec3::new(3.0, 0.25, 1.0)))
+        };
+        world.spawn(ramp(20f32.to_radians(), 2.0));
+        let (mut steep, collider) = ramp(60f32.to_radians(), 2.0);
+        steep.position.z = 10.0;
+        world.spawn((steep, collider));
+
+        let gentle = world.spawn((
+            Transform::from_position(Vec3::new(0.0, 1.41, 0.0)),
+            CharacterController { movement: Vec3::X * 2.0, ..Default::default() },
+        ));
+        let blocked = world.spawn((
+            Transform::from_position(Vec3::new(0.0, 1.41, 10.0)),
+            CharacterController { movement: Vec3::X * 2.0, ..Default::default() },
+        ));
+        run(&mut world, &mut resources, 180);
+        let up = world.get::<Transform>(gentle).unwrap().position;
+        let stuck = world.get::<Transform>(blocked).unwrap().position;
+        assert!(up.y > 2.2, "{up:?}");
+        assert!(world.get::<CharacterController>(gentle).unwrap().is_grounded());
+        assert!(stuck.y < 1.6 && stuck.x < 2.5, "{stuck:?}");
+    }
+}
@@ -0,0 +1,294 @@
+//! Collision shapes and the `Collider` component.
+
+use std::f32::consts::PI;
+use std::sync::Arc;
+
+use ferrous_assets::AssetMesh;
+use ferrous_core::bounds::Aabb;
+use ferrous_core::bvh::AabbTree;
+use ferrous_core::scene::MeshGeometry;
+use ferrous_ecs::prelude::*;
+use glam::{Mat3, Quat, Vec3};
+
+use crate::pose::Pose;
+
+// ────────────────────────────────────────────────────────────────────────────
+// Shapes
+
+/// Geometry of a [`Collider`], in the collider's own space.
+#[derive(Debug, Clone)]
+pub enum ColliderShape {
+    Sphere { radius: f32 },
+    /// Box centred on the origin.
+    Cuboid { half_extents: Vec3 },
+    /// Cylinder along local Y capped by two hemispheres; `half_height` is the
+    /// half-length of the cylinder part.
+    Capsule { half_height: f32, radius: f32 },
+    ConvexHull(Arc<ConvexHull>),
+    /// Triangle soup.  Not a volume: collides with convex shapes only, and
+    /// on a dynamic body its mass is that of its bounding box.
+    TriMesh(Arc<TriMesh>),
+}
+
+impl ColliderShape {
+    /// Bounds in the shape's space.
+    pub fn local_bounds(&self) -> Aabb {
+        match self {
+            ColliderShape::Sphere { radius } => Aabb { center: Vec3::ZERO, half_extents: Vec3::splat(*radius) },
+            ColliderShape::Cuboid { half_extents } => Aabb { center: Vec3::ZERO, half_extents: *half_extents },
+            ColliderShape::Capsule { half_height, radius } => Aabb {
+                center: Vec3::ZERO,
+                half_extents: Vec3::new(*radius, half_height + radius, *radius),
+            },
+            ColliderShape::ConvexHull(hull) => hull.bounds,
+            ColliderShape::TriMesh(mesh) => mesh.bounds,
+        }
+    }
+
+    /// Mass and principal moments of inertia (about the shape origin, along
+    /// its axes) for a uniform `density`.  Hulls and meshes use their
+    /// bounding box.
+    pub fn mass_properties(&self, density: f32) -> (f32, Vec3) {
+        match self {
+            ColliderShape::Sphere { radius } => {
+                let mass = density * 4.0 / 3.0 * PI * radius.powi(3);
+                (mass, Vec3::splat(0.4 * mass * radius * radius))
+            }
+            ColliderShape::Cuboid { half_extents } => cuboid_mass(density, *half_extents),
+            ColliderShape::Capsule { half_height, radius } => {
+                let (h, r) = (*half_height, *radius);
+                let cylinder = density * PI * r * r * 2.0 * h;
+                let caps = density * 4.0 / 3.0 * PI * r.powi(3);
+                let axial = cylinder * r * r * 0.5 + caps * 0.4 * r * r;
+                let lateral = cylinder * (h * h / 3.0 + r * r / 4.0) + caps * (0.4 * r * r + h * h + 0.75 * h * r);
+                (cylinder + caps, Vec3::new(lateral, axial, lateral))
+            }
+            ColliderShape::ConvexHull(hull) => cuboid_mass(density, hull.bounds.half_extents),
+            ColliderShape::TriMesh(mesh) => cuboid_mass(density, mesh.bounds.half_extents),
+        }
+    }
+
+    /// Local centre of mass (the bounds centre for hulls and meshes).
+    pub fn center_of_mass(&self) -> Vec3 {
+        match self {
+            ColliderShape::ConvexHull(_) | ColliderShape::TriMesh(_) => self.local_bounds().center,
+            _ => Vec3::ZERO,
+        }
+    }
+}
+
+fn cuboid_mass(density: f32, half: Vec3) -> (f32, Vec3) {
+    let mass = density * 8.0 * half.x * half.y * half.z;
+    let sq = half * half;
+    (mass, Vec3::new(sq.y + sq.z, sq.x + sq.z, sq.x + sq.y) * (mass / 3.0))
+}
+
+/// Convex shape given by a point cloud; collision uses the support mapping
+/// of the points, so they need not be hull vertices only.
+#[derive(Debug, Clone)]
+pub struct ConvexHull {
+    points: Vec<Vec3>,
+    bounds: Aabb,
+}
+
+impl ConvexHull {
+    /// `None` for an empty point set.
+    pub fn new(points: impl IntoIterator<Item = Vec3>) -> Option<Self> {
+        let mut points: Vec<Vec3> = points.into_iter().collect();
+        points.sort_by(|a, b| a.to_array().partial_cmp(&b.to_array()).unwrap_or(std::cmp::Ordering::Equal));
+        points.dedup();
+        let bounds = Aabb::from_points(points.iter().copied())?;
+        Some(ConvexHull { points, bounds })
+    }
+
+    pub fn points(&self) -> &[Vec3] {
+        &self.points
+    }
+
+    pub fn bounds(&self) -> Aabb {
+        self.bounds
+    }
+}
+
+/// Indexed triangle mesh with a bounding volume hierarchy over its
+/// triangles.
+#[derive(Debug, Clone)]
+pub struct TriMesh {
+    positions: Vec<Vec3>,
+    triangles: Vec<[u32; 3]>,
+    tree: AabbTree<u32>,
+    bounds: Aabb,
+}
+
+impl TriMesh {
+    /// Builds the mesh from a triangle list.  Triangles with an index out of
+    /// range are skipped; `None` when no triangle is left.
+    pub fn new(positions: Vec<Vec3>, indices: &[u32]) -> Option<Self> {
+        let triangles: Vec<[u32; 3]> = indices
+            .chunks_exact(3)
+            .map(|t| [t[0], t[1], t[2]])
+            .filter(|t| t.iter().all(|&i| (i as usize) < positions.len()))
+            .collect();
+        let mut tree = AabbTree::with_margin(0.0);
+        for (i, t) in triangles.iter().enumerate() {
+            let bounds = Aabb::from_points(t.iter().map(|&v| positions[v as usize]))?;
+            tree.insert(bounds, i as u32);
+        }
+        tree.rebuild();
+        let bounds = Aabb::from_points(triangles.iter().flatten().map(|&v| positions[v as usize]))?;
+        Some(TriMesh { positions, triangles, tree, bounds })
+    }
+
+    pub fn positions(&self) -> &[Vec3] {
+        &self.positions
+    }
+
+    pub fn triangles(&self) -> &[[u32; 3]] {
+        &self.triangles
+    }
+
+    /// Corners of triangle `index`.
+    pub fn triangle(&self, index: u32) -> [Vec3; 3] {
+        self.triangles[index as usize].map(|v| self.positions[v as usize])
+    }
+
+    pub fn bounds(&self) -> Aabb {
+        self.bounds
+    }
+
+    /// Triangles whose bounds overlap `aabb` (mesh space).
+    pub(crate) fn query(&self, aabb: &Aabb, mut visit: impl FnMut(u32)) {
+        self.tree.query_aabb(aabb, |_, &t| visit(t));
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Collider component
+
+/// Collision shape of an entity.
+///
+/// An entity with a `Collider` and a `Transform` but no
+/// [`RigidBody`](crate::RigidBody) is a static obstacle.  The shape is placed
+/// at `offset` relative to the entity's `Transform` (whose scale is ignored).
+///
+/// # Example
+/// ```rust,ignore
+/// world.spawn((Transform::IDENTITY, Collider::cuboid(Vec3::new(10.0, 0.5, 10.0))));
+/// world.spawn((
+///     Transform::from_position(Vec3::Y * 5.0),
+///     RigidBody::dynamic(),
+///     Collider::sphere(0.5).with_restitution(0.6),
+/// ));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Collider {
+    pub shape: ColliderShape,
+    /// Placement of the shape relative to the entity.
+    pub offset: Pose,
+    /// Coulomb friction coefficient; pairs use the geometric mean.
+    pub friction: f32,
+    /// Bounciness in `[0, 1]`; pairs use the larger value.
+    pub restitution: f32,
+    /// Mass per unit volume, used unless the body sets an explicit mass.
+    pub density: f32,
+    /// Sensors report [`CollisionEvent`](crate::CollisionEvent)s but never
+    /// push bodies.
+    pub sensor: bool,
+}
+impl Component for Collider {}
+
+impl Collider {
+    pub fn new(shape: ColliderShape) -> Self {
+        Collider { shape, offset: Pose::IDENTITY, friction: 0.5, restitution: 0.0, density: 1.0, sensor: false }
+    }
+
+    pub fn sphere(radius: f32) -> Self {
+        Self::new(ColliderShape::Sphere { radius })
+    }
+
+    pub fn cuboid(half_extents: Vec3) -> Self {
+        Self::new(ColliderShape::Cuboid { half_extents })
+    }
+
+    /// Capsule along local Y.
+    pub fn capsule(half_height: f32, radius: f32) -> Self {
+        Self::new(ColliderShape::Capsule { half_height, radius })
+    }
+
+    /// `None` for an empty point set.
+    pub fn convex_hull(points: impl IntoIterator<Item = Vec3>) -> Option<Self> {
+        Some(Self::new(ColliderShape::ConvexHull(Arc::new(ConvexHull::new(points)?))))
+    }
+
+    /// `None` when `indices` describe no valid triangle.
+    pub fn trimesh(positions: Vec<Vec3>, indices: &[u32]) -> Option<Self> {
+        Some(Self::new(ColliderShape::TriMesh(Arc::new(TriMesh::new(positions, indices)?))))
+    }
+
+    /// Triangle-mesh collider from an imported mesh.
+    pub fn from_asset_mesh(mesh: &AssetMesh) -> Option<Self> {
+        Self::trimesh(mesh.positions.iter().map(|&p| Vec3::from(p)).collect(), &mesh.indices)
+    }
+
+    /// Convex collider wrapping an imported mesh's vertices.
+    pub fn convex_from_asset_mesh(mesh: &AssetMesh) -> Option<Self> {
+        Self::convex_hull(mesh.positions.iter().map(|&p| Vec3::from(p)))
+    }
+
+    /// Triangle-mesh collider from geometry registered with the scene
+    /// `World` (see `World::mesh_geometry`).
+    pub fn from_mesh_geometry(geometry: &MeshGeometry) -> Option<Self> {
+        Self::trimesh(geometry.positions.clone(), &geometry.indices)
+    }
+
+    pub fn with_offset(mut self, position: Vec3) -> Self {
+        self.offset.position = position;
+        self
+    }
+
+    pub fn with_rotation(mut self, rotation: Quat) -> Self {
+        self.offset.rotation = rotation;
+        self
+    }
+
+    pub fn with_friction(mut self, friction: f32) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    pub fn with_restitution(mut self, restitution: f32) -> Self {
+        self.restitution = restitution;
+        self
+    }
+
+    pub fn with_density(mut self, density: f32) -> Self {
+        self.density = density;
+        self
+    }
+
+    pub fn sensor(mut self) -> Self {
+        self.sensor = true;
+        self
+    }
+
+    /// World-space bounds when the entity sits at `pose`.
+    pub fn world_bounds(&self, pose: &Pose) -> Aabb {
+        self.shape.local_bounds().transform(&pose.mul(&self.offset).matrix())
+    }
+
+    /// Mass, centre of mass (entity space) and inverse inertia tensor about
+    /// it (entity axes).
+    pub(crate) fn mass_properties(&self, mass_override: Option<f32>) -> (f32, Vec3, Mat3) {
+        let (mut mass, mut inertia) = self.shape.mass_properties(self.density);
+        if let Some(m) = mass_override {
+            if mass > 0.0 {
+                inertia *= m / mass;
+            }
+            mass = m;
+        }
+        let com = self.offset.transform_point(self.shape.center_of_mass());
+        let inv = Vec3::select(inertia.cmpgt(Vec3::splat(1e-12)), inertia.recip(), Vec3::ZERO);
+        let rot = Mat3::from_quat(self.offset.rotation);
+        (mass, com, rot * Mat3::from_diagonal(inv) * rot.transpose())
+    }
+}
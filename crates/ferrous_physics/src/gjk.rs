@@ -0,0 +1,540 @@
+//! GJK distance and EPA penetration queries on convex shapes.
+//!
+//! Rounded shapes are split into a polytope *core* and a radius (a sphere is
+//! a point plus its radius, a capsule a segment plus its radius), so GJK
+//! only ever runs on polytopes and the radius is added afterwards.  When the
+//! cores themselves overlap, EPA finds the penetration depth and normal.
+
+use glam::{DMat2, DMat3, DVec2, DVec3, Vec3};
+
+use crate::collider::ColliderShape;
+use crate::pose::Pose;
+
+/// Shape of the polytope core, in local space.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Core<'a> {
+    Point,
+    /// Segment from `-half_height` to `half_height` along Y.
+    Segment(f32),
+    Cuboid(Vec3),
+    Points(&'a [Vec3]),
+    Triangle([Vec3; 3]),
+}
+
+/// A convex core placed in the world, with the radius around it.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Convex<'a> {
+    pub core: Core<'a>,
+    pub radius: f32,
+    pub pose: Pose,
+}
+
+impl<'a> Convex<'a> {
+    /// `None` for triangle meshes, which are not convex.
+    pub fn from_shape(shape: &'a ColliderShape, pose: Pose) -> Option<Self> {
+        let (core, radius) = match shape {
+            ColliderShape::Sphere { radius } => (Core::Point, *radius),
+            ColliderShape::Cuboid { half_extents } => (Core::Cuboid(*half_extents), 0.0),
+            ColliderShape::Capsule { half_height, radius } => (Core::Segment(*half_height), *radius),
+            ColliderShape::ConvexHull(hull) => (Core::Points(hull.points()), 0.0),
+            ColliderShape::TriMesh(_) => return None,
+        };
+        Some(Convex { core, radius, pose })
+    }
+
+    pub fn triangle(corners: [Vec3; 3], pose: Pose) -> Self {
+        Convex { core: Core::Triangle(corners), radius: 0.0, pose }
+    }
+
+    /// World-space centre of the core.
+    pub fn center(&self) -> Vec3 {
+        let local = match self.core {
+            Core::Points(points) => points.iter().copied().sum::<Vec3>() / points.len().max(1) as f32,
+            Core::Triangle([a, b, c]) => (a + b + c) / 3.0,
+            _ => Vec3::ZERO,
+        };
+        self.pose.transform_point(local)
+    }
+
+    /// Radius of a sphere around the core's local origin enclosing it.
+    pub fn extent(&self) -> f32 {
+        match self.core {
+            Core::Point => 0.0,
+            Core::Segment(h) => h,
+            Core::Cuboid(half) => half.length(),
+            Core::Points(points) => points.iter().map(|p| p.length()).fold(0.0, f32::max),
+            Core::Triangle(corners) => corners.iter().map(|p| p.length()).fold(0.0, f32::max),
+        }
+    }
+
+    /// Farthest core point along `dir` (world space).
+    pub fn support(&self, dir: Vec3) -> Vec3 {
+        let d = self.pose.inverse_transform_vector(dir);
+        let local = match self.core {
+            Core::Point => Vec3::ZERO,
+            Core::Segment(h) => Vec3::new(0.0, if d.y >= 0.0 { h } else { -h }, 0.0),
+            Core::Cuboid(half) => Vec3::select(d.cmpge(Vec3::ZERO), half, -half),
+            Core::Points(points) => argmax(points.iter().copied(), d),
+            Core::Triangle(corners) => argmax(corners.iter().copied(), d),
+        };
+        self.pose.transform_point(local)
+    }
+
+    /// The core's vertices within `tolerance` of the farthest one along
+    /// `dir` (world space): the face, edge or vertex the shape presents in
+    /// that direction.
+    pub fn feature(&self, dir: Vec3, tolerance: f32, out: &mut Vec<Vec3>) {
+        out.clear();
+        let d = self.pose.inverse_transform_vector(dir);
+        let mut corners = [Vec3::ZERO; 8];
+        let vertices: &[Vec3] = match self.core {
+            Core::Point => &[Vec3::ZERO],
+            Core::Segment(h) => {
+                corners[0] = Vec3::Y * h;
+                corners[1] = Vec3::Y * -h;
+                &corners[..2]
+            }
+            Core::Cuboid(half) => {
+                for (i, c) in corners.iter_mut().enumerate() {
+                    *c = half * Vec3::new(sign(i & 1), sign(i & 2), sign(i & 4));
+                }
+                &corners
+            }
+            Core::Points(points) => points,
+            Core::Triangle(tri) => {
+                corners[..3].copy_from_slice(&tri);
+                &corners[..3]
+            }
+        };
+        let max = vertices.iter().map(|v| v.dot(d)).fold(f32::NEG_INFINITY, f32::max);
+        out.extend(vertices.iter().filter(|v| v.dot(d) >= max - tolerance).map(|&v| self.pose.transform_point(v)));
+    }
+}
+
+fn sign(bit: usize) -> f32 {
+    if bit != 0 {
+        1.0
+    } else {
+        -1.0
+    }
+}
+
+fn argmax(points: impl Iterator<Item = Vec3>, dir: Vec3) -> Vec3 {
+    points
+        .map(|p| (p.dot(dir), p))
+        .fold((f32::NEG_INFINITY, Vec3::ZERO), |best, c| if c.0 > best.0 { c } else { best })
+        .1
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// GJK
+
+/// Point of the Minkowski difference `A - B` with the support points that
+/// produced it.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SupportPoint {
+    pub w: Vec3,
+    pub a: Vec3,
+    pub b: Vec3,
+}
+
+/// Support point of the Minkowski difference `A − B`, with A grown by
+/// `skin`.
+fn support(a: &Convex, b: &Convex, dir: Vec3, skin: f32) -> SupportPoint {
+    let (pa, pb) = (a.support(dir) + dir.normalize_or_zero() * skin, b.support(-dir));
+    SupportPoint { w: pa - pb, a: pa, b: pb }
+}
+
+/// Result of [`gjk`] on the cores.
+pub(crate) enum Gjk {
+    /// Closest points on the cores of A and B.
+    Separated { distance: f32, point_a: Vec3, point_b: Vec3 },
+    /// The cores touch or overlap; the simplex encloses the origin (or has
+    /// it on its boundary) and seeds EPA.
+    Overlap(Vec<SupportPoint>),
+}
+
+const GJK_MAX_ITERATIONS: usize = 48;
+/// Cores closer than this count as touching: the witness points are too
+/// close for their difference to give a reliable normal.
+pub(crate) const TOUCH_DISTANCE: f32 = 0.005;
+/// Radius added around the Minkowski difference when EPA runs on touching
+/// or overlapping cores, so the origin lies well inside it even after
+/// GJK's rounding on large shapes.
+const EPA_SKIN: f32 = 0.01;
+
+/// Distance between the cores of `a` and `b` (radii are ignored).
+pub(crate) fn gjk(a: &Convex, b: &Convex) -> Gjk {
+    gjk_with_skin(a, b, 0.0)
+}
+
+fn gjk_with_skin(a: &Convex, b: &Convex, skin: f32) -> Gjk {
+    let mut dir = b.center() - a.center();
+    if dir.length_squared() < 1e-12 {
+        dir = Vec3::X;
+    }
+    let first = support(a, b, dir, skin);
+    let mut simplex = vec![first];
+    let mut weights = [1.0, 0.0, 0.0, 0.0];
+    let mut v = first.w;
+
+    for _ in 0..GJK_MAX_ITERATIONS {
+        let v2 = v.length_squared();
+        if v2 < 1e-12 {
+            return Gjk::Overlap(simplex);
+        }
+        let next = support(a, b, -v, skin);
+        // No vertex gets closer to the origin than `v`: converged.
+        if v2 - v.dot(next.w) <= 1e-6 * v2 || simplex.iter().any(|s| s.w.distance_squared(next.w) < 1e-12) {
+            break;
+        }
+        simplex.push(next);
+        let (point, kept, w) = closest_on_simplex(&simplex);
+        simplex = kept;
+        weights = w;
+        v = point;
+        if simplex.len() == 4 {
+            return Gjk::Overlap(simplex);
+        }
+    }
+    if v.length_squared() < 1e-12 {
+        return Gjk::Overlap(simplex);
+    }
+    let point_a = simplex.iter().zip(weights).map(|(s, w)| s.a * w).sum();
+    let point_b = simplex.iter().zip(weights).map(|(s, w)| s.b * w).sum();
+    Gjk::Separated { distance: v.length(), point_a, point_b }
+}
+
+/// Closest point to the origin of the convex hull of `simplex` (1–4
+/// points): the point, the smallest sub-simplex containing it and its
+/// barycentric weights.
+///
+/// Every subset is tried: the answer is the nearest projection of the
+/// origin onto a subset's affine hull that falls strictly inside it.
+fn closest_on_simplex(simplex: &[SupportPoint]) -> (Vec3, Vec<SupportPoint>, [f32; 4]) {
+    let n = simplex.len();
+    let mut best: Option<(f32, u32, [f32; 4])> = None;
+    let mut masks: Vec<u32> = (1..(1u32 << n)).collect();
+    masks.sort_by_key(|m| m.count_ones());
+    for mask in masks {
+        let idx: Vec<usize> = (0..n).filter(|i| mask & (1 << i) != 0).collect();
+        let Some(w) = project_origin(&idx.iter().map(|&i| simplex[i].w).collect::<Vec<_>>()) else {
+            continue;
+        };
+        if w[..idx.len()].iter().any(|&x| x <= 0.0) {
+            continue;
+        }
+        let p: Vec3 = idx.iter().zip(w).map(|(&i, w)| simplex[i].w * w).sum();
+        let d = p.length_squared();
+        if best.is_none_or(|(bd, _, _)| d < bd - 1e-12) {
+            best = Some((d, mask, w));
+        }
+    }
+    let (_, mask, w) = best.unwrap_or((0.0, 1, [1.0, 0.0, 0.0, 0.0]));
+    let kept: Vec<SupportPoint> = (0..n).filter(|i| mask & (1 << i) != 0).map(|i| simplex[i]).collect();
+    let point = kept.iter().zip(w).map(|(s, w)| s.w * w).sum();
+    (point, kept, w)
+}
+
+/// Barycentric weights of the origin projected onto the affine hull of
+/// `points`, or `None` if they are degenerate.
+///
+/// Solved in double precision: on large shapes the Gram matrices lose too
+/// much in `f32` to tell whether the origin is just inside a tetrahedron.
+fn project_origin(points: &[Vec3]) -> Option<[f32; 4]> {
+    let p0 = points[0].as_dvec3();
+    let e: Vec<DVec3> = points[1..].iter().map(|&p| p.as_dvec3() - p0).collect();
+    let mu: Vec<f64> = match e.len() {
+        0 => vec![],
+        1 => {
+            let g = e[0].dot(e[0]);
+            if g < 1e-12 {
+                return None;
+            }
+            vec![-e[0].dot(p0) / g]
+        }
+        2 => {
+            let g = DMat2::from_cols(
+                DVec2::new(e[0].dot(e[0]), e[0].dot(e[1])),
+                DVec2::new(e[1].dot(e[0]), e[1].dot(e[1])),
+            );
+            if g.determinant() <= 1e-6 * g.x_axis.x * g.y_axis.y {
+                return None;
+            }
+            let m = g.inverse() * DVec2::new(-e[0].dot(p0), -e[1].dot(p0));
+            vec![m.x, m.y]
+        }
+        _ => {
+            let col = |i: usize| DVec3::new(e[0].dot(e[i]), e[1].dot(e[i]), e[2].dot(e[i]));
+            let g = DMat3::from_cols(col(0), col(1), col(2));
+            if g.determinant() <= 1e-6 * g.x_axis.x * g.y_axis.y * g.z_axis.z {
+                return None;
+            }
+            let m = g.inverse() * DVec3::new(-e[0].dot(p0), -e[1].dot(p0), -e[2].dot(p0));
+            vec![m.x, m.y, m.z]
+        }
+    };
+    let mut w = [0.0; 4];
+    w[0] = (1.0 - mu.iter().sum::<f64>()) as f32;
+    for (w, mu) in w[1..].iter_mut().zip(mu) {
+        *w = mu as f32;
+    }
+    Some(w)
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// EPA
+
+/// Penetration of two overlapping cores.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Penetration {
+    /// Direction from A to B along which to separate them.
+    pub normal: Vec3,
+    pub depth: f32,
+    pub point_a: Vec3,
+    pub point_b: Vec3,
+}
+
+const EPA_MAX_ITERATIONS: usize = 64;
+const EPA_TOLERANCE: f32 = 1e-4;
+
+#[derive(Clone, Copy)]
+struct Face {
+    v: [usize; 3],
+    normal: Vec3,
+    distance: f32,
+}
+
+/// Penetration of cores that touch or overlap.  EPA needs the origin
+/// strictly inside the Minkowski difference, which fails for shapes at rest
+/// exactly on each other; growing A by a skin and taking it back off the
+/// result keeps the normal exact.
+pub(crate) fn touching(a: &Convex, b: &Convex) -> Option<Penetration> {
+    let Gjk::Overlap(simplex) = gjk_with_skin(a, b, EPA_SKIN) else { return None };
+    let pen = epa(a, b, simplex, EPA_SKIN)?;
+    Some(Penetration { depth: pen.depth - EPA_SKIN, point_a: pen.point_a - pen.normal * EPA_SKIN, ..pen })
+}
+
+/// Expanding polytope algorithm, seeded with the GJK simplex of the same
+/// `skin`.  `None` when the Minkowski difference is flat (e.g. two parallel
+/// segments) and no tetrahedron can be built.
+fn epa(a: &Convex, b: &Convex, simplex: Vec<SupportPoint>, skin: f32) -> Option<Penetration> {
+    let mut verts = simplex;
+    if !inflate_simplex(a, b, &mut verts, skin) {
+        return None;
+    }
+    let inner = verts.iter().map(|s| s.w).sum::<Vec3>() / 4.0;
+    let mut faces = Vec::new();
+    for f in [[0, 1, 2], [0, 1, 3], [0, 2, 3], [1, 2, 3]] {
+        faces.push(make_face(&verts, f, inner)?);
+    }
+
+    for _ in 0..EPA_MAX_ITERATIONS {
+        let closest = *faces.iter().min_by(|x, y| x.distance.total_cmp(&y.distance))?;
+        let next = support(a, b, closest.normal, skin);
+        if next.w.dot(closest.normal) - closest.distance < EPA_TOLERANCE {
+            return Some(penetration(&verts, &closest));
+        }
+        // Replace every face that sees the new point by a fan to it.
+        let mut horizon: Vec<[usize; 2]> = Vec::new();
+        faces.retain(|f| {
+            if f.normal.dot(next.w - verts[f.v[0]].w) <= 0.0 {
+                return true;
+            }
+            for e in [[f.v[0], f.v[1]], [f.v[1], f.v[2]], [f.v[2], f.v[0]]] {
+                if let Some(i) = horizon.iter().position(|h| (h[0] == e[1] && h[1] == e[0]) || *h == e) {
+                    horizon.swap_remove(i);
+                } else {
+                    horizon.push(e);
+                }
+            }
+            false
+        });
+        let index = verts.len();
+        verts.push(next);
+        for e in horizon {
+            match make_face(&verts, [e[0], e[1], index], inner) {
+                Some(face) => faces.push(face),
+                None => continue,
+            }
+        }
+        if faces.is_empty() {
+            return None;
+        }
+    }
+    let closest = faces.iter().min_by(|x, y| x.distance.total_cmp(&y.distance))?;
+    Some(penetration(&verts, closest))
+}
+
+fn make_face(verts: &[SupportPoint], v: [usize; 3], inner: Vec3) -> Option<Face> {
+    let (p0, p1, p2) = (verts[v[0]].w, verts[v[1]].w, verts[v[2]].w);
+    let mut normal = (p1 - p0).cross(p2 - p0).try_normalize()?;
+    if normal.dot(p0 - inner) < 0.0 {
+        normal = -normal;
+    }
+    Some(Face { v, normal, distance: normal.dot(p0) })
+}
+
+fn penetration(verts: &[SupportPoint], face: &Face) -> Penetration {
+    let [s0, s1, s2] = face.v.map(|i| verts[i]);
+    let p = face.normal * face.distance;
+    // Barycentric coordinates of the projected origin on the face.
+    let (e0, e1, ep) = (s1.w - s0.w, s2.w - s0.w, p - s0.w);
+    let (d00, d01, d11) = (e0.dot(e0), e0.dot(e1), e1.dot(e1));
+    let (d20, d21) = (ep.dot(e0), ep.dot(e1));
+    let denom = d00 * d11 - d01 * d01;
+    let (v, w) = if denom.abs() > 1e-12 {
+        ((d11 * d20 - d01 * d21) / denom, (d00 * d21 - d01 * d20) / denom)
+    } else {
+        (1.0 / 3.0, 1.0 / 3.0)
+    };
+    let u = 1.0 - v - w;
+    Penetration {
+        normal: face.normal,
+        depth: face.distance.max(0.0),
+        point_a: s0.a * u + s1.a * v + s2.a * w,
+        point_b: s0.b * u + s1.b * v + s2.b * w,
+    }
+}
+
+/// Grow the GJK simplex into a non-degenerate tetrahedron by adding support
+/// points in directions away from it.
+fn inflate_simplex(a: &Convex, b: &Convex, verts: &mut Vec<SupportPoint>, skin: f32) -> bool {
+    const AXES: [Vec3; 6] = [Vec3::X, Vec3::Y, Vec3::Z, Vec3::NEG_X, Vec3::NEG_Y, Vec3::NEG_Z];
+    if verts.len() == 1 {
+        for dir in AXES {
+            let s = support(a, b, dir, skin);
+            if s.w.distance_squared(verts[0].w) > 1e-10 {
+                verts.push(s);
+                break;
+            }
+        }
+    }
+    if verts.len() == 2 {
+        let edge = verts[1].w - verts[0].w;
+        let (u, v) = edge.normalize_or(Vec3::X).any_orthonormal_pair();
+        for dir in [u, v, -u, -v] {
+            let s = support(a, b, dir, skin);
+            if edge.cross(s.w - verts[0].w).length_squared() > 1e-10 {
+                verts.push(s);
+                break;
+            }
+        }
+    }
+    if verts.len() == 3 {
+        let normal = (verts[1].w - verts[0].w).cross(verts[2].w - verts[0].w);
+        for dir in [normal, -normal] {
+            let s = support(a, b, dir, skin);
+            if normal.dot(s.w - verts[0].w).abs() > 1e-8 {
+                verts.push(s);
+                break;
+            }
+        }
+    }
+    verts.len() == 4
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Quat;
+
+    #[test]
+    fn gjk_distance_and_epa_depth_between_boxes() {
+        let cube = ColliderShape::Cuboid { half_extents: Vec3::splat(0.5) };
+        let a = Convex::from_shape(&cube, Pose::IDENTITY).unwrap();
+        let far = Convex::from_shape(&cube, Pose::new(Vec3::new(3.0, 0.2, 0.0), Quat::IDENTITY)).unwrap();
+        match gjk(&a, &far) {
+            Gjk::Separated { distance, point_a, point_b } => {
+                assert!((distance - 2.0).abs() < 1e-4);
+                assert!((point_a.x - 0.5).abs() < 1e-4 && (point_b.x - 2.5).abs() < 1e-4);
+            }
+            Gjk::Overlap(_) => panic!("boxes are apart"),
+        }
+
+        let near = Convex::from_shape(&cube, Pose::new(Vec3::new(0.1, 0.8, 0.0), Quat::IDENTITY)).unwrap();
+        let Gjk::Overlap(simplex) = gjk(&a, &near) else { panic!("boxes overlap") };
+        let pen = epa(&a, &near, simplex, 0.0).unwrap();
+        assert!(pen.normal.abs_diff_eq(Vec3::Y, 1e-4));
+        assert!((pen.depth - 0.2).abs() < 1e-3);
+    }
+
+    #[test]
+    fn resting_box_touching_large_ground_gets_face_normal() {
+        let ground = ColliderShape::Cuboid { half_extents: Vec3::new(20.0, 0.5, 20.0) };
+        let cube = ColliderShape::Cuboid { half_extents: Vec3::splat(0.5) };
+        let a = Convex::from_shape(&ground, Pose::IDENTITY).unwrap();
+        let b = Convex::from_shape(&cube, Pose::new(Vec3::new(-5.0, 1.0, 0.0), Quat::IDENTITY)).unwrap();
+        let pen = touching(&a, &b).unwrap();
+        assert!(pen.normal.abs_diff_eq(Vec3::Y, 1e-4));
+        assert!(pen.depth.abs() < 1e-4);
+    }
+
+    fn separated(a: &Convex, b: &Convex) -> (f32, Vec3, Vec3) {
+        match gjk(a, b) {
+            Gjk::Separated { distance, point_a, point_b } => (distance, point_a, point_b),
+            Gjk::Overlap(_) => panic!("cores overlap"),
+        }
+    }
+
+    #[test]
+    fn sphere_and_capsule_cores_are_a_point_and_a_segment() {
+        let ball = ColliderShape::Sphere { radius: 0.5 };
+        let capsule = ColliderShape::Capsule { half_height: 0.5, radius: 0.25 };
+        let a = Convex::from_shape(&capsule, Pose::IDENTITY).unwrap();
+        assert_eq!((a.radius, Convex::from_shape(&ball, Pose::IDENTITY).unwrap().radius), (0.25, 0.5));
+
+        // Beside the segment the closest point is level with the sphere...
+        let beside = Convex::from_shape(&ball, Pose::new(Vec3::new(2.0, 0.3, 0.0), Quat::IDENTITY)).unwrap();
+        let (distance, point_a, point_b) = separated(&a, &beside);
+        assert!((distance - 2.0).abs() < 1e-4);
+        assert!(point_a.abs_diff_eq(Vec3::new(0.0, 0.3, 0.0), 1e-4));
+        assert!(point_b.abs_diff_eq(Vec3::new(2.0, 0.3, 0.0), 1e-4));
+        // ...and past its end it is the end point.
+        let above = Convex::from_shape(&ball, Pose::new(Vec3::new(1.0, 2.5, 0.0), Quat::IDENTITY)).unwrap();
+        let (distance, point_a, _) = separated(&a, &above);
+        assert!((distance - 5.0f32.sqrt()).abs() < 1e-4);
+        assert!(point_a.abs_diff_eq(Vec3::Y * 0.5, 1e-4));
+
+        // Crossed segments: the common perpendicular between the axes.
+        let crossed = Convex::from_shape(
+            &capsule,
+            Pose::new(Vec3::new(0.2, 0.1, 0.3), Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
+        )
+        .unwrap();
+        let (distance, point_a, point_b) = separated(&a, &crossed);
+        assert!((distance - 0.2).abs() < 1e-4);
+        assert!(point_a.abs_diff_eq(Vec3::new(0.0, 0.1, 0.0), 1e-4));
+        assert!(point_b.abs_diff_eq(Vec3::new(0.2, 0.1, 0.0), 1e-4));
+
+        // Concentric spheres have no direction to separate along.
+        let centre = Convex::from_shape(&ball, Pose::IDENTITY).unwrap();
+        assert!(matches!(gjk(&centre, &centre), Gjk::Overlap(_)));
+    }
+
+    #[test]
+    fn convex_hull_distance_and_penetration() {
+        let corners: Vec<Vec3> = (0..8).map(|i| Vec3::new(sign(i & 1), sign(i & 2), sign(i & 4)) * 0.5).collect();
+        let hull = ColliderShape::ConvexHull(std::sync::Arc::new(crate::collider::ConvexHull::new(corners).unwrap()));
+        let a = Convex::from_shape(&hull, Pose::IDENTITY).unwrap();
+
+        let rotated = Pose::new(Vec3::new(0.0, 2.0, 0.0), Quat::from_rotation_z(std::f32::consts::FRAC_PI_4));
+        let (distance, point_a, point_b) = separated(&a, &Convex::from_shape(&hull, rotated).unwrap());
+        // The rotated cube points a corner down at the top face.
+        assert!((distance - (1.5 - 0.5 * 2.0f32.sqrt())).abs() < 1e-4);
+        assert!((point_a.y - 0.5).abs() < 1e-4);
+        assert!(point_b.x.abs() < 1e-4);
+
+        let sunk = Convex::from_shape(&hull, Pose::new(Vec3::new(0.2, 0.7, 0.0), Quat::IDENTITY)).unwrap();
+        let Gjk::Overlap(simplex) = gjk(&a, &sunk) else { panic!("hulls overlap") };
+        let pen = epa(&a, &sunk, simplex, 0.0).unwrap();
+        assert!(pen.normal.abs_diff_eq(Vec3::Y, 1e-4));
+        assert!((pen.depth - 0.3).abs() < 1e-3);
+
+        // A triangle against the hull's side.
+        let tri = Convex::triangle([Vec3::new(1.0, -1.0, -1.0), Vec3::new(1.0, 1.0, 0.0), Vec3::new(1.0, -1.0, 1.0)], Pose::IDENTITY);
+        let (distance, _, point_b) = separated(&a, &tri);
+        assert!((distance - 0.5).abs() < 1e-4);
+        assert!((point_b.x - 1.0).abs() < 1e-4);
+    }
+}
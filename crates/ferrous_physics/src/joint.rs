@@ -0,0 +1,628 @@
+//! The `Joint` component and its velocity constraints.
+//!
+//! A joint is an entity of its own linking two bodies (or a body and the
+//! world).  Anchors and axes are given in world space; the first step after
+//! the joint appears records them in each body's frame, together with the
+//! bodies' relative rotation as the rest pose.  Joints are solved in the
+//! same iterations as contacts, as one block for the shared anchor point
+//! plus one row per locked axis, limit and motor.
+
+use ferrous_ecs::prelude::*;
+use glam::{Mat3, Quat, Vec3};
+
+use crate::solver::SolverBody;
+
+/// Fraction of the joint drift corrected per step.
+const BAUMGARTE: f32 = 0.2;
+/// Cap on the velocity used to correct drift (m/s or rad/s).
+const MAX_CORRECTION_VELOCITY: f32 = 4.0;
+/// Fraction of the distance to a position motor's target covered per step.
+const SERVO_RATE: f32 = 0.2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JointKind {
+    /// No relative motion.
+    Fixed,
+    /// Shared anchor point, free rotation (optionally inside a cone).
+    Ball,
+    /// Rotation around one axis only.
+    Hinge,
+    /// Translation along one axis only.
+    Prismatic,
+    /// Keeps the anchors at a distance (or within a range).
+    Distance,
+}
+
+/// Drives a hinge's angle or a prismatic joint's offset.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Motor {
+    /// Wanted speed (rad/s or m/s); the top speed when `target_position`
+    /// is set.
+    pub target_velocity: f32,
+    /// Angle or offset to move to and hold.
+    pub target_position: Option<f32>,
+    /// Strongest torque or force the motor applies.
+    pub max_force: f32,
+}
+
+impl Motor {
+    pub fn velocity(target_velocity: f32, max_force: f32) -> Self {
+        Motor { target_velocity, target_position: None, max_force }
+    }
+
+    pub fn position(target: f32, max_speed: f32, max_force: f32) -> Self {
+        Motor { target_velocity: max_speed, target_position: Some(target), max_force }
+    }
+}
+
+/// Sent into `Events<JointBroken>` when a joint exceeds its break force or
+/// torque.  The joint stays on its entity, marked broken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JointBroken {
+    pub joint: Entity,
+    pub body_a: Option<Entity>,
+    pub body_b: Entity,
+}
+
+/// Anchors and axes in each body's frame (relative to its centre of mass),
+/// recorded when the joint is first solved.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct JointFrames {
+    anchor_a: Vec3,
+    anchor_b: Vec3,
+    axis_a: Vec3,
+    axis_b: Vec3,
+    /// Perpendicular to the axis; measures hinge angles.
+    reference_a: Vec3,
+    reference_b: Vec3,
+    /// `rotation_a⁻¹ · rotation_b` at rest.
+    rest_rotation: Quat,
+}
+
+/// Links `body_b` to `body_a` (or to the world when `body_a` is `None`).
+///
+/// Both bodies need a `Collider`, a `Transform` and a `RigidBody`.  Unless
+/// [`with_collisions`](Self::with_collisions) is used the two bodies pass
+/// through each other.
+///
+/// Limits depend on the kind: the hinge angle range (radians), the
+/// prismatic offset range, the distance range, or for a ball joint the
+/// cone half-angle around `axis` (upper bound only).
+///
+/// # Example
+/// ```rust,ignore
+/// // A door swinging up to 100° on a hinge fixed to the world.
+/// world.spawn((Joint::hinge(None, door, Vec3::new(-0.5, 1.0, 0.0), Vec3::Y)
+///     .with_limits(0.0, 100f32.to_radians()),));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Joint {
+    pub body_a: Option<Entity>,
+    pub body_b: Entity,
+    pub kind: JointKind,
+    /// World-space anchor on each body when the joint is created.  Equal
+    /// for every kind but `Distance`.
+    pub anchor_a: Vec3,
+    pub anchor_b: Vec3,
+    /// World-space axis of hinges, prismatic joints and ball cones.
+    pub axis: Vec3,
+    pub limits: Option<(f32, f32)>,
+    pub motor: Option<Motor>,
+    /// Force (N) above which the joint breaks.
+    pub break_force: Option<f32>,
+    /// Torque (N·m) above which the joint breaks.
+    pub break_torque: Option<f32>,
+    /// Let the two bodies collide with each other.
+    pub collide_connected: bool,
+    broken: bool,
+    position: f32,
+    frames: Option<JointFrames>,
+    impulses: [f32; 8],
+}
+
+impl Component for Joint {}
+
+impl MapEntities for Joint {
+    fn map_entities(&mut self, map: &EntityMap) {
+        self.body_a = self.body_a.map(|e| map.map(e));
+        self.body_b = map.map(self.body_b);
+    }
+}
+
+impl Joint {
+    pub fn new(kind: JointKind, body_a: Option<Entity>, body_b: Entity, anchor: Vec3, axis: Vec3) -> Self {
+        Joint {
+            body_a,
+            body_b,
+            kind,
+            anchor_a: anchor,
+            anchor_b: anchor,
+            axis: axis.normalize_or(Vec3::Y),
+            limits: None,
+            motor: None,
+            break_force: None,
+            break_torque: None,
+            collide_connected: false,
+            broken: false,
+            position: 0.0,
+            frames: None,
+            impulses: [0.0; 8],
+        }
+    }
+
+    pub fn fixed(body_a: Option<Entity>, body_b: Entity, anchor: Vec3) -> Self {
+        Self::new(JointKind::Fixed, body_a, body_b, anchor, Vec3::Y)
+    }
+
+    pub fn ball(body_a: Option<Entity>, body_b: Entity, anchor: Vec3) -> Self {
+        Self::new(JointKind::Ball, body_a, body_b, anchor, Vec3::Y)
+    }
+
+    pub fn hinge(body_a: Option<Entity>, body_b: Entity, anchor: Vec3, axis: Vec3) -> Self {
+        Self::new(JointKind::Hinge, body_a, body_b, anchor, axis)
+    }
+
+    pub fn prismatic(body_a: Option<Entity>, body_b: Entity, anchor: Vec3, axis: Vec3) -> Self {
+        Self::new(JointKind::Prismatic, body_a, body_b, anchor, axis)
+    }
+
+    /// Keeps `anchor_a` and `anchor_b` at their current distance; use
+    /// [`with_limits`](Self::with_limits) for a rope or a spring range.
+    pub fn distance(body_a: Option<Entity>, body_b: Entity, anchor_a: Vec3, anchor_b: Vec3) -> Self {
+        let mut joint = Self::new(JointKind::Distance, body_a, body_b, anchor_a, Vec3::Y);
+        joint.anchor_b = anchor_b;
+        joint
+    }
+
+    pub fn with_limits(mut self, min: f32, max: f32) -> Self {
+        self.limits = Some((min, max));
+        self
+    }
+
+    /// Limit a ball joint to a cone of `half_angle` around `axis`.
+    pub fn with_cone(mut self, axis: Vec3, half_angle: f32) -> Self {
+        self.axis = axis.normalize_or(Vec3::Y);
+        self.limits = Some((0.0, half_angle));
+        self
+    }
+
+    pub fn with_motor(mut self, motor: Motor) -> Self {
+        self.motor = Some(motor);
+        self
+    }
+
+    pub fn with_break_force(mut self, force: f32) -> Self {
+        self.break_force = Some(force);
+        self
+    }
+
+    pub fn with_break_torque(mut self, torque: f32) -> Self {
+        self.break_torque = Some(torque);
+        self
+    }
+
+    pub fn with_collisions(mut self) -> Self {
+        self.collide_connected = true;
+        self
+    }
+
+    pub fn is_broken(&self) -> bool {
+        self.broken
+    }
+
+    /// Hinge angle, prismatic offset, anchor distance or ball-joint swing
+    /// angle at the last step.
+    pub fn position(&self) -> f32 {
+        self.position
+    }
+
+    /// Keep the impulses of a solved step for warm starting, and break if
+    /// they exceed the thresholds.  Returns `true` if the joint broke.
+    pub(crate) fn store(&mut self, constraint: &JointConstraint, dt: f32) -> bool {
+        self.impulses = constraint.impulses;
+        self.position = constraint.position;
+        let (force, torque) = constraint.loads(dt);
+        if self.break_force.is_some_and(|f| force > f) || self.break_torque.is_some_and(|t| torque > t) {
+            self.broken = true;
+            self.impulses = [0.0; 8];
+        }
+        self.broken
+    }
+
+    /// Record the anchors, axes and rest pose in the bodies' frames.
+    pub(crate) fn bind(&mut self, a: &SolverBody, b: &SolverBody) {
+        if self.frames.is_some() {
+            return;
+        }
+        let reference = self.axis.any_orthogonal_vector().normalize();
+        let (inv_a, inv_b) = (a.rotation.inverse(), b.rotation.inverse());
+        self.frames = Some(JointFrames {
+            anchor_a: inv_a * (self.anchor_a - a.position),
+            anchor_b: inv_b * (self.anchor_b - b.position),
+            axis_a: inv_a * self.axis,
+            axis_b: inv_b * self.axis,
+            reference_a: inv_a * reference,
+            reference_b: inv_b * reference,
+            rest_rotation: inv_a * b.rotation,
+        });
+        if self.kind == JointKind::Distance && self.limits.is_none() {
+            let length = self.anchor_a.distance(self.anchor_b);
+            self.limits = Some((length, length));
+        }
+    }
+}
+
+/// One scalar constraint: `J·v` (with `J` split into a linear part and the
+/// angular parts of A and B) is driven towards `bias`, the accumulated
+/// impulse staying within `min..=max`.
+#[derive(Debug, Clone, Copy)]
+struct Row {
+    slot: usize,
+    linear: Vec3,
+    angular_a: Vec3,
+    angular_b: Vec3,
+    mass: f32,
+    bias: f32,
+    min: f32,
+    max: f32,
+    /// Counts towards the break torque instead of the break force.
+    rotational: bool,
+}
+
+impl Row {
+    fn new(a: &SolverBody, b: &SolverBody, slot: usize, linear: Vec3, angular_a: Vec3, angular_b: Vec3, bias: f32) -> Self {
+        let k = (a.inv_mass + b.inv_mass) * linear.length_squared()
+            + angular_a.dot(a.inv_inertia * angular_a)
+            + angular_b.dot(b.inv_inertia * angular_b);
+        Row {
+            slot,
+            linear,
+            angular_a,
+            angular_b,
+            mass: if k > 0.0 { 1.0 / k } else { 0.0 },
+            bias,
+            min: f32::NEG_INFINITY,
+            max: f32::INFINITY,
+            rotational: linear == Vec3::ZERO,
+        }
+    }
+
+    /// Along the line through the anchor points: relative point velocity.
+    fn point(a: &SolverBody, b: &SolverBody, slot: usize, r_a: Vec3, r_b: Vec3, dir: Vec3, bias: f32) -> Self {
+        Self::new(a, b, slot, dir, r_a.cross(dir), r_b.cross(dir), bias)
+    }
+
+    /// About `axis`: relative angular velocity.
+    fn angular(a: &SolverBody, b: &SolverBody, slot: usize, axis: Vec3, bias: f32) -> Self {
+        Self::new(a, b, slot, Vec3::ZERO, axis, axis, bias)
+    }
+
+    fn clamped(mut self, min: f32, max: f32) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    fn velocity(&self, a: &SolverBody, b: &SolverBody) -> f32 {
+        self.linear.dot(b.linear_velocity - a.linear_velocity) + self.angular_b.dot(b.angular_velocity)
+            - self.angular_a.dot(a.angular_velocity)
+    }
+
+    fn apply(&self, a: &mut SolverBody, b: &mut SolverBody, impulse: f32) {
+        a.linear_velocity -= self.linear * (impulse * a.inv_mass);
+        a.angular_velocity -= a.inv_inertia * self.angular_a * impulse;
+        b.linear_velocity += self.linear * (impulse * b.inv_mass);
+        b.angular_velocity += b.inv_inertia * self.angular_b * impulse;
+    }
+}
+
+/// Upper and lower limit rows for a coordinate at `position` moving at
+/// `J·v`: the gap to each limit may close this step, drift past it is
+/// pushed back.
+fn limit_rows(row: Row, position: f32, (min, max): (f32, f32), dt: f32) -> [Row; 2] {
+    let target = |gap: f32| if gap >= 0.0 { gap / dt } else { (BAUMGARTE * gap / dt).max(-MAX_CORRECTION_VELOCITY) };
+    let lower = Row { bias: -target(position - min), ..row }.clamped(0.0, f32::INFINITY);
+    let upper = Row { slot: row.slot + 1, bias: target(max - position), ..row }.clamped(f32::NEG_INFINITY, 0.0);
+    [lower, upper]
+}
+
+fn motor_row(row: Row, position: f32, motor: &Motor, dt: f32) -> Row {
+    let speed = match motor.target_position {
+        Some(target) => {
+            let top = motor.target_velocity.abs();
+            ((target - position) * SERVO_RATE / dt).clamp(-top, top)
+        }
+        None => motor.target_velocity,
+    };
+    let limit = motor.max_force.max(0.0) * dt;
+    Row { bias: speed, ..row }.clamped(-limit, limit)
+}
+
+fn correction(error: Vec3, dt: f32) -> Vec3 {
+    (error * (BAUMGARTE / dt)).clamp_length_max(MAX_CORRECTION_VELOCITY)
+}
+
+/// Matrix of `r × v`.
+fn skew(r: Vec3) -> Mat3 {
+    Mat3::from_cols(Vec3::new(0.0, r.z, -r.y), Vec3::new(-r.z, 0.0, r.x), Vec3::new(r.y, -r.x, 0.0))
+}
+
+/// A joint prepared for one step.
+#[derive(Debug, Clone)]
+pub(crate) struct JointConstraint {
+    pub body_a: usize,
+    pub body_b: usize,
+    /// Shared anchor point: arms and inverse effective mass of the 3×3
+    /// block (slots 0–2).
+    point: Option<(Vec3, Vec3, Mat3, Vec3)>,
+    rows: Vec<Row>,
+    pub impulses: [f32; 8],
+    /// The joint coordinate at the start of the step.
+    pub position: f32,
+}
+
+impl JointConstraint {
+    /// Constraint for a bound `joint` between `bodies[pair.0]` and
+    /// `bodies[pair.1]`.
+    pub fn new(bodies: &[SolverBody], pair: (usize, usize), joint: &Joint, dt: f32) -> Self {
+        let (body_a, body_b) = pair;
+        let (a, b) = (&bodies[body_a], &bodies[body_b]);
+        let f = joint.frames.expect("joint not bound");
+        let (r_a, r_b) = (a.rotation * f.anchor_a, b.rotation * f.anchor_b);
+        let (p_a, p_b) = (a.position + r_a, b.position + r_b);
+        let (axis_a, axis_b) = (a.rotation * f.axis_a, b.rotation * f.axis_b);
+        let mut point = None;
+        let mut rows = Vec::new();
+        let mut position = 0.0;
+
+        let shares_point = matches!(joint.kind, JointKind::Fixed | JointKind::Ball | JointKind::Hinge);
+        if shares_point {
+            let k = Mat3::from_diagonal(Vec3::splat(a.inv_mass + b.inv_mass))
+                - skew(r_a) * a.inv_inertia * skew(r_a)
+                - skew(r_b) * b.inv_inertia * skew(r_b);
+            if k.determinant().abs() > 1e-12 {
+                point = Some((r_a, r_b, k.inverse(), -correction(p_b - p_a, dt)));
+            }
+        }
+        // Angular drift from the rest pose, as a rotation vector.
+        let locked_rotation = || {
+            let mut error = a.rotation * f.rest_rotation * b.rotation.inverse();
+            if error.w < 0.0 {
+                error = -error;
+            }
+            Vec3::new(error.x, error.y, error.z) * 2.0
+        };
+
+        match joint.kind {
+            JointKind::Fixed => {
+                let error = locked_rotation();
+                for (i, axis) in [Vec3::X, Vec3::Y, Vec3::Z].into_iter().enumerate() {
+                    rows.push(Row::angular(a, b, 3 + i, axis, correction(error, dt).dot(axis)));
+                }
+            }
+            JointKind::Ball => {
+                position = axis_a.dot(axis_b).clamp(-1.0, 1.0).acos();
+                let swing = axis_a.cross(axis_b).try_normalize();
+                if let (Some((_, half_angle)), Some(n)) = (joint.limits, swing) {
+                    // Only the upper row (slot 3) of the pair is needed.
+                    let [_, upper] = limit_rows(Row::angular(a, b, 2, n, 0.0), position, (0.0, half_angle), dt);
+                    rows.push(upper);
+                }
+            }
+            JointKind::Hinge => {
+                let (t0, t1) = axis_a.any_orthonormal_pair();
+                let error = correction(axis_b.cross(axis_a), dt);
+                rows.push(Row::angular(a, b, 3, t0, error.dot(t0)));
+                rows.push(Row::angular(a, b, 4, t1, error.dot(t1)));
+                let (ref_a, ref_b) = (a.rotation * f.reference_a, b.rotation * f.reference_b);
+                position = axis_a.dot(ref_a.cross(ref_b)).atan2(ref_a.dot(ref_b));
+                let row = Row::angular(a, b, 5, axis_a, 0.0);
+                if let Some(limits) = joint.limits {
+                    rows.extend(limit_rows(row, position, limits, dt));
+                }
+                if let Some(motor) = &joint.motor {
+                    rows.push(motor_row(Row { slot: 7, ..row }, position, motor, dt));
+                }
+            }
+            JointKind::Prismatic => {
+                // Arms to B's anchor so the rows see the sliding point.
+                let arm_a = p_b - a.position;
+                let offset = p_b - p_a;
+                let (t0, t1) = axis_a.any_orthonormal_pair();
+                let drift = correction(offset - axis_a * offset.dot(axis_a), dt);
+                rows.push(Row::point(a, b, 0, arm_a, r_b, t0, -drift.dot(t0)));
+                rows.push(Row::point(a, b, 1, arm_a, r_b, t1, -drift.dot(t1)));
+                let error = locked_rotation();
+                for (i, axis) in [Vec3::X, Vec3::Y, Vec3::Z].into_iter().enumerate() {
+                    rows.push(Row::angular(a, b, 2 + i, axis, correction(error, dt).dot(axis)));
+                }
+                position = offset.dot(axis_a);
+                let row = Row::point(a, b, 5, arm_a, r_b, axis_a, 0.0);
+                if let Some(limits) = joint.limits {
+                    rows.extend(limit_rows(row, position, limits, dt));
+                }
+                if let Some(motor) = &joint.motor {
+                    rows.push(motor_row(Row { slot: 7, ..row }, position, motor, dt));
+                }
+            }
+            JointKind::Distance => {
+                let offset = p_b - p_a;
+                position = offset.length();
+                let dir = offset.try_normalize().unwrap_or(Vec3::Y);
+                let row = Row::point(a, b, 0, r_a, r_b, dir, 0.0);
+                let limits = joint.limits.unwrap_or((position, position));
+                rows.extend(limit_rows(row, position, limits, dt));
+            }
+        }
+
+        JointConstraint { body_a, body_b, point, rows, impulses: joint.impulses, position }
+    }
+
+    fn bodies<'b>(&self, bodies: &'b mut [SolverBody]) -> (&'b mut SolverBody, &'b mut SolverBody) {
+        debug_assert_ne!(self.body_a, self.body_b);
+        if self.body_a < self.body_b {
+            let (low, high) = bodies.split_at_mut(self.body_b);
+            (&mut low[self.body_a], &mut high[0])
+        } else {
+            let (low, high) = bodies.split_at_mut(self.body_a);
+            (&mut high[0], &mut low[self.body_b])
+        }
+    }
+
+    /// Re-apply last step's impulses.
+    pub fn warm_start(&mut self, bodies: &mut [SolverBody]) {
+        let (a, b) = self.bodies(bodies);
+        if let Some((r_a, r_b, _, _)) = self.point {
+            let p = Vec3::new(self.impulses[0], self.impulses[1], self.impulses[2]);
+            a.apply_impulse(r_a, -p);
+            b.apply_impulse(r_b, p);
+        }
+        for row in &self.rows {
+            row.apply(a, b, self.impulses[row.slot]);
+        }
+    }
+
+    pub fn solve(&mut self, bodies: &mut [SolverBody]) {
+        let (a, b) = self.bodies(bodies);
+        for row in &self.rows {
+            let old = self.impulses[row.slot];
+            let new = (old + row.mass * (row.bias - row.velocity(a, b))).clamp(row.min, row.max);
+            self.impulses[row.slot] = new;
+            row.apply(a, b, new - old);
+        }
+        if let Some((r_a, r_b, inv_k, bias)) = self.point {
+            let relative = b.velocity_at(r_b) - a.velocity_at(r_a);
+            let p = inv_k * (bias - relative);
+            a.apply_impulse(r_a, -p);
+            b.apply_impulse(r_b, p);
+            for (slot, dp) in p.to_array().into_iter().enumerate() {
+                self.impulses[slot] += dp;
+            }
+        }
+    }
+
+    /// Force and torque the joint applied this step.
+    pub fn loads(&self, dt: f32) -> (f32, f32) {
+        let (mut force, mut torque) = (0.0f32, 0.0f32);
+        if self.point.is_some() {
+            force += self.impulses[..3].iter().map(|i| i * i).sum::<f32>();
+        }
+        for row in &self.rows {
+            let i = self.impulses[row.slot];
+            if row.rotational {
+                torque += i * i;
+            } else {
+                force += i * i;
+            }
+        }
+        (force.sqrt() / dt, torque.sqrt() / dt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Collider, PhysicsSystem, RigidBody};
+    use ferrous_core::transform::Transform;
+    use ferrous_ecs::event::Events;
+    use ferrous_ecs::system::System;
+
+    #[test]
+    fn hinged_door_swings_to_its_limit_and_breaks_under_load() {
+        let mut world = ferrous_ecs::world::World::new();
+        let mut resources = ResourceMap::new();
+        // Door hanging sideways from a horizontal hinge: gravity swings it
+        // down until the 60° limit holds it.
+        let door = world.spawn((
+            Transform::from_position(Vec3::new(1.0, 0.0, 0.0)),
+            RigidBody::dynamic().with_sleeping_disabled(),
+            Collider::cuboid(Vec3::new(1.0, 0.05, 0.5)),
+        ));
+        let hinge = world.spawn((Joint::hinge(None, door, Vec3::ZERO, Vec3::Z).with_limits(-60f32.to_radians(), 0.0),));
+        for _ in 0..180 {
+            PhysicsSystem.run(&mut world, &mut resources);
+        }
+        let joint = world.get::<Joint>(hinge).unwrap();
+        assert!((joint.position() + 60f32.to_radians()).abs() < 0.03, "{}", joint.position());
+        let t = world.get::<Transform>(door).unwrap();
+        let expected = Quat::from_rotation_z(-60f32.to_radians()) * Vec3::X;
+        assert!(t.position.distance(expected) < 0.03, "{:?}", t.position);
+
+        // A falling weight on the door snaps the hinge.
+        world.get_mut::<Joint>(hinge).unwrap().break_force = Some(400.0);
+        world.spawn((
+            Transform::from_position(expected + Vec3::new(0.0, 2.0, 0.0)),
+            RigidBody::dynamic().with_mass(50.0),
+            Collider::sphere(0.2),
+        ));
+        let mut broken = Vec::new();
+        for _ in 0..120 {
+            PhysicsSystem.run(&mut world, &mut resources);
+            if let Some(events) = resources.get_mut::<Events<JointBroken>>() {
+                events.update();
+                broken.extend(events.read().copied());
+            }
+        }
+        assert_eq!(broken, vec![JointBroken { joint: hinge, body_a: None, body_b: door }]);
+        assert!(world.get::<Joint>(hinge).unwrap().is_broken());
+        assert!(world.get::<Transform>(door).unwrap().position.y < -2.0);
+    }
+
+    #[test]
+    fn chain_and_slider_hold_their_constraints() {
+        let mut world = ferrous_ecs::world::World::new();
+        let mut resources = ResourceMap::new();
+        // Three damped links on ball joints, hanging from the world.
+        let mut links = Vec::new();
+        for i in 0..3 {
+            let link = world.spawn((
+                Transform::from_position(Vec3::new(0.5 + i as f32, 5.0, 0.0)),
+                RigidBody::dynamic().with_damping(2.0, 2.0),
+                Collider::capsule(0.3, 0.1).with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_2)),
+            ));
+            let anchor = Vec3::new(i as f32, 5.0, 0.0);
+            world.spawn((Joint::ball(links.last().copied(), link, anchor),));
+            links.push(link);
+        }
+        // A block sliding along X on a rail, driven by a motor until the
+        // end stop.
+        let slider = world.spawn((
+            Transform::from_position(Vec3::new(0.0, -5.0, 0.0)),
+            RigidBody::dynamic(),
+            Collider::cuboid(Vec3::splat(0.25)),
+        ));
+        let rail = world.spawn((Joint::prismatic(None, slider, Vec3::new(0.0, -5.0, 0.0), Vec3::X)
+            .with_limits(-1.0, 2.0)
+            .with_motor(Motor::velocity(1.0, 100.0)),));
+
+        for _ in 0..240 {
+            PhysicsSystem.run(&mut world, &mut resources);
+        }
+        let tip = world.get::<Transform>(links[2]).unwrap();
+        let end = tip.position + tip.rotation * Vec3::X * 0.5;
+        // Hanging straight down, three unit links below the anchor.
+        assert!(end.distance(Vec3::new(0.0, 2.0, 0.0)) < 0.1, "{end:?}");
+        let block = world.get::<Transform>(slider).unwrap();
+        assert!(block.position.distance(Vec3::new(2.0, -5.0, 0.0)) < 0.02, "{:?}", block.position);
+        assert!(block.rotation.angle_between(Quat::IDENTITY) < 0.01);
+        assert!((world.get::<Joint>(rail).unwrap().position() - 2.0).abs() < 0.02);
+    }
+
+    #[test]
+    fn joints_follow_remapped_bodies() {
+        let mut world = ferrous_ecs::world::World::new();
+        let a = world.spawn((Transform::IDENTITY,));
+        let b = world.spawn((Transform::IDENTITY,));
+        let c = world.spawn((Transform::IDENTITY,));
+        let mut map = EntityMap::new();
+        map.insert(a, c);
+        map.insert(b, a);
+
+        let mut joint = Joint::ball(Some(a), b, Vec3::ZERO);
+        joint.map_entities(&map);
+        assert_eq!((joint.body_a, joint.body_b), (Some(c), a));
+        let mut anchored = Joint::hinge(None, c, Vec3::ZERO, Vec3::Y);
+        anchored.map_entities(&map);
+        assert_eq!((anchored.body_a, anchored.body_b), (None, c));
+    }
+}
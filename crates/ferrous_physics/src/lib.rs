@@ -0,0 +1,67 @@
+//! `ferrous_physics` — rigid-body dynamics for Ferrous Engine's ECS.
+//!
+//! # Architecture
+//!
+//! | Module        | Responsibility                                              |
+//! |---------------|-------------------------------------------------------------|
+//! | `body`        | `RigidBody` component (dynamic / kinematic / static)        |
+//! | `character`   | `CharacterController`: capsule collide-and-slide movement   |
+//! | `collider`    | `Collider` component: sphere, box, capsule, hull, tri-mesh  |
+//! | `pose`        | `Pose` — translation + rotation                             |
+//! | `gjk`         | GJK distance and EPA penetration on convex shapes           |
+//! | `joint`       | `Joint` component: fixed, ball, hinge, prismatic, distance  |
+//! | `narrowphase` | contact manifolds (up to four points per pair)              |
+//! | `solver`      | sequential impulses with friction, restitution, warm start  |
+//! | `world`       | `PhysicsWorld` resource: broadphase, step, islands, sleeping|
+//! | `system`      | `PhysicsSystem`, `CollisionEvent`                           |
+//!
+//! Each fixed step the world gathers every entity with a [`Collider`] and a
+//! `Transform`, updates the broadphase ([`AabbTree`](ferrous_core::bvh::AabbTree))
+//! with their bounds, generates contacts for the overlapping pairs, solves
+//! them together with gravity, user forces and [`Joint`]s, and writes the
+//! new poses back to `Transform`.  Bodies that stay at rest fall asleep per
+//! island and stop costing anything until something touches them.
+//!
+//! Player characters use a [`CharacterController`] instead of a dynamic
+//! body: `CharacterControllerSystem` sweeps its capsule through the world
+//! after the step, sliding along walls, climbing steps and snapping to the
+//! ground.
+//!
+//! # Example
+//! ```rust,ignore
+//! use ferrous_physics::{Collider, PhysicsSystem, RigidBody};
+//!
+//! world.spawn((Transform::IDENTITY, Collider::cuboid(Vec3::new(10.0, 0.5, 10.0))));
+//! world.spawn((
+//!     Transform::from_position(Vec3::Y * 4.0),
+//!     RigidBody::dynamic(),
+//!     Collider::sphere(0.5).with_restitution(0.5),
+//! ));
+//! scheduler.add(Stage::FixedUpdate, PhysicsSystem.label(ferrous_physics::labels::PHYSICS));
+//! ```
+
+pub mod body;
+pub mod character;
+pub mod collider;
+pub(crate) mod gjk;
+pub mod joint;
+pub mod narrowphase;
+pub mod pose;
+pub(crate) mod solver;
+pub mod system;
+pub mod world;
+
+/// Labels attached to the physics systems when the app registers them.
+pub mod labels {
+    pub const PHYSICS: &str = "ferrous::physics";
+    pub const CHARACTER: &str = "ferrous::character";
+}
+
+pub use body::{BodyKind, RigidBody};
+pub use character::{CharacterController, CharacterControllerSystem};
+pub use collider::{Collider, ColliderShape, ConvexHull, TriMesh};
+pub use joint::{Joint, JointBroken, JointKind, Motor};
+pub use narrowphase::ContactPoint;
+pub use pose::Pose;
+pub use system::{CollisionEvent, PhysicsSystem};
+pub use world::{Manifold, PhysicsWorld};
@@ -0,0 +1,431 @@
+//! Contact manifolds between pairs of colliders.
+//!
+//! GJK/EPA gives one normal and one pair of witness points.  To let boxes
+//! rest on faces instead of rocking on a corner, the *features* both shapes
+//! present along that normal (face, edge or vertex) are then clipped
+//! against each other, yielding up to four points per pair.
+
+use ferrous_core::bounds::Aabb;
+use glam::{Vec2, Vec3};
+
+use crate::collider::{Collider, ColliderShape, TriMesh};
+use crate::gjk::{gjk, touching, Convex, Gjk, TOUCH_DISTANCE};
+use crate::pose::Pose;
+
+/// One point of contact between colliders A and B.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContactPoint {
+    /// World-space point halfway between the two surfaces.
+    pub point: Vec3,
+    /// Unit normal pointing from A towards B.
+    pub normal: Vec3,
+    /// Penetration depth; negative for a speculative contact between shapes
+    /// still apart by less than the contact margin.
+    pub depth: f32,
+}
+
+/// Vertices within this fraction of a shape's size of its extreme point
+/// count as one feature (≈ 1.5° of tilt).
+const FEATURE_TOLERANCE: f32 = 0.025;
+/// Triangle normals are preferred over the EPA normal while they need at
+/// most this much more push-out, so shapes slide over the inner edges of
+/// a mesh instead of catching on them.
+const FACE_NORMAL_BIAS: f32 = 0.02;
+const MAX_POINTS: usize = 4;
+
+/// Append the contacts between `a` (entity at `pose_a`) and `b` to `out`.
+/// Shapes further apart than `margin` produce none.
+pub(crate) fn collide(a: &Collider, pose_a: &Pose, b: &Collider, pose_b: &Pose, margin: f32, out: &mut Vec<ContactPoint>) {
+    let start = out.len();
+    let (pose_a, pose_b) = (pose_a.mul(&a.offset), pose_b.mul(&b.offset));
+    match (&a.shape, &b.shape) {
+        (ColliderShape::TriMesh(_), ColliderShape::TriMesh(_)) => {}
+        (ColliderShape::TriMesh(mesh), _) => {
+            let Some(convex) = Convex::from_shape(&b.shape, pose_b) else { return };
+            convex_trimesh(&convex, mesh, &pose_a, margin, out);
+            for c in &mut out[start..] {
+                c.normal = -c.normal;
+            }
+        }
+        (_, ColliderShape::TriMesh(mesh)) => {
+            let Some(convex) = Convex::from_shape(&a.shape, pose_a) else { return };
+            convex_trimesh(&convex, mesh, &pose_b, margin, out);
+        }
+        _ => {
+            let (Some(ca), Some(cb)) = (Convex::from_shape(&a.shape, pose_a), Convex::from_shape(&b.shape, pose_b)) else {
+                return;
+            };
+            if let Some(contact) = closest(&ca, &cb, margin) {
+                manifold(&ca, &cb, contact, margin, out);
+            }
+        }
+    }
+    reduce(out, start);
+}
+
+/// Deepest contact of two convex shapes: normal, depth and the surface
+/// points on A and B.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Closest {
+    pub normal: Vec3,
+    pub depth: f32,
+    pub point_a: Vec3,
+    pub point_b: Vec3,
+}
+
+pub(crate) fn closest(a: &Convex, b: &Convex, margin: f32) -> Option<Closest> {
+    let radii = a.radius + b.radius;
+    let (normal, depth, core_a, core_b) = match gjk(a, b) {
+        Gjk::Separated { distance, point_a, point_b } if distance >= TOUCH_DISTANCE => {
+            if radii - distance < -margin {
+                return None;
+            }
+            ((point_b - point_a) / distance, radii - distance, point_a, point_b)
+        }
+        // Touching or overlapping cores: the witness points say nothing
+        // about the normal, so take it from the penetration instead.
+        _ => match touching(a, b) {
+            Some(pen) => (pen.normal, pen.depth + radii, pen.point_a, pen.point_b),
+            // Flat Minkowski difference (cores touching edge-on): push the
+            // centres apart.
+            None => {
+                let normal = (b.center() - a.center()).normalize_or(Vec3::Y);
+                (normal, radii, a.support(normal), b.support(-normal))
+            }
+        },
+    };
+    Some(Closest { normal, depth, point_a: core_a + normal * a.radius, point_b: core_b - normal * b.radius })
+}
+
+fn convex_trimesh(convex: &Convex, mesh: &TriMesh, mesh_pose: &Pose, margin: f32, out: &mut Vec<ContactPoint>) {
+    let reach = convex.radius + margin;
+    let min = Vec3::new(convex.support(Vec3::NEG_X).x, convex.support(Vec3::NEG_Y).y, convex.support(Vec3::NEG_Z).z);
+    let max = Vec3::new(convex.support(Vec3::X).x, convex.support(Vec3::Y).y, convex.support(Vec3::Z).z);
+    let bounds = Aabb::new(min - Vec3::splat(reach), max + Vec3::splat(reach));
+    let mut triangles = Vec::new();
+    mesh.query(&bounds.transform(&mesh_pose.inverse().matrix()), |t| triangles.push(t));
+
+    let center = convex.center();
+    let start = out.len();
+    for t in triangles {
+        let tri = Convex::triangle(mesh.triangle(t), *mesh_pose);
+        let [v0, v1, v2] = mesh.triangle(t).map(|v| mesh_pose.transform_point(v));
+        let Some(face) = (v1 - v0).cross(v2 - v0).try_normalize() else { continue };
+        // Triangles are one-sided: shapes behind them pass through.
+        if face.dot(center - v0) < 0.0 {
+            continue;
+        }
+        let Some(mut contact) = closest(convex, &tri, margin) else { continue };
+        let lowest = convex.support(-face) - face * convex.radius;
+        let face_depth = face.dot(v0 - lowest);
+        if face_depth <= contact.depth + FACE_NORMAL_BIAS {
+            contact = Closest { normal: -face, depth: face_depth, point_a: lowest, point_b: lowest + face * face_depth };
+        }
+        if contact.depth >= -margin {
+            let before = out.len();
+            manifold(convex, &tri, contact, margin, out);
+            // Triangles sharing an edge or vertex both report contacts on it.
+            let new: Vec<ContactPoint> = out.drain(before..).collect();
+            for c in new {
+                if !out[start..].iter().any(|o| o.point.distance_squared(c.point) < 1e-8) {
+                    out.push(c);
+                }
+            }
+        }
+    }
+}
+
+/// Turn the single deepest contact into up to four points by clipping the
+/// features of A and B facing each other.
+fn manifold(a: &Convex, b: &Convex, contact: Closest, margin: f32, out: &mut Vec<ContactPoint>) {
+    let n = contact.normal;
+    let mut fa = Vec::new();
+    let mut fb = Vec::new();
+    a.feature(n, FEATURE_TOLERANCE * a.extent(), &mut fa);
+    b.feature(-n, FEATURE_TOLERANCE * b.extent(), &mut fb);
+    fa.iter_mut().for_each(|p| *p += n * a.radius);
+    fb.iter_mut().for_each(|p| *p -= n * b.radius);
+
+    let single = ContactPoint { point: (contact.point_a + contact.point_b) * 0.5, normal: n, depth: contact.depth };
+    let start = out.len();
+    match (fa.len(), fb.len()) {
+        (1, _) | (_, 1) => {}
+        (2, 2) => {
+            let (da, db) = (fa[1] - fa[0], fb[1] - fb[0]);
+            if da.normalize().cross(db.normalize()).length() < 0.05 {
+                // Parallel edges: keep the overlapping stretch of B's edge.
+                let mut seg = clip_half_space(&fb, fa[0], da);
+                seg = clip_half_space(&seg, fa[1], -da);
+                push_clipped(&seg, &fa, n, true, margin, out);
+            }
+        }
+        (na, nb) => {
+            let ref_is_a = na >= nb;
+            let (reference, incident) = if ref_is_a { (&fa, &fb) } else { (&fb, &fa) };
+            let reference = order_polygon(reference, n);
+            let mut poly = if incident.len() >= 3 { order_polygon(incident, n) } else { incident.clone() };
+            let centroid = reference.iter().copied().sum::<Vec3>() / reference.len() as f32;
+            for i in 0..reference.len() {
+                let (p, q) = (reference[i], reference[(i + 1) % reference.len()]);
+                let mut inward = n.cross(q - p);
+                if inward.dot(centroid - p) < 0.0 {
+                    inward = -inward;
+                }
+                poly = clip_half_space(&poly, p, inward);
+                if poly.is_empty() {
+                    break;
+                }
+            }
+            push_clipped(&poly, &reference, n, ref_is_a, margin, out);
+        }
+    }
+    if out.len() == start {
+        out.push(single);
+    }
+}
+
+/// Emit the clipped incident points whose depth below the reference
+/// feature's plane is at least `-margin`.
+fn push_clipped(points: &[Vec3], reference: &[Vec3], n: Vec3, ref_is_a: bool, margin: f32, out: &mut Vec<ContactPoint>) {
+    let start = out.len();
+    let plane = if ref_is_a {
+        reference.iter().map(|p| p.dot(n)).fold(f32::NEG_INFINITY, f32::max)
+    } else {
+        reference.iter().map(|p| p.dot(n)).fold(f32::INFINITY, f32::min)
+    };
+    for &p in points {
+        let depth = if ref_is_a { plane - p.dot(n) } else { p.dot(n) - plane };
+        if depth < -margin || out[start..].iter().any(|c| c.point.distance_squared(p) < 1e-8) {
+            continue;
+        }
+        let point = if ref_is_a { p + n * depth * 0.5 } else { p - n * depth * 0.5 };
+        out.push(ContactPoint { point, normal: n, depth });
+    }
+}
+
+/// Sutherland–Hodgman: the part of `poly` on the side of the plane through
+/// `origin` that `normal` points to.
+fn clip_half_space(poly: &[Vec3], origin: Vec3, normal: Vec3) -> Vec<Vec3> {
+    let mut out = Vec::with_capacity(poly.len() + 2);
+    for i in 0..poly.len() {
+        let (p, q) = (poly[i], poly[(i + 1) % poly.len()]);
+        let (dp, dq) = ((p - origin).dot(normal), (q - origin).dot(normal));
+        if dp >= 0.0 {
+            out.push(p);
+        }
+        if (dp >= 0.0) != (dq >= 0.0) {
+            out.push(p + (q - p) * (dp / (dp - dq)));
+        }
+    }
+    out
+}
+
+/// The convex hull of `points` seen along `n`, in winding order.
+fn order_polygon(points: &[Vec3], n: Vec3) -> Vec<Vec3> {
+    let (u, v) = n.any_orthonormal_pair();
+    let mut idx: Vec<(Vec2, Vec3)> = points.iter().map(|&p| (Vec2::new(p.dot(u), p.dot(v)), p)).collect();
+    idx.sort_by(|a, b| a.0.x.total_cmp(&b.0.x).then(a.0.y.total_cmp(&b.0.y)));
+    let cross = |o: Vec2, a: Vec2, b: Vec2| (a - o).perp_dot(b - o);
+    // Andrew's monotone chain.
+    let mut hull: Vec<(Vec2, Vec3)> = Vec::with_capacity(idx.len() * 2);
+    for pass in 0..2 {
+        let floor = hull.len();
+        let iter: Box<dyn Iterator<Item = &(Vec2, Vec3)>> =
+            if pass == 0 { Box::new(idx.iter()) } else { Box::new(idx.iter().rev()) };
+        for &p in iter {
+            while hull.len() >= floor + 2 && cross(hull[hull.len() - 2].0, hull[hull.len() - 1].0, p.0) <= 1e-9 {
+                hull.pop();
+            }
+            hull.push(p);
+        }
+        hull.pop();
+    }
+    if hull.len() < 2 {
+        return points.to_vec();
+    }
+    hull.into_iter().map(|(_, p)| p).collect()
+}
+
+/// Keep at most [`MAX_POINTS`] of `out[start..]`: the deepest point, then
+/// the ones spanning the largest area.
+fn reduce(out: &mut Vec<ContactPoint>, start: usize) {
+    if out.len() - start <= MAX_POINTS {
+        return;
+    }
+    let points: Vec<ContactPoint> = out.drain(start..).collect();
+    let deepest = (0..points.len()).max_by(|&i, &j| points[i].depth.total_cmp(&points[j].depth)).unwrap();
+    let p0 = points[deepest].point;
+    let far = (0..points.len())
+        .max_by(|&i, &j| points[i].point.distance_squared(p0).total_cmp(&points[j].point.distance_squared(p0)))
+        .unwrap();
+    let p1 = points[far].point;
+    let area = |p: Vec3| (p1 - p0).cross(p - p0);
+    let third = (0..points.len())
+        .max_by(|&i, &j| area(points[i].point).length_squared().total_cmp(&area(points[j].point).length_squared()))
+        .unwrap();
+    let p2 = points[third].point;
+    let normal = area(p2).normalize_or(points[deepest].normal);
+    // The fourth point adds the most area outside the triangle.
+    let tri = [p0, p1, p2];
+    let outside = |p: Vec3| -> f32 {
+        (0..3).map(|k| (-(tri[(k + 1) % 3] - tri[k]).cross(p - tri[k]).dot(normal)).max(0.0)).sum()
+    };
+    let fourth = (0..points.len())
+        .filter(|i| ![deepest, far, third].contains(i))
+        .max_by(|&i, &j| outside(points[i].point).total_cmp(&outside(points[j].point)));
+    let mut keep = vec![deepest, far, third];
+    keep.extend(fourth);
+    keep.dedup();
+    out.extend(keep.into_iter().map(|i| points[i]));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Quat;
+
+    #[test]
+    fn box_on_box_yields_four_face_contacts() {
+        let ground = Collider::cuboid(Vec3::new(5.0, 0.5, 5.0));
+        let cube = Collider::cuboid(Vec3::splat(0.5));
+        let mut out = Vec::new();
+        let pose = Pose::new(Vec3::new(0.3, 0.99, 0.0), Quat::IDENTITY);
+        collide(&ground, &Pose::IDENTITY, &cube, &pose, 0.02, &mut out);
+        assert_eq!(out.len(), 4);
+        for c in &out {
+            assert!(c.normal.abs_diff_eq(Vec3::Y, 1e-4));
+            assert!((c.depth - 0.01).abs() < 1e-4);
+            assert!((c.point.x - 0.3).abs() > 0.49);
+        }
+
+        // A capsule lying on a triangle mesh touches along its whole length.
+        let floor = Collider::trimesh(
+            vec![Vec3::new(-5.0, 0.0, -5.0), Vec3::new(-5.0, 0.0, 5.0), Vec3::new(5.0, 0.0, 5.0), Vec3::new(5.0, 0.0, -5.0)],
+            &[0, 1, 2, 0, 2, 3],
+        )
+        .unwrap();
+        let capsule = Collider::capsule(1.0, 0.25).with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_2));
+        out.clear();
+        collide(&capsule, &Pose::new(Vec3::Y * 0.24, Quat::IDENTITY), &floor, &Pose::IDENTITY, 0.02, &mut out);
+        assert!(out.len() >= 2);
+        for c in &out {
+            assert!(c.normal.abs_diff_eq(Vec3::NEG_Y, 1e-4));
+            assert!((c.depth - 0.01).abs() < 1e-3);
+        }
+    }
+
+    fn contacts(a: &Collider, pose_a: Pose, b: &Collider, pose_b: Pose) -> Vec<ContactPoint> {
+        let mut out = Vec::new();
+        collide(a, &pose_a, b, &pose_b, 0.02, &mut out);
+        out
+    }
+
+    fn at(position: Vec3) -> Pose {
+        Pose::new(position, Quat::IDENTITY)
+    }
+
+    #[test]
+    fn sphere_pairs_touch_at_one_point() {
+        let ball = Collider::sphere(0.5);
+        let out = contacts(&ball, Pose::IDENTITY, &ball, at(Vec3::new(0.9, 0.0, 0.0)));
+        assert_eq!(out.len(), 1);
+        assert!(out[0].normal.abs_diff_eq(Vec3::X, 1e-4));
+        assert!((out[0].depth - 0.1).abs() < 1e-4);
+        assert!(out[0].point.abs_diff_eq(Vec3::new(0.45, 0.0, 0.0), 1e-4));
+
+        // Within the margin the contact is speculative; beyond it there is none.
+        let out = contacts(&ball, Pose::IDENTITY, &ball, at(Vec3::new(1.01, 0.0, 0.0)));
+        assert_eq!(out.len(), 1);
+        assert!((out[0].depth + 0.01).abs() < 1e-4);
+        assert!(contacts(&ball, Pose::IDENTITY, &ball, at(Vec3::new(1.05, 0.0, 0.0))).is_empty());
+
+        let ground = Collider::cuboid(Vec3::new(5.0, 0.5, 5.0));
+        let out = contacts(&ground, Pose::IDENTITY, &ball, at(Vec3::new(1.0, 0.99, 0.0)));
+        assert_eq!(out.len(), 1);
+        assert!(out[0].normal.abs_diff_eq(Vec3::Y, 1e-4));
+        assert!((out[0].depth - 0.01).abs() < 1e-4);
+    }
+
+    #[test]
+    fn capsule_pairs_touch_at_their_closest_points() {
+        let ground = Collider::cuboid(Vec3::new(5.0, 0.5, 5.0));
+        let upright = Collider::capsule(0.5, 0.25);
+        let out = contacts(&ground, Pose::IDENTITY, &upright, at(Vec3::Y * 1.24));
+        assert_eq!(out.len(), 1);
+        assert!(out[0].normal.abs_diff_eq(Vec3::Y, 1e-4));
+        assert!((out[0].depth - 0.01).abs() < 1e-4);
+
+        // Crossed capsules meet where their axes pass each other.
+        let along_x = Collider::capsule(1.0, 0.25).with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_2));
+        let along_z = Collider::capsule(1.0, 0.25).with_rotation(Quat::from_rotation_x(std::f32::consts::FRAC_PI_2));
+        let out = contacts(&along_x, Pose::IDENTITY, &along_z, at(Vec3::new(0.3, 0.45, -0.2)));
+        assert_eq!(out.len(), 1);
+        assert!(out[0].normal.abs_diff_eq(Vec3::Y, 1e-4));
+        assert!((out[0].depth - 0.05).abs() < 1e-4);
+        assert!(out[0].point.abs_diff_eq(Vec3::new(0.3, 0.225, 0.0), 1e-3));
+    }
+
+    #[test]
+    fn convex_hulls_rest_on_faces() {
+        let corners = (0..8).map(|i| Vec3::new(sign(i & 1), sign(i & 2), sign(i & 4)) * 0.5);
+        let hull = Collider::convex_hull(corners).unwrap();
+        let ground = Collider::cuboid(Vec3::new(5.0, 0.5, 5.0));
+        let out = contacts(&ground, Pose::IDENTITY, &hull, at(Vec3::new(0.3, 0.99, 0.0)));
+        assert_eq!(out.len(), 4);
+        for c in &out {
+            assert!(c.normal.abs_diff_eq(Vec3::Y, 1e-4));
+            assert!((c.depth - 0.01).abs() < 1e-4);
+        }
+
+        // A tetrahedron standing on its tip touches at one point.
+        let tip = Collider::convex_hull([Vec3::ZERO, Vec3::new(-1.0, 1.0, -1.0), Vec3::new(1.0, 1.0, -1.0), Vec3::new(0.0, 1.0, 1.0)]).unwrap();
+        let out = contacts(&ground, Pose::IDENTITY, &tip, at(Vec3::Y * 0.49));
+        assert_eq!(out.len(), 1);
+        assert!(out[0].point.abs_diff_eq(Vec3::Y * 0.495, 1e-3));
+        assert!((out[0].depth - 0.01).abs() < 1e-4);
+
+        let ball = Collider::sphere(0.5);
+        let out = contacts(&hull, Pose::IDENTITY, &ball, at(Vec3::new(0.0, 0.0, 0.98)));
+        assert_eq!(out.len(), 1);
+        assert!(out[0].normal.abs_diff_eq(Vec3::Z, 1e-4));
+        assert!((out[0].depth - 0.02).abs() < 1e-4);
+    }
+
+    #[test]
+    fn asset_mesh_trimesh_contacts() {
+        let mesh = ferrous_assets::AssetMesh {
+            positions: vec![[-5.0, 0.0, -5.0], [-5.0, 0.0, 5.0], [5.0, 0.0, 5.0], [5.0, 0.0, -5.0]],
+            indices: vec![0, 1, 2, 0, 2, 3],
+            ..Default::default()
+        };
+        let floor = Collider::from_asset_mesh(&mesh).unwrap();
+        assert!(Collider::from_asset_mesh(&ferrous_assets::AssetMesh::default()).is_none());
+
+        // Normals point from A to B whichever side the mesh is on.
+        let ball = Collider::sphere(0.5);
+        let out = contacts(&ball, at(Vec3::new(1.0, 0.49, 1.0)), &floor, Pose::IDENTITY);
+        assert_eq!(out.len(), 1);
+        assert!(out[0].normal.abs_diff_eq(Vec3::NEG_Y, 1e-4));
+        assert!((out[0].depth - 0.01).abs() < 1e-4);
+        let out = contacts(&floor, Pose::IDENTITY, &ball, at(Vec3::new(1.0, 0.49, 1.0)));
+        assert!(out[0].normal.abs_diff_eq(Vec3::Y, 1e-4));
+
+        // A box straddling the diagonal edge between the two triangles
+        // still rests on four face contacts.
+        let cube = Collider::cuboid(Vec3::splat(0.5));
+        let out = contacts(&floor, Pose::IDENTITY, &cube, at(Vec3::Y * 0.49));
+        assert_eq!(out.len(), 4);
+        for c in &out {
+            assert!(c.normal.abs_diff_eq(Vec3::Y, 1e-4));
+            assert!((c.depth - 0.01).abs() < 1e-4);
+        }
+
+        // Moved meshes are collided in their own pose; two meshes never collide.
+        assert!(contacts(&floor, at(Vec3::Y * -2.0), &cube, at(Vec3::Y * 0.49)).is_empty());
+        assert!(contacts(&floor, Pose::IDENTITY, &floor, Pose::IDENTITY).is_empty());
+    }
+
+    fn sign(bit: usize) -> f32 {
+        if bit == 0 { -1.0 } else { 1.0 }
+    }
+}
@@ -0,0 +1,64 @@
+//! Rigid placement (translation + rotation) used throughout the solver.
+
+use glam::{Mat4, Quat, Vec3};
+
+/// Position and orientation without scale.
+///
+/// Physics ignores `Transform::scale`: colliders are sized directly.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pose {
+    pub position: Vec3,
+    pub rotation: Quat,
+}
+
+impl Pose {
+    pub const IDENTITY: Pose = Pose { position: Vec3::ZERO, rotation: Quat::IDENTITY };
+
+    pub fn new(position: Vec3, rotation: Quat) -> Self {
+        Pose { position, rotation }
+    }
+
+    #[inline]
+    pub fn transform_point(&self, p: Vec3) -> Vec3 {
+        self.position + self.rotation * p
+    }
+
+    #[inline]
+    pub fn transform_vector(&self, v: Vec3) -> Vec3 {
+        self.rotation * v
+    }
+
+    #[inline]
+    pub fn inverse_transform_point(&self, p: Vec3) -> Vec3 {
+        self.rotation.inverse() * (p - self.position)
+    }
+
+    #[inline]
+    pub fn inverse_transform_vector(&self, v: Vec3) -> Vec3 {
+        self.rotation.inverse() * v
+    }
+
+    /// `self * other`: `other` expressed in the space of `self`.
+    #[inline]
+    pub fn mul(&self, other: &Pose) -> Pose {
+        Pose {
+            position: self.transform_point(other.position),
+            rotation: (self.rotation * other.rotation).normalize(),
+        }
+    }
+
+    pub fn inverse(&self) -> Pose {
+        let rotation = self.rotation.inverse();
+        Pose { position: rotation * -self.position, rotation }
+    }
+
+    pub fn matrix(&self) -> Mat4 {
+        Mat4::from_rotation_translation(self.rotation, self.position)
+    }
+}
+
+impl Default for Pose {
+    fn default() -> Self {
+        Pose::IDENTITY
+    }
+}
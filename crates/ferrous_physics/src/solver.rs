@@ -0,0 +1,170 @@
+//! Sequential-impulse velocity solver.
+//!
+//! Each contact point is a non-penetration constraint plus two friction
+//! constraints.  Impulses are accumulated over the iterations (clamped so
+//! contacts only push and friction stays inside its cone) and carried over
+//! to the next step for warm starting.
+
+use glam::{Mat3, Quat, Vec3};
+
+use crate::narrowphase::ContactPoint;
+
+/// Contacts deeper than this are pushed apart.
+const LINEAR_SLOP: f32 = 0.005;
+/// Fraction of the remaining penetration resolved per step.
+const BAUMGARTE: f32 = 0.2;
+/// Cap on the separation velocity used to resolve penetration (m/s).
+const MAX_CORRECTION_VELOCITY: f32 = 4.0;
+/// Approach speeds below this don't bounce (m/s).
+const RESTITUTION_THRESHOLD: f32 = 1.0;
+
+/// Motion state of one body while solving.  Static, kinematic and sleeping
+/// bodies have zero inverse mass.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SolverBody {
+    pub inv_mass: f32,
+    /// World-space inverse inertia tensor.
+    pub inv_inertia: Mat3,
+    /// Centre of mass.
+    pub position: Vec3,
+    pub rotation: Quat,
+    pub linear_velocity: Vec3,
+    pub angular_velocity: Vec3,
+}
+
+impl SolverBody {
+    /// Velocity of the point at offset `r` from the centre of mass.
+    #[inline]
+    pub fn velocity_at(&self, r: Vec3) -> Vec3 {
+        self.linear_velocity + self.angular_velocity.cross(r)
+    }
+
+    #[inline]
+    pub fn apply_impulse(&mut self, r: Vec3, impulse: Vec3) {
+        self.linear_velocity += impulse * self.inv_mass;
+        self.angular_velocity += self.inv_inertia * r.cross(impulse);
+    }
+
+    /// Effective mass inverse along `dir` at offset `r`.
+    #[inline]
+    pub fn inv_effective_mass(&self, r: Vec3, dir: Vec3) -> f32 {
+        let rn = r.cross(dir);
+        self.inv_mass + rn.dot(self.inv_inertia * rn)
+    }
+
+    pub fn integrate(&mut self, dt: f32) {
+        self.position += self.linear_velocity * dt;
+        let w = self.angular_velocity;
+        let spin = Quat::from_xyzw(w.x, w.y, w.z, 0.0) * self.rotation;
+        self.rotation = (self.rotation + spin * (0.5 * dt)).normalize();
+    }
+}
+
+/// Impulses accumulated by one contact point.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct ContactImpulse {
+    pub normal: f32,
+    pub tangent: [f32; 2],
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ContactConstraint {
+    pub body_a: usize,
+    pub body_b: usize,
+    r_a: Vec3,
+    r_b: Vec3,
+    normal: Vec3,
+    tangents: [Vec3; 2],
+    normal_mass: f32,
+    tangent_mass: [f32; 2],
+    friction: f32,
+    /// Target normal velocity: push-out, bounce or allowed approach.
+    bias: f32,
+    pub impulse: ContactImpulse,
+}
+
+impl ContactConstraint {
+    /// Constraint for `contact` between bodies `pair.0` (A) and `pair.1`
+    /// (B); its negative `depth` is a gap that may close this step.
+    pub fn new(
+        bodies: &[SolverBody],
+        pair: (usize, usize),
+        contact: &ContactPoint,
+        friction: f32,
+        restitution: f32,
+        dt: f32,
+        impulse: ContactImpulse,
+    ) -> Self {
+        let (body_a, body_b) = pair;
+        let ContactPoint { point, normal, depth } = *contact;
+        let (a, b) = (&bodies[body_a], &bodies[body_b]);
+        let (r_a, r_b) = (point - a.position, point - b.position);
+        let (t0, t1) = normal.any_orthonormal_pair();
+        let mass = |dir: Vec3| {
+            let k = a.inv_effective_mass(r_a, dir) + b.inv_effective_mass(r_b, dir);
+            if k > 0.0 {
+                1.0 / k
+            } else {
+                0.0
+            }
+        };
+
+        let approach = (b.velocity_at(r_b) - a.velocity_at(r_a)).dot(normal);
+        let mut bias = if depth > LINEAR_SLOP {
+            (BAUMGARTE / dt * (depth - LINEAR_SLOP)).min(MAX_CORRECTION_VELOCITY)
+        } else if depth < 0.0 {
+            depth / dt
+        } else {
+            0.0
+        };
+        if approach < -RESTITUTION_THRESHOLD {
+            bias = bias.max(-restitution * approach);
+        }
+
+        ContactConstraint {
+            body_a,
+            body_b,
+            r_a,
+            r_b,
+            normal,
+            tangents: [t0, t1],
+            normal_mass: mass(normal),
+            tangent_mass: [mass(t0), mass(t1)],
+            friction,
+            bias,
+            impulse,
+        }
+    }
+
+    fn apply(&self, bodies: &mut [SolverBody], impulse: Vec3) {
+        bodies[self.body_a].apply_impulse(self.r_a, -impulse);
+        bodies[self.body_b].apply_impulse(self.r_b, impulse);
+    }
+
+    /// Re-apply last step's impulses.
+    pub fn warm_start(&self, bodies: &mut [SolverBody]) {
+        let i = self.impulse;
+        let p = self.normal * i.normal + self.tangents[0] * i.tangent[0] + self.tangents[1] * i.tangent[1];
+        self.apply(bodies, p);
+    }
+
+    pub fn solve(&mut self, bodies: &mut [SolverBody]) {
+        let relative = |bodies: &[SolverBody], c: &Self| {
+            bodies[c.body_b].velocity_at(c.r_b) - bodies[c.body_a].velocity_at(c.r_a)
+        };
+
+        // Friction first, limited by the current normal impulse.
+        let limit = self.friction * self.impulse.normal;
+        for k in 0..2 {
+            let vt = relative(bodies, self).dot(self.tangents[k]);
+            let old = self.impulse.tangent[k];
+            self.impulse.tangent[k] = (old - self.tangent_mass[k] * vt).clamp(-limit, limit);
+            self.apply(bodies, self.tangents[k] * (self.impulse.tangent[k] - old));
+        }
+
+        let vn = relative(bodies, self).dot(self.normal);
+        let old = self.impulse.normal;
+        self.impulse.normal = (old + self.normal_mass * (self.bias - vn)).max(0.0);
+        self.apply(bodies, self.normal * (self.impulse.normal - old));
+    }
+}
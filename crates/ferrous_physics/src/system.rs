@@ -0,0 +1,149 @@
+//! `PhysicsSystem` and the collision events it sends.
+
+use ferrous_ecs::event::Events;
+use ferrous_ecs::fixed::DEFAULT_FIXED_STEP;
+use ferrous_ecs::prelude::*;
+use ferrous_ecs::system::System;
+
+use crate::world::PhysicsWorld;
+
+/// Sent into `Events<CollisionEvent>` when two colliders start or stop
+/// touching.  The entities are in the same order as in
+/// [`PhysicsWorld::contacts`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollisionEvent {
+    Started(Entity, Entity),
+    Stopped(Entity, Entity),
+}
+
+impl CollisionEvent {
+    pub fn entities(&self) -> (Entity, Entity) {
+        match *self {
+            CollisionEvent::Started(a, b) | CollisionEvent::Stopped(a, b) => (a, b),
+        }
+    }
+
+    /// The other entity of the pair, if `entity` is part of it.
+    pub fn other(&self, entity: Entity) -> Option<Entity> {
+        match self.entities() {
+            (a, b) if a == entity => Some(b),
+            (a, b) if b == entity => Some(a),
+            _ => None,
+        }
+    }
+}
+
+/// Steps the [`PhysicsWorld`] (inserted on first run) once per fixed step.
+///
+/// Register in `Stage::FixedUpdate`, after `TransformSnapshotSystem` so
+/// rendering can interpolate bodies that carry a `PreviousTransform`.
+/// Collision events go to the `Events<CollisionEvent>` resource and broken
+/// joints to `Events<JointBroken>` (inserted on first use; rotate them with
+/// `EventUpdateSystem`).
+pub struct PhysicsSystem;
+
+impl System for PhysicsSystem {
+    fn name(&self) -> &'static str {
+        "PhysicsSystem"
+    }
+
+    fn run(&mut self, world: &mut ferrous_ecs::world::World, resources: &mut ResourceMap) {
+        let dt = resources.get::<FixedTime>().map_or(DEFAULT_FIXED_STEP, |f| f.step);
+        let physics = resources.get_or_insert_default::<PhysicsWorld>();
+        let events = physics.step(world, dt);
+        let broken = physics.broken_joints().to_vec();
+        send(resources, events);
+        send(resources, broken);
+    }
+}
+
+fn send<T: Send + Sync + 'static>(resources: &mut ResourceMap, events: Vec<T>) {
+    if events.is_empty() {
+        return;
+    }
+    if resources.get::<Events<T>>().is_none() {
+        resources.insert(Events::<T>::new());
+    }
+    let queue = resources.get_mut::<Events<T>>().unwrap();
+    for event in events {
+        queue.send(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Collider, RigidBody};
+    use ferrous_core::transform::Transform;
+    use glam::Vec3;
+
+    #[test]
+    fn boxes_fall_stack_and_fall_asleep() {
+        let mut world = ferrous_ecs::world::World::new();
+        let mut resources = ResourceMap::new();
+        let ground = world.spawn((Transform::IDENTITY, Collider::cuboid(Vec3::new(10.0, 0.5, 10.0))));
+        let low = world.spawn((
+            Transform::from_position(Vec3::new(0.0, 1.5, 0.0)),
+            RigidBody::dynamic(),
+            Collider::cuboid(Vec3::splat(0.5)),
+        ));
+        let high = world.spawn((
+            Transform::from_position(Vec3::new(0.1, 3.0, 0.0)),
+            RigidBody::dynamic(),
+            Collider::cuboid(Vec3::splat(0.5)),
+        ));
+
+        let mut started = Vec::new();
+        for _ in 0..240 {
+            PhysicsSystem.run(&mut world, &mut resources);
+            if let Some(events) = resources.get_mut::<Events<CollisionEvent>>() {
+                events.update();
+                started.extend(events.read().copied());
+            }
+        }
+        let low_t = world.get::<Transform>(low).unwrap();
+        let high_t = world.get::<Transform>(high).unwrap();
+        assert!((low_t.position.y - 1.0).abs() < 0.02, "{:?}", low_t.position);
+        assert!((high_t.position.y - 2.0).abs() < 0.03, "{:?}", high_t.position);
+        assert!((high_t.position.x - 0.1).abs() < 0.02);
+        assert!(world.get::<RigidBody>(low).unwrap().is_sleeping());
+        assert!(world.get::<RigidBody>(high).unwrap().is_sleeping());
+        assert!(started.contains(&CollisionEvent::Started(ground, low)));
+        assert!(started.iter().any(|e| e.other(high) == Some(low)));
+        assert!(!started.iter().any(|e| matches!(e, CollisionEvent::Stopped(..))));
+    }
+
+    #[test]
+    fn ball_bounces_and_sensor_reports_without_pushing() {
+        let mut world = ferrous_ecs::world::World::new();
+        let mut resources = ResourceMap::new();
+        world.spawn((Transform::IDENTITY, Collider::cuboid(Vec3::new(10.0, 0.5, 10.0)).with_restitution(0.8)));
+        let trigger = world.spawn((Transform::from_position(Vec3::Y * 2.0), Collider::sphere(0.5).sensor()));
+        let ball = world.spawn((
+            Transform::from_position(Vec3::new(0.0, 4.0, 0.0)),
+            RigidBody::dynamic(),
+            Collider::sphere(0.5),
+        ));
+
+        let mut entered = false;
+        let mut peak_after_bounce: f32 = 0.0;
+        let mut bounced = false;
+        for _ in 0..120 {
+            PhysicsSystem.run(&mut world, &mut resources);
+            if let Some(events) = resources.get_mut::<Events<CollisionEvent>>() {
+                events.update();
+                entered |= events.read().any(|e| *e == CollisionEvent::Started(trigger, ball));
+            }
+            let rb = world.get::<RigidBody>(ball).unwrap();
+            bounced |= rb.linear_velocity.y > 1.0;
+            if bounced {
+                peak_after_bounce = peak_after_bounce.max(world.get::<Transform>(ball).unwrap().position.y);
+            }
+        }
+        assert!(entered, "ball passed through the sensor");
+        assert!(bounced);
+        // 3.5 m drop, restitution 0.8 → back up to ~0.64 of the drop.
+        assert!(peak_after_bounce > 2.5 && peak_after_bounce < 3.5, "{peak_after_bounce}");
+        assert_eq!(world.get::<Transform>(trigger).unwrap().position, Vec3::Y * 2.0);
+    }
+}
@@ -0,0 +1,515 @@
+//! The `PhysicsWorld` resource and the simulation step.
+
+use std::collections::{HashMap, HashSet};
+
+use ferrous_core::bvh::{AabbTree, ProxyId};
+use ferrous_core::transform::Transform;
+use ferrous_ecs::prelude::*;
+use glam::{Mat3, Quat, Vec3};
+
+use crate::body::{BodyKind, RigidBody};
+use crate::collider::Collider;
+use crate::joint::{Joint, JointBroken, JointConstraint};
+use crate::narrowphase::{collide, ContactPoint};
+use crate::pose::Pose;
+use crate::solver::{ContactConstraint, ContactImpulse, SolverBody};
+use crate::CollisionEvent;
+
+/// Shapes closer than this get (speculative) contacts.
+const CONTACT_MARGIN: f32 = 0.02;
+/// A new contact inherits the impulses of last step's contact within this
+/// distance (in A's space) for warm starting.
+const MATCH_DISTANCE: f32 = 0.05;
+const SLEEP_LINEAR_VELOCITY: f32 = 0.05;
+const SLEEP_ANGULAR_VELOCITY: f32 = 0.05;
+/// Seconds an island must stay below the sleep velocities to fall asleep.
+const TIME_TO_SLEEP: f32 = 0.5;
+
+/// Contacts between two colliders during the last step.
+#[derive(Debug, Clone)]
+pub struct Manifold {
+    /// Normals point from the first entity of the pair to the second.
+    pub points: Vec<ContactPoint>,
+    /// One of the colliders is a sensor: the pair is reported but not
+    /// solved.
+    pub sensor: bool,
+    /// Accumulated impulses per point, keyed by the point in A's space.
+    impulses: Vec<(Vec3, ContactImpulse)>,
+}
+
+impl Manifold {
+    /// `true` if the shapes actually touch (not just within the margin).
+    pub fn is_touching(&self) -> bool {
+        self.points.iter().any(|c| c.depth >= 0.0)
+    }
+}
+
+/// Per-entity data gathered at the start of a step.
+struct StepBody {
+    entity: Entity,
+    collider: Collider,
+    body: Option<RigidBody>,
+    kind: BodyKind,
+    /// Centre of mass in entity space.
+    center_of_mass: Vec3,
+    inv_mass: f32,
+    inv_inertia: Mat3,
+    awake: bool,
+}
+
+impl StepBody {
+    /// Bodies whose motion this step can create new contacts.
+    fn is_mover(&self) -> bool {
+        self.kind == BodyKind::Kinematic || (self.kind == BodyKind::Dynamic && self.awake)
+    }
+}
+
+/// Simulation settings and state shared across steps.
+///
+/// Inserted by `PhysicsSystem` on its first run; insert one yourself to
+/// change the defaults.  Besides the broadphase tree it keeps the contact
+/// manifolds of the last step, so other systems can inspect them.
+#[derive(Debug)]
+pub struct PhysicsWorld {
+    pub gravity: Vec3,
+    /// Solver iterations per step; more makes stacks stiffer.
+    pub velocity_iterations: usize,
+    broadphase: AabbTree<Entity>,
+    proxies: HashMap<Entity, ProxyId>,
+    manifolds: HashMap<(Entity, Entity), Manifold>,
+    broken_joints: Vec<JointBroken>,
+}
+
+impl Default for PhysicsWorld {
+    fn default() -> Self {
+        PhysicsWorld {
+            gravity: Vec3::new(0.0, -9.81, 0.0),
+            velocity_iterations: 8,
+            broadphase: AabbTree::new(),
+            proxies: HashMap::new(),
+            manifolds: HashMap::new(),
+            broken_joints: Vec::new(),
+        }
+    }
+}
+
+/// Canonical order of a pair: lower entity bits first.
+fn pair_key(a: Entity, b: Entity) -> (Entity, Entity) {
+    if a.to_bits() <= b.to_bits() {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+impl PhysicsWorld {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_gravity(mut self, gravity: Vec3) -> Self {
+        self.gravity = gravity;
+        self
+    }
+
+    /// The broadphase tree over every collider's world bounds.
+    pub fn broadphase(&self) -> &AabbTree<Entity> {
+        &self.broadphase
+    }
+
+    /// Every pair in contact (or within the contact margin) after the last
+    /// step.
+    pub fn contacts(&self) -> impl Iterator<Item = (Entity, Entity, &Manifold)> {
+        self.manifolds.iter().map(|(&(a, b), m)| (a, b, m))
+    }
+
+    /// Joints that broke during the last step.
+    pub fn broken_joints(&self) -> &[JointBroken] {
+        &self.broken_joints
+    }
+
+    /// Whether `a` and `b` touched during the last step.
+    pub fn is_touching(&self, a: Entity, b: Entity) -> bool {
+        self.manifolds.get(&pair_key(a, b)).is_some_and(Manifold::is_touching)
+    }
+
+    /// Advance the simulation by `dt` seconds: integrate every body with a
+    /// `Collider` and a `Transform`, resolve contacts and joints and write
+    /// the results back to `RigidBody`, `Transform` and `Joint`.  Returns
+    /// the collision events of the step.
+    pub fn step(&mut self, world: &mut ferrous_ecs::world::World, dt: f32) -> Vec<CollisionEvent> {
+        self.broken_joints.clear();
+        if dt <= 0.0 {
+            return Vec::new();
+        }
+        let (mut steps, mut bodies) = self.gather(world, dt);
+        let index: HashMap<Entity, usize> = steps.iter().enumerate().map(|(i, s)| (s.entity, i)).collect();
+
+        // ── Joints ────────────────────────────────────────────────────────
+        // Joints to the world attach to an extra massless body at the end.
+        let ground = bodies.len();
+        bodies.push(SolverBody {
+            inv_mass: 0.0,
+            inv_inertia: Mat3::ZERO,
+            position: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+            linear_velocity: Vec3::ZERO,
+            angular_velocity: Vec3::ZERO,
+        });
+        let mut joints = Vec::new();
+        for (entity, joint) in Query::<&Joint>::new(world).iter() {
+            let Some(&j) = index.get(&joint.body_b) else { continue };
+            let i = match joint.body_a {
+                Some(a) => match index.get(&a) {
+                    Some(&i) => i,
+                    None => continue,
+                },
+                None => ground,
+            };
+            if joint.is_broken() || i == j {
+                continue;
+            }
+            let mut joint = joint.clone();
+            joint.bind(&bodies[i], &bodies[j]);
+            joints.push((entity, joint, i, j));
+        }
+        let unjoined: HashSet<(Entity, Entity)> = joints
+            .iter()
+            .filter(|(_, joint, ..)| !joint.collide_connected)
+            .filter_map(|(_, joint, ..)| joint.body_a.map(|a| pair_key(a, joint.body_b)))
+            .collect();
+
+        // ── Broadphase ────────────────────────────────────────────────────
+        self.proxies.retain(|e, id| {
+            let keep = index.contains_key(e);
+            if !keep {
+                self.broadphase.remove(*id);
+            }
+            keep
+        });
+        let mut bounds = Vec::with_capacity(steps.len());
+        for (s, b) in steps.iter().zip(&bodies) {
+            let pose = Pose::new(b.position - b.rotation * s.center_of_mass, b.rotation);
+            let aabb = s.collider.world_bounds(&pose);
+            match self.proxies.get(&s.entity) {
+                Some(&id) => {
+                    self.broadphase.update(id, aabb);
+                }
+                None => {
+                    self.proxies.insert(s.entity, self.broadphase.insert(aabb, s.entity));
+                }
+            }
+            bounds.push((pose, aabb));
+        }
+        let mut pairs = HashSet::new();
+        for (i, s) in steps.iter().enumerate() {
+            if !s.is_mover() {
+                continue;
+            }
+            self.broadphase.query_aabb(&bounds[i].1.expanded(CONTACT_MARGIN), |_, e| {
+                let j = index[e];
+                let other = &steps[j];
+                let sensor = s.collider.sensor || other.collider.sensor;
+                let dynamic = s.kind == BodyKind::Dynamic || other.kind == BodyKind::Dynamic;
+                if j != i && (dynamic || sensor) && !unjoined.contains(&pair_key(s.entity, other.entity)) {
+                    pairs.insert(if s.entity.to_bits() <= other.entity.to_bits() { (i, j) } else { (j, i) });
+                }
+            });
+        }
+
+        // ── Narrowphase ───────────────────────────────────────────────────
+        let mut manifolds = HashMap::with_capacity(pairs.len());
+        let mut points = Vec::new();
+        for &(i, j) in &pairs {
+            let (a, b) = (&steps[i], &steps[j]);
+            points.clear();
+            collide(&a.collider, &bounds[i].0, &b.collider, &bounds[j].0, CONTACT_MARGIN, &mut points);
+            if points.is_empty() {
+                continue;
+            }
+            let key = (a.entity, b.entity);
+            let previous = self.manifolds.get(&key).map(|m| m.impulses.as_slice()).unwrap_or(&[]);
+            let body_a = &bodies[i];
+            let impulses = points
+                .iter()
+                .map(|c| {
+                    let local = body_a.rotation.inverse() * (c.point - body_a.position);
+                    let inherited = previous
+                        .iter()
+                        .find(|(p, _)| p.distance_squared(local) < MATCH_DISTANCE * MATCH_DISTANCE)
+                        .map_or(ContactImpulse::default(), |&(_, imp)| imp);
+                    (local, inherited)
+                })
+                .collect();
+            let sensor = a.collider.sensor || b.collider.sensor;
+            manifolds.insert(key, Manifold { points: points.clone(), sensor, impulses });
+        }
+        // Pairs of resting bodies were not re-tested: keep their contacts.
+        for (key, m) in &self.manifolds {
+            if let (Some(&i), Some(&j)) = (index.get(&key.0), index.get(&key.1)) {
+                if !steps[i].is_mover() && !steps[j].is_mover() {
+                    manifolds.entry(*key).or_insert_with(|| m.clone());
+                }
+            }
+        }
+
+        // ── Islands: wake everything touching an awake body ───────────────
+        let mut islands: Vec<usize> = (0..steps.len()).collect();
+        let contact_links = manifolds.iter().filter(|(_, m)| !m.sensor).map(|(key, _)| (index[&key.0], index[&key.1]));
+        let joint_links = joints.iter().filter(|&&(.., i, _)| i != ground).map(|&(.., i, j)| (i, j));
+        let links: Vec<(usize, usize)> = contact_links.chain(joint_links).collect();
+        for (i, j) in links {
+            let (a, b) = (&steps[i], &steps[j]);
+            if a.kind == BodyKind::Dynamic && b.kind == BodyKind::Dynamic {
+                union(&mut islands, i, j);
+            }
+            // A moving kinematic body keeps what it touches awake.
+            for (k, d) in [(i, j), (j, i)] {
+                if steps[k].kind == BodyKind::Kinematic
+                    && steps[d].kind == BodyKind::Dynamic
+                    && (bodies[k].linear_velocity != Vec3::ZERO || bodies[k].angular_velocity != Vec3::ZERO)
+                {
+                    wake(&mut steps[d], &mut bodies[d]);
+                }
+            }
+        }
+        let mut island_awake = vec![false; steps.len()];
+        for (i, s) in steps.iter().enumerate() {
+            if s.kind == BodyKind::Dynamic && s.awake {
+                island_awake[find(&mut islands, i)] = true;
+            }
+        }
+        for i in 0..steps.len() {
+            let root = find(&mut islands, i);
+            if island_awake[root] && steps[i].kind == BodyKind::Dynamic && !steps[i].awake {
+                wake(&mut steps[i], &mut bodies[i]);
+            }
+        }
+
+        // ── Solve ─────────────────────────────────────────────────────────
+        let mut constraints = Vec::new();
+        let mut owners = Vec::new();
+        for (key, m) in &manifolds {
+            let (i, j) = (index[&key.0], index[&key.1]);
+            if m.sensor || (bodies[i].inv_mass == 0.0 && bodies[j].inv_mass == 0.0) {
+                continue;
+            }
+            let (ca, cb) = (&steps[i].collider, &steps[j].collider);
+            let friction = (ca.friction * cb.friction).max(0.0).sqrt();
+            let restitution = ca.restitution.max(cb.restitution);
+            for (k, c) in m.points.iter().enumerate() {
+                constraints.push(ContactConstraint::new(&bodies, (i, j), c, friction, restitution, dt, m.impulses[k].1));
+                owners.push((*key, k));
+            }
+        }
+        let mut joint_constraints: Vec<(usize, JointConstraint)> = joints
+            .iter()
+            .enumerate()
+            .filter(|(_, &(.., i, j))| bodies[i].inv_mass > 0.0 || bodies[j].inv_mass > 0.0)
+            .map(|(k, (_, joint, i, j))| (k, JointConstraint::new(&bodies, (*i, *j), joint, dt)))
+            .collect();
+        for (_, c) in &mut joint_constraints {
+            c.warm_start(&mut bodies);
+        }
+        for c in &constraints {
+            c.warm_start(&mut bodies);
+        }
+        for _ in 0..self.velocity_iterations {
+            for (_, c) in &mut joint_constraints {
+                c.solve(&mut bodies);
+            }
+            for c in &mut constraints {
+                c.solve(&mut bodies);
+            }
+        }
+        for (k, c) in &joint_constraints {
+            let (entity, joint, ..) = &mut joints[*k];
+            if joint.store(c, dt) {
+                self.broken_joints.push(JointBroken { joint: *entity, body_a: joint.body_a, body_b: joint.body_b });
+            }
+        }
+        for (c, (key, k)) in constraints.iter().zip(owners) {
+            if let Some(m) = manifolds.get_mut(&key) {
+                m.impulses[k].1 = c.impulse;
+            }
+        }
+
+        // ── Integrate and sleep ───────────────────────────────────────────
+        let mut island_rest = vec![f32::INFINITY; steps.len()];
+        for i in 0..steps.len() {
+            let (s, b) = (&mut steps[i], &mut bodies[i]);
+            if !s.is_mover() {
+                continue;
+            }
+            b.integrate(dt);
+            let (Some(rb), BodyKind::Dynamic) = (s.body.as_mut(), s.kind) else { continue };
+            let resting = b.linear_velocity.length_squared() < SLEEP_LINEAR_VELOCITY * SLEEP_LINEAR_VELOCITY
+                && b.angular_velocity.length_squared() < SLEEP_ANGULAR_VELOCITY * SLEEP_ANGULAR_VELOCITY;
+            rb.sleep_time = if rb.can_sleep && resting { rb.sleep_time + dt } else { 0.0 };
+            let root = find(&mut islands, i);
+            island_rest[root] = island_rest[root].min(rb.sleep_time);
+        }
+        for i in 0..steps.len() {
+            let root = find(&mut islands, i);
+            if steps[i].kind == BodyKind::Dynamic && steps[i].awake && island_rest[root] >= TIME_TO_SLEEP {
+                if let Some(rb) = steps[i].body.as_mut() {
+                    rb.sleep();
+                }
+                bodies[i].linear_velocity = Vec3::ZERO;
+                bodies[i].angular_velocity = Vec3::ZERO;
+            }
+        }
+
+        self.write_back(world, &steps, &bodies);
+        for (entity, joint, ..) in joints {
+            if world.get::<Joint>(entity) != Some(&joint) {
+                if let Some(slot) = world.get_mut::<Joint>(entity) {
+                    *slot = joint;
+                }
+            }
+        }
+        let events = self.collision_events(&manifolds);
+        self.manifolds = manifolds;
+        events
+    }
+
+    /// Collect the bodies and apply gravity, forces and impulses.
+    fn gather(&self, world: &ferrous_ecs::world::World, dt: f32) -> (Vec<StepBody>, Vec<SolverBody>) {
+        let mut steps = Vec::new();
+        let mut bodies = Vec::new();
+        for (entity, (collider, transform, body)) in
+            Query::<(&Collider, &Transform, Option<&RigidBody>)>::new(world).iter()
+        {
+            let has_body = body.is_some();
+            let mut body = body.copied();
+            let rb = body.get_or_insert_with(RigidBody::fixed);
+            let kind = rb.kind;
+            let has_input = rb.linear_velocity != Vec3::ZERO
+                || rb.angular_velocity != Vec3::ZERO
+                || rb.force != Vec3::ZERO
+                || rb.torque != Vec3::ZERO
+                || rb.impulse != Vec3::ZERO
+                || rb.angular_impulse != Vec3::ZERO;
+            if rb.sleeping && has_input {
+                rb.wake_up();
+            }
+            let awake = kind == BodyKind::Dynamic && !rb.sleeping;
+
+            let (mass, center_of_mass, inv_inertia_local) = collider.mass_properties(rb.mass);
+            let dynamic = kind == BodyKind::Dynamic;
+            let inv_mass = if dynamic && mass > 0.0 { 1.0 / mass } else { 0.0 };
+            let rot = Mat3::from_quat(transform.rotation);
+            let inv_inertia = if dynamic && !rb.lock_rotation {
+                rot * inv_inertia_local * rot.transpose()
+            } else {
+                Mat3::ZERO
+            };
+
+            let mut solver = SolverBody {
+                inv_mass: if awake { inv_mass } else { 0.0 },
+                inv_inertia: if awake { inv_inertia } else { Mat3::ZERO },
+                position: transform.position + transform.rotation * center_of_mass,
+                rotation: transform.rotation,
+                linear_velocity: Vec3::ZERO,
+                angular_velocity: Vec3::ZERO,
+            };
+            if awake || kind == BodyKind::Kinematic {
+                solver.linear_velocity = rb.linear_velocity;
+                solver.angular_velocity = if dynamic && rb.lock_rotation { Vec3::ZERO } else { rb.angular_velocity };
+            }
+            if awake {
+                let v = &mut solver.linear_velocity;
+                *v += (self.gravity * rb.gravity_scale + rb.force * inv_mass) * dt + rb.impulse * inv_mass;
+                *v /= 1.0 + dt * rb.linear_damping;
+                let w = &mut solver.angular_velocity;
+                *w += inv_inertia * (rb.torque * dt + rb.angular_impulse);
+                *w /= 1.0 + dt * rb.angular_damping;
+            }
+            rb.force = Vec3::ZERO;
+            rb.torque = Vec3::ZERO;
+            rb.impulse = Vec3::ZERO;
+            rb.angular_impulse = Vec3::ZERO;
+
+            steps.push(StepBody {
+                entity,
+                collider: collider.clone(),
+                body: if has_body { body } else { None },
+                kind,
+                center_of_mass,
+                inv_mass,
+                inv_inertia,
+                awake,
+            });
+            bodies.push(solver);
+        }
+        (steps, bodies)
+    }
+
+    fn write_back(&self, world: &mut ferrous_ecs::world::World, steps: &[StepBody], bodies: &[SolverBody]) {
+        for (s, b) in steps.iter().zip(bodies) {
+            let Some(mut rb) = s.body else { continue };
+            if s.kind == BodyKind::Static {
+                continue;
+            }
+            if s.is_mover() {
+                if !rb.sleeping {
+                    rb.linear_velocity = b.linear_velocity;
+                    rb.angular_velocity = b.angular_velocity;
+                }
+                if let Some(t) = world.get_mut::<Transform>(s.entity) {
+                    t.position = b.position - b.rotation * s.center_of_mass;
+                    t.rotation = b.rotation;
+                }
+            }
+            if world.get::<RigidBody>(s.entity) != Some(&rb) {
+                if let Some(slot) = world.get_mut::<RigidBody>(s.entity) {
+                    *slot = rb;
+                }
+            }
+        }
+    }
+
+    fn collision_events(&self, manifolds: &HashMap<(Entity, Entity), Manifold>) -> Vec<CollisionEvent> {
+        let mut events = Vec::new();
+        for (&(a, b), m) in manifolds {
+            let was = self.manifolds.get(&(a, b)).is_some_and(Manifold::is_touching);
+            if m.is_touching() && !was {
+                events.push(CollisionEvent::Started(a, b));
+            }
+        }
+        for (&(a, b), m) in &self.manifolds {
+            let now = manifolds.get(&(a, b)).is_some_and(Manifold::is_touching);
+            if m.is_touching() && !now {
+                events.push(CollisionEvent::Stopped(a, b));
+            }
+        }
+        events
+    }
+}
+
+fn wake(step: &mut StepBody, body: &mut SolverBody) {
+    if step.awake {
+        return;
+    }
+    step.awake = true;
+    if let Some(rb) = step.body.as_mut() {
+        rb.wake_up();
+    }
+    body.inv_mass = step.inv_mass;
+    body.inv_inertia = step.inv_inertia;
+}
+
+fn find(parents: &mut [usize], mut i: usize) -> usize {
+    while parents[i] != i {
+        parents[i] = parents[parents[i]];
+        i = parents[i];
+    }
+    i
+}
+
+fn union(parents: &mut [usize], a: usize, b: usize) {
+    let (ra, rb) = (find(parents, a), find(parents, b));
+    if ra != rb {
+        parents[ra] = rb;
+    }
+}
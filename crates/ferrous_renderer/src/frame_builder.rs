@@ -3,7 +3,8 @@
 //!
 //! ## Responsibilities
 //! - Maintain caches of draw commands (reuse `Vec` between frames)
-//! - Frustum culling of world (ECS) objects
+//! - Frustum culling of world (ECS) objects through the `SpatialIndex`
+//!   kept by `SpatialIndexSystem`
 //! - Group world objects by mesh (instancing)
 //! - Upload matrices to `InstanceBuffer`
 //! - Calculate `RenderStats` for the frame
@@ -12,7 +13,10 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use ferrous_core::scene::world::{Element, ElementKind, MaterialComponent};
+use ferrous_core::scene::{Billboard, GlobalTransform, LocalBounds, SpatialIndex};
 use ferrous_core::transform::Transform;
+use ferrous_ecs::entity::Entity;
+use ferrous_ecs::query::{Or, Query, With, Without};
 
 use crate::geometry::primitives::{
     capsule::capsule as create_capsule,
@@ -29,6 +33,10 @@ use crate::render_stats::RenderStats;
 use crate::resources::InstanceBuffer;
 use crate::scene::Frustum;
 
+/// Instances sharing a mesh, material slot and sidedness.
+type MeshGroupKey = (usize, usize, bool);
+type MeshGroupVal = (crate::geometry::Mesh, usize, Vec<glam::Mat4>);
+
 /// All per-frame scratch state that `FrameBuilder` needs to track between calls.
 pub struct FrameBuilder {
     // Reusable draw command lists (zeroed each frame, allocated once)
@@ -81,6 +89,11 @@ pub struct FrameBuilder {
     pub world_instance_matrices: Vec<glam::Mat4>,
     /// Scratch matrices for shadow instancing.
     world_shadow_matrices: Vec<glam::Mat4>,
+
+    /// Culling index used when the caller passes no `SpatialIndex`, synced
+    /// from the world's change ticks after `cull_tick`.
+    cull_index: SpatialIndex,
+    cull_tick: u64,
 }
 
 impl Default for FrameBuilder {
@@ -114,6 +127,8 @@ impl FrameBuilder {
             world_shadow_instanced: Vec::new(),
             world_instance_matrices: Vec::new(),
             world_shadow_matrices: Vec::new(),
+            cull_index: SpatialIndex::new(),
+            cull_tick: 0,
         }
     }
 
@@ -132,11 +147,14 @@ impl FrameBuilder {
     /// Called by `Renderer::sync_world` whenever the scene changes.  Replaces
     /// the old `sync_world → world_objects Vec` indirection.
     ///
-    /// Frustum culling is deferred to `build()` so that a camera change still
-    /// re-culls even when the scene is otherwise static.
+    /// Entities are culled with `spatial` — the app's `SpatialIndex`
+    /// resource — or, when `None`, an index of the builder's own.  Kinds the
+    /// index cannot hold (no `LocalBounds` or `GlobalTransform` yet) and
+    /// billboards are culled individually.
     pub fn build_world_commands(
         &mut self,
         world: &ferrous_core::scene::World,
+        spatial: Option<&SpatialIndex>,
         device: &wgpu::Device,
         frustum: &Frustum,
         camera_eye: glam::Vec3,
@@ -165,258 +183,60 @@ impl FrameBuilder {
         // Note: procedural_mesh_cache is NOT pruned automatically — caller
         // must call `free_procedural_mesh` explicitly when geometry is freed.
 
-        type MeshGroupKey = (usize, usize, bool);
-        type MeshGroupVal = (crate::geometry::Mesh, usize, Vec<glam::Mat4>);
-
         // Visible (camera-culled) groups for main draw pass
         let mut visible_groups: HashMap<MeshGroupKey, MeshGroupVal> = HashMap::new();
         // All-objects groups for shadow pass (no frustum culling)
         let mut shadow_groups: HashMap<MeshGroupKey, MeshGroupVal> = HashMap::new();
-        
-        for (_entity, (element, transform, material, shadow_caster, billboard)) in
-            ferrous_ecs::query::Query::<(&Element, &Transform, &MaterialComponent, Option<&ferrous_core::scene::ShadowCaster>, Option<&ferrous_core::scene::Billboard>)>::new(&world.ecs).iter()
-        {
-            let is_renderable = matches!(
-                element.kind,
-                ElementKind::Cube { .. }
-                    | ElementKind::Mesh { .. }
-                    | ElementKind::Quad { .. }
-                    | ElementKind::Sphere { .. }
-                    | ElementKind::Cylinder { .. }
-                    | ElementKind::Torus { .. }
-                    | ElementKind::Plane { .. }
-                    | ElementKind::Capsule { .. }
-                    | ElementKind::Circle { .. }
-                    | ElementKind::Ring { .. }
-                    | ElementKind::Text3D { .. }
-            );
-            if !is_renderable || !element.visible {
-                continue;
-            }
 
-            let is_double_sided = if let ElementKind::Quad { double_sided, .. } = element.kind {
-                double_sided
-            } else {
-                false
-            };
+        // Shadow pass — only entities with ShadowCaster
+        for entity in world.render_index().shadow_casters() {
+            if let Some((key, mesh, matrix)) = self.draw_item(world, entity, device, camera_eye) {
+                shadow_groups.entry(key).or_insert_with(|| (mesh, key.1, Vec::new())).2.push(matrix);
+            }
+        }
 
-            let mesh = match &element.kind {
-                ElementKind::Cube { .. } => self
-                    .shared_cube_mesh
-                    .get_or_insert_with(|| create_cube(device))
-                    .clone(),
-                ElementKind::Mesh { asset_key } => {
-                    // 1. Try unconditional procedural cache first (terrain, runtime geometry)
-                    if let Some(m) = self.procedural_mesh_cache.get(asset_key.as_str()) {
-                        m.clone()
-                    } else {
-                        // 2. Fall back to the asset-file cache when feature is enabled
-                        #[cfg(feature = "assets")]
-                        {
-                            if let Some(m) = self.mesh_cache.get(asset_key.as_str()) {
-                                m.clone()
-                            } else {
-                                self.shared_cube_mesh
-                                    .get_or_insert_with(|| create_cube(device))
-                                    .clone()
-                            }
-                        }
-                        #[cfg(not(feature = "assets"))]
-                        {
-                            // No match in either cache — fall back to a cube placeholder
-                            self.shared_cube_mesh
-                                .get_or_insert_with(|| create_cube(device))
-                                .clone()
-                        }
-                    }
-                }
-                ElementKind::Quad { .. } => self
-                    .shared_quad_mesh
-                    .get_or_insert_with(|| create_quad(device))
-                    .clone(),
-                ElementKind::Sphere {
-                    latitudes,
-                    longitudes,
-                    ..
-                } => {
-                    let use_mesh = if let Some((m, l, o)) = &self.shared_sphere_mesh {
-                        if l == latitudes && o == longitudes {
-                            Some(m.clone())
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    };
-                    if let Some(m) = use_mesh {
-                        m
-                    } else {
-                        let new = create_sphere(device, 1.0, *latitudes, *longitudes);
-                        self.shared_sphere_mesh = Some((new.clone(), *latitudes, *longitudes));
-                        new
-                    }
-                }
-                ElementKind::Cylinder {
-                    radius_top,
-                    radius_bottom,
-                    height,
-                    radial_segments,
-                    height_segments,
-                    open_ended,
-                } => {
-                    let key = (
-                        radius_top.to_bits(), radius_bottom.to_bits(), height.to_bits(),
-                        *radial_segments, *height_segments, *open_ended as u8,
-                    );
-                    self.cylinder_cache
-                        .entry(key)
-                        .or_insert_with(|| create_cylinder(
-                            device, *radius_top, *radius_bottom, *height,
-                            *radial_segments, *height_segments, *open_ended,
-                        ))
-                        .clone()
-                }
-                ElementKind::Torus { radius, tube, radial_segments, tubular_segments } => {
-                    let key = (
-                        radius.to_bits(), tube.to_bits(),
-                        *radial_segments, *tubular_segments,
-                    );
-                    self.torus_cache
-                        .entry(key)
-                        .or_insert_with(|| create_torus(
-                            device, *radius, *tube,
-                            *radial_segments, *tubular_segments,
-                            std::f32::consts::TAU,
-                        ))
-                        .clone()
-                }
-                ElementKind::Plane { width, height, width_segments, height_segments } => {
-                    let key = (
-                        width.to_bits(), height.to_bits(),
-                        *width_segments, *height_segments,
-                    );
-                    self.plane_cache
-                        .entry(key)
-                        .or_insert_with(|| create_plane(
-                            device, *width, *height,
-                            *width_segments, *height_segments,
-                        ))
-                        .clone()
-                }
-                ElementKind::Capsule { radius, height, radial_segments, cap_segments } => {
-                    let key = (
-                        radius.to_bits(), height.to_bits(),
-                        *radial_segments, *cap_segments,
-                    );
-                    self.capsule_cache
-                        .entry(key)
-                        .or_insert_with(|| create_capsule(
-                            device, *radius, *height,
-                            *radial_segments, *cap_segments,
-                        ))
-                        .clone()
-                }
-                ElementKind::Circle { radius, segments } => {
-                    let key = (0u32, radius.to_bits(), *segments, 1u32);
-                    self.disc_cache
-                        .entry(key)
-                        .or_insert_with(|| create_circle(device, *radius, *segments))
-                        .clone()
+        // Main pass — what the spatial index puts inside the frustum.  Without
+        // an index from the app, keep our own in step with the entities whose
+        // bounds changed since the last call.
+        let indexed = match spatial {
+            Some(index) => index.query_frustum(frustum),
+            None => {
+                if world.ecs.change_tick() < self.cull_tick || !self.cull_index.is_tracking(world.spatial_removals()) {
+                    // A replaced world restarts its tick counter or reports
+                    // removals to another hub; start over.
+                    self.cull_index = SpatialIndex::new();
+                    self.cull_index.track(world.spatial_removals());
+                    self.cull_tick = 0;
                 }
-                ElementKind::Ring { inner_radius, outer_radius, segments, rings } => {
-                    let key = (
-                        inner_radius.to_bits(), outer_radius.to_bits(),
-                        *segments, *rings,
-                    );
-                    self.disc_cache
-                        .entry(key)
-                        .or_insert_with(|| create_ring(
-                            device, *inner_radius, *outer_radius, *segments, *rings,
-                        ))
-                        .clone()
-                }
-                ElementKind::Text3D { text, font_data, depth, bevel_enabled, bevel_thickness, bevel_size, quality } => {
-                    let key = (
-                        text.clone(),
-                        depth.to_bits(),
-                        *bevel_enabled,
-                        bevel_thickness.to_bits(),
-                        bevel_size.to_bits(),
-                        *quality,
-                    );
-                    self.text3d_cache
-                        .entry(key)
-                        .or_insert_with(|| {
-                            let builder = crate::geometry::primitives::Text3dBuilder::new(text, font_data)
-                                .depth(*depth)
-                                .quality(*quality)
-                                .bevel(*bevel_enabled, *bevel_thickness, *bevel_size);
-                            
-                            builder.build(device).unwrap_or_else(|_| create_cube(device))
-                        })
-                        .clone()
-                }
-                _ => continue,
-            };
-
-            let mut matrix = transform.matrix();
-            if let Some(bb) = billboard {
-                use ferrous_core::scene::BillboardMode;
-                let rot = match bb.mode {
-                    BillboardMode::Spherical => {
-                        let dir = (camera_eye - transform.position).normalize_or_zero();
-                        if dir.length_squared() < 1e-10 {
-                            glam::Quat::IDENTITY
-                        } else {
-                            glam::Mat4::look_at_rh(transform.position, camera_eye, glam::Vec3::Y)
-                                .to_scale_rotation_translation()
-                                .1
-                                .inverse()
-                        }
-                    }
-                    BillboardMode::Cylindrical => {
-                        let mut target = camera_eye;
-                        target.y = transform.position.y; // constrain to Y axis
-                        let dir = (target - transform.position).normalize_or_zero();
-                        if dir.length_squared() < 1e-10 {
-                            glam::Quat::IDENTITY
-                        } else {
-                            glam::Mat4::look_at_rh(transform.position, target, glam::Vec3::Y)
-                                .to_scale_rotation_translation()
-                                .1
-                                .inverse()
-                        }
-                    }
-                };
-                matrix = glam::Mat4::from_scale_rotation_translation(transform.scale, rot, transform.position);
+                self.cull_index.sync(&world.ecs, self.cull_tick);
+                self.cull_tick = world.ecs.change_tick();
+                self.cull_index.query_frustum(frustum)
             }
-            let material_slot = material.handle.0 as usize;
-
-            // Compute a quick AABB from the matrix for frustum culling
-            let world_aabb = mesh.aabb.transform(&matrix);
-
-            let key = (
-                Arc::as_ptr(&mesh.vertex_buffer) as usize,
-                material_slot,
-                is_double_sided,
-            );
-
-            // Shadow pass — only entities with ShadowCaster
-            if shadow_caster.is_some() {
-                shadow_groups
-                    .entry(key)
-                    .or_insert_with(|| (mesh.clone(), material_slot, Vec::new()))
-                    .2
-                    .push(matrix);
+        };
+        for entity in indexed {
+            // Billboards face the camera, not their `GlobalTransform`.
+            if world.ecs.has::<Billboard>(entity) {
+                continue;
             }
+            if let Some((key, mesh, matrix)) = self.draw_item(world, entity, device, camera_eye) {
+                visible_groups.entry(key).or_insert_with(|| (mesh, key.1, Vec::new())).2.push(matrix);
+            }
+        }
 
-            // Main pass — frustum culled
-            if frustum.intersects_aabb(&world_aabb) {
-                visible_groups
-                    .entry(key)
-                    .or_insert_with(|| (mesh.clone(), material_slot, Vec::new()))
-                    .2
-                    .push(matrix);
+        // Entities the index cannot hold are culled one by one.
+        let unindexed: Vec<Entity> = Query::<
+            &Element,
+            Or<(Without<LocalBounds>, Without<GlobalTransform>, With<Billboard>)>,
+        >::new(&world.ecs)
+        .iter()
+        .map(|(entity, _)| entity)
+        .collect();
+        for entity in unindexed {
+            let Some((key, mesh, matrix)) = self.draw_item(world, entity, device, camera_eye) else {
+                continue;
+            };
+            if frustum.intersects_aabb(&mesh.aabb.transform(&matrix)) {
+                visible_groups.entry(key).or_insert_with(|| (mesh, key.1, Vec::new())).2.push(matrix);
             }
         }
 
@@ -513,6 +333,248 @@ impl FrameBuilder {
         }
     }
 
+    /// Mesh, instancing key and world matrix of a drawable entity; `None`
+    /// for hidden entities and kinds without geometry.
+    fn draw_item(
+        &mut self,
+        world: &ferrous_core::scene::World,
+        entity: Entity,
+        device: &wgpu::Device,
+        camera_eye: glam::Vec3,
+    ) -> Option<(MeshGroupKey, crate::geometry::Mesh, glam::Mat4)> {
+        let element = world.ecs.get::<Element>(entity)?;
+        let transform = world.ecs.get::<Transform>(entity)?;
+        let material = world.ecs.get::<MaterialComponent>(entity)?;
+        let global = world.ecs.get::<GlobalTransform>(entity);
+        let billboard = world.ecs.get::<Billboard>(entity);
+
+        let is_renderable = matches!(
+            element.kind,
+            ElementKind::Cube { .. }
+                | ElementKind::Mesh { .. }
+                | ElementKind::Quad { .. }
+                | ElementKind::Sphere { .. }
+                | ElementKind::Cylinder { .. }
+                | ElementKind::Torus { .. }
+                | ElementKind::Plane { .. }
+                | ElementKind::Capsule { .. }
+                | ElementKind::Circle { .. }
+                | ElementKind::Ring { .. }
+                | ElementKind::Text3D { .. }
+        );
+        if !is_renderable || !element.visible {
+            return None;
+        }
+
+        let is_double_sided = if let ElementKind::Quad { double_sided, .. } = element.kind {
+            double_sided
+        } else {
+            false
+        };
+
+        let mesh = match &element.kind {
+            ElementKind::Cube { .. } => self
+                .shared_cube_mesh
+                .get_or_insert_with(|| create_cube(device))
+                .clone(),
+            ElementKind::Mesh { asset_key } => {
+                // 1. Try unconditional procedural cache first (terrain, runtime geometry)
+                if let Some(m) = self.procedural_mesh_cache.get(asset_key.as_str()) {
+                    m.clone()
+                } else {
+                    // 2. Fall back to the asset-file cache when feature is enabled
+                    #[cfg(feature = "assets")]
+                    {
+                        if let Some(m) = self.mesh_cache.get(asset_key.as_str()) {
+                            m.clone()
+                        } else {
+                            self.shared_cube_mesh
+                                .get_or_insert_with(|| create_cube(device))
+                                .clone()
+                        }
+                    }
+                    #[cfg(not(feature = "assets"))]
+                    {
+                        // No match in either cache — fall back to a cube placeholder
+                        self.shared_cube_mesh
+                            .get_or_insert_with(|| create_cube(device))
+                            .clone()
+                    }
+                }
+            }
+            ElementKind::Quad { .. } => self
+                .shared_quad_mesh
+                .get_or_insert_with(|| create_quad(device))
+                .clone(),
+            ElementKind::Sphere {
+                latitudes,
+                longitudes,
+                ..
+            } => {
+                let use_mesh = if let Some((m, l, o)) = &self.shared_sphere_mesh {
+                    if l == latitudes && o == longitudes {
+                        Some(m.clone())
+                    } else {
+                        None
+                    }
+                } else {
+                    None
+                };
+                if let Some(m) = use_mesh {
+                    m
+                } else {
+                    let new = create_sphere(device, 1.0, *latitudes, *longitudes);
+                    self.shared_sphere_mesh = Some((new.clone(), *latitudes, *longitudes));
+                    new
+                }
+            }
+            ElementKind::Cylinder {
+                radius_top,
+                radius_bottom,
+                height,
+                radial_segments,
+                height_segments,
+                open_ended,
+            } => {
+                let key = (
+                    radius_top.to_bits(), radius_bottom.to_bits(), height.to_bits(),
+                    *radial_segments, *height_segments, *open_ended as u8,
+                );
+                self.cylinder_cache
+                    .entry(key)
+                    .or_insert_with(|| create_cylinder(
+                        device, *radius_top, *radius_bottom, *height,
+                        *radial_segments, *height_segments, *open_ended,
+                    ))
+                    .clone()
+            }
+            ElementKind::Torus { radius, tube, radial_segments, tubular_segments } => {
+                let key = (
+                    radius.to_bits(), tube.to_bits(),
+                    *radial_segments, *tubular_segments,
+                );
+                self.torus_cache
+                    .entry(key)
+                    .or_insert_with(|| create_torus(
+                        device, *radius, *tube,
+                        *radial_segments, *tubular_segments,
+                        std::f32::consts::TAU,
+                    ))
+                    .clone()
+            }
+            ElementKind::Plane { width, height, width_segments, height_segments } => {
+                let key = (
+                    width.to_bits(), height.to_bits(),
+                    *width_segments, *height_segments,
+                );
+                self.plane_cache
+                    .entry(key)
+                    .or_insert_with(|| create_plane(
+                        device, *width, *height,
+                        *width_segments, *height_segments,
+                    ))
+                    .clone()
+            }
+            ElementKind::Capsule { radius, height, radial_segments, cap_segments } => {
+                let key = (
+                    radius.to_bits(), height.to_bits(),
+                    *radial_segments, *cap_segments,
+                );
+                self.capsule_cache
+                    .entry(key)
+                    .or_insert_with(|| create_capsule(
+                        device, *radius, *height,
+                        *radial_segments, *cap_segments,
+                    ))
+                    .clone()
+            }
+            ElementKind::Circle { radius, segments } => {
+                let key = (0u32, radius.to_bits(), *segments, 1u32);
+                self.disc_cache
+                    .entry(key)
+                    .or_insert_with(|| create_circle(device, *radius, *segments))
+                    .clone()
+            }
+            ElementKind::Ring { inner_radius, outer_radius, segments, rings } => {
+                let key = (
+                    inner_radius.to_bits(), outer_radius.to_bits(),
+                    *segments, *rings,
+                );
+                self.disc_cache
+                    .entry(key)
+                    .or_insert_with(|| create_ring(
+                        device, *inner_radius, *outer_radius, *segments, *rings,
+                    ))
+                    .clone()
+            }
+            ElementKind::Text3D { text, font_data, depth, bevel_enabled, bevel_thickness, bevel_size, quality } => {
+                let key = (
+                    text.clone(),
+                    depth.to_bits(),
+                    *bevel_enabled,
+                    bevel_thickness.to_bits(),
+                    bevel_size.to_bits(),
+                    *quality,
+                );
+                self.text3d_cache
+                    .entry(key)
+                    .or_insert_with(|| {
+                        let builder = crate::geometry::primitives::Text3dBuilder::new(text, font_data)
+                            .depth(*depth)
+                            .quality(*quality)
+                            .bevel(*bevel_enabled, *bevel_thickness, *bevel_size);
+                        
+                        builder.build(device).unwrap_or_else(|_| create_cube(device))
+                    })
+                    .clone()
+            }
+            _ => return None,
+        };
+
+        // World-space matrix from the hierarchy; roots that
+        // `TransformSystem` has not visited yet use their local one.
+        let mut matrix = global.map_or_else(|| transform.matrix(), |g| g.0);
+        if let Some(bb) = billboard {
+            use ferrous_core::scene::BillboardMode;
+            let (scale, _, position) = matrix.to_scale_rotation_translation();
+            let rot = match bb.mode {
+                BillboardMode::Spherical => {
+                    let dir = (camera_eye - position).normalize_or_zero();
+                    if dir.length_squared() < 1e-10 {
+                        glam::Quat::IDENTITY
+                    } else {
+                        glam::Mat4::look_at_rh(position, camera_eye, glam::Vec3::Y)
+                            .to_scale_rotation_translation()
+                            .1
+                            .inverse()
+                    }
+                }
+                BillboardMode::Cylindrical => {
+                    let mut target = camera_eye;
+                    target.y = position.y; // constrain to Y axis
+                    let dir = (target - position).normalize_or_zero();
+                    if dir.length_squared() < 1e-10 {
+                        glam::Quat::IDENTITY
+                    } else {
+                        glam::Mat4::look_at_rh(position, target, glam::Vec3::Y)
+                            .to_scale_rotation_translation()
+                            .1
+                            .inverse()
+                    }
+                }
+            };
+            matrix = glam::Mat4::from_scale_rotation_translation(scale, rot, position);
+        }
+        let material_slot = material.handle.0 as usize;
+
+        let key = (
+            Arc::as_ptr(&mesh.vertex_buffer) as usize,
+            material_slot,
+            is_double_sided,
+        );
+        Some((key, mesh, matrix))
+    }
+
     // -----------------------------------------------------------------------
 
     /// Build a `FramePacket` for the current frame.
@@ -5,13 +5,21 @@
 //!
 //! * [`GizmoSystem::queue`] — push a [`GizmoDraw`] for the current frame.
 //! * [`GizmoSystem::execute`] — bake a vertex buffer and record a render pass.
+//!
+//! Splines are drawn as debug lines through [`GizmoSystem::draw_spline`].
 
 use std::sync::Arc;
 
+use ferrous_core::scene::{Spline, SplineKind};
+
 use crate::geometry::Vertex;
 use crate::pipeline::{GizmoPipeline, PipelineLayouts};
+use crate::renderer_core::DebugLine;
 use crate::scene::GizmoDraw;
 
+/// Polyline resolution of [`GizmoSystem::draw_spline`].
+const SPLINE_SEGMENTS_PER_CURVE: usize = 24;
+
 // --------------------------------------------------------------------------
 // GizmoSystem
 // --------------------------------------------------------------------------
@@ -52,6 +60,14 @@ impl GizmoSystem {
         self.lines.push(line);
     }
 
+    /// Queue a spline to be rendered this frame: the curve in `color`, a
+    /// cross on every control point and, for Bézier and B-splines (whose
+    /// points are not all on the curve), the control polygon dimmed.
+    /// `transform` maps the spline's points to world space.
+    pub fn draw_spline(&mut self, spline: &Spline, transform: glam::Mat4, color: [f32; 3]) {
+        self.lines.extend(spline_lines(spline, transform, color));
+    }
+
     /// Queue a gizmo to be rendered this frame.
     ///
     /// The gizmo list is cleared at the end of [`execute`](Self::execute),
@@ -290,3 +306,56 @@ impl GizmoSystem {
         self.lines.clear();
     }
 }
+
+/// Debug lines drawing `spline` (see [`GizmoSystem::draw_spline`]).
+pub fn spline_lines(spline: &Spline, transform: glam::Mat4, color: [f32; 3]) -> Vec<DebugLine> {
+    let mut lines = Vec::new();
+    let mut polyline = |points: &[glam::Vec3], color: [f32; 3]| {
+        for pair in points.windows(2) {
+            lines.push(DebugLine {
+                start: transform.transform_point3(pair[0]),
+                end: transform.transform_point3(pair[1]),
+                color,
+            });
+        }
+    };
+
+    polyline(&spline.polyline(SPLINE_SEGMENTS_PER_CURVE), color);
+    if spline.kind() != SplineKind::CatmullRom {
+        let dim = color.map(|c| c * 0.4);
+        let mut control = spline.points().to_vec();
+        if spline.is_closed() {
+            control.extend(spline.points().first());
+        }
+        polyline(&control, dim);
+    }
+
+    // Control point crosses, sized relative to the curve.
+    let size = (spline.length() * 0.01).clamp(0.02, 0.25);
+    for &p in spline.points() {
+        for axis in [glam::Vec3::X, glam::Vec3::Y, glam::Vec3::Z] {
+            polyline(&[p - axis * size, p + axis * size], color);
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::{Mat4, Vec3};
+
+    #[test]
+    fn spline_gizmo_draws_curve_control_polygon_and_points() {
+        let points = vec![Vec3::ZERO, Vec3::X, Vec3::new(1.0, 0.0, 1.0), Vec3::Z];
+        let offset = Mat4::from_translation(Vec3::Y);
+
+        let catmull = spline_lines(&Spline::new(SplineKind::CatmullRom, points.clone()), offset, [1.0; 3]);
+        // 3 segments × 24 lines of curve + 3 cross lines per point.
+        assert_eq!(catmull.len(), 3 * SPLINE_SEGMENTS_PER_CURVE + 4 * 3);
+        assert!(catmull[0].start.abs_diff_eq(Vec3::Y, 1e-5), "lines are in world space");
+
+        let bezier = spline_lines(&Spline::new(SplineKind::Bezier, points), Mat4::IDENTITY, [1.0; 3]);
+        assert_eq!(bezier.len(), SPLINE_SEGMENTS_PER_CURVE + 3 + 4 * 3);
+    }
+}
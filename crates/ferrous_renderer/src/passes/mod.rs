@@ -27,7 +27,7 @@ pub use outline_pass::{OutlineFrameData, OutlinePass};
 pub use particle_pass::ParticleSystem;
 pub use post_process_pass::PostProcessPass;
 pub use prepass::PrePass;
-pub use skinning_pass::SkinningPass;
+pub use skinning_pass::{pack_morph_deltas, GpuMorphDelta, MorphParams, MorphTargetDeltas, SkinningPass, MAX_MORPH_TARGETS};
 pub use procedural_sky_pass::ProceduralSkyPass;
 pub use skybox_pass::{SkyboxPass, SkyboxPipeline};
 pub use ssao_blur_pass::SsaoBlurPass;
@@ -1,15 +1,94 @@
 //! GPU Skinning Pass
 //!
 //! Transforms mesh vertices according to bone transforms using compute shaders.
+//! Morph targets (blend shapes) are applied first, in bind space:
+//!
+//! | Group | Binding | Contents |
+//! |-------|---------|----------|
+//! | 0 | 0 | input vertices |
+//! | 0 | 1 | bone influences |
+//! | 0 | 2 | morph deltas ([`pack_morph_deltas`]) |
+//! | 1 | 0 | bone palette (`Skeleton::joint_matrices`) |
+//! | 1 | 1 | morph weights and vertex count ([`MorphParams`]) |
+//! | 2 | 0 | output vertices |
+//!
+//! Tangent displacements are not applied: the skinning vertex has no tangent.
+//!
+//! The renderer does not dispatch this pass yet: its output vertex layout
+//! differs from [`Vertex`](crate::geometry::Vertex), so nothing draws from
+//! it.  Until it is wired in, `ferrous_app` blends morph targets on the CPU
+//! with `AssetMesh::blend_morph_targets` whenever an entity's `MorphWeights`
+//! change and re-registers the result as ordinary mesh data.
 
+use bytemuck::{Pod, Zeroable};
 use wgpu::{
     BindGroup, BindGroupLayout, CommandEncoder, ComputePipeline, Device,
 };
 
+/// Maximum number of morph targets blended per mesh.
+pub const MAX_MORPH_TARGETS: usize = 64;
+
+/// Displacement of one vertex by one morph target (`w` unused).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+pub struct GpuMorphDelta {
+    pub position: [f32; 4],
+    pub normal: [f32; 4],
+}
+
+/// Position and normal displacements of one morph target, one per vertex.
+pub type MorphTargetDeltas<'a> = (&'a [[f32; 3]], &'a [[f32; 3]]);
+
+/// Pack per-target `(position, normal)` displacements into the target-major
+/// layout read by the shader.  Missing displacements are zero.  Always
+/// returns at least one element, since storage bindings cannot be empty.
+pub fn pack_morph_deltas(vertex_count: usize, targets: &[MorphTargetDeltas]) -> Vec<GpuMorphDelta> {
+    let mut deltas = vec![GpuMorphDelta::zeroed(); (vertex_count * targets.len()).max(1)];
+    for (t, (positions, normals)) in targets.iter().take(MAX_MORPH_TARGETS).enumerate() {
+        let base = t * vertex_count;
+        for (i, p) in positions.iter().take(vertex_count).enumerate() {
+            deltas[base + i].position = [p[0], p[1], p[2], 0.0];
+        }
+        for (i, n) in normals.iter().take(vertex_count).enumerate() {
+            deltas[base + i].normal = [n[0], n[1], n[2], 0.0];
+        }
+    }
+    deltas
+}
+
+/// Uniform block with the vertex count and current morph weights (usually
+/// the entity's `MorphWeights`).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+pub struct MorphParams {
+    pub vertex_count: u32,
+    pub target_count: u32,
+    pub _pad: [u32; 2],
+    pub weights: [[f32; 4]; MAX_MORPH_TARGETS / 4],
+}
+
+impl MorphParams {
+    /// `target_count` is the number of targets packed into the delta buffer;
+    /// the shader reads `min(weights, targets)` of them, at most
+    /// [`MAX_MORPH_TARGETS`], so surplus weights never index past the deltas.
+    pub fn new(vertex_count: u32, target_count: usize, weights: &[f32]) -> Self {
+        let mut params = MorphParams {
+            vertex_count,
+            target_count: weights.len().min(target_count).min(MAX_MORPH_TARGETS) as u32,
+            _pad: [0; 2],
+            weights: [[0.0; 4]; MAX_MORPH_TARGETS / 4],
+        };
+        for (i, &w) in weights.iter().take(MAX_MORPH_TARGETS).enumerate() {
+            params.weights[i / 4][i % 4] = w;
+        }
+        params
+    }
+}
+
 pub struct SkinningPass {
     pipeline: ComputePipeline,
-    group_layout0: BindGroupLayout, // Vertices + Influences
-    group_layout1: BindGroupLayout, // Palette
+    group_layout0: BindGroupLayout, // Vertices + Influences + Morph deltas
+    group_layout1: BindGroupLayout, // Palette + Morph params
     group_layout2: BindGroupLayout, // Output
 }
 
@@ -40,21 +119,43 @@ impl SkinningPass {
                     },
                     count: None,
                 },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
             ],
         });
 
         let group_layout1 = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
             label: Some("Skinning Palette BGL"),
-            entries: &[wgpu::BindGroupLayoutEntry {
-                binding: 0,
-                visibility: wgpu::ShaderStages::COMPUTE,
-                ty: wgpu::BindingType::Buffer {
-                    ty: wgpu::BufferBindingType::Uniform,
-                    has_dynamic_offset: false,
-                    min_binding_size: None,
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
                 },
-                count: None,
-            }],
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
         });
 
         let group_layout2 = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
@@ -115,3 +216,30 @@ impl SkinningPass {
         cpass.dispatch_workgroups(x, 1, 1);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn morph_data_is_packed_for_the_shader() {
+        let params = MorphParams::new(3, 8, &[0.25, 0.5, 0.0, 1.0, 0.75]);
+        assert_eq!(std::mem::size_of::<MorphParams>(), 16 + 16 * 16);
+        assert_eq!(params.target_count, 5);
+        assert_eq!(params.weights[0], [0.25, 0.5, 0.0, 1.0]);
+        assert_eq!(params.weights[1][0], 0.75);
+        // More weights than packed targets: the shader stops at the deltas.
+        assert_eq!(MorphParams::new(3, 2, &[1.0; 5]).target_count, 2);
+
+        let p0 = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        let n1 = [[0.0, 0.5, 0.0]];
+        let deltas = pack_morph_deltas(3, &[(&p0, &[]), (&[], &n1)]);
+        assert_eq!(deltas.len(), 6);
+        assert_eq!(deltas[2].position, [0.0, 0.0, 1.0, 0.0]);
+        assert_eq!(deltas[3].normal, [0.0, 0.5, 0.0, 0.0]);
+        assert_eq!(deltas[3].position, [0.0; 4]);
+
+        // Meshes without morph targets still need a bindable buffer.
+        assert_eq!(pack_morph_deltas(3, &[]).len(), 1);
+    }
+}
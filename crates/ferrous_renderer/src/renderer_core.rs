@@ -117,6 +117,12 @@ pub struct Renderer {
     /// CPU-side material descriptor cache for detecting changes during sync_world.
     /// Keyed by entity id (u64).
     world_material_descs: HashMap<u64, ferrous_core::scene::MaterialDescriptor>,
+    /// ECS change tick observed at the end of the previous `sync_world`.
+    /// Only components changed after this tick are re-synced.
+    world_sync_tick: u64,
+    /// Point lights uploaded by the previous `sync_world`; while it stays at
+    /// zero, lights set through `set_point_lights` are left alone.
+    world_point_light_count: usize,
     /// Storage buffer for instanced World entities.
     instance_buf: InstanceBuffer,
     /// Layout for the instance storage buffer bind group.
@@ -417,6 +423,8 @@ impl Renderer {
             extra_passes: Vec::new(),
             camera_system,
             world_material_descs: HashMap::new(),
+            world_sync_tick: 0,
+            world_point_light_count: 0,
             instance_buf,
             particle_system: Some(particle_system),
             instance_layout: layouts.instance.clone(),
@@ -798,7 +806,33 @@ impl Renderer {
         self.set_sky_mode(SkyMode::Procedural(sky));
     }
 
+    /// Upload the world's lights, camera and materials and rebuild the draw
+    /// lists, culling with an index the renderer maintains itself.  Apps that
+    /// run `SpatialIndexSystem` should call [`Self::sync_world_indexed`].
     pub fn sync_world(&mut self, world: &ferrous_core::scene::World) {
+        self.sync_world_with(world, None);
+    }
+
+    /// [`Self::sync_world`], culling with the `SpatialIndex` resource kept by
+    /// `SpatialIndexSystem`.
+    pub fn sync_world_indexed(
+        &mut self,
+        world: &ferrous_core::scene::World,
+        index: &ferrous_core::scene::SpatialIndex,
+    ) {
+        self.sync_world_with(world, Some(index));
+    }
+
+    fn sync_world_with(
+        &mut self,
+        world: &ferrous_core::scene::World,
+        spatial: Option<&ferrous_core::scene::SpatialIndex>,
+    ) {
+        // A replaced world restarts its tick counter; resync everything.
+        if world.ecs.change_tick() < self.world_sync_tick {
+            self.world_sync_tick = 0;
+        }
+
         // 0. Sync DirectionalLight ECS component → GPU uniform (if present)
         {
             use ferrous_core::scene::DirectionalLight;
@@ -853,9 +887,14 @@ impl Renderer {
         // 0c. Sync Material ECS components → MaterialDescriptors
         {
             use ferrous_core::scene::world::MaterialComponent;
-            let mat_updates: Vec<(u64, ferrous_core::scene::MaterialHandle, ferrous_core::scene::MaterialDescriptor)> = world
-                .ecs
-                .query::<MaterialComponent>()
+            use ferrous_ecs::prelude::{Changed, Query};
+            // Only materials touched since the last sync are considered.
+            let mat_updates: Vec<(u64, ferrous_core::scene::MaterialHandle, ferrous_core::scene::MaterialDescriptor)> =
+                Query::<&MaterialComponent, Changed<MaterialComponent>>::new_since(
+                    &world.ecs,
+                    self.world_sync_tick,
+                )
+                .iter()
                 .map(|(e, m)| (e.index as u64, m.handle, m.descriptor.clone()))
                 .collect();
 
@@ -876,6 +915,38 @@ impl Renderer {
                 }
             }
         }
+        self.world_sync_tick = world.ecs.change_tick();
+
+        // 0e. Upload point lights listed in the hook-maintained render index
+        {
+            use ferrous_core::scene::{GlobalTransform, PointLightComponent};
+            let lights: Vec<crate::resources::PointLightUniform> = world
+                .render_index()
+                .point_lights()
+                .iter()
+                .filter_map(|&e| {
+                    let pl = world.ecs.get::<PointLightComponent>(e)?;
+                    let pos = match world.ecs.get::<GlobalTransform>(e) {
+                        Some(global) => global.0.w_axis.truncate(),
+                        None => world.ecs.get::<ferrous_core::Transform>(e)?.position,
+                    };
+                    Some(crate::resources::PointLightUniform::new(
+                        pos.into(),
+                        pl.radius,
+                        pl.color,
+                        pl.intensity,
+                    ))
+                })
+                .collect();
+            if !lights.is_empty() || self.world_point_light_count > 0 {
+                self.world_pass.update_point_lights(
+                    &self.context.device,
+                    &self.context.queue,
+                    &lights,
+                );
+            }
+            self.world_point_light_count = lights.len();
+        }
 
         // 1. Build frustum from current camera
         let camera_packet = crate::graph::frame_packet::CameraPacket {
@@ -890,6 +961,7 @@ impl Renderer {
             let prepass_ref = &mut self.prepass;
             self.frame_builder.build_world_commands(
                 world,
+                spatial,
                 &self.context.device,
                 &frustum,
                 self.camera_system.camera.eye,
@@ -1438,6 +1510,13 @@ impl Renderer {
         self.debug_lines.push(DebugLine { start, end, color: [r, g, b] });
     }
 
+    /// Draw a spline for one frame; `transform` maps its points to world
+    /// space (see [`GizmoSystem::draw_spline`]).
+    pub fn draw_spline(&mut self, spline: &ferrous_core::scene::Spline, transform: ferrous_core::glam::Mat4, color: Color) {
+        let [r, g, b, _] = color.to_array();
+        self.gizmo_system.draw_spline(spline, transform, [r, g, b]);
+    }
+
     pub fn viewport_size(&self) -> ferrous_core::glam::Vec2 {
         ferrous_core::glam::Vec2::new(
             self.render_target.width as f32,
@@ -551,6 +551,7 @@ impl RendererPasses {
             let prepass_ref = &mut self.prepass;
             self.frame_builder.build_world_commands(
                 world,
+                None,
                 &self.context.device,
                 &frustum,
                 self.camera_system.camera.eye,
@@ -677,18 +678,25 @@ impl RendererPasses {
         );
         self.sync_style_material_table();
 
-        // 5. Collect point lights from World entities
+        // 5. Collect point lights from the hook-maintained render index
         let mut point_light_uniforms: Vec<crate::resources::PointLightUniform> = Vec::new();
-        for element in world.iter() {
-            if let Some(pl) = &element.point_light {
-                let pos = element.transform.position;
-                point_light_uniforms.push(crate::resources::PointLightUniform::new(
-                    [pos.x, pos.y, pos.z],
-                    pl.radius,
-                    pl.color,
-                    pl.intensity,
-                ));
-            }
+        for &entity in world.render_index().point_lights() {
+            let Some(pl) = world.ecs.get::<ferrous_core::scene::PointLightComponent>(entity) else {
+                continue;
+            };
+            let pos = match world.ecs.get::<ferrous_core::scene::GlobalTransform>(entity) {
+                Some(global) => global.0.w_axis.truncate(),
+                None => match world.ecs.get::<ferrous_core::Transform>(entity) {
+                    Some(t) => t.position,
+                    None => continue,
+                },
+            };
+            point_light_uniforms.push(crate::resources::PointLightUniform::new(
+                [pos.x, pos.y, pos.z],
+                pl.radius,
+                pl.color,
+                pl.intensity,
+            ));
         }
         self.world_pass.update_point_lights(
             &self.context.device,